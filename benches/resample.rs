@@ -0,0 +1,51 @@
+//! Cost of feeding 48kHz audio at each resample quality
+//!
+//! Run with `cargo bench --bench resample`. Feeds 10ms chunks, as a microphone
+//! callback delivers them, to a stand-in helper that discards its input, and
+//! reports the time per chunk. Every preset should stay well under the 10ms
+//! of audio each chunk holds, or feeding can't keep up with real time.
+
+use std::f64::consts::PI;
+use std::os::unix::fs::PermissionsExt;
+use std::time::{Duration, Instant};
+use swift_scribe::{ResampleQuality, StreamingTranscriber};
+
+/// Chunks fed per quality
+const CHUNKS: u32 = 2000;
+
+fn main() {
+    let dir = tempfile::tempdir().unwrap();
+    let helper = dir.path().join("transcribe_stream");
+    std::fs::write(
+        &helper,
+        "#!/bin/sh\necho '{\"protocol\":1}'\ncat > /dev/null\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let chunk: Vec<i16> = (0..480)
+        .map(|i| (10000.0 * (2.0 * PI * 440.0 * i as f64 / 48000.0).sin()) as i16)
+        .collect();
+    for quality in [
+        ResampleQuality::Fast,
+        ResampleQuality::Medium,
+        ResampleQuality::Best,
+    ] {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_programmatic_input()
+            .with_helper_path(&helper)
+            .with_resample_quality(quality)
+            .with_backlog_limit(Duration::MAX)
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+
+        let started = Instant::now();
+        for _ in 0..CHUNKS {
+            transcriber.feed_audio_i16(&chunk, 48000, 1).unwrap();
+        }
+        let per_chunk = started.elapsed() / CHUNKS;
+        println!("{:?}: {:?} per 10ms chunk", quality, per_chunk);
+        transcriber.stop().unwrap();
+    }
+}
@@ -1,12 +1,9 @@
+use std::io::{self, Write};
 /// Example: Live microphone transcription
 ///
 /// Demonstrates how to use the StreamingTranscriber API for real-time
 /// speech-to-text from microphone input.
-
 use swift_scribe::StreamingTranscriber;
-use std::io::{self, Write};
-use std::thread;
-use std::time::Duration;
 
 fn main() {
     println!("🎤 Microphone Streaming Example");
@@ -35,38 +32,33 @@ fn main() {
     let mut partial_active = false;
     let mut final_transcription = Vec::new();
 
-    // Poll for results
-    loop {
-        match transcriber.poll_result() {
-            Ok(Some(result)) => {
-                if result.is_final {
-                    // Move to new line if partial was active
-                    if partial_active {
-                        println!();
-                        partial_active = false;
-                    }
-
-                    // Print and save final result
-                    println!("[FINAL] {}", result.text);
-                    final_transcription.push(result.text);
-                    io::stdout().flush().unwrap();
-                } else {
-                    // Display partial in-place with carriage return
-                    print!("\r\x1B[K[partial] {}", result.text);
-                    io::stdout().flush().unwrap();
-                    partial_active = true;
+    // Wait for results until the helper ends
+    for result in transcriber.results() {
+        match result {
+            Ok(result) if result.is_final => {
+                // Move to new line if partial was active
+                if partial_active {
+                    println!();
+                    partial_active = false;
                 }
+
+                // Print and save final result
+                println!("[FINAL] {}", result.text);
+                final_transcription.push(result.text);
+                io::stdout().flush().unwrap();
             }
-            Ok(None) => {
-                // No data available, sleep briefly
-                thread::sleep(Duration::from_millis(10));
+            Ok(result) => {
+                // Display partial in-place with carriage return
+                print!("\r\x1B[K[partial] {}", result.text);
+                io::stdout().flush().unwrap();
+                partial_active = true;
             }
             Err(e) => {
                 if partial_active {
                     println!();
+                    partial_active = false;
                 }
                 eprintln!("\nError: {}", e);
-                break;
             }
         }
     }
@@ -2,11 +2,32 @@ import AVFoundation
 import Foundation
 import Speech
 
+// A recognized span with its position in the audio and recognizer confidence
+struct Segment: Codable {
+    let text: String
+    let start: Double
+    let duration: Double
+    let confidence: Double?
+    // Locale the segment was recognized in
+    let locale: String?
+}
+
+// Receives each segment as it is finalized instead of collecting them (--segments)
+var segmentHandler: ((Segment) -> Void)?
+
+// Hands a finalized segment to the handler, or keeps it for the final result
+func collect(_ segment: Segment, into segments: inout [Segment]) {
+    if let handler = segmentHandler {
+        handler(segment)
+    } else {
+        segments.append(segment)
+    }
+}
+
 // Modern SpeechAnalyzer API (macOS 26+)
 @available(macOS 26.0, iOS 26.0, *)
-func transcribeWithSpeechAnalyzer(audioURL: URL) async throws -> String {
-    let locale = Locale(identifier: "en-US")
-    
+func transcribeWithSpeechAnalyzer(audioURL: URL, locale: Locale, contextualStrings: [String]) async throws -> (text: String, segments: [Segment]) {
+
     // Verify locale is supported
     let supportedLocales = await SpeechTranscriber.supportedLocales
     guard supportedLocales.map({ $0.identifier(.bcp47) }).contains(locale.identifier(.bcp47)) else {
@@ -24,15 +45,15 @@ func transcribeWithSpeechAnalyzer(audioURL: URL) async throws -> String {
     // - .timeIndexedTranscriptionWithAlternatives: Includes alternatives + audio time ranges
     // - .progressiveTranscription: Real-time volatile results
     // - .timeIndexedProgressiveTranscription: Real-time + time ranges
-    let transcriber = SpeechTranscriber(locale: locale, preset: .transcription)
-    
-    // Alternative: Custom configuration for more control
-    // let transcriber = SpeechTranscriber(
-    //     locale: locale,
-    //     transcriptionOptions: [],              // e.g., [.etiquetteReplacements] for censoring
-    //     reportingOptions: [],                  // e.g., [.volatileResults] for real-time
-    //     attributeOptions: []                   // e.g., [.audioTimeRange] for timestamps
-    // )
+    //
+    // Custom configuration equivalent to .transcription, plus the time ranges and
+    // confidences needed for per-segment metadata
+    let transcriber = SpeechTranscriber(
+        locale: locale,
+        transcriptionOptions: [],
+        reportingOptions: [],
+        attributeOptions: [.audioTimeRange, .transcriptionConfidence]
+    )
     
     // Check if model is installed (optional - will auto-download if needed)
     let installedLocales = await SpeechTranscriber.installedLocales
@@ -46,6 +67,13 @@ func transcribeWithSpeechAnalyzer(audioURL: URL) async throws -> String {
     let modules: [any SpeechModule] = [transcriber]
     let analyzer = SpeechAnalyzer(modules: modules)
     
+    // Bias recognition toward the words of a reference script (--reference)
+    if !contextualStrings.isEmpty {
+        let context = AnalysisContext()
+        context.contextualStrings[.general] = contextualStrings
+        try await analyzer.setContext(context)
+    }
+    
     // Load audio file (must use AVAudioFile, not raw URL)
     let audioFile = try AVAudioFile(forReading: audioURL)
     
@@ -54,6 +82,7 @@ func transcribeWithSpeechAnalyzer(audioURL: URL) async throws -> String {
     
     // Stream results and build transcription
     var fullTranscription = ""
+    var segments: [Segment] = []
     for try await result in transcriber.results {
         // result.text is AttributedString with the most likely transcription
         // result.isFinal: true = finalized, false = volatile (may change)
@@ -61,20 +90,36 @@ func transcribeWithSpeechAnalyzer(audioURL: URL) async throws -> String {
         // result.range: CMTimeRange of audio this result covers
         
         if result.isFinal {
-            // Final result - will not be updated
-            fullTranscription += String(result.text.characters)
+            // Final result - will not be updated. Segments handed straight to
+            // the handler aren't kept as text either
+            if segmentHandler == nil {
+                fullTranscription += String(result.text.characters)
+            }
+            
+            for run in result.text.runs {
+                let text = String(result.text[run.range].characters)
+                    .trimmingCharacters(in: .whitespaces)
+                guard !text.isEmpty, let range = run.audioTimeRange else { continue }
+                collect(Segment(
+                    text: text,
+                    start: range.start.seconds,
+                    duration: range.duration.seconds,
+                    confidence: run.transcriptionConfidence,
+                    locale: locale.identifier
+                ), into: &segments)
+            }
         }
         // Note: We ignore volatile results for file transcription
         // For real-time use, you'd update UI with volatile results
     }
     
-    return fullTranscription
+    return (fullTranscription, segments)
 }
 
 // Legacy API for older macOS versions using SFSpeechRecognizer
 @available(macOS 10.15, *)
-func transcribeWithLegacyAPI(audioURL: URL) async throws -> String {
-    guard let recognizer = SFSpeechRecognizer(locale: Locale(identifier: "en-US")) else {
+func transcribeWithLegacyAPI(audioURL: URL, locale: Locale, taskHint: SFSpeechRecognitionTaskHint, contextualStrings: [String]) async throws -> (text: String, segments: [Segment]) {
+    guard let recognizer = SFSpeechRecognizer(locale: locale) else {
         throw NSError(domain: "SpeechRecognition", code: 1, userInfo: [NSLocalizedDescriptionKey: "Speech recognizer not available"])
     }
     
@@ -84,6 +129,8 @@ func transcribeWithLegacyAPI(audioURL: URL) async throws -> String {
     
     let request = SFSpeechURLRecognitionRequest(url: audioURL)
     request.shouldReportPartialResults = false
+    request.taskHint = taskHint
+    request.contextualStrings = contextualStrings
     
     return try await withCheckedThrowingContinuation { continuation in
         recognizer.recognitionTask(with: request) { result, error in
@@ -93,43 +140,720 @@ func transcribeWithLegacyAPI(audioURL: URL) async throws -> String {
             }
             
             if let result = result, result.isFinal {
-                continuation.resume(returning: result.bestTranscription.formattedString)
+                var segments: [Segment] = []
+                for segment in result.bestTranscription.segments {
+                    collect(Segment(
+                        text: segment.substring,
+                        start: segment.timestamp,
+                        duration: segment.duration,
+                        confidence: Double(segment.confidence),
+                        locale: locale.identifier
+                    ), into: &segments)
+                }
+                continuation.resume(returning: (result.bestTranscription.formattedString, segments))
             }
         }
     }
 }
 
-// Main execution
+// Picks the first of the --locales candidates this Mac can recognize. Both
+// recognizers work in a single locale per recognition, so the later candidates
+// are fallbacks for an unsupported primary, not extra languages recognized
+// alongside it: code-switched audio is recognized entirely in the chosen locale.
 @available(macOS 10.15, *)
-@MainActor
-func main() async {
-    guard CommandLine.arguments.count > 1 else {
-        fputs("Usage: transcribe <audio-file-path>\n", stderr)
-        exit(1)
+func selectLocale(candidates: [String]) async -> Locale {
+    for identifier in candidates {
+        let locale = Locale(identifier: identifier)
+        if #available(macOS 26.0, *) {
+            let supportedLocales = await SpeechTranscriber.supportedLocales
+            if supportedLocales.map({ $0.identifier(.bcp47) }).contains(locale.identifier(.bcp47)) {
+                return locale
+            }
+        } else if SFSpeechRecognizer.supportedLocales().contains(where: { $0.identifier == locale.identifier }) {
+            return locale
+        }
+        fputs("Note: Locale \(identifier) is not supported; trying the next candidate\n", stderr)
+    }
+    // Nothing matched: let the recognizer report the primary as unsupported
+    return Locale(identifier: candidates.first ?? "en-US")
+}
+
+// Structured output for --json
+struct FileResult: Codable {
+    let text: String
+    let segments: [Segment]
+    let trimmedLeading: Double?
+    let trimmedTrailing: Double?
+    // speech_analyzer or sf_speech_recognizer
+    let backend: String
+    let locale: String
+    // Length of the audio in seconds, before any trimming
+    let duration: Double?
+}
+
+// Windows quieter than this (RMS, dBFS) count as silence for --trim-silence
+let silenceThresholdDB: Float = -45
+let silenceWindowSeconds = 0.02
+
+// Frames read or written at a time while trimming, so long files aren't held in memory
+let trimChunkFrames = 1 << 16
+
+// Copies the audio between the first and last non-silent windows to a temp file.
+// Returns nil for the URL when the whole file is silent.
+func trimSilence(audioURL: URL) throws -> (url: URL?, leading: Double, trailing: Double) {
+    let file = try AVAudioFile(forReading: audioURL)
+    let format = file.processingFormat
+    let sampleRate = format.sampleRate
+    let channelCount = Int(format.channelCount)
+    let window = max(1, Int(sampleRate * silenceWindowSeconds))
+    let threshold = powf(10, silenceThresholdDB / 20)
+    // Whole windows per chunk, so no window straddles two reads
+    let chunkFrames = max(1, trimChunkFrames / window) * window
+    guard file.length > 0,
+          let buffer = AVAudioPCMBuffer(pcmFormat: format, frameCapacity: AVAudioFrameCount(chunkFrames)) else {
+        return (audioURL, 0, 0)
+    }
+    
+    func isLoud(_ channels: UnsafePointer<UnsafeMutablePointer<Float>>, _ start: Int, _ end: Int) -> Bool {
+        var sum: Float = 0
+        for ch in 0..<channelCount {
+            let data = channels[ch]
+            for i in start..<end {
+                sum += data[i] * data[i]
+            }
+        }
+        return sqrtf(sum / Float((end - start) * channelCount)) >= threshold
+    }
+    
+    // First pass: find the first and last loud windows, a chunk at a time
+    var frames = 0
+    var first: Int?
+    var last: Int?
+    while file.framePosition < file.length {
+        try file.read(into: buffer, frameCount: AVAudioFrameCount(chunkFrames))
+        let read = Int(buffer.frameLength)
+        guard read > 0, let channels = buffer.floatChannelData else { break }
+        for start in stride(from: 0, to: read, by: window)
+        where isLoud(channels, start, min(start + window, read)) {
+            first = first ?? frames + start
+            last = frames + start
+        }
+        frames += read
+    }
+    guard let first = first, let last = last else {
+        return (nil, Double(frames) / sampleRate, 0)
+    }
+    
+    let startFrame = first
+    let endFrame = min(last + window, frames)
+    let leading = Double(startFrame) / sampleRate
+    let trailing = Double(frames - endFrame) / sampleRate
+    if startFrame == 0 && endFrame == frames {
+        return (audioURL, 0, 0)
+    }
+    
+    // TMPDIR is honoured here, so the library's temp directory setting applies
+    let trimmedURL = FileManager.default.temporaryDirectory
+        .appendingPathComponent("transcribe-trim-\(UUID().uuidString).caf")
+    let output = try AVAudioFile(
+        forWriting: trimmedURL,
+        settings: format.settings,
+        commonFormat: format.commonFormat,
+        interleaved: format.isInterleaved
+    )
+    
+    // Second pass: copy the frames between them, a chunk at a time
+    file.framePosition = AVAudioFramePosition(startFrame)
+    var remaining = endFrame - startFrame
+    while remaining > 0 {
+        try file.read(into: buffer, frameCount: AVAudioFrameCount(min(chunkFrames, remaining)))
+        guard buffer.frameLength > 0 else { break }
+        try output.write(from: buffer)
+        remaining -= Int(buffer.frameLength)
+    }
+    return (trimmedURL, leading, trailing)
+}
+
+// Wraps a headerless PCM file (--raw-format) in a temp CAF the recognizers can read.
+// Samples are little-endian: s16le, s24le, s32le or f32le, interleaved by channel.
+func wrapRawPCM(audioURL: URL, format: String, sampleRate: Double, channels: Int) throws -> URL {
+    let bytesPerSample: Int
+    switch format {
+    case "s16le": bytesPerSample = 2
+    case "s24le": bytesPerSample = 3
+    case "s32le", "f32le": bytesPerSample = 4
+    default: throw RequestError(message: "Error: Unknown raw PCM format: \(format)")
+    }
+    guard channels > 0, sampleRate > 0 else {
+        throw RequestError(message: "Error: Raw PCM needs a positive --sample-rate and --channels")
+    }
+    
+    let data = try Data(contentsOf: audioURL)
+    let frameSize = bytesPerSample * channels
+    guard data.count % frameSize == 0 else {
+        throw RequestError(message: "Error: Raw PCM size \(data.count) is not a multiple of the \(frameSize)-byte frame")
+    }
+    let frames = data.count / frameSize
+    
+    guard let pcmFormat = AVAudioFormat(
+            commonFormat: .pcmFormatFloat32,
+            sampleRate: sampleRate,
+            channels: AVAudioChannelCount(channels),
+            interleaved: false
+          ),
+          let buffer = AVAudioPCMBuffer(pcmFormat: pcmFormat, frameCapacity: AVAudioFrameCount(max(frames, 1))),
+          let output = buffer.floatChannelData else {
+        throw RequestError(message: "Error: Unsupported raw PCM layout")
+    }
+    
+    data.withUnsafeBytes { (raw: UnsafeRawBufferPointer) in
+        for frame in 0..<frames {
+            for channel in 0..<channels {
+                let offset = (frame * channels + channel) * bytesPerSample
+                let value: Float
+                switch format {
+                case "s16le":
+                    value = Float(Int16(littleEndian: raw.loadUnaligned(fromByteOffset: offset, as: Int16.self))) / 32768
+                case "s24le":
+                    let sample = Int32(raw[offset]) | Int32(raw[offset + 1]) << 8 | Int32(Int8(bitPattern: raw[offset + 2])) << 16
+                    value = Float(sample) / 8388608
+                case "s32le":
+                    value = Float(Int32(littleEndian: raw.loadUnaligned(fromByteOffset: offset, as: Int32.self))) / 2147483648
+                default:
+                    value = Float(bitPattern: UInt32(littleEndian: raw.loadUnaligned(fromByteOffset: offset, as: UInt32.self)))
+                }
+                output[channel][frame] = value
+            }
+        }
+    }
+    buffer.frameLength = AVAudioFrameCount(frames)
+    
+    // TMPDIR is honoured here, so the library's temp directory setting applies
+    let wrappedURL = FileManager.default.temporaryDirectory
+        .appendingPathComponent("transcribe-raw-\(UUID().uuidString).caf")
+    let file = try AVAudioFile(
+        forWriting: wrappedURL,
+        settings: pcmFormat.settings,
+        commonFormat: .pcmFormatFloat32,
+        interleaved: false
+    )
+    try file.write(from: buffer)
+    return wrappedURL
+}
+
+// One entry of the --list-tracks output
+struct TrackInfo: Codable {
+    let index: Int
+    let channels: Int
+    let sampleRate: Double
+    let duration: Double
+    let language: String?
+}
+
+func listTracks(audioURL: URL) -> [TrackInfo] {
+    let asset = AVURLAsset(url: audioURL)
+    return asset.tracks(withMediaType: .audio).enumerated().map { index, track in
+        let description = track.formatDescriptions.first.map { $0 as! CMAudioFormatDescription }
+        let format = description.flatMap { CMAudioFormatDescriptionGetStreamBasicDescription($0)?.pointee }
+        return TrackInfo(
+            index: index,
+            channels: Int(format?.mChannelsPerFrame ?? 0),
+            sampleRate: format?.mSampleRate ?? 0,
+            duration: track.timeRange.duration.seconds,
+            language: track.languageCode
+        )
+    }
+}
+
+// Whether the file has an audio track with something in it that can be played
+func hasPlayableAudio(audioURL: URL) -> Bool {
+    let tracks = AVURLAsset(url: audioURL).tracks(withMediaType: .audio)
+    return tracks.contains { $0.isPlayable && $0.timeRange.duration.seconds > 0 }
+}
+
+// Decodes audio track `index` to a temp file, for --track on multi-track files
+func extractTrack(audioURL: URL, index: Int) throws -> URL {
+    let asset = AVURLAsset(url: audioURL)
+    let tracks = asset.tracks(withMediaType: .audio)
+    guard index < tracks.count else {
+        throw RequestError(message: "Error: Track \(index) not found (the file has \(tracks.count) audio tracks)")
+    }
+    
+    let reader = try AVAssetReader(asset: asset)
+    let output = AVAssetReaderTrackOutput(track: tracks[index], outputSettings: [
+        AVFormatIDKey: kAudioFormatLinearPCM,
+        AVLinearPCMBitDepthKey: 32,
+        AVLinearPCMIsFloatKey: true,
+        AVLinearPCMIsBigEndianKey: false,
+        AVLinearPCMIsNonInterleaved: false,
+    ])
+    reader.add(output)
+    guard reader.startReading() else {
+        throw reader.error ?? RequestError(message: "Error: Failed to read track \(index)")
+    }
+    
+    // TMPDIR is honoured here, so the library's temp directory setting applies
+    let trackURL = FileManager.default.temporaryDirectory
+        .appendingPathComponent("transcribe-track-\(UUID().uuidString).caf")
+    var file: AVAudioFile?
+    do {
+        while let sample = output.copyNextSampleBuffer() {
+            guard let description = CMSampleBufferGetFormatDescription(sample) else { continue }
+            let format = AVAudioFormat(cmAudioFormatDescription: description)
+            let frames = AVAudioFrameCount(CMSampleBufferGetNumSamples(sample))
+            guard let buffer = AVAudioPCMBuffer(pcmFormat: format, frameCapacity: frames) else { continue }
+            buffer.frameLength = frames
+            let status = CMSampleBufferCopyPCMDataIntoAudioBufferList(
+                sample, at: 0, frameCount: Int32(frames), into: buffer.mutableAudioBufferList
+            )
+            guard status == noErr else { continue }
+            
+            if file == nil {
+                file = try AVAudioFile(
+                    forWriting: trackURL,
+                    settings: format.settings,
+                    commonFormat: format.commonFormat,
+                    interleaved: format.isInterleaved
+                )
+            }
+            try file?.write(from: buffer)
+        }
+        if reader.status == .failed {
+            throw reader.error ?? RequestError(message: "Error: Failed to read track \(index)")
+        }
+        guard file != nil else {
+            throw RequestError(message: "Error: Track \(index) contains no audio")
+        }
+    } catch {
+        try? FileManager.default.removeItem(at: trackURL)
+        throw error
+    }
+    return trackURL
+}
+
+// The locale --warm-up and --serve load: the one given with --locale, or the
+// first of --locales
+func warmUpLocale(arguments: [String]) -> Locale {
+    if let index = arguments.firstIndex(of: "--locale"), index + 1 < arguments.count {
+        return Locale(identifier: arguments[index + 1])
+    }
+    if let index = arguments.firstIndex(of: "--locales"), index + 1 < arguments.count,
+       let first = arguments[index + 1].split(separator: ",").first {
+        return Locale(identifier: String(first))
+    }
+    return Locale(identifier: "en-US")
+}
+
+// Loads the recognizer and its on-device model so the next request starts immediately
+@available(macOS 10.15, *)
+func warmUp(locale: Locale) async throws {
+    
+    if #available(macOS 26.0, *) {
+        let transcriber = SpeechTranscriber(locale: locale, preset: .transcription)
+        if let request = try await AssetInventory.assetInstallationRequest(supporting: [transcriber]) {
+            try await request.downloadAndInstall()
+        }
+        let analyzer = SpeechAnalyzer(modules: [transcriber])
+        let format = await SpeechAnalyzer.bestAvailableAudioFormat(compatibleWith: [transcriber])
+        try await analyzer.prepareToAnalyze(in: format)
+        await analyzer.cancelAndFinishNow()
+    } else {
+        guard let recognizer = SFSpeechRecognizer(locale: locale), recognizer.isAvailable else {
+            throw NSError(domain: "SpeechRecognition", code: 2, userInfo: [NSLocalizedDescriptionKey: "Speech recognizer not available"])
+        }
+    }
+}
+
+// --locale-status output: progress lines while downloading, then the status
+struct LocaleProgress: Codable {
+    let progress: Double
+}
+
+struct LocaleStatusOutput: Codable {
+    let status: String
+}
+
+// --authorization output
+struct AuthorizationOutput: Codable {
+    let authorization: String
+}
+
+// --backend-info output
+struct BackendInfoOutput: Codable {
+    let backend: String
+    let os_version: String
+}
+
+// The recognition engine this system uses, named as in FileResult
+func currentBackend() -> String {
+    if #available(macOS 26.0, *) {
+        return "speech_analyzer"
+    }
+    return "sf_speech_recognizer"
+}
+
+func osVersionString() -> String {
+    let version = ProcessInfo.processInfo.operatingSystemVersion
+    return "\(version.majorVersion).\(version.minorVersion)"
+}
+
+// Whether file transcription may use speech recognition: SpeechAnalyzer needs
+// no authorization; SFSpeechRecognizer reports authorized, denied, restricted
+// or not_determined (this helper never prompts, so that means not granted)
+func authorizationStatus() -> String {
+    if #available(macOS 26.0, *) {
+        return "not_required"
+    }
+    switch SFSpeechRecognizer.authorizationStatus() {
+    case .authorized: return "authorized"
+    case .denied: return "denied"
+    case .restricted: return "restricted"
+    case .notDetermined: return "not_determined"
+    @unknown default: return "not_determined"
+    }
+}
+
+func emit<T: Encodable>(_ value: T) {
+    if let data = try? JSONEncoder().encode(value), let line = String(data: data, encoding: .utf8) {
+        print(line)
+        fflush(stdout)
+    }
+}
+
+// Reports whether on-device recognition assets for `identifier` are installed,
+// downloading them first when `download` is set: installed, not_installed or unsupported
+@available(macOS 10.15, *)
+func checkLocale(identifier: String, download: Bool) async throws -> String {
+    let locale = Locale(identifier: identifier)
+    
+    if #available(macOS 26.0, *) {
+        let bcp47 = locale.identifier(.bcp47)
+        let supportedLocales = await SpeechTranscriber.supportedLocales
+        guard supportedLocales.map({ $0.identifier(.bcp47) }).contains(bcp47) else {
+            return "unsupported"
+        }
+        let installedLocales = await SpeechTranscriber.installedLocales
+        if installedLocales.map({ $0.identifier(.bcp47) }).contains(bcp47) {
+            return "installed"
+        }
+        guard download else {
+            return "not_installed"
+        }
+        
+        let transcriber = SpeechTranscriber(locale: locale, preset: .transcription)
+        if let request = try await AssetInventory.assetInstallationRequest(supporting: [transcriber]) {
+            let reporter = Task {
+                while !Task.isCancelled {
+                    emit(LocaleProgress(progress: request.progress.fractionCompleted))
+                    try? await Task.sleep(nanoseconds: 250_000_000)
+                }
+            }
+            defer { reporter.cancel() }
+            try await request.downloadAndInstall()
+        }
+        emit(LocaleProgress(progress: 1))
+        return "installed"
+    } else {
+        guard let recognizer = SFSpeechRecognizer(locale: locale) else {
+            return "unsupported"
+        }
+        // The legacy API has no download hook; the system fetches models on its own
+        return recognizer.supportsOnDeviceRecognition ? "installed" : "not_installed"
+    }
+}
+
+struct RequestError: Error {
+    let message: String
+}
+
+// Parses the value of --task-hint; SpeechAnalyzer has no equivalent, so only the
+// legacy recognizer uses it
+@available(macOS 10.15, *)
+func parseTaskHint(_ value: String?) throws -> SFSpeechRecognitionTaskHint {
+    switch value {
+    case "dictation": return .dictation
+    case "search": return .search
+    case "confirmation": return .confirmation
+    case "unspecified": return .unspecified
+    default: throw RequestError(message: "Error: --task-hint expects dictation, search, confirmation or unspecified")
+    }
+}
+
+// Words of a --reference script to bias recognition toward, deduplicated and
+// capped at the 100 contextual strings the recognizers handle well
+func contextualStrings(from reference: String?) -> [String] {
+    guard let reference = reference else { return [] }
+    var seen = Set<String>()
+    var words: [String] = []
+    for word in reference.split(whereSeparator: { $0.isWhitespace }) {
+        let word = word.trimmingCharacters(in: .punctuationCharacters)
+        if !word.isEmpty && seen.insert(word.lowercased()).inserted {
+            words.append(word)
+        }
+    }
+    return Array(words.prefix(100))
+}
+
+// Handles one request: [--trim-silence] [--json | --segments] [--track <n>] [--task-hint <hint>] [--locale <id> | --locales <a,b,...>] [--reference <text>] <audio-file-path>,
+// [--trim-silence] [--json] --raw-format <fmt> --sample-rate <hz> --channels <n> <raw-file-path>,
+// or --list-tracks <audio-file-path>
+// Returns what would be printed to stdout
+@available(macOS 10.15, *)
+func runRequest(arguments: [String]) async throws -> String {
+    var trimSilenceEnabled = false
+    var jsonOutput = false
+    var segmentsOutput = false
+    var listTracksOnly = false
+    var track = 0
+    var rawFormat: String?
+    var rawSampleRate = 0.0
+    var rawChannels = 0
+    var taskHint = SFSpeechRecognitionTaskHint.unspecified
+    var reference: String?
+    var candidateLocales: [String] = []
+    var audioPath: String?
+    
+    var remaining = arguments.makeIterator()
+    while let argument = remaining.next() {
+        switch argument {
+        case "--trim-silence":
+            trimSilenceEnabled = true
+        case "--json":
+            jsonOutput = true
+        case "--segments":
+            segmentsOutput = true
+        case "--list-tracks":
+            listTracksOnly = true
+        case "--track":
+            guard let value = remaining.next(), let index = Int(value), index >= 0 else {
+                throw RequestError(message: "Error: --track expects a track index")
+            }
+            track = index
+        case "--raw-format":
+            rawFormat = remaining.next()
+        case "--sample-rate":
+            rawSampleRate = remaining.next().flatMap(Double.init) ?? 0
+        case "--channels":
+            rawChannels = remaining.next().flatMap(Int.init) ?? 0
+        case "--task-hint":
+            taskHint = try parseTaskHint(remaining.next())
+        case "--reference":
+            reference = remaining.next()
+        case "--locales":
+            candidateLocales = (remaining.next() ?? "").split(separator: ",").map(String.init)
+        case "--locale":
+            // A single candidate has no fallback: an unsupported one fails the request
+            guard let value = remaining.next() else {
+                throw RequestError(message: "Error: --locale expects a locale identifier")
+            }
+            candidateLocales = [value]
+        default:
+            audioPath = argument
+        }
+    }
+    
+    guard let audioPath = audioPath else {
+        throw RequestError(message: "Usage: transcribe [--warm-up | --serve] [--trim-silence] [--json | --segments] [--track <n>] [--task-hint <hint>] [--locale <id> | --locales <a,b,...>] [--reference <text>] <audio-file-path>\n       transcribe --list-tracks <audio-file-path>")
     }
     
-    let audioPath = CommandLine.arguments[1]
     let audioURL = URL(fileURLWithPath: audioPath)
     
     guard FileManager.default.fileExists(atPath: audioPath) else {
-        fputs("Error: File not found: \(audioPath)\n", stderr)
-        exit(1)
+        throw RequestError(message: "Error: File not found: \(audioPath)")
     }
     
-    do {
-        var transcription: String
+    if listTracksOnly {
+        let data = try JSONEncoder().encode(listTracks(audioURL: audioURL))
+        return String(data: data, encoding: .utf8) ?? "[]"
+    }
+    
+    // Video-only files and files whose audio track can't be decoded; the library
+    // recognizes this message and reports the file as having no audio track
+    if rawFormat == nil && !hasPlayableAudio(audioURL: audioURL) {
+        throw RequestError(message: "Error: No audio track in \(audioPath)")
+    }
+    
+    // Raw PCM and tracks other than 0 (which AVAudioFile reads anyway) are decoded
+    // to a temp file first
+    let sourceURL: URL
+    if let rawFormat = rawFormat {
+        sourceURL = try wrapRawPCM(audioURL: audioURL, format: rawFormat, sampleRate: rawSampleRate, channels: rawChannels)
+    } else if track != 0 {
+        sourceURL = try extractTrack(audioURL: audioURL, index: track)
+    } else {
+        sourceURL = audioURL
+    }
+    defer {
+        if sourceURL != audioURL {
+            try? FileManager.default.removeItem(at: sourceURL)
+        }
+    }
+    
+    let locale = await selectLocale(candidates: candidateLocales)
+    var transcription = ""
+    var segments: [Segment] = []
+    var recognitionURL: URL? = sourceURL
+    var trimmedLeading: Double?
+    var trimmedTrailing: Double?
+    
+    let duration = (try? AVAudioFile(forReading: sourceURL)).map {
+        Double($0.length) / $0.processingFormat.sampleRate
+    }
+    let backend = currentBackend()
+    
+    if trimSilenceEnabled {
+        let trimmed = try trimSilence(audioURL: sourceURL)
+        recognitionURL = trimmed.url
+        trimmedLeading = trimmed.leading
+        trimmedTrailing = trimmed.trailing
+    }
+    // Print each segment as one JSON line as soon as it is finalized, against the
+    // original file, so long files never have to be held as a whole
+    if segmentsOutput {
+        let offset = trimmedLeading ?? 0
+        segmentHandler = {
+            emit(Segment(text: $0.text, start: $0.start + offset, duration: $0.duration, confidence: $0.confidence, locale: $0.locale))
+        }
+    }
+    defer { segmentHandler = nil }
+    if let url = recognitionURL {
+        defer {
+            if url != sourceURL {
+                try? FileManager.default.removeItem(at: url)
+            }
+        }
         
         // Use SpeechAnalyzer on macOS 26+, fallback to legacy API otherwise
         if #available(macOS 26.0, *) {
-            transcription = try await transcribeWithSpeechAnalyzer(audioURL: audioURL)
+            (transcription, segments) = try await transcribeWithSpeechAnalyzer(audioURL: url, locale: locale, contextualStrings: contextualStrings(from: reference))
         } else {
-            transcription = try await transcribeWithLegacyAPI(audioURL: audioURL)
+            (transcription, segments) = try await transcribeWithLegacyAPI(audioURL: url, locale: locale, taskHint: taskHint, contextualStrings: contextualStrings(from: reference))
         }
-        
-        print(transcription)
+    }
+    
+    // Report segment times against the original file, not the trimmed copy
+    if let offset = trimmedLeading, offset > 0 {
+        segments = segments.map {
+            Segment(text: $0.text, start: $0.start + offset, duration: $0.duration, confidence: $0.confidence, locale: $0.locale)
+        }
+    }
+    
+    if segmentsOutput {
+        return ""
+    }
+    if jsonOutput {
+        let result = FileResult(
+            text: transcription,
+            segments: segments,
+            trimmedLeading: trimmedLeading,
+            trimmedTrailing: trimmedTrailing,
+            backend: backend,
+            locale: locale.identifier,
+            duration: duration
+        )
+        let data = try JSONEncoder().encode(result)
+        return String(data: data, encoding: .utf8) ?? "{}"
+    }
+    return transcription
+}
+
+// One response line per request in --serve mode
+struct ServeResponse: Codable {
+    let ok: Bool
+    let output: String?
+    let error: String?
+}
+
+func describe(_ error: Error) -> String {
+    if let error = error as? RequestError {
+        return error.message
+    }
+    return "Error: \(error.localizedDescription)"
+}
+
+func respond(_ response: ServeResponse) {
+    let data = (try? JSONEncoder().encode(response)) ?? Data()
+    print(String(data: data, encoding: .utf8) ?? "{\"ok\":false}")
+    fflush(stdout)
+}
+
+// Stays resident, reading one JSON array of request arguments per stdin line.
+// Sends a "ready" response once the model is loaded, then one response per request.
+@available(macOS 10.15, *)
+func serve(locale: Locale) async -> Never {
+    do {
+        try await warmUp(locale: locale)
+        respond(ServeResponse(ok: true, output: "ready", error: nil))
+    } catch {
+        respond(ServeResponse(ok: false, output: nil, error: describe(error)))
+        exit(1)
+    }
+    
+    while let line = readLine() {
+        guard let data = line.data(using: .utf8),
+              let arguments = try? JSONDecoder().decode([String].self, from: data) else {
+            respond(ServeResponse(ok: false, output: nil, error: "Error: Malformed request: \(line)"))
+            continue
+        }
+        do {
+            let output = try await runRequest(arguments: arguments)
+            respond(ServeResponse(ok: true, output: output, error: nil))
+        } catch {
+            respond(ServeResponse(ok: false, output: nil, error: describe(error)))
+        }
+    }
+    exit(0)
+}
+
+// Main execution
+@available(macOS 10.15, *)
+@MainActor
+func main() async {
+    let arguments = Array(CommandLine.arguments.dropFirst())
+    
+    if arguments.contains("--serve") {
+        await serve(locale: warmUpLocale(arguments: arguments))
+    }
+    
+    if arguments.contains("--warm-up") {
+        do {
+            try await warmUp(locale: warmUpLocale(arguments: arguments))
+            exit(0)
+        } catch {
+            fputs("\(describe(error))\n", stderr)
+            exit(1)
+        }
+    }
+    
+    if arguments.contains("--authorization") {
+        emit(AuthorizationOutput(authorization: authorizationStatus()))
+        exit(0)
+    }
+    
+    if arguments.contains("--backend-info") {
+        emit(BackendInfoOutput(backend: currentBackend(), os_version: osVersionString()))
+        exit(0)
+    }
+    
+    if let index = arguments.firstIndex(of: "--locale-status"), index + 1 < arguments.count {
+        do {
+            let status = try await checkLocale(
+                identifier: arguments[index + 1],
+                download: arguments.contains("--download")
+            )
+            emit(LocaleStatusOutput(status: status))
+            exit(0)
+        } catch {
+            fputs("\(describe(error))\n", stderr)
+            exit(1)
+        }
+    }
+    
+    do {
+        print(try await runRequest(arguments: arguments))
         exit(0)
     } catch {
-        fputs("Error: \(error.localizedDescription)\n", stderr)
+        fputs("\(describe(error))\n", stderr)
         exit(1)
     }
 }
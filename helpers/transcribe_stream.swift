@@ -6,17 +6,264 @@ import Speech
 struct TranscriptionOutput: Codable {
     let text: String
     let isFinal: Bool
+    // "volatile", "final", "committed" once the recognizer won't revise it, or
+    // "endofspeech" (with no text) when the speaker stops talking
+    let kind: String
     let timestamp: Double
+    // Other readings of a final, only with --alternatives
+    var alternatives: [String]? = nil
+    // Leading words of a partial the recognizer has settled on, only with --stable-text
+    var stableText: String? = nil
+    // Timing of each word, in seconds from the start of the session's audio
+    var words: [WordTiming]? = nil
+}
+
+struct WordTiming: Codable {
+    let text: String
+    let start: Double
+    let end: Double
+    let confidence: Double?
+}
+
+// Set by --alternatives: report other readings of each final result
+var wantAlternatives = false
+
+// Set by --stable-text: mark the settled part of each partial result
+var wantStableText = false
+
+// Splits partials into a settled prefix and a volatile tail. Neither backend
+// reports per-word stability, so a word counts as settled once the recognizer
+// has kept it, and everything before it, through a revision of its hypothesis.
+final class StableTextTracker {
+    private var previous: [Substring] = []
+
+    // The settled prefix of a partial's text; finals are settled as a whole and
+    // start the next segment afresh
+    func mark(_ text: String, isFinal: Bool) -> String? {
+        guard wantStableText else { return nil }
+        let words = text.split(separator: " ")
+        defer { previous = isFinal ? [] : words }
+        guard !isFinal else { return nil }
+        let settled = zip(words, previous).prefix { $0 == $1 }.count
+        return words.prefix(settled).joined(separator: " ")
+    }
+}
+
+// One session per process, so one tracker
+let stableTexts = StableTextTracker()
+
+// The progressive preset with word time ranges and confidences, plus
+// alternative transcriptions when they are wanted
+@available(macOS 26.0, *)
+func makeSpeechTranscriber(locale: Locale) -> SpeechTranscriber {
+    var reporting: Set<SpeechTranscriber.ReportingOption> = [.volatileResults, .fastResults]
+    if wantAlternatives {
+        reporting.insert(.alternativeTranscriptions)
+    }
+    return SpeechTranscriber(
+        locale: locale,
+        transcriptionOptions: [],
+        reportingOptions: reporting,
+        attributeOptions: [.audioTimeRange, .transcriptionConfidence]
+    )
+}
+
+// Timing of each word of a result, from the time ranges of its text's runs
+@available(macOS 26.0, *)
+func wordTimings(_ result: SpeechTranscriber.Result) -> [WordTiming]? {
+    var words: [WordTiming] = []
+    for run in result.text.runs {
+        let text = String(result.text[run.range].characters)
+            .trimmingCharacters(in: .whitespaces)
+        guard !text.isEmpty, let range = run.audioTimeRange else { continue }
+        words.append(WordTiming(
+            text: text,
+            start: range.start.seconds,
+            end: range.end.seconds,
+            confidence: run.transcriptionConfidence
+        ))
+    }
+    return words.isEmpty ? nil : words
+}
+
+// The legacy recognizer only reports confidences on final results
+@available(macOS 10.15, *)
+func wordTimings(_ result: SFSpeechRecognitionResult) -> [WordTiming]? {
+    let words = result.bestTranscription.segments.map { segment in
+        WordTiming(
+            text: segment.substring,
+            start: segment.timestamp,
+            end: segment.timestamp + segment.duration,
+            confidence: result.isFinal ? Double(segment.confidence) : nil
+        )
+    }
+    return words.isEmpty ? nil : words
+}
+
+// Alternatives of a final result other than its text; partials get none, as
+// they are replaced too quickly for anyone to pick from them
+@available(macOS 26.0, *)
+func alternativeTexts(_ result: SpeechTranscriber.Result) -> [String]? {
+    guard wantAlternatives, result.isFinal else { return nil }
+    let text = String(result.text.characters)
+    return result.alternatives.map { String($0.characters) }.filter { $0 != text }
+}
+
+@available(macOS 10.15, *)
+func alternativeTexts(_ result: SFSpeechRecognitionResult) -> [String]? {
+    guard wantAlternatives, result.isFinal else { return nil }
+    let text = result.bestTranscription.formattedString
+    return result.transcriptions.map(\.formattedString).filter { $0 != text }
+}
+
+// A final result is committed once the analyzer's finalization time has passed
+// its end; until then, earlier audio may still be reinterpreted. The kind is
+// only reported once, so a final isn't re-sent when it later becomes committed
+@available(macOS 26.0, *)
+func resultKind(_ result: SpeechTranscriber.Result) -> String {
+    guard result.isFinal else { return "volatile" }
+    return CMTimeCompare(result.range.end, result.resultsFinalizationTime) <= 0 ? "committed" : "final"
+}
+
+// Speech detection for live sessions, reporting each end of speech to Rust
+@available(macOS 26.0, *)
+func makeSpeechDetector() -> SpeechDetector {
+    SpeechDetector(detectionOptions: .init(sensitivityLevel: .medium), reportResults: true)
+}
+
+// Prints an "endofspeech" event whenever the detector reports that speech
+// stopped; it usually arrives ahead of the final result for that speech
+@available(macOS 26.0, *)
+func reportEndsOfSpeech(_ detector: SpeechDetector) async {
+    var speaking = false
+    do {
+        for try await result in detector.results {
+            if speaking && !result.speechDetected {
+                let output = TranscriptionOutput(
+                    text: "",
+                    isFinal: false,
+                    kind: "endofspeech",
+                    timestamp: Date().timeIntervalSince1970
+                )
+                if let jsonData = try? JSONEncoder().encode(output),
+                   let jsonString = String(data: jsonData, encoding: .utf8) {
+                    print(jsonString)
+                    fflush(stdout)
+                }
+            }
+            speaking = result.speechDetected
+        }
+    } catch {
+        fputs("Error detecting speech: \(error.localizedDescription)\n", stderr)
+    }
+}
+
+// Copies captured microphone audio to a file descriptor (--audio-out <fd>) as
+// 16kHz mono s16le, the same format the --stdin mode accepts
+final class AudioPassthrough {
+    private let fd: Int32
+    private let format = AVAudioFormat(commonFormat: .pcmFormatInt16, sampleRate: 16000, channels: 1, interleaved: true)!
+    private var converter: AVAudioConverter?
+    
+    init(fd: Int32) {
+        self.fd = fd
+        // Never stall capture on a reader that has fallen behind
+        let flags = fcntl(fd, F_GETFL)
+        if flags != -1 {
+            _ = fcntl(fd, F_SETFL, flags | O_NONBLOCK)
+        }
+    }
+    
+    func write(_ buffer: AVAudioPCMBuffer) {
+        if converter?.inputFormat != buffer.format {
+            converter = AVAudioConverter(from: buffer.format, to: format)
+        }
+        guard let converter = converter else { return }
+        
+        let capacity = AVAudioFrameCount(Double(buffer.frameLength) * format.sampleRate / buffer.format.sampleRate) + 1
+        guard let converted = AVAudioPCMBuffer(pcmFormat: format, frameCapacity: capacity) else { return }
+        
+        var consumed = false
+        var error: NSError?
+        converter.convert(to: converted, error: &error) { _, outStatus in
+            if consumed {
+                outStatus.pointee = .noDataNow
+                return nil
+            }
+            consumed = true
+            outStatus.pointee = .haveData
+            return buffer
+        }
+        guard error == nil, let samples = converted.int16ChannelData else { return }
+        
+        let bytes = UnsafeRawPointer(samples[0])
+        let total = Int(converted.frameLength) * MemoryLayout<Int16>.size
+        var written = 0
+        while written < total {
+            let n = Darwin.write(fd, bytes + written, total - written)
+            if n > 0 {
+                written += n
+            } else if n < 0 && errno == EAGAIN && written > 0 {
+                // Finish a partly written buffer so samples stay aligned
+                usleep(1000)
+            } else {
+                // Pipe full (drop this buffer) or the reader went away
+                return
+            }
+        }
+    }
+}
+
+// Set from --audio-out <fd> for microphone sessions
+var audioPassthrough: AudioPassthrough?
+
+// Set from --buffer-frames <n> for microphone sessions; the engine treats it as a request
+var micBufferFrames: AVAudioFrameCount?
+
+// Set from --task-hint <hint>; only the legacy recognizer has task hints
+var taskHint = SFSpeechRecognitionTaskHint.unspecified
+
+// Set from --locale <id>; sessions start recognizing in it
+var sessionLocale = Locale(identifier: "en-US")
+
+// Words of the previous transcript's tail, from --context <text>, to bias
+// recognition toward when a session continues an earlier one
+var contextualStrings: [String] = []
+
+// Words of `text` to bias recognition toward, deduplicated and capped at the
+// 100 contextual strings the recognizers handle well; later words win the cap
+func contextWords(from text: String) -> [String] {
+    var seen = Set<String>()
+    var words: [String] = []
+    for word in text.split(whereSeparator: { $0.isWhitespace }).reversed() {
+        let word = word.trimmingCharacters(in: .punctuationCharacters)
+        if !word.isEmpty && seen.insert(word.lowercased()).inserted {
+            words.append(word)
+        }
+    }
+    return Array(words.prefix(100).reversed())
+}
+
+// Hands the --context words to a new analyzer
+@available(macOS 26.0, *)
+func applyContext(to analyzer: SpeechAnalyzer) async throws {
+    guard !contextualStrings.isEmpty else { return }
+    let context = AnalysisContext()
+    context.contextualStrings[.general] = contextualStrings
+    try await analyzer.setContext(context)
 }
 
 // Modern SpeechAnalyzer API with microphone input (macOS 26+)
 @available(macOS 26.0, *)
 class StreamingTranscriber {
-    private let locale: Locale
+    private var locale: Locale
     private let audioEngine: AVAudioEngine
     private var analyzer: SpeechAnalyzer?
     private var transcriber: SpeechTranscriber?
+    private var detector: SpeechDetector?
     private var inputBuilder: AsyncStream<AnalyzerInput>.Continuation?
+    private var resultsTask: Task<Void, Never>?
+    private var speechTask: Task<Void, Never>?
     
     init(locale: Locale = Locale(identifier: "en-US")) {
         self.locale = locale
@@ -41,12 +288,15 @@ class StreamingTranscriber {
         }
         
         // Initialize transcriber with progressive preset for real-time results
-        let transcriber = SpeechTranscriber(locale: locale, preset: .progressiveTranscription)
+        let transcriber = makeSpeechTranscriber(locale: locale)
         self.transcriber = transcriber
         
-        // Create analyzer
-        let modules: [any SpeechModule] = [transcriber]
+        // Create analyzer, with a detector to report where speech ends
+        let detector = makeSpeechDetector()
+        self.detector = detector
+        let modules: [any SpeechModule] = [transcriber, detector]
         let analyzer = SpeechAnalyzer(modules: modules)
+        try await applyContext(to: analyzer)
         self.analyzer = analyzer
         
         // Get best audio format for the analyzer
@@ -76,8 +326,9 @@ class StreamingTranscriber {
         self.inputBuilder = inputBuilder
         
         // Install tap on microphone input
-        inputNode.installTap(onBus: 0, bufferSize: 4096, format: inputFormat) { [weak self] buffer, _ in
+        inputNode.installTap(onBus: 0, bufferSize: micBufferFrames ?? 4096, format: inputFormat) { [weak self] buffer, _ in
             guard let self = self else { return }
+            audioPassthrough?.write(buffer)
             
             // Convert audio format if needed
             let convertedBuffer = self.convertAudioBuffer(buffer, using: converter, to: audioFormat)
@@ -94,9 +345,12 @@ class StreamingTranscriber {
         try await analyzer.start(inputSequence: inputSequence)
         
         // Process results in parallel
-        Task {
+        resultsTask = Task {
             await self.processResults()
         }
+        speechTask = Task {
+            await reportEndsOfSpeech(detector)
+        }
     }
     
     private func convertAudioBuffer(_ buffer: AVAudioPCMBuffer, using converter: AVAudioConverter, to format: AVAudioFormat) -> AVAudioPCMBuffer {
@@ -132,7 +386,11 @@ class StreamingTranscriber {
                 let output = TranscriptionOutput(
                     text: String(result.text.characters),
                     isFinal: result.isFinal,
-                    timestamp: Date().timeIntervalSince1970
+                    kind: resultKind(result),
+                    timestamp: Date().timeIntervalSince1970,
+                    alternatives: alternativeTexts(result),
+                    stableText: stableTexts.mark(String(result.text.characters), isFinal: result.isFinal),
+                    words: wordTimings(result)
                 )
                 
                 // Output as JSON to stdout
@@ -169,6 +427,7 @@ class LegacyStreamingTranscriber {
     private var recognizer: SFSpeechRecognizer?
     private var recognitionRequest: SFSpeechAudioBufferRecognitionRequest?
     private var recognitionTask: SFSpeechRecognitionTask?
+    private var finalReported = false
     
     init(locale: Locale = Locale(identifier: "en-US")) {
         self.locale = locale
@@ -196,19 +455,30 @@ class LegacyStreamingTranscriber {
         
         let request = SFSpeechAudioBufferRecognitionRequest()
         request.shouldReportPartialResults = true
+        request.taskHint = taskHint
+        request.contextualStrings = contextualStrings
         self.recognitionRequest = request
         
-        recognitionTask = recognizer.recognitionTask(with: request) { result, error in
+        recognitionTask = recognizer.recognitionTask(with: request) { [weak self] result, error in
             if let error = error {
                 fputs("Recognition error: \(error.localizedDescription)\n", stderr)
+                self?.finalReported = true
                 return
             }
             
             if let result = result {
+                if result.isFinal {
+                    self?.finalReported = true
+                }
                 let output = TranscriptionOutput(
                     text: result.bestTranscription.formattedString,
                     isFinal: result.isFinal,
-                    timestamp: Date().timeIntervalSince1970
+                    // The legacy recognizer stops after its final result, so nothing gets revised
+                    kind: result.isFinal ? "committed" : "volatile",
+                    timestamp: Date().timeIntervalSince1970,
+                    alternatives: alternativeTexts(result),
+                    stableText: stableTexts.mark(result.bestTranscription.formattedString, isFinal: result.isFinal),
+                    words: wordTimings(result)
                 )
                 
                 if let jsonData = try? JSONEncoder().encode(output),
@@ -222,7 +492,8 @@ class LegacyStreamingTranscriber {
         let inputNode = audioEngine.inputNode
         let recordingFormat = inputNode.outputFormat(forBus: 0)
         
-        inputNode.installTap(onBus: 0, bufferSize: 1024, format: recordingFormat) { [weak self] buffer, _ in
+        inputNode.installTap(onBus: 0, bufferSize: micBufferFrames ?? 1024, format: recordingFormat) { [weak self] buffer, _ in
+            audioPassthrough?.write(buffer)
             self?.recognitionRequest?.append(buffer)
         }
         
@@ -237,13 +508,152 @@ class LegacyStreamingTranscriber {
     }
 }
 
+// Decodes a CAF, AIFF or WAV byte stream arriving on stdin in arbitrary chunks.
+// --stdin input that starts with one of these containers' magic numbers is
+// decoded with this instead of being read as raw 16kHz PCM.
+final class EncodedAudioDecoder {
+    private let output: AVAudioFormat
+    private let sink: (AVAudioPCMBuffer) -> Void
+    private var stream: AudioFileStreamID?
+    private var sourceFormat: AVAudioFormat?
+    private var converter: AVAudioConverter?
+    
+    static func fileType(of bytes: [UInt8], count: Int) -> AudioFileTypeID? {
+        guard count >= 4 else { return nil }
+        switch String(bytes: bytes[0..<4], encoding: .ascii) {
+        case "caff": return kAudioFileCAFType
+        case "FORM": return kAudioFileAIFFType
+        case "RIFF": return kAudioFileWAVEType
+        default: return nil
+        }
+    }
+    
+    init?(fileType: AudioFileTypeID, output: AVAudioFormat, sink: @escaping (AVAudioPCMBuffer) -> Void) {
+        self.output = output
+        self.sink = sink
+        
+        let context = Unmanaged.passUnretained(self).toOpaque()
+        let status = AudioFileStreamOpen(
+            context,
+            { context, stream, property, _ in
+                Unmanaged<EncodedAudioDecoder>.fromOpaque(context).takeUnretainedValue()
+                    .propertyChanged(stream, property)
+            },
+            { context, byteCount, packetCount, data, descriptions in
+                Unmanaged<EncodedAudioDecoder>.fromOpaque(context).takeUnretainedValue()
+                    .decode(byteCount, packetCount, data, descriptions)
+            },
+            fileType,
+            &stream
+        )
+        if status != noErr {
+            return nil
+        }
+    }
+    
+    deinit {
+        if let stream = stream {
+            AudioFileStreamClose(stream)
+        }
+    }
+    
+    func parse(_ bytes: [UInt8], count: Int) {
+        guard let stream = stream else { return }
+        let status = AudioFileStreamParseBytes(stream, UInt32(count), bytes, [])
+        if status != noErr {
+            fputs("Error: failed to parse encoded audio (OSStatus \(status))\n", stderr)
+        }
+    }
+    
+    private func propertyChanged(_ stream: AudioFileStreamID, _ property: AudioFileStreamPropertyID) {
+        guard property == kAudioFileStreamProperty_ReadyToProducePackets else { return }
+        
+        var description = AudioStreamBasicDescription()
+        var size = UInt32(MemoryLayout<AudioStreamBasicDescription>.size)
+        guard AudioFileStreamGetProperty(stream, kAudioFileStreamProperty_DataFormat, &size, &description) == noErr,
+              let format = AVAudioFormat(streamDescription: &description),
+              let converter = AVAudioConverter(from: format, to: output) else {
+            fputs("Error: unsupported encoded audio format\n", stderr)
+            return
+        }
+        
+        // Compressed payloads (e.g. AAC in CAF) need the codec configuration
+        var cookieSize: UInt32 = 0
+        if AudioFileStreamGetPropertyInfo(stream, kAudioFileStreamProperty_MagicCookieData, &cookieSize, nil) == noErr,
+           cookieSize > 0 {
+            var cookie = [UInt8](repeating: 0, count: Int(cookieSize))
+            if AudioFileStreamGetProperty(stream, kAudioFileStreamProperty_MagicCookieData, &cookieSize, &cookie) == noErr {
+                converter.magicCookie = Data(cookie)
+            }
+        }
+        
+        self.sourceFormat = format
+        self.converter = converter
+    }
+    
+    private func decode(_ byteCount: UInt32, _ packetCount: UInt32, _ data: UnsafeRawPointer,
+                        _ descriptions: UnsafeMutablePointer<AudioStreamPacketDescription>?) {
+        guard let sourceFormat = sourceFormat, let converter = converter else { return }
+        let source = sourceFormat.streamDescription.pointee
+        
+        let input: AVAudioBuffer
+        let frames: AVAudioFrameCount
+        if let descriptions = descriptions {
+            let buffer = AVAudioCompressedBuffer(
+                format: sourceFormat,
+                packetCapacity: packetCount,
+                maximumPacketSize: Int(byteCount)
+            )
+            buffer.data.copyMemory(from: data, byteCount: Int(byteCount))
+            buffer.packetDescriptions?.update(from: descriptions, count: Int(packetCount))
+            buffer.packetCount = packetCount
+            buffer.byteLength = byteCount
+            input = buffer
+            frames = packetCount * source.mFramesPerPacket
+        } else {
+            guard source.mBytesPerFrame > 0 else { return }
+            frames = byteCount / source.mBytesPerFrame
+            guard let buffer = AVAudioPCMBuffer(pcmFormat: sourceFormat, frameCapacity: frames),
+                  let destination = buffer.mutableAudioBufferList.pointee.mBuffers.mData else { return }
+            buffer.frameLength = frames
+            destination.copyMemory(from: data, byteCount: Int(byteCount))
+            input = buffer
+        }
+        
+        let capacity = AVAudioFrameCount(Double(frames) * output.sampleRate / sourceFormat.sampleRate) + 1
+        guard let converted = AVAudioPCMBuffer(pcmFormat: output, frameCapacity: capacity) else { return }
+        
+        var consumed = false
+        var error: NSError?
+        converter.convert(to: converted, error: &error) { _, outStatus in
+            if consumed {
+                outStatus.pointee = .noDataNow
+                return nil
+            }
+            consumed = true
+            outStatus.pointee = .haveData
+            return input
+        }
+        if let error = error {
+            fputs("Error: failed to decode audio: \(error.localizedDescription)\n", stderr)
+            return
+        }
+        if converted.frameLength > 0 {
+            sink(converted)
+        }
+    }
+}
+
 // Modern SpeechAnalyzer with stdin audio input (macOS 26+)
 @available(macOS 26.0, *)
 class StdinStreamingTranscriber {
-    private let locale: Locale
+    private var locale: Locale
     private var analyzer: SpeechAnalyzer?
     private var transcriber: SpeechTranscriber?
+    private var detector: SpeechDetector?
     private var inputBuilder: AsyncStream<AnalyzerInput>.Continuation?
+    private var resultsTask: Task<Void, Never>?
+    private var speechTask: Task<Void, Never>?
     
     init(locale: Locale = Locale(identifier: "en-US")) {
         self.locale = locale
@@ -261,12 +671,15 @@ class StdinStreamingTranscriber {
         }
         
         // Initialize transcriber with progressive preset
-        let transcriber = SpeechTranscriber(locale: locale, preset: .progressiveTranscription)
+        let transcriber = makeSpeechTranscriber(locale: locale)
         self.transcriber = transcriber
         
-        // Create analyzer
-        let modules: [any SpeechModule] = [transcriber]
+        // Create analyzer, with a detector to report where speech ends
+        let detector = makeSpeechDetector()
+        self.detector = detector
+        let modules: [any SpeechModule] = [transcriber, detector]
         let analyzer = SpeechAnalyzer(modules: modules)
+        try await applyContext(to: analyzer)
         self.analyzer = analyzer
         
         // Expected format: 16kHz, 16-bit, mono PCM
@@ -285,9 +698,12 @@ class StdinStreamingTranscriber {
         try await analyzer.start(inputSequence: inputSequence)
         
         // Process results in parallel
-        Task {
+        resultsTask = Task {
             await self.processResults()
         }
+        speechTask = Task {
+            await reportEndsOfSpeech(detector)
+        }
         
         // Read audio from stdin in background
         Task.detached {
@@ -298,6 +714,8 @@ class StdinStreamingTranscriber {
     private func readStdinAudio(format: AVAudioFormat, inputBuilder: AsyncStream<AnalyzerInput>.Continuation) async {
         let bufferSize = 4096
         var buffer = [UInt8](repeating: 0, count: bufferSize)
+        var decoder: EncodedAudioDecoder?
+        var firstRead = true
         
         while true {
             let bytesRead = fread(&buffer, 1, bufferSize, stdin)
@@ -305,6 +723,18 @@ class StdinStreamingTranscriber {
                 break
             }
             
+            // A container header at the very start selects encoded input
+            if firstRead, let fileType = EncodedAudioDecoder.fileType(of: buffer, count: bytesRead) {
+                decoder = EncodedAudioDecoder(fileType: fileType, output: format) { pcmBuffer in
+                    inputBuilder.yield(AnalyzerInput(buffer: pcmBuffer))
+                }
+            }
+            firstRead = false
+            if let decoder = decoder {
+                decoder.parse(buffer, count: bytesRead)
+                continue
+            }
+            
             // Convert raw bytes to AVAudioPCMBuffer
             if let pcmBuffer = self.createPCMBuffer(from: buffer, count: bytesRead, format: format) {
                 let input = AnalyzerInput(buffer: pcmBuffer)
@@ -342,7 +772,11 @@ class StdinStreamingTranscriber {
                 let output = TranscriptionOutput(
                     text: String(result.text.characters),
                     isFinal: result.isFinal,
-                    timestamp: Date().timeIntervalSince1970
+                    kind: resultKind(result),
+                    timestamp: Date().timeIntervalSince1970,
+                    alternatives: alternativeTexts(result),
+                    stableText: stableTexts.mark(String(result.text.characters), isFinal: result.isFinal),
+                    words: wordTimings(result)
                 )
                 
                 if let jsonData = try? JSONEncoder().encode(output),
@@ -371,6 +805,7 @@ class LegacyStdinStreamingTranscriber {
     private var recognizer: SFSpeechRecognizer?
     private var recognitionRequest: SFSpeechAudioBufferRecognitionRequest?
     private var recognitionTask: SFSpeechRecognitionTask?
+    private var finalReported = false
     
     init(locale: Locale = Locale(identifier: "en-US")) {
         self.locale = locale
@@ -397,19 +832,30 @@ class LegacyStdinStreamingTranscriber {
         
         let request = SFSpeechAudioBufferRecognitionRequest()
         request.shouldReportPartialResults = true
+        request.taskHint = taskHint
+        request.contextualStrings = contextualStrings
         self.recognitionRequest = request
         
-        recognitionTask = recognizer.recognitionTask(with: request) { result, error in
+        recognitionTask = recognizer.recognitionTask(with: request) { [weak self] result, error in
             if let error = error {
                 fputs("Recognition error: \(error.localizedDescription)\n", stderr)
+                self?.finalReported = true
                 return
             }
             
             if let result = result {
+                if result.isFinal {
+                    self?.finalReported = true
+                }
                 let output = TranscriptionOutput(
                     text: result.bestTranscription.formattedString,
                     isFinal: result.isFinal,
-                    timestamp: Date().timeIntervalSince1970
+                    // The legacy recognizer stops after its final result, so nothing gets revised
+                    kind: result.isFinal ? "committed" : "volatile",
+                    timestamp: Date().timeIntervalSince1970,
+                    alternatives: alternativeTexts(result),
+                    stableText: stableTexts.mark(result.bestTranscription.formattedString, isFinal: result.isFinal),
+                    words: wordTimings(result)
                 )
                 
                 if let jsonData = try? JSONEncoder().encode(output),
@@ -431,6 +877,8 @@ class LegacyStdinStreamingTranscriber {
             
             let bufferSize = 4096
             var buffer = [UInt8](repeating: 0, count: bufferSize)
+            var decoder: EncodedAudioDecoder?
+            var firstRead = true
             
             while true {
                 let bytesRead = fread(&buffer, 1, bufferSize, stdin)
@@ -438,6 +886,17 @@ class LegacyStdinStreamingTranscriber {
                     break
                 }
                 
+                if firstRead, let fileType = EncodedAudioDecoder.fileType(of: buffer, count: bytesRead) {
+                    decoder = EncodedAudioDecoder(fileType: fileType, output: format) { pcmBuffer in
+                        self.recognitionRequest?.append(pcmBuffer)
+                    }
+                }
+                firstRead = false
+                if let decoder = decoder {
+                    decoder.parse(buffer, count: bytesRead)
+                    continue
+                }
+                
                 if let pcmBuffer = self.createPCMBuffer(from: buffer, count: bytesRead, format: format) {
                     self.recognitionRequest?.append(pcmBuffer)
                 }
@@ -471,11 +930,445 @@ class LegacyStdinStreamingTranscriber {
     }
 }
 
+// Modern SpeechAnalyzer streaming an audio file with progressive results (macOS 26+)
+@available(macOS 26.0, *)
+class FileStreamingTranscriber {
+    private let locale: Locale
+    private let audioURL: URL
+    
+    init(audioURL: URL, locale: Locale = Locale(identifier: "en-US")) {
+        self.audioURL = audioURL
+        self.locale = locale
+    }
+    
+    func run() async throws {
+        // Verify locale is supported
+        let supportedLocales = await SpeechTranscriber.supportedLocales
+        guard supportedLocales.map({ $0.identifier(.bcp47) }).contains(locale.identifier(.bcp47)) else {
+            throw NSError(
+                domain: "SpeechRecognition",
+                code: 3,
+                userInfo: [NSLocalizedDescriptionKey: "Locale '\(locale.identifier)' not supported"]
+            )
+        }
+        
+        // Progressive preset so volatile results are reported while the file is analyzed
+        let transcriber = makeSpeechTranscriber(locale: locale)
+        let modules: [any SpeechModule] = [transcriber]
+        let analyzer = SpeechAnalyzer(modules: modules)
+        try await applyContext(to: analyzer)
+        
+        let audioFile = try AVAudioFile(forReading: audioURL)
+        try await analyzer.start(inputAudioFile: audioFile, finishAfterFile: true)
+        
+        for try await result in transcriber.results {
+            let output = TranscriptionOutput(
+                text: String(result.text.characters),
+                isFinal: result.isFinal,
+                kind: resultKind(result),
+                timestamp: Date().timeIntervalSince1970,
+                alternatives: alternativeTexts(result),
+                stableText: stableTexts.mark(String(result.text.characters), isFinal: result.isFinal),
+                words: wordTimings(result)
+            )
+            
+            if let jsonData = try? JSONEncoder().encode(output),
+               let jsonString = String(data: jsonData, encoding: .utf8) {
+                print(jsonString)
+                fflush(stdout)
+            }
+        }
+    }
+}
+
+// Legacy file streaming (macOS 10.15+)
+@available(macOS 10.15, *)
+class LegacyFileStreamingTranscriber {
+    private let locale: Locale
+    private let audioURL: URL
+    
+    init(audioURL: URL, locale: Locale = Locale(identifier: "en-US")) {
+        self.audioURL = audioURL
+        self.locale = locale
+    }
+    
+    func run() async throws {
+        guard let recognizer = SFSpeechRecognizer(locale: locale), recognizer.isAvailable else {
+            throw NSError(
+                domain: "SpeechRecognition",
+                code: 2,
+                userInfo: [NSLocalizedDescriptionKey: "Speech recognizer not available"]
+            )
+        }
+        
+        let request = SFSpeechURLRecognitionRequest(url: audioURL)
+        request.shouldReportPartialResults = true
+        request.taskHint = taskHint
+        request.contextualStrings = contextualStrings
+        
+        try await withCheckedThrowingContinuation { (continuation: CheckedContinuation<Void, Error>) in
+            recognizer.recognitionTask(with: request) { result, error in
+                if let error = error {
+                    continuation.resume(throwing: error)
+                    return
+                }
+                
+                if let result = result {
+                    let output = TranscriptionOutput(
+                        text: result.bestTranscription.formattedString,
+                        isFinal: result.isFinal,
+                        // The legacy recognizer stops after its final result, so nothing gets revised
+                        kind: result.isFinal ? "committed" : "volatile",
+                        timestamp: Date().timeIntervalSince1970,
+                        alternatives: alternativeTexts(result),
+                        stableText: stableTexts.mark(result.bestTranscription.formattedString, isFinal: result.isFinal),
+                        words: wordTimings(result)
+                    )
+                    
+                    if let jsonData = try? JSONEncoder().encode(output),
+                       let jsonString = String(data: jsonData, encoding: .utf8) {
+                        print(jsonString)
+                        fflush(stdout)
+                    }
+                    
+                    if result.isFinal {
+                        continuation.resume()
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Binds a Unix socket at `path`, waits for one client, and makes the connection
+// this process's stdin and stdout
+func acceptSocketClient(path: String) throws {
+    func failure(_ what: String) -> NSError {
+        NSError(domain: NSPOSIXErrorDomain, code: Int(errno),
+                userInfo: [NSLocalizedDescriptionKey: "\(what) \(path): \(String(cString: strerror(errno)))"])
+    }
+    
+    unlink(path)
+    let listener = socket(AF_UNIX, SOCK_STREAM, 0)
+    guard listener >= 0 else { throw failure("Failed to create socket for") }
+    
+    var address = sockaddr_un()
+    address.sun_family = sa_family_t(AF_UNIX)
+    let capacity = MemoryLayout.size(ofValue: address.sun_path)
+    guard path.utf8.count < capacity else { throw failure("Socket path too long:") }
+    withUnsafeMutableBytes(of: &address.sun_path) { buffer in
+        buffer.copyBytes(from: path.utf8)
+        buffer[path.utf8.count] = 0
+    }
+    
+    let bound = withUnsafePointer(to: &address) {
+        $0.withMemoryRebound(to: sockaddr.self, capacity: 1) {
+            bind(listener, $0, socklen_t(MemoryLayout<sockaddr_un>.size))
+        }
+    }
+    guard bound == 0, listen(listener, 1) == 0 else { throw failure("Failed to listen on") }
+    
+    fputs("Listening on \(path)...\n", stderr)
+    let client = accept(listener, nil, nil)
+    guard client >= 0 else { throw failure("Failed to accept on") }
+    close(listener)
+    
+    dup2(client, STDIN_FILENO)
+    dup2(client, STDOUT_FILENO)
+    close(client)
+}
+
+// Sessions that the "finish" control message can end before the audio does
+protocol FinishableSession: AnyObject {
+    // Stops taking audio and returns once the last results have been written
+    func finish() async
+}
+
+@available(macOS 26.0, *)
+extension StreamingTranscriber: FinishableSession {
+    func finish() async {
+        try? await stop()
+        await resultsTask?.value
+    }
+}
+
+@available(macOS 26.0, *)
+extension StdinStreamingTranscriber: FinishableSession {
+    func finish() async {
+        try? await stop()
+        await resultsTask?.value
+    }
+}
+
+@available(macOS 10.15, *)
+extension LegacyStreamingTranscriber: FinishableSession {
+    func finish() async {
+        // stop() cancels the task, which would drop the final result
+        audioEngine.stop()
+        audioEngine.inputNode.removeTap(onBus: 0)
+        recognitionRequest?.endAudio()
+        let deadline = Date().addingTimeInterval(5)
+        while !finalReported && Date() < deadline {
+            try? await Task.sleep(nanoseconds: 50_000_000)
+        }
+    }
+}
+
+@available(macOS 10.15, *)
+extension LegacyStdinStreamingTranscriber: FinishableSession {
+    func finish() async {
+        recognitionRequest?.endAudio()
+        let deadline = Date().addingTimeInterval(5)
+        while !finalReported && Date() < deadline {
+            try? await Task.sleep(nanoseconds: 50_000_000)
+        }
+    }
+}
+
+// Sessions whose recognition locale the "locale" control message can switch
+protocol LocaleSwitchableSession: AnyObject {
+    // Finalizes what has been heard so far and recognizes from then on in `locale`
+    func switchLocale(to locale: Locale) async throws
+}
+
+// Finalizes everything `analyzer` has analyzed and replaces its transcriber with
+// one for `locale`, keeping the detector and the audio input. Audio queued but
+// not analyzed yet is recognized in the new locale.
+@available(macOS 26.0, *)
+func swapTranscriber(in analyzer: SpeechAnalyzer, detector: SpeechDetector, locale: Locale) async throws -> SpeechTranscriber {
+    let bcp47 = locale.identifier(.bcp47)
+    guard await SpeechTranscriber.supportedLocales.map({ $0.identifier(.bcp47) }).contains(bcp47) else {
+        throw NSError(domain: "SpeechRecognition", code: 3, userInfo: [NSLocalizedDescriptionKey: "Locale '\(locale.identifier)' not supported"])
+    }
+    // Downloading a model mid-session would stall the audio for minutes
+    guard await SpeechTranscriber.installedLocales.map({ $0.identifier(.bcp47) }).contains(bcp47) else {
+        throw NSError(domain: "SpeechRecognition", code: 5, userInfo: [NSLocalizedDescriptionKey: "Speech model for '\(locale.identifier)' is not installed"])
+    }
+    try await analyzer.finalize(through: nil)
+    let transcriber = makeSpeechTranscriber(locale: locale)
+    try await analyzer.setModules([transcriber, detector])
+    return transcriber
+}
+
+@available(macOS 26.0, *)
+extension StreamingTranscriber: LocaleSwitchableSession {
+    func switchLocale(to locale: Locale) async throws {
+        guard let analyzer = analyzer, let detector = detector else { return }
+        let transcriber = try await swapTranscriber(in: analyzer, detector: detector, locale: locale)
+        // The old transcriber's results end once it is removed; write them first
+        await resultsTask?.value
+        self.transcriber = transcriber
+        self.locale = locale
+        resultsTask = Task {
+            await self.processResults()
+        }
+    }
+}
+
+@available(macOS 26.0, *)
+extension StdinStreamingTranscriber: LocaleSwitchableSession {
+    func switchLocale(to locale: Locale) async throws {
+        guard let analyzer = analyzer, let detector = detector else { return }
+        let transcriber = try await swapTranscriber(in: analyzer, detector: detector, locale: locale)
+        await resultsTask?.value
+        self.transcriber = transcriber
+        self.locale = locale
+        resultsTask = Task {
+            await self.processResults()
+        }
+    }
+}
+
+// Answer to a "locale" control message; error is set when the switch failed
+struct LocaleReply: Codable {
+    let locale: String
+    let error: String?
+}
+
+// Reads control messages from a descriptor (--control <fd>):
+// - "ping <id>" is answered with {"pong":<id>} on stdout. Replies go through the
+//   main actor, so a helper that has wedged stops answering even though this
+//   reader thread is still alive.
+// - "finish" stops taking audio, writes the last results and exits.
+// - "locale <id>" finalizes the current results and switches recognition to
+//   another locale without stopping the audio, answering with a LocaleReply.
+//   Only SpeechAnalyzer sessions can switch.
+func startControlReader(fd: Int32) {
+    Thread.detachNewThread {
+        guard let stream = fdopen(fd, "r") else { return }
+        var line: UnsafeMutablePointer<CChar>?
+        var capacity = 0
+        while getline(&line, &capacity, stream) > 0, let line = line {
+            let parts = String(cString: line).split(whereSeparator: \.isWhitespace)
+            if parts == ["finish"] {
+                Task { @MainActor in
+                    await (globalTranscriber as? FinishableSession)?.finish()
+                    exit(0)
+                }
+                continue
+            }
+            if parts.count == 2, parts[0] == "locale" {
+                let identifier = String(parts[1])
+                Task { @MainActor in
+                    var failure: String? = "This recognizer can't switch locale mid-session"
+                    if let session = globalTranscriber as? LocaleSwitchableSession {
+                        do {
+                            try await session.switchLocale(to: Locale(identifier: identifier))
+                            failure = nil
+                        } catch {
+                            failure = error.localizedDescription
+                        }
+                    }
+                    if let data = try? JSONEncoder().encode(LocaleReply(locale: identifier, error: failure)),
+                       let json = String(data: data, encoding: .utf8) {
+                        print(json)
+                        fflush(stdout)
+                    }
+                }
+                continue
+            }
+            guard parts.count == 2, parts[0] == "ping", let id = UInt64(parts[1]) else {
+                continue
+            }
+            Task { @MainActor in
+                print("{\"pong\":\(id)}")
+                fflush(stdout)
+            }
+        }
+        free(line)
+    }
+}
+
+// Version of the output protocol: the result JSON lines, pongs and control
+// messages. Bump it on any change the Rust side can't read, together with the
+// crate's SUPPORTED_PROTOCOL_VERSIONS.
+let protocolVersion = 1
+
+// Announces the protocol version; must be the first line a session writes
+func announceProtocol() {
+    print("{\"protocol\":\(protocolVersion)}")
+    fflush(stdout)
+}
+
+// Exit status for a session whose microphone access was revoked (EX_NOPERM); the
+// Rust side reports it as a recoverable "Permission revoked" error
+let permissionRevokedExit: Int32 = 77
+
+// Checks microphone authorization once a second. Revoking access doesn't stop the
+// audio engine, it just delivers silence, so without this the session would go
+// quiet with no indication of why. Only a change away from authorized counts, so
+// a session waiting on the first permission prompt isn't cut short.
+func startPermissionWatch() {
+    Thread.detachNewThread {
+        var wasAuthorized = false
+        while true {
+            let authorized = AVCaptureDevice.authorizationStatus(for: .audio) == .authorized
+            if wasAuthorized && !authorized {
+                fputs("Error: Microphone permission revoked\n", stderr)
+                exit(permissionRevokedExit)
+            }
+            wasAuthorized = authorized
+            Thread.sleep(forTimeInterval: 1)
+        }
+    }
+}
+
+// Loads the recognizer and its on-device model so a following session starts immediately
+@available(macOS 10.15, *)
+func warmUp() async throws {
+    let locale = sessionLocale
+    
+    if #available(macOS 26.0, *) {
+        let transcriber = SpeechTranscriber(locale: locale, preset: .progressiveTranscription)
+        if let request = try await AssetInventory.assetInstallationRequest(supporting: [transcriber]) {
+            try await request.downloadAndInstall()
+        }
+        let analyzer = SpeechAnalyzer(modules: [transcriber])
+        let format = await SpeechAnalyzer.bestAvailableAudioFormat(compatibleWith: [transcriber])
+        try await analyzer.prepareToAnalyze(in: format)
+        await analyzer.cancelAndFinishNow()
+    } else {
+        guard let recognizer = SFSpeechRecognizer(locale: locale), recognizer.isAvailable else {
+            throw NSError(domain: "SpeechRecognition", code: 2, userInfo: [NSLocalizedDescriptionKey: "Speech recognizer not available"])
+        }
+    }
+}
+
 // Main execution
 @available(macOS 10.15, *)
 @MainActor
 func main() async {
-    let useStdin = CommandLine.arguments.contains("--stdin")
+    if let localeIndex = CommandLine.arguments.firstIndex(of: "--locale"),
+       localeIndex + 1 < CommandLine.arguments.count {
+        sessionLocale = Locale(identifier: CommandLine.arguments[localeIndex + 1])
+    }
+    
+    if CommandLine.arguments.contains("--warm-up") {
+        do {
+            try await warmUp()
+            exit(0)
+        } catch {
+            fputs("Error: \(error.localizedDescription)\n", stderr)
+            exit(1)
+        }
+    }
+    
+    if let hintIndex = CommandLine.arguments.firstIndex(of: "--task-hint"),
+       hintIndex + 1 < CommandLine.arguments.count {
+        switch CommandLine.arguments[hintIndex + 1] {
+        case "dictation": taskHint = .dictation
+        case "search": taskHint = .search
+        case "confirmation": taskHint = .confirmation
+        default: taskHint = .unspecified
+        }
+    }
+    wantAlternatives = CommandLine.arguments.contains("--alternatives")
+    wantStableText = CommandLine.arguments.contains("--stable-text")
+    if let contextIndex = CommandLine.arguments.firstIndex(of: "--context"),
+       contextIndex + 1 < CommandLine.arguments.count {
+        contextualStrings = contextWords(from: CommandLine.arguments[contextIndex + 1])
+    }
+    
+    if let fileIndex = CommandLine.arguments.firstIndex(of: "--file"),
+       fileIndex + 1 < CommandLine.arguments.count {
+        let audioURL = URL(fileURLWithPath: CommandLine.arguments[fileIndex + 1])
+        fputs("Streaming transcription of \(audioURL.path)...\n", stderr)
+        announceProtocol()
+        
+        do {
+            if #available(macOS 26.0, *) {
+                try await FileStreamingTranscriber(audioURL: audioURL, locale: sessionLocale).run()
+            } else {
+                try await LegacyFileStreamingTranscriber(audioURL: audioURL, locale: sessionLocale).run()
+            }
+            exit(0)
+        } catch {
+            fputs("Error: \(error.localizedDescription)\n", stderr)
+            exit(1)
+        }
+    }
+    
+    // --listen <path>: accept one client on a Unix socket and speak the --stdin
+    // protocol over it, for helpers run by a separate agent
+    var useStdin = CommandLine.arguments.contains("--stdin")
+    if let listenIndex = CommandLine.arguments.firstIndex(of: "--listen"),
+       listenIndex + 1 < CommandLine.arguments.count {
+        do {
+            try acceptSocketClient(path: CommandLine.arguments[listenIndex + 1])
+            useStdin = true
+        } catch {
+            fputs("Error: \(error.localizedDescription)\n", stderr)
+            exit(1)
+        }
+    }
+    // After any --listen setup, so a socket client receives it too
+    announceProtocol()
+    
+    if let controlIndex = CommandLine.arguments.firstIndex(of: "--control"),
+       controlIndex + 1 < CommandLine.arguments.count,
+       let fd = Int32(CommandLine.arguments[controlIndex + 1]) {
+        startControlReader(fd: fd)
+    }
     
     if useStdin {
         fputs("Starting stdin audio transcription... (Press Ctrl+C to stop)\n", stderr)
@@ -483,7 +1376,7 @@ func main() async {
         
         do {
             if #available(macOS 26.0, *) {
-                let transcriber = StdinStreamingTranscriber()
+                let transcriber = StdinStreamingTranscriber(locale: sessionLocale)
                 globalTranscriber = transcriber
                 try await transcriber.start()
                 
@@ -491,7 +1384,7 @@ func main() async {
                     try await Task.sleep(nanoseconds: 86_400_000_000_000)
                 }
             } else {
-                let transcriber = LegacyStdinStreamingTranscriber()
+                let transcriber = LegacyStdinStreamingTranscriber(locale: sessionLocale)
                 globalTranscriber = transcriber
                 try transcriber.start()
                 
@@ -507,9 +1400,23 @@ func main() async {
         fputs("Starting live microphone transcription... (Press Ctrl+C to stop)\n", stderr)
         fputs("Speak into your microphone.\n", stderr)
         
+        if let outIndex = CommandLine.arguments.firstIndex(of: "--audio-out"),
+           outIndex + 1 < CommandLine.arguments.count,
+           let fd = Int32(CommandLine.arguments[outIndex + 1]) {
+            // A client that stops reading must not kill the helper
+            signal(SIGPIPE, SIG_IGN)
+            audioPassthrough = AudioPassthrough(fd: fd)
+        }
+        if let framesIndex = CommandLine.arguments.firstIndex(of: "--buffer-frames"),
+           framesIndex + 1 < CommandLine.arguments.count,
+           let frames = AVAudioFrameCount(CommandLine.arguments[framesIndex + 1]) {
+            micBufferFrames = frames
+        }
+        startPermissionWatch()
+        
         do {
             if #available(macOS 26.0, *) {
-                let transcriber = StreamingTranscriber()
+                let transcriber = StreamingTranscriber(locale: sessionLocale)
                 globalTranscriber = transcriber
                 try await transcriber.start()
                 
@@ -517,7 +1424,7 @@ func main() async {
                     try await Task.sleep(nanoseconds: 86_400_000_000_000)
                 }
             } else {
-                let transcriber = LegacyStreamingTranscriber()
+                let transcriber = LegacyStreamingTranscriber(locale: sessionLocale)
                 globalTranscriber = transcriber
                 try transcriber.start()
                 
@@ -0,0 +1,305 @@
+//! Alignment of a known transcript against recognized audio
+
+use crate::{Transcriber, TranscriptionResult, TranscriptionSegment};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A word of a reference transcript with its timing in the audio
+///
+/// Returned by [`Transcriber::align`]. Words the recognizer didn't hear as
+/// written have no timing; see [`is_aligned`](Self::is_aligned).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordSegment {
+    /// The word as written in the reference text, punctuation included
+    pub word: String,
+    /// Offset of the word from the start of the file, in seconds
+    #[serde(rename = "start")]
+    pub start_secs: Option<f64>,
+    /// Length of the word, in seconds
+    #[serde(rename = "duration")]
+    pub duration_secs: Option<f64>,
+    /// Recognizer confidence (0.0-1.0) of the matching word, when reported
+    pub confidence: Option<f32>,
+}
+
+impl WordSegment {
+    /// Whether the word was found in the audio and has a timing
+    pub fn is_aligned(&self) -> bool {
+        self.start_secs.is_some()
+    }
+}
+
+impl Transcriber {
+    /// Finds when each word of a known transcript is spoken in an audio file
+    ///
+    /// The reference text is passed to the helper as contextual strings that
+    /// bias recognition toward its words; the Speech framework has no true
+    /// forced alignment. The recognized words are then matched to the
+    /// reference in order, ignoring case and punctuation, and every reference
+    /// word gets the timing of the word it matched. Words that were misheard,
+    /// skipped, or not spoken at all are returned without a timing, so the
+    /// result always has one entry per word of `reference_text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference text is empty, or the same errors as
+    /// [`transcribe_file_with_metadata`](Self::transcribe_file_with_metadata).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let words = transcriber
+    ///     .align(Path::new("verse.m4a"), "Twinkle, twinkle, little star")
+    ///     .unwrap();
+    /// for word in words.iter().filter(|word| word.is_aligned()) {
+    ///     println!("{:>6.2}s {}", word.start_secs.unwrap(), word.word);
+    /// }
+    /// ```
+    pub fn align(&self, audio: &Path, reference_text: &str) -> Result<Vec<WordSegment>, String> {
+        if normalized_words(reference_text).next().is_none() {
+            return Err("Reference text is empty".to_string());
+        }
+        let output = self.run_helper(audio, &["--json", "--reference", reference_text])?;
+        let mut result: TranscriptionResult = serde_json::from_str(&output)
+            .map_err(|e| format!("Failed to parse result: {} (output: {})", e, output))?;
+        self.shift_timings(&mut result.segments);
+        Ok(align_words(reference_text, &result.segments))
+    }
+}
+
+/// Words beyond which an alignment may drift from the proportional diagonal
+const BAND: usize = 200;
+
+/// A recognized word with its share of its segment's timing
+struct Heard {
+    word: String,
+    start_secs: f64,
+    duration_secs: f64,
+    confidence: Option<f32>,
+}
+
+/// Matches the words of `reference` to the recognized `segments`
+fn align_words(reference: &str, segments: &[TranscriptionSegment]) -> Vec<WordSegment> {
+    let written: Vec<(&str, String)> = reference
+        .split_whitespace()
+        .map(|word| (word, normalize(word)))
+        .filter(|(_, norm)| !norm.is_empty())
+        .collect();
+    let heard = heard_words(segments);
+
+    let expected: Vec<&str> = written.iter().map(|(_, norm)| norm.as_str()).collect();
+    let recognized: Vec<&str> = heard.iter().map(|heard| heard.word.as_str()).collect();
+    let matches = match_words(&expected, &recognized);
+
+    written
+        .iter()
+        .zip(matches)
+        .map(|((word, _), matched)| {
+            let heard = matched.map(|idx| &heard[idx]);
+            WordSegment {
+                word: word.to_string(),
+                start_secs: heard.map(|heard| heard.start_secs),
+                duration_secs: heard.map(|heard| heard.duration_secs),
+                confidence: heard.and_then(|heard| heard.confidence),
+            }
+        })
+        .collect()
+}
+
+/// Splits segments into words, sharing each segment's span by word length
+fn heard_words(segments: &[TranscriptionSegment]) -> Vec<Heard> {
+    let mut heard = Vec::new();
+    for segment in segments {
+        let words: Vec<String> = normalized_words(&segment.text).collect();
+        let total: usize = words.iter().map(|word| word.chars().count()).sum();
+        let mut start = segment.start_secs;
+        for word in words {
+            let share = word.chars().count() as f64 / total as f64;
+            let duration = segment.duration_secs * share;
+            heard.push(Heard {
+                word,
+                start_secs: start,
+                duration_secs: duration,
+                confidence: segment.confidence,
+            });
+            start += duration;
+        }
+    }
+    heard
+}
+
+fn normalized_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(normalize)
+        .filter(|word| !word.is_empty())
+}
+
+/// Lowercases a word and drops its punctuation, so "Star," matches "star"
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+const MATCH: u8 = 0;
+const SUBSTITUTE: u8 = 1;
+const SKIP_EXPECTED: u8 = 2;
+const SKIP_RECOGNIZED: u8 = 3;
+
+/// Aligns two word sequences by edit distance, returning each expected word's match
+///
+/// Only identical words are matched; a substituted word counts as unmatched.
+/// The dynamic program is restricted to a band around the diagonal, which
+/// keeps long transcripts cheap as long as the recognizer doesn't drop or
+/// invent hundreds of words in a row.
+fn match_words(expected: &[&str], recognized: &[&str]) -> Vec<Option<usize>> {
+    let (n, m) = (expected.len(), recognized.len());
+    if n == 0 || m == 0 {
+        return vec![None; n];
+    }
+
+    // Wide enough that consecutive rows' bands always overlap
+    let width = BAND + m.div_ceil(n);
+    let band = |i: usize| {
+        let center = i * m / n;
+        (center.saturating_sub(width), (center + width).min(m))
+    };
+
+    const UNREACHABLE: u32 = u32::MAX / 2;
+    let mut prev = vec![UNREACHABLE; m + 1];
+    let (_, hi) = band(0);
+    for (j, cost) in prev.iter_mut().enumerate().take(hi + 1) {
+        *cost = j as u32;
+    }
+    let mut steps = vec![(0, vec![SKIP_RECOGNIZED; hi + 1])];
+
+    for i in 1..=n {
+        let (lo, hi) = band(i);
+        let mut cost = vec![UNREACHABLE; m + 1];
+        let mut row = Vec::with_capacity(hi - lo + 1);
+        for j in lo..=hi {
+            let mut best = (prev[j] + 1, SKIP_EXPECTED);
+            if j > 0 {
+                let (diagonal, step) = if expected[i - 1] == recognized[j - 1] {
+                    (prev[j - 1], MATCH)
+                } else {
+                    (prev[j - 1] + 1, SUBSTITUTE)
+                };
+                if diagonal <= best.0 {
+                    best = (diagonal, step);
+                }
+                if cost[j - 1] + 1 < best.0 {
+                    best = (cost[j - 1] + 1, SKIP_RECOGNIZED);
+                }
+            }
+            cost[j] = best.0;
+            row.push(best.1);
+        }
+        prev = cost;
+        steps.push((lo, row));
+    }
+
+    let mut matches = vec![None; n];
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        let (lo, row) = &steps[i];
+        match row[j - lo] {
+            MATCH => {
+                matches[i - 1] = Some(j - 1);
+                i -= 1;
+                j -= 1;
+            }
+            SUBSTITUTE => {
+                i -= 1;
+                j -= 1;
+            }
+            SKIP_EXPECTED => i -= 1,
+            _ => j -= 1,
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_secs: f64, duration_secs: f64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_secs,
+            duration_secs,
+            confidence: Some(0.9),
+            script: None,
+            locale: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn aligns_reference_words_to_recognized_timings() {
+        let segments = [
+            segment("twinkle", 0.0, 0.5),
+            segment("twinkle", 0.5, 0.5),
+            segment("little", 1.0, 0.4),
+            segment("car", 1.4, 0.6),
+            segment("um", 2.0, 0.2),
+            segment("how I", 2.2, 0.3),
+        ];
+        let words = align_words("Twinkle, twinkle, little star. How I — wonder", &segments);
+
+        let summary: Vec<(&str, Option<f64>)> = words
+            .iter()
+            .map(|word| (word.word.as_str(), word.start_secs))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Twinkle,", Some(0.0)),
+                ("twinkle,", Some(0.5)),
+                ("little", Some(1.0)),
+                ("star.", None),
+                ("How", Some(2.2)),
+                ("I", Some(2.2 + 0.3 * 0.75)),
+                ("wonder", None),
+            ]
+        );
+        assert_eq!(words[0].duration_secs, Some(0.5));
+        assert_eq!(words[0].confidence, Some(0.9));
+        assert!(!words[3].is_aligned());
+    }
+
+    #[test]
+    fn handles_empty_sides() {
+        assert!(align_words("nothing was heard", &[])
+            .iter()
+            .all(|w| !w.is_aligned()));
+        assert!(align_words("", &[segment("hello", 0.0, 1.0)]).is_empty());
+    }
+
+    #[test]
+    fn stays_on_track_over_long_transcripts() {
+        // The recognizer drops a word every so often across a long recording
+        let expected: Vec<String> = (0..5000).map(|i| format!("w{}", i)).collect();
+        let recognized: Vec<&str> = expected
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % 50 != 7)
+            .map(|(_, word)| word.as_str())
+            .collect();
+        let expected: Vec<&str> = expected.iter().map(String::as_str).collect();
+
+        let matches = match_words(&expected, &recognized);
+        for (i, matched) in matches.iter().enumerate() {
+            assert_eq!(matched.is_some(), i % 50 != 7, "word {}", i);
+            if let Some(j) = matched {
+                assert_eq!(recognized[*j], expected[i]);
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Feeding and polling the streaming helper from async code, on tokio
+
+use crate::{
+    AudioInputMode, BackpressureWarning, PcmFeed, ScribeError, StreamingResult,
+    StreamingTranscriber, HANDSHAKE_TIMEOUT,
+};
+use std::io::{ErrorKind, Write};
+use std::os::unix::io::RawFd;
+use std::time::Duration;
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
+
+/// Longest `poll_result_async` waits for output before re-checking held
+/// partials and deadlines, which come due without the helper writing anything
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The helper's stdout and stdin, registered with the tokio reactor
+pub(crate) struct AsyncPipes {
+    output: AsyncFd<RawFd>,
+    /// Dropped once the audio input is closed to ask the helper to finish
+    pub(crate) input: Option<AsyncFd<RawFd>>,
+}
+
+impl AsyncPipes {
+    /// Waits until the helper's stdout may have something to read
+    async fn output_readable(&self) -> Result<(), String> {
+        let mut guard = self
+            .output
+            .readable()
+            .await
+            .map_err(|e| format!("Failed to read from helper: {}", e))?;
+        guard.clear_ready();
+        Ok(())
+    }
+}
+
+impl StreamingTranscriber {
+    /// Starts a session whose audio is fed with the `*_async` methods
+    ///
+    /// Same as [`start`](Self::start), but the helper's pipes are driven by the
+    /// tokio reactor: [`feed_audio_i16_async`](Self::feed_audio_i16_async)
+    /// awaits instead of blocking the thread when the helper is busy and its
+    /// input pipe is full, and [`poll_result_async`](Self::poll_result_async)
+    /// awaits the next result. The blocking `feed_*` methods can't be used in
+    /// a session started this way.
+    ///
+    /// Must be called from within a tokio runtime with I/O enabled. Requires
+    /// the `async` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for transcribers created with
+    /// [`connect_socket`](Self::connect_socket), and for the same reasons as
+    /// [`start`](Self::start).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// # async fn run() -> Result<(), String> {
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()?;
+    /// transcriber.start_async().await?;
+    ///
+    /// let samples = vec![0i16; 16000];
+    /// transcriber.feed_audio_i16_async(&samples, 16000, 1).await?;
+    /// let result = transcriber.poll_result_async().await?;
+    /// println!("{}", result.text);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn start_async(&mut self) -> Result<(), ScribeError> {
+        if self.socket_path.is_some() {
+            return Err(
+                "start_async needs a spawned helper; use start for socket connections".into(),
+            );
+        }
+        self.begin_session()?;
+        let started = match self.register_pipes() {
+            Ok(()) => tokio::time::timeout(HANDSHAKE_TIMEOUT, self.handshake_async())
+                .await
+                .unwrap_or_else(|_| {
+                    Err(format!(
+                        "Helper did not announce its protocol version within {:?}",
+                        HANDSHAKE_TIMEOUT
+                    ))
+                }),
+            Err(e) => Err(e),
+        };
+        match started {
+            Ok(version) => self.protocol_version = Some(version),
+            Err(e) => {
+                let _ = self.stop();
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Registers the spawned helper's pipes with the reactor
+    fn register_pipes(&mut self) -> Result<(), String> {
+        let (output, input) = self.pipe_fds.ok_or(ScribeError::NotStarted)?;
+        let register_error = |e: std::io::Error| format!("Failed to register helper pipes: {}", e);
+
+        let output = AsyncFd::with_interest(output, Interest::READABLE).map_err(register_error)?;
+        let input = match input {
+            Some(fd) => {
+                set_nonblocking(fd).map_err(register_error)?;
+                Some(AsyncFd::with_interest(fd, Interest::WRITABLE).map_err(register_error)?)
+            }
+            None => None,
+        };
+        self.async_pipes = Some(AsyncPipes { output, input });
+        Ok(())
+    }
+
+    /// Awaits the helper's protocol announcement, as `handshake` does
+    async fn handshake_async(&mut self) -> Result<u32, String> {
+        let mut line = Vec::new();
+        while !self.read_announcement(&mut line)? {
+            self.async_pipes()?.output_readable().await?;
+        }
+        self.check_announcement(&line)
+    }
+
+    /// The pipes registered by `start_async`
+    fn async_pipes(&self) -> Result<&AsyncPipes, ScribeError> {
+        self.async_pipes.as_ref().ok_or(ScribeError::NotStarted)
+    }
+
+    /// Feeds i16 audio, awaiting while the helper's input pipe is full
+    ///
+    /// The async counterpart of [`feed_audio_i16`](Self::feed_audio_i16), for
+    /// sessions started with [`start_async`](Self::start_async). With
+    /// [`BackpressurePolicy::Block`](crate::BackpressurePolicy::Block), a helper
+    /// that falls behind suspends the calling task rather than blocking the
+    /// runtime's thread.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session wasn't started with `start_async`, or
+    /// for the same reasons as `feed_audio_i16`.
+    pub async fn feed_audio_i16_async(
+        &mut self,
+        samples: &[i16],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        if self.async_pipes.is_none() {
+            return Err("feed_audio_i16_async needs a session started with start_async".into());
+        }
+        let (pcm, fed, warning) =
+            match self.prepare_pcm(samples, sample_rate, channels, "feed_audio_i16_async")? {
+                PcmFeed::Done(warning) => return Ok(warning),
+                PcmFeed::Write { pcm, fed, warning } => (pcm, fed, warning),
+            };
+        let bytes: Vec<u8> = pcm.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+        self.write_async(&bytes).await?;
+        self.record_fed_audio(&pcm);
+        self.fed_since_result = fed;
+        Ok(warning)
+    }
+
+    /// Feeds f32 audio, awaiting while the helper's input pipe is full
+    ///
+    /// The async counterpart of [`feed_audio_f32`](Self::feed_audio_f32); see
+    /// [`feed_audio_i16_async`](Self::feed_audio_i16_async).
+    ///
+    /// Requires the `async` feature.
+    pub async fn feed_audio_f32_async(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "feed_audio_f32_async can only be used with programmatic input mode".to_string(),
+            ));
+        }
+
+        let i16_samples = Self::f32_to_i16(samples);
+        self.feed_audio_i16_async(&i16_samples, sample_rate, channels)
+            .await
+    }
+
+    /// Writes all of `bytes` to the helper's stdin, awaiting whenever it is full
+    async fn write_async(&mut self, bytes: &[u8]) -> Result<(), ScribeError> {
+        let mut written = 0;
+        while written < bytes.len() {
+            let writer = self.writer.as_mut().ok_or(ScribeError::NotStarted)?;
+            match writer.write(&bytes[written..]) {
+                Ok(0) => return Err(self.write_error(ErrorKind::WriteZero.into())),
+                Ok(n) => written += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    let input = self
+                        .async_pipes
+                        .as_ref()
+                        .and_then(|pipes| pipes.input.as_ref())
+                        .ok_or(ScribeError::NotStarted)?;
+                    match input.writable().await {
+                        Ok(mut guard) => guard.clear_ready(),
+                        Err(e) => return Err(self.write_error(e)),
+                    }
+                }
+                Err(e) => return Err(self.write_error(e)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Awaits the next streaming result
+    ///
+    /// The async counterpart of [`poll_result`](Self::poll_result), for
+    /// sessions started with [`start_async`](Self::start_async): instead of
+    /// returning `None` when nothing is ready, it waits for the helper to
+    /// write more output. Results are post-processed exactly as `poll_result`
+    /// does, including held partials and session deadlines.
+    ///
+    /// Requires the `async` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session wasn't started with `start_async`, and
+    /// for the same reasons as `poll_result`, including once the helper has
+    /// exited and every result has been returned.
+    pub async fn poll_result_async(&mut self) -> Result<StreamingResult, ScribeError> {
+        if self.async_pipes.is_none() {
+            return Err("poll_result_async needs a session started with start_async".into());
+        }
+        loop {
+            if let Some(result) = self.poll_result()? {
+                return Ok(result);
+            }
+            // A held partial or a deadline can come due while the helper is quiet
+            let pipes = self.async_pipes()?;
+            let _ = tokio::time::timeout(POLL_INTERVAL, pipes.output_readable()).await;
+        }
+    }
+
+    /// Rejects the blocking `feed_*` methods in a session started with `start_async`
+    pub(crate) fn ensure_blocking_input(&self, method: &str) -> Result<(), ScribeError> {
+        if self.async_pipes.is_some() {
+            return Err(format!(
+                "Session was started with start_async; {} can't be used, feed it with the *_async methods",
+                method
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Puts `fd` in non-blocking mode so writes return `WouldBlock` instead of waiting
+fn set_nonblocking(fd: RawFd) -> std::io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL);
+        if flags == -1 || libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[tokio::test]
+    async fn feeding_a_busy_helper_yields_to_other_tasks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Reads nothing for a while, so 10s of audio overfills the pipe
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transcribe_stream");
+        std::fs::write(
+            &path,
+            "#!/bin/sh\necho '{\"protocol\":1}'\nsleep 0.3\nhead -c 320000 >/dev/null\n\
+             echo '{\"text\":\"done\",\"isFinal\":true,\"timestamp\":10}'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path(&path)
+            .with_programmatic_input()
+            .build()
+            .unwrap();
+        let start = transcriber.start_async();
+        assert_send(&start);
+        start.await.unwrap();
+        assert!(transcriber
+            .feed_audio_i16(&[0; 160], 16000, 1)
+            .unwrap_err()
+            .to_string()
+            .contains("start_async"));
+
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticker = tokio::spawn({
+            let ticks = Arc::clone(&ticks);
+            async move {
+                loop {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    ticks.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        });
+        let samples = vec![1i16; 160_000];
+        let feed = transcriber.feed_audio_i16_async(&samples, 16000, 1);
+        assert_send(&feed);
+        feed.await.unwrap();
+        assert!(
+            ticks.load(Ordering::Relaxed) >= 10,
+            "feeding blocked the runtime"
+        );
+
+        let poll = transcriber.poll_result_async();
+        assert_send(&poll);
+        assert_eq!(poll.await.unwrap().text, "done");
+        ticker.abort();
+    }
+}
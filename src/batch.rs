@@ -0,0 +1,516 @@
+//! Batch transcription of every audio file in a directory
+
+use crate::{ScribeError, Transcriber, TranscriptionResult};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+
+/// Name of the combined transcript written by the `Overwrite` and `Append` modes
+pub const COMBINED_TRANSCRIPT: &str = "transcriptions.txt";
+
+/// Where batch transcriptions are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchOutput {
+    /// Replace `transcriptions.txt` in the directory on every run (default)
+    #[default]
+    Overwrite,
+    /// Append new sections to `transcriptions.txt`
+    ///
+    /// Files that already have a section are skipped, so re-running over a
+    /// growing archive never duplicates sections.
+    Append,
+    /// Write `foo.m4a.txt` next to each `foo.m4a`
+    ///
+    /// The sidecar keeps the audio file's extension, so `foo.m4a` and
+    /// `foo.wav` in the same directory get separate transcripts.
+    PerFileSidecar,
+}
+
+/// Line terminator used in written transcripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// Unix line endings, `\n` (default)
+    #[default]
+    Lf,
+    /// Windows line endings, `\r\n`, for consumers like Notepad
+    CrLf,
+}
+
+impl LineEnding {
+    /// Rewrites the line endings in `text`, which may use either convention
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let text = if text.contains("\r\n") {
+            Cow::Owned(text.replace("\r\n", "\n"))
+        } else {
+            Cow::Borrowed(text)
+        };
+        match self {
+            LineEnding::Lf => text,
+            LineEnding::CrLf => Cow::Owned(text.replace('\n', "\r\n")),
+        }
+    }
+}
+
+/// UTF-8 byte order mark
+pub(crate) const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Options for [`Transcriber::transcribe_directory`]
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Where transcriptions are written
+    pub output: BatchOutput,
+    /// Skip files whose sidecar already exists (`PerFileSidecar` mode)
+    ///
+    /// `Append` mode always skips files that already have a section.
+    pub skip_existing: bool,
+    /// File extensions (lowercase, without the dot) treated as audio
+    pub extensions: Vec<String>,
+    /// Line terminator for written transcripts
+    pub line_ending: LineEnding,
+    /// Start each new transcript file with a UTF-8 byte order mark
+    ///
+    /// Helps consumers that otherwise guess a legacy or UTF-16 encoding. In
+    /// `Append` mode the mark is only written when the file is created.
+    pub bom: bool,
+    /// Deliver parallel results in name order rather than completion order
+    ///
+    /// Only affects [`Transcriber::transcribe_directory_channel`]; the other
+    /// batch methods work through files in name order anyway.
+    pub ordered: bool,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            output: BatchOutput::Overwrite,
+            skip_existing: false,
+            extensions: ["m4a", "wav", "mp3", "aac", "flac", "aiff"]
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            line_ending: LineEnding::Lf,
+            bom: false,
+            ordered: false,
+        }
+    }
+}
+
+/// Why a file was not transcribed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    /// A transcript for the file already exists
+    AlreadyTranscribed,
+    /// The file has no playable audio, like a video-only movie or a file whose
+    /// audio track is corrupt
+    NoAudioTrack,
+}
+
+/// Outcome of transcribing a single file in a batch
+#[derive(Debug, Clone)]
+pub enum BatchOutcome {
+    /// The file was transcribed and its transcript written
+    Transcribed(String),
+    /// The file was not transcribed
+    Skipped(SkipReason),
+    /// Transcription failed
+    Failed(String),
+}
+
+/// A file and its result, as yielded by [`Transcriber::transcribe_directory_iter`]
+///
+/// A file with no playable audio fails with [`ScribeError::NoAudioTrack`], the
+/// error [`Transcriber::transcribe_directory`] reports as
+/// [`SkipReason::NoAudioTrack`].
+pub type DirectoryResult = (PathBuf, Result<TranscriptionResult, ScribeError>);
+
+/// A single file's entry in a batch run
+#[derive(Debug, Clone)]
+pub struct BatchEntry {
+    /// Path to the audio file
+    pub path: PathBuf,
+    /// What happened to it
+    pub outcome: BatchOutcome,
+}
+
+impl Transcriber {
+    /// Transcribes every audio file in a directory
+    ///
+    /// Files are processed in name order and written according to
+    /// [`BatchOptions::output`] as each one completes. A failure on one file
+    /// is recorded in its [`BatchEntry`] and doesn't stop the batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be read or a transcript can't
+    /// be written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{BatchOptions, BatchOutput, Transcriber};
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let options = BatchOptions {
+    ///     output: BatchOutput::Append,
+    ///     ..Default::default()
+    /// };
+    /// for entry in transcriber.transcribe_directory(Path::new("recordings"), &options).unwrap() {
+    ///     println!("{}: {:?}", entry.path.display(), entry.outcome);
+    /// }
+    /// ```
+    pub fn transcribe_directory(
+        &self,
+        dir: &Path,
+        options: &BatchOptions,
+    ) -> Result<Vec<BatchEntry>, String> {
+        let files = audio_files(dir, options)?;
+        let combined_path = dir.join(COMBINED_TRANSCRIPT);
+        let existing_sections = match options.output {
+            BatchOutput::Append => existing_sections(&combined_path)?,
+            _ => HashSet::new(),
+        };
+
+        // Truncated up front, so a run where no file succeeds leaves no stale transcript
+        let mut combined = match options.output {
+            BatchOutput::Overwrite => Some(open_combined(&combined_path, options)?),
+            _ => None,
+        };
+        let mut entries = Vec::with_capacity(files.len());
+
+        for path in files {
+            let name = file_name(&path);
+            let sidecar = sidecar_path(&path);
+
+            let already_done = match options.output {
+                BatchOutput::Overwrite => false,
+                BatchOutput::Append => existing_sections.contains(&name),
+                BatchOutput::PerFileSidecar => options.skip_existing && sidecar.exists(),
+            };
+            if already_done {
+                entries.push(BatchEntry {
+                    path,
+                    outcome: BatchOutcome::Skipped(SkipReason::AlreadyTranscribed),
+                });
+                continue;
+            }
+
+            let text = match self.transcribe_file(&path) {
+                Ok(text) => text,
+                Err(ScribeError::NoAudioTrack(_)) => {
+                    entries.push(BatchEntry {
+                        path,
+                        outcome: BatchOutcome::Skipped(SkipReason::NoAudioTrack),
+                    });
+                    continue;
+                }
+                Err(e) => {
+                    entries.push(BatchEntry {
+                        path,
+                        outcome: BatchOutcome::Failed(e.to_string()),
+                    });
+                    continue;
+                }
+            };
+
+            match options.output {
+                BatchOutput::PerFileSidecar => {
+                    let mut contents = if options.bom {
+                        BOM.to_vec()
+                    } else {
+                        Vec::new()
+                    };
+                    let section = format!("{}\n", text);
+                    contents.extend_from_slice(options.line_ending.apply(&section).as_bytes());
+                    fs::write(&sidecar, contents)
+                        .map_err(|e| format!("Failed to write {}: {}", sidecar.display(), e))?;
+                }
+                BatchOutput::Overwrite | BatchOutput::Append => {
+                    if combined.is_none() {
+                        combined = Some(open_combined(&combined_path, options)?);
+                    }
+                    if let Some(file) = combined.as_mut() {
+                        let section = format!("\n=== {} ===\n{}\n", name, text);
+                        file.write_all(options.line_ending.apply(&section).as_bytes())
+                            .map_err(|e| {
+                                format!("Failed to write {}: {}", combined_path.display(), e)
+                            })?;
+                    }
+                }
+            }
+
+            entries.push(BatchEntry {
+                path,
+                outcome: BatchOutcome::Transcribed(text),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Transcribes the audio files in a directory one at a time, lazily
+    ///
+    /// Unlike [`transcribe_directory`](Self::transcribe_directory), nothing is
+    /// written and nothing is transcribed up front: each call to `next` runs
+    /// [`transcribe_file_with_metadata`](Self::transcribe_file_with_metadata) on
+    /// the next file in name order and yields its path and result, so progress
+    /// can be shown and output written as the batch goes. Only
+    /// [`BatchOptions::extensions`] applies; the output settings are left to the
+    /// caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be read. A failure on one file is
+    /// yielded with that file and doesn't end the iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{BatchOptions, Transcriber};
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let options = BatchOptions::default();
+    /// let results = transcriber.transcribe_directory_iter(Path::new("recordings"), &options);
+    /// for (path, result) in results.unwrap() {
+    ///     match result {
+    ///         Ok(result) => println!("{}: {}", path.display(), result.text),
+    ///         Err(e) => eprintln!("{}: {}", path.display(), e),
+    ///     }
+    /// }
+    /// ```
+    pub fn transcribe_directory_iter<'a>(
+        &'a self,
+        dir: &Path,
+        options: &BatchOptions,
+    ) -> Result<impl Iterator<Item = DirectoryResult> + 'a, String> {
+        let files = audio_files(dir, options)?;
+        Ok(files.into_iter().map(move |path| {
+            let result = self.transcribe_file_with_metadata(&path);
+            (path, result)
+        }))
+    }
+
+    /// Transcribes a directory on worker threads, sending each result as it completes
+    ///
+    /// Like [`transcribe_directory_iter`](Self::transcribe_directory_iter), but
+    /// `workers` files are transcribed at once and results arrive on the
+    /// returned channel in completion order rather than name order. The channel
+    /// closes once every file has been sent. Dropping the receiver stops the
+    /// workers after the files they are working on.
+    ///
+    /// With [`BatchOptions::ordered`], results arrive in name order instead: a
+    /// result that finishes early is held until those before it have been
+    /// sent. Workers run at most `2 * workers` files ahead of the next result
+    /// due, so a slow file stalls the batch briefly rather than letting held
+    /// results pile up.
+    ///
+    /// Takes the transcriber by `Arc` so the workers can share it; a resident
+    /// helper from [`warm_up`](Self::warm_up) serves one worker at a time while
+    /// the others use one-shot helpers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{BatchOptions, Transcriber};
+    /// use std::path::Path;
+    /// use std::sync::Arc;
+    ///
+    /// let transcriber = Arc::new(Transcriber::new().unwrap());
+    /// let results = transcriber
+    ///     .transcribe_directory_channel(Path::new("recordings"), &BatchOptions::default(), 4)
+    ///     .unwrap();
+    /// for (path, result) in results {
+    ///     println!("{}: {:?}", path.display(), result.map(|r| r.text));
+    /// }
+    /// ```
+    pub fn transcribe_directory_channel(
+        self: Arc<Self>,
+        dir: &Path,
+        options: &BatchOptions,
+        workers: usize,
+    ) -> Result<Receiver<DirectoryResult>, String> {
+        let files = audio_files(dir, options)?;
+        let workers = workers.clamp(1, files.len().max(1));
+        let lookahead = options.ordered.then_some(2 * workers);
+        let dispatch = Arc::new(Dispatch {
+            state: Mutex::new(DispatchState {
+                queue: files.into_iter().enumerate().collect(),
+                next_to_send: 0,
+                held: BTreeMap::new(),
+                closed: false,
+            }),
+            sent: Condvar::new(),
+        });
+        let (sender, receiver) = channel();
+
+        for _ in 0..workers {
+            let transcriber = Arc::clone(&self);
+            let dispatch = Arc::clone(&dispatch);
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                while let Some((index, path)) = dispatch.take(lookahead) {
+                    let result = transcriber.transcribe_file_with_metadata(&path);
+                    if !dispatch.send(&sender, index, (path, result), lookahead.is_some()) {
+                        break;
+                    }
+                }
+            });
+        }
+
+        Ok(receiver)
+    }
+}
+
+/// Files shared out to the workers of `transcribe_directory_channel`
+struct Dispatch {
+    state: Mutex<DispatchState>,
+    /// Signalled when a result has been sent or the receiver has gone
+    sent: Condvar,
+}
+
+struct DispatchState {
+    /// Files not taken yet, with their position in name order
+    queue: VecDeque<(usize, PathBuf)>,
+    /// Position of the next result due, when delivering in order
+    next_to_send: usize,
+    /// Results finished ahead of their turn, when delivering in order
+    held: BTreeMap<usize, DirectoryResult>,
+    /// Whether the receiver was dropped
+    closed: bool,
+}
+
+impl Dispatch {
+    /// Takes the next file, waiting while it is `lookahead` or more past the next result due
+    fn take(&self, lookahead: Option<usize>) -> Option<(usize, PathBuf)> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if state.closed {
+                return None;
+            }
+            let (index, _) = state.queue.front()?;
+            if lookahead.is_some_and(|lookahead| *index >= state.next_to_send + lookahead) {
+                state = self
+                    .sent
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+                continue;
+            }
+            return state.queue.pop_front();
+        }
+    }
+
+    /// Sends a finished result, or holds it until its turn when `ordered`
+    ///
+    /// Returns `false` once the receiver is gone.
+    fn send(
+        &self,
+        sender: &Sender<DirectoryResult>,
+        index: usize,
+        result: DirectoryResult,
+        ordered: bool,
+    ) -> bool {
+        if !ordered {
+            return sender.send(result).is_ok();
+        }
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.held.insert(index, result);
+        loop {
+            let next = state.next_to_send;
+            let Some(result) = state.held.remove(&next) else {
+                break;
+            };
+            if sender.send(result).is_err() {
+                state.closed = true;
+                break;
+            }
+            state.next_to_send += 1;
+        }
+        self.sent.notify_all();
+        !state.closed
+    }
+}
+
+/// Lists the audio files in `dir` matching the configured extensions, sorted by name
+fn audio_files(dir: &Path, options: &BatchOptions) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| {
+                    options
+                        .extensions
+                        .iter()
+                        .any(|e| e.eq_ignore_ascii_case(ext))
+                })
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// The per-file transcript of `path`: its full file name with `.txt` added
+fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".txt");
+    path.with_file_name(name)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Collects the file names that already have a `=== name ===` section
+fn existing_sections(path: &Path) -> Result<HashSet<String>, String> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(contents
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .filter_map(|line| line.strip_prefix("=== ")?.strip_suffix(" ==="))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Opens the combined transcript, writing a BOM first if requested and the file is empty
+fn open_combined(path: &Path, options: &BatchOptions) -> Result<File, String> {
+    let mut open_options = OpenOptions::new();
+    open_options.create(true);
+    if options.output == BatchOutput::Append {
+        open_options.append(true);
+    } else {
+        open_options.write(true).truncate(true);
+    }
+    let mut file = open_options
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    let is_empty = file.metadata().map(|m| m.len() == 0).unwrap_or(false);
+    if options.bom && is_empty {
+        file.write_all(BOM)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(file)
+}
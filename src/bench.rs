@@ -1,8 +1,11 @@
 use clap::Parser;
 use reqwest::blocking::multipart;
 use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
+use std::process::{Command, Stdio};
 use std::time::Instant;
+use swift_scribe::{StreamingResult, TranscriptionResult};
 
 #[derive(Parser, Debug)]
 #[command(name = "swift-scribe-bench")]
@@ -27,6 +30,10 @@ struct Args {
     /// Whisper model to use
     #[arg(short, long, default_value = "whisper-large-v3-turbo")]
     model: String,
+
+    /// Also measure how streaming partial results converge to the final text
+    #[arg(short, long)]
+    streaming: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -36,6 +43,14 @@ struct WhisperResponse {
     duration: Option<f64>,
     #[serde(default)]
     language: Option<String>,
+    #[serde(default)]
+    segments: Vec<WhisperSegment>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct WhisperSegment {
+    /// Mean token log probability, the closest thing Whisper has to a confidence
+    avg_logprob: f64,
 }
 
 #[derive(Serialize, Debug)]
@@ -45,6 +60,8 @@ struct BenchmarkResult {
     local: LocalResult,
     api: ApiResult,
     speedup: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    streaming: Option<StreamingConvergence>,
 }
 
 #[derive(Serialize, Debug)]
@@ -52,6 +69,9 @@ struct LocalResult {
     duration_secs: f64,
     text: String,
     method: String,
+    /// Segment counts per confidence bucket, when the recognizer reports confidence
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence_histogram: Option<Vec<usize>>,
 }
 
 #[derive(Serialize, Debug)]
@@ -59,13 +79,43 @@ struct ApiResult {
     duration_secs: f64,
     text: String,
     model: String,
+    /// Segment counts per confidence bucket, taking exp(avg_logprob) as confidence
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confidence_histogram: Option<Vec<usize>>,
+}
+
+/// Number of equal-width buckets spanning confidences 0.0-1.0
+const CONFIDENCE_BUCKETS: usize = 10;
+
+/// Number of buckets used to summarize WER by progress through a segment
+const PROGRESS_BUCKETS: usize = 10;
+
+#[derive(Serialize, Debug)]
+struct StreamingConvergence {
+    duration_secs: f64,
+    first_partial_secs: Option<f64>,
+    segments: usize,
+    final_text: String,
+    /// Mean WER of partials, bucketed by how far through their segment they arrived
+    mean_wer_by_progress: Vec<Option<f64>>,
+    /// WER of every partial against the final text of its segment
+    curve: Vec<ConvergencePoint>,
+}
+
+#[derive(Serialize, Debug)]
+struct ConvergencePoint {
+    elapsed_secs: f64,
+    segment: usize,
+    words: usize,
+    wer: f64,
 }
 
 fn main() {
     let args = Args::parse();
 
     // Get API key from args or environment
-    let api_key = args.api_key
+    let api_key = args
+        .api_key
         .or_else(|| std::env::var("GROQ_API_KEY").ok())
         .expect("GROQ_API_KEY not provided. Use --api-key or set GROQ_API_KEY env var");
 
@@ -91,8 +141,8 @@ fn main() {
     // Run benchmarks
     let mut local_times = Vec::new();
     let mut api_times = Vec::new();
-    let mut local_text = String::new();
-    let mut api_text = String::new();
+    let mut local = Transcript::default();
+    let mut api = Transcript::default();
 
     for run in 1..=args.runs {
         if !args.json && args.runs > 1 {
@@ -104,9 +154,9 @@ fn main() {
             print!("  ⚡ Testing local SpeechAnalyzer... ");
             std::io::Write::flush(&mut std::io::stdout()).unwrap();
         }
-        
+
         let start = Instant::now();
-        local_text = run_local_transcription(&args.audio_file);
+        local = run_local_transcription(&args.audio_file);
         let local_duration = start.elapsed().as_secs_f64();
         local_times.push(local_duration);
 
@@ -121,7 +171,7 @@ fn main() {
         }
 
         let start = Instant::now();
-        api_text = run_whisper_api(&args.audio_file, &api_key, &args.model);
+        api = run_whisper_api(&args.audio_file, &api_key, &args.model);
         let api_duration = start.elapsed().as_secs_f64();
         api_times.push(api_duration);
 
@@ -135,20 +185,39 @@ fn main() {
     let avg_api = api_times.iter().sum::<f64>() / api_times.len() as f64;
     let speedup = avg_api / avg_local;
 
+    let streaming = if args.streaming {
+        if !args.json {
+            print!("  📈 Measuring streaming convergence... ");
+            std::io::Write::flush(&mut std::io::stdout()).unwrap();
+        }
+
+        let convergence = run_streaming_convergence(&args.audio_file);
+
+        if !args.json {
+            println!("{:.2}s", convergence.duration_secs);
+        }
+        Some(convergence)
+    } else {
+        None
+    };
+
     let result = BenchmarkResult {
         audio_file: args.audio_file.display().to_string(),
         file_size_mb,
         local: LocalResult {
             duration_secs: avg_local,
-            text: local_text.clone(),
+            confidence_histogram: confidence_histogram(&local.confidences),
+            text: local.text,
             method: "SpeechAnalyzer".to_string(),
         },
         api: ApiResult {
             duration_secs: avg_api,
-            text: api_text.clone(),
+            confidence_histogram: confidence_histogram(&api.confidences),
+            text: api.text,
             model: args.model.clone(),
         },
         speedup,
+        streaming,
     };
 
     if args.json {
@@ -158,10 +227,25 @@ fn main() {
     }
 }
 
-fn run_local_transcription(audio_file: &PathBuf) -> String {
-    use std::process::Command;
+/// Text of one transcription with the confidence of each segment that reported one
+#[derive(Default)]
+struct Transcript {
+    text: String,
+    confidences: Vec<f64>,
+}
+
+impl Transcript {
+    fn error() -> Self {
+        Self {
+            text: String::from("[ERROR]"),
+            confidences: Vec::new(),
+        }
+    }
+}
 
+fn run_local_transcription(audio_file: &PathBuf) -> Transcript {
     let output = Command::new("./helpers/transcribe")
+        .arg("--json")
         .arg(audio_file)
         .output()
         .expect("Failed to run local transcriber");
@@ -169,19 +253,144 @@ fn run_local_transcription(audio_file: &PathBuf) -> String {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         eprintln!("Local transcription failed: {}", stderr);
-        return String::from("[ERROR]");
+        return Transcript::error();
     }
 
-    String::from_utf8_lossy(&output.stdout).trim().to_string()
+    let result: TranscriptionResult =
+        serde_json::from_slice(&output.stdout).expect("Failed to parse local result");
+    Transcript {
+        text: result.text.trim().to_string(),
+        confidences: result
+            .segments
+            .iter()
+            .filter_map(|segment| segment.confidence)
+            .map(f64::from)
+            .collect(),
+    }
 }
 
-fn run_whisper_api(audio_file: &PathBuf, api_key: &str, model: &str) -> String {
+/// Counts confidences into equal-width buckets, or `None` if there are none
+fn confidence_histogram(confidences: &[f64]) -> Option<Vec<usize>> {
+    if confidences.is_empty() {
+        return None;
+    }
+    let mut buckets = vec![0; CONFIDENCE_BUCKETS];
+    for confidence in confidences {
+        let bucket = (confidence.clamp(0.0, 1.0) * CONFIDENCE_BUCKETS as f64) as usize;
+        buckets[bucket.min(CONFIDENCE_BUCKETS - 1)] += 1;
+    }
+    Some(buckets)
+}
+
+fn run_streaming_convergence(audio_file: &PathBuf) -> StreamingConvergence {
+    let start = Instant::now();
+    let mut child = Command::new("./helpers/transcribe_stream")
+        .arg("--file")
+        .arg(audio_file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("Failed to run streaming transcriber");
+
+    let stdout = child
+        .stdout
+        .take()
+        .expect("Failed to capture streaming stdout");
+    let mut first_partial_secs = None;
+    let mut pending: Vec<(f64, String)> = Vec::new();
+    let mut finals: Vec<String> = Vec::new();
+    let mut curve = Vec::new();
+    let mut buckets = vec![Vec::new(); PROGRESS_BUCKETS];
+
+    for line in BufReader::new(stdout).lines() {
+        let Ok(line) = line else { break };
+        let Ok(result) = serde_json::from_str::<StreamingResult>(&line) else {
+            continue;
+        };
+        let elapsed = start.elapsed().as_secs_f64();
+
+        if !result.is_final {
+            first_partial_secs.get_or_insert(elapsed);
+            pending.push((elapsed, result.text));
+            continue;
+        }
+
+        // Score each partial of this segment against the text it converged to
+        let count = pending.len();
+        for (i, (elapsed_secs, text)) in pending.drain(..).enumerate() {
+            let wer = word_error_rate(&text, &result.text);
+            buckets[i * PROGRESS_BUCKETS / count].push(wer);
+            curve.push(ConvergencePoint {
+                elapsed_secs,
+                segment: finals.len(),
+                words: normalize_words(&text).len(),
+                wer,
+            });
+        }
+        finals.push(result.text);
+    }
+
+    let status = child
+        .wait()
+        .expect("Failed to wait for streaming transcriber");
+    if !status.success() {
+        eprintln!("Streaming transcription failed: {}", status);
+    }
+
+    StreamingConvergence {
+        duration_secs: start.elapsed().as_secs_f64(),
+        first_partial_secs,
+        segments: finals.len(),
+        final_text: finals.join(" "),
+        mean_wer_by_progress: buckets
+            .iter()
+            .map(|b| (!b.is_empty()).then(|| b.iter().sum::<f64>() / b.len() as f64))
+            .collect(),
+        curve,
+    }
+}
+
+/// Lowercased words with punctuation stripped, for WER comparison
+fn normalize_words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '\'')
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Word error rate of `hypothesis` against `reference` (word-level edit distance)
+fn word_error_rate(hypothesis: &str, reference: &str) -> f64 {
+    let hyp = normalize_words(hypothesis);
+    let reference = normalize_words(reference);
+    if reference.is_empty() {
+        return if hyp.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    let mut prev: Vec<usize> = (0..=hyp.len()).collect();
+    for (i, ref_word) in reference.iter().enumerate() {
+        let mut row = vec![i + 1; hyp.len() + 1];
+        for (j, hyp_word) in hyp.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ref_word != hyp_word);
+            row[j + 1] = substitution.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+
+    prev[hyp.len()] as f64 / reference.len() as f64
+}
+
+fn run_whisper_api(audio_file: &PathBuf, api_key: &str, model: &str) -> Transcript {
     let client = reqwest::blocking::Client::new();
 
     let form = multipart::Form::new()
         .text("model", model.to_string())
         .text("temperature", "0")
-        .text("response_format", "json")
+        .text("response_format", "verbose_json")
         .file("file", audio_file)
         .expect("Failed to read audio file");
 
@@ -195,26 +404,56 @@ fn run_whisper_api(audio_file: &PathBuf, api_key: &str, model: &str) -> String {
     if !response.status().is_success() {
         eprintln!("API request failed: {}", response.status());
         eprintln!("Response: {}", response.text().unwrap_or_default());
-        return String::from("[ERROR]");
+        return Transcript::error();
     }
 
     let text = response.text().expect("Failed to read response");
     let whisper: WhisperResponse = serde_json::from_str(&text).expect("Failed to parse response");
-    whisper.text
+    Transcript {
+        text: whisper.text,
+        confidences: whisper
+            .segments
+            .iter()
+            .map(|segment| segment.avg_logprob.exp())
+            .collect(),
+    }
+}
+
+/// Prints a histogram as one bar per bucket, scaled to the fullest bucket
+fn print_confidence_histogram(histogram: &[usize]) {
+    let total: usize = histogram.iter().sum();
+    let max = histogram.iter().copied().max().unwrap_or(0).max(1);
+    println!("  Confidence:    {} segments", total);
+    for (i, count) in histogram.iter().enumerate() {
+        let width = 1.0 / CONFIDENCE_BUCKETS as f64;
+        println!(
+            "    {:.1}-{:.1}   {:<30} {}",
+            i as f64 * width,
+            (i + 1) as f64 * width,
+            "█".repeat(count * 30 / max),
+            count
+        );
+    }
 }
 
 fn print_results(result: &BenchmarkResult, local_times: &[f64], api_times: &[f64]) {
     println!("\n📊 Results");
     println!("═══════════════════════════════════════════");
-    
+
     println!("\n⚡ Local SpeechAnalyzer");
     println!("  Average time:  {:.2}s", result.local.duration_secs);
     if local_times.len() > 1 {
         let min = local_times.iter().cloned().fold(f64::INFINITY, f64::min);
-        let max = local_times.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let max = local_times
+            .iter()
+            .cloned()
+            .fold(f64::NEG_INFINITY, f64::max);
         println!("  Min/Max:       {:.2}s / {:.2}s", min, max);
     }
     println!("  Output:        {} chars", result.local.text.len());
+    if let Some(histogram) = &result.local.confidence_histogram {
+        print_confidence_histogram(histogram);
+    }
 
     println!("\n🌐 Whisper API ({})", result.api.model);
     println!("  Average time:  {:.2}s", result.api.duration_secs);
@@ -224,15 +463,48 @@ fn print_results(result: &BenchmarkResult, local_times: &[f64], api_times: &[f64
         println!("  Min/Max:       {:.2}s / {:.2}s", min, max);
     }
     println!("  Output:        {} chars", result.api.text.len());
+    if let Some(histogram) = &result.api.confidence_histogram {
+        print_confidence_histogram(histogram);
+    }
+
+    if let Some(streaming) = &result.streaming {
+        println!("\n📈 Streaming Convergence");
+        if let Some(first) = streaming.first_partial_secs {
+            println!("  First partial: {:.2}s", first);
+        }
+        println!(
+            "  Partials:      {} across {} segments",
+            streaming.curve.len(),
+            streaming.segments
+        );
+        println!("  Mean WER vs final, by progress through segment:");
+        for (i, wer) in streaming.mean_wer_by_progress.iter().enumerate() {
+            let pct = 100 / PROGRESS_BUCKETS;
+            if let Some(wer) = wer {
+                println!(
+                    "    {:>3}-{:<3}%   {:.1}%",
+                    i * pct,
+                    (i + 1) * pct,
+                    wer * 100.0
+                );
+            }
+        }
+    }
 
     println!("\n🏆 Comparison");
     println!("  Speedup:       {:.2}x faster (local)", result.speedup);
-    
+
     let percentage = ((result.speedup - 1.0) * 100.0).abs();
     if result.speedup > 1.0 {
-        println!("  Improvement:   {:.1}% faster with SpeechAnalyzer", percentage);
+        println!(
+            "  Improvement:   {:.1}% faster with SpeechAnalyzer",
+            percentage
+        );
     } else {
-        println!("  Improvement:   {:.1}% faster with Whisper API", percentage);
+        println!(
+            "  Improvement:   {:.1}% faster with Whisper API",
+            percentage
+        );
     }
 
     // Show text comparison if they differ
@@ -240,9 +512,15 @@ fn print_results(result: &BenchmarkResult, local_times: &[f64], api_times: &[f64
         println!("\n📝 Transcription Comparison");
         println!("  Note: Outputs differ in length/content");
         println!("\n  Local (first 200 chars):");
-        println!("  {}", &result.local.text.chars().take(200).collect::<String>());
+        println!(
+            "  {}",
+            &result.local.text.chars().take(200).collect::<String>()
+        );
         println!("\n  API (first 200 chars):");
-        println!("  {}", &result.api.text.chars().take(200).collect::<String>());
+        println!(
+            "  {}",
+            &result.api.text.chars().take(200).collect::<String>()
+        );
     } else {
         println!("\n✓ Both transcriptions match!");
     }
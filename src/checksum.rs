@@ -0,0 +1,189 @@
+//! SHA-256 pinning of the helper binary
+
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Expected SHA-256 of a helper, checked before the helper runs
+pub(crate) struct HelperChecksum {
+    expected: [u8; 32],
+    /// Outcome of the last check, with the identity of the file it hashed
+    last: Mutex<Option<(FileIdentity, Result<(), String>)>>,
+}
+
+/// What identifies one version of a file without reading it
+///
+/// Replacing the helper, whether by renaming another file over it or by
+/// writing into it, changes at least one of these, so the file is hashed again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileIdentity {
+    dev: u64,
+    ino: u64,
+    len: u64,
+    mtime: (i64, i64),
+    ctime: (i64, i64),
+}
+
+impl FileIdentity {
+    fn of(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+            len: metadata.size(),
+            mtime: (metadata.mtime(), metadata.mtime_nsec()),
+            ctime: (metadata.ctime(), metadata.ctime_nsec()),
+        }
+    }
+}
+
+impl HelperChecksum {
+    /// Parses a digest written as 64 hex digits, in either case
+    pub(crate) fn parse(hex: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid helper SHA-256 `{}`: expected 64 hex digits", hex);
+        if hex.len() != 64 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let mut expected = [0u8; 32];
+        for (byte, i) in expected.iter_mut().zip((0..64).step_by(2)) {
+            *byte = u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Self {
+            expected,
+            last: Mutex::new(None),
+        })
+    }
+
+    /// The expected digest as lowercase hex
+    pub(crate) fn hex(&self) -> String {
+        to_hex(&self.expected)
+    }
+
+    /// Fails if the helper at `path` doesn't match the expected digest
+    ///
+    /// Called before every spawn. The helper is hashed again whenever the file
+    /// at `path` is no longer the one hashed last time.
+    pub(crate) fn verify(&self, path: &Path) -> Result<(), String> {
+        let hash_error =
+            |e: std::io::Error| format!("Failed to hash helper {}: {}", path.display(), e);
+        let identity = FileIdentity::of(&std::fs::metadata(path).map_err(hash_error)?);
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((checked, outcome)) = last.as_ref() {
+            if *checked == identity {
+                return outcome.clone();
+            }
+        }
+
+        let (identity, actual) = hash_file(path).map_err(hash_error)?;
+        let outcome = if actual == self.expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "Helper checksum mismatch: {} has SHA-256 {}, expected {}",
+                path.display(),
+                to_hex(&actual),
+                to_hex(&self.expected)
+            ))
+        };
+        *last = Some((identity, outcome.clone()));
+        outcome
+    }
+}
+
+fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Hashes the file at `path`, along with the identity of the file it read
+fn hash_file(path: &Path) -> std::io::Result<(FileIdentity, [u8; 32])> {
+    let mut file = File::open(path)?;
+    let identity = FileIdentity::of(&file.metadata()?);
+    let mut sha = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok((identity, sha.finalize().into()));
+        }
+        sha.update(&buffer[..read]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha256_hex(data: &[u8]) -> String {
+        to_hex(&Sha256::digest(data))
+    }
+
+    #[test]
+    fn matches_known_digests() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two blocks once padded
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            sha256_hex(&vec![b'a'; 1_000_000]),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    /// Writes `contents` to a fresh file in its own directory
+    fn helper_file(dir: &tempfile::TempDir, contents: &[u8]) -> std::path::PathBuf {
+        let path = dir.path().join("helper");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn accepts_a_matching_helper() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = helper_file(&dir, b"abc");
+        let checksum = HelperChecksum::parse(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        )
+        .unwrap();
+        assert_eq!(checksum.verify(&path), Ok(()));
+    }
+
+    #[test]
+    fn rehashes_a_replaced_helper() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = helper_file(&dir, b"abc");
+        let checksum = HelperChecksum::parse(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        )
+        .unwrap();
+        assert_eq!(checksum.verify(&path), Ok(()));
+
+        let swapped = dir.path().join("swapped");
+        std::fs::write(&swapped, b"abd").unwrap();
+        std::fs::rename(&swapped, &path).unwrap();
+        let error = checksum.verify(&path).unwrap_err();
+        assert!(error.starts_with("Helper checksum mismatch"), "{}", error);
+    }
+
+    #[test]
+    fn parses_hex_digests() {
+        let digest = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert_eq!(
+            HelperChecksum::parse(digest).unwrap().expected[..2],
+            [0xba, 0x78]
+        );
+        assert!(HelperChecksum::parse("ba7816bf").is_err());
+        assert!(HelperChecksum::parse(&"g".repeat(64)).is_err());
+        assert!(HelperChecksum::parse(&"+f".repeat(32)).is_err());
+    }
+}
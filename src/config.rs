@@ -0,0 +1,407 @@
+//! Serializable transcription settings
+
+use crate::checksum::HelperChecksum;
+use crate::{
+    AudioInputMode, BackpressurePolicy, ChannelMix, ConfidenceAggregation, EmptyOutputPolicy,
+    FormatChangePolicy, LineEnding, Priority, ResampleQuality, RestartPolicy, ScribeError,
+    StabilityMode, StderrMode, StreamingTranscriber, TaskHint, TempNaming, Transcriber,
+    DEFAULT_BACKLOG_LIMIT,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Transcription settings that can be saved to and loaded from a config file
+///
+/// Mirrors the builder options of [`Transcriber`] and [`StreamingTranscriber`] so
+/// a profile can be applied in one call with `from_config`. Every field is
+/// optional when deserializing; missing fields take the builder defaults.
+/// Options that only apply to one transcriber are ignored by the other.
+///
+/// # Examples
+///
+/// ```no_run
+/// use swift_scribe::{StreamingTranscriber, TranscriptionConfig};
+///
+/// let config: TranscriptionConfig = serde_json::from_str(
+///     r#"{ "input_mode": "programmatic", "sentence_segmentation": true }"#,
+/// ).unwrap();
+/// let transcriber = StreamingTranscriber::from_config(&config).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranscriptionConfig {
+    /// Custom helper binary path; discovered automatically when unset
+    pub helper_path: Option<PathBuf>,
+    /// SHA-256 the helper must match, as 64 hex digits; unchecked when unset
+    pub expected_helper_sha256: Option<String>,
+    /// How many times helper discovery scans the default locations
+    pub discovery_attempts: u32,
+    /// Seconds between helper discovery attempts
+    pub discovery_delay_secs: f64,
+    /// Scheduling priority of the spawned helper
+    pub priority: Priority,
+    /// Kind of speech the recognizer should expect
+    pub task_hint: TaskHint,
+    /// Arguments passed to the helper verbatim, after the crate's own
+    pub extra_args: Vec<String>,
+    /// Directory for temporary files (file transcription only)
+    pub temp_dir: Option<PathBuf>,
+    /// How temporary files are named (file transcription only)
+    pub temp_naming: TempNaming,
+    /// Skip leading and trailing silence (file transcription only)
+    pub trim_silence: bool,
+    /// How segment confidences combine into a file confidence (file transcription only)
+    pub confidence_aggregation: ConfidenceAggregation,
+    /// Whether an empty transcript is an error (file transcription only)
+    pub empty_output: EmptyOutputPolicy,
+    /// Keep filler words and false starts (file transcription only)
+    pub verbatim: bool,
+    /// Write spelled-out numbers, times and amounts in digits (file transcription only)
+    pub inverse_text_normalization: bool,
+    /// Seconds added to every segment start, may be negative (file transcription only)
+    pub timestamp_offset_secs: f64,
+    /// Length in seconds beyond which segments are split; unlimited when unset
+    /// (file transcription only)
+    pub max_segment_duration_secs: Option<f64>,
+    /// Line terminator of subtitles written to a writer (file transcription only)
+    pub line_ending: LineEnding,
+    /// Start subtitles written to a writer with a UTF-8 BOM (file transcription only)
+    pub bom: bool,
+    /// Locale the audio is spoken in; the helper's default when unset
+    pub locale: Option<String>,
+    /// Locales the audio may be spoken in, most likely first; the helper's
+    /// default when empty (file transcription only)
+    pub candidate_locales: Vec<String>,
+    /// Seconds a resident helper may go unused before it is stopped; never when
+    /// unset (file transcription only)
+    pub helper_idle_timeout_secs: Option<f64>,
+    /// Where streaming audio comes from (streaming only)
+    pub input_mode: AudioInputMode,
+    /// Sample rate of fed audio, declared up front with `input_channels` (streaming only)
+    pub input_sample_rate: Option<u32>,
+    /// Channel count of fed audio, declared up front with `input_sample_rate`
+    /// (streaming only)
+    pub input_channels: Option<u16>,
+    /// How a mid-session input format change is handled (streaming only)
+    pub format_change: FormatChangePolicy,
+    /// How multi-channel fed audio is mixed down to mono (streaming only)
+    pub channel_mix: ChannelMix,
+    /// What starting does while a session is running (streaming only)
+    pub restart_policy: RestartPolicy,
+    /// Emit one final result per sentence (streaming only)
+    pub sentence_segmentation: bool,
+    /// Report alternative readings with each final (streaming only)
+    pub alternatives_stream: bool,
+    /// How fed audio is resampled to 16kHz (streaming only)
+    pub resample_quality: ResampleQuality,
+    /// End the session on a malformed helper output line (streaming only)
+    pub strict_parsing: bool,
+    /// Maximum partial results per second; unthrottled when unset (streaming only)
+    pub partial_rate: Option<f32>,
+    /// Whether partials wait until they stop changing (streaming only)
+    pub stability: StabilityMode,
+    /// Deliver captured microphone audio to the caller (streaming only)
+    pub audio_passthrough: bool,
+    /// Microphone capture buffer size in frames; the helper's choice when unset (streaming only)
+    pub mic_buffer_frames: Option<u32>,
+    /// What feeding does once the helper falls behind (streaming only)
+    pub backpressure: BackpressurePolicy,
+    /// How far fed audio may run ahead of the helper, in seconds (streaming only)
+    pub backlog_limit_secs: f64,
+    /// Don't send entirely silent fed chunks to the helper (streaming only)
+    pub skip_silence: bool,
+    /// WAV file recording the audio sent to the helper (streaming only)
+    pub capture_fed_audio: Option<PathBuf>,
+    /// Start partials with the text of the final before them (streaming only)
+    pub final_carryover: bool,
+    /// Include the words partials may still revise (streaming only)
+    pub volatile_tail: bool,
+    /// Length after which a session is finalized, in seconds; unlimited when unset
+    /// (streaming only)
+    pub max_duration_secs: Option<f64>,
+    /// Where the helper's stderr goes (streaming only)
+    pub stderr: StderrMode,
+    /// Unix socket of an already running helper to connect to instead of
+    /// spawning one (streaming only)
+    pub socket_path: Option<PathBuf>,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            helper_path: None,
+            expected_helper_sha256: None,
+            discovery_attempts: 1,
+            discovery_delay_secs: 0.0,
+            priority: Priority::default(),
+            task_hint: TaskHint::default(),
+            extra_args: Vec::new(),
+            temp_dir: None,
+            temp_naming: TempNaming::default(),
+            trim_silence: false,
+            confidence_aggregation: ConfidenceAggregation::default(),
+            empty_output: EmptyOutputPolicy::default(),
+            verbatim: true,
+            inverse_text_normalization: false,
+            timestamp_offset_secs: 0.0,
+            max_segment_duration_secs: None,
+            line_ending: LineEnding::default(),
+            bom: false,
+            locale: None,
+            candidate_locales: Vec::new(),
+            helper_idle_timeout_secs: None,
+            input_mode: AudioInputMode::default(),
+            input_sample_rate: None,
+            input_channels: None,
+            format_change: FormatChangePolicy::default(),
+            channel_mix: ChannelMix::default(),
+            restart_policy: RestartPolicy::default(),
+            sentence_segmentation: false,
+            alternatives_stream: false,
+            resample_quality: ResampleQuality::default(),
+            strict_parsing: false,
+            partial_rate: None,
+            stability: StabilityMode::default(),
+            audio_passthrough: false,
+            mic_buffer_frames: None,
+            backpressure: BackpressurePolicy::default(),
+            backlog_limit_secs: DEFAULT_BACKLOG_LIMIT.as_secs_f64(),
+            skip_silence: false,
+            capture_fed_audio: None,
+            final_carryover: false,
+            volatile_tail: true,
+            max_duration_secs: None,
+            stderr: StderrMode::default(),
+            socket_path: None,
+        }
+    }
+}
+
+impl TranscriptionConfig {
+    /// The discovery delay, treating a negative or unrepresentable value as none
+    fn discovery_delay(&self) -> Duration {
+        Duration::try_from_secs_f64(self.discovery_delay_secs).unwrap_or_default()
+    }
+}
+
+impl Transcriber {
+    /// Creates a transcriber from a [`TranscriptionConfig`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`TranscriberBuilder::build`](crate::TranscriberBuilder::build).
+    pub fn from_config(config: &TranscriptionConfig) -> Result<Self, ScribeError> {
+        let mut builder = Self::builder()
+            .with_priority(config.priority)
+            .with_task_hint(config.task_hint)
+            .with_trim_silence(config.trim_silence)
+            .with_confidence_aggregation(config.confidence_aggregation)
+            .with_empty_output_policy(config.empty_output)
+            .with_verbatim(config.verbatim)
+            .with_inverse_text_normalization(config.inverse_text_normalization)
+            .with_temp_naming(config.temp_naming.clone())
+            .with_timestamp_offset(config.timestamp_offset_secs)
+            .with_line_ending(config.line_ending)
+            .with_bom(config.bom)
+            .with_candidate_locales(&config.candidate_locales)
+            .with_extra_args(&config.extra_args)
+            .with_discovery_retry(config.discovery_attempts, config.discovery_delay());
+        if let Some(path) = &config.helper_path {
+            builder = builder.with_helper_path(path);
+        }
+        if let Some(hex) = &config.expected_helper_sha256 {
+            builder = builder.with_expected_helper_sha256(hex);
+        }
+        if let Some(locale) = &config.locale {
+            builder = builder.with_locale(locale);
+        }
+        if let Some(dir) = &config.temp_dir {
+            builder = builder.with_temp_dir(dir);
+        }
+        if let Some(secs) = config.max_segment_duration_secs {
+            builder = builder.with_max_segment_duration(
+                Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX),
+            );
+        }
+        if let Some(secs) = config.helper_idle_timeout_secs {
+            builder = builder.with_helper_idle_timeout(
+                Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX),
+            );
+        }
+        builder.build()
+    }
+
+    /// Returns the settings this transcriber actually uses
+    ///
+    /// The builder options as applied, with the discovered helper path filled
+    /// in. Streaming-only fields keep their defaults. Handy for logging the
+    /// startup configuration; passing the result to
+    /// [`from_config`](Self::from_config) builds an equivalent transcriber.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// println!("{}", serde_json::to_string_pretty(&transcriber.config()).unwrap());
+    /// ```
+    pub fn config(&self) -> TranscriptionConfig {
+        TranscriptionConfig {
+            helper_path: Some(self.helper_path.clone()),
+            expected_helper_sha256: self.helper_checksum.as_ref().map(HelperChecksum::hex),
+            discovery_attempts: self.discovery_retry.attempts,
+            discovery_delay_secs: self.discovery_retry.delay.as_secs_f64(),
+            priority: self.priority,
+            task_hint: self.task_hint,
+            locale: self.locale.clone(),
+            extra_args: self.extra_args.clone(),
+            temp_dir: self.temp_dir.clone(),
+            temp_naming: self.temp_naming.clone(),
+            trim_silence: self.trim_silence,
+            confidence_aggregation: self.confidence_aggregation,
+            empty_output: self.empty_output,
+            verbatim: self.verbatim,
+            inverse_text_normalization: self.inverse_text_normalization,
+            timestamp_offset_secs: self.timestamp_offset_secs,
+            max_segment_duration_secs: self.max_segment_duration.map(|max| max.as_secs_f64()),
+            line_ending: self.line_ending,
+            bom: self.bom,
+            candidate_locales: self.candidate_locales(),
+            helper_idle_timeout_secs: self
+                .helper_idle_timeout
+                .map(|timeout| timeout.as_secs_f64()),
+            ..TranscriptionConfig::default()
+        }
+    }
+}
+
+impl StreamingTranscriber {
+    /// Creates a streaming transcriber from a [`TranscriptionConfig`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as
+    /// [`StreamingTranscriberBuilder::build`](crate::StreamingTranscriberBuilder::build).
+    pub fn from_config(config: &TranscriptionConfig) -> Result<Self, ScribeError> {
+        let mut builder = Self::builder()
+            .with_priority(config.priority)
+            .with_task_hint(config.task_hint)
+            .with_format_change_policy(config.format_change)
+            .with_channel_mix(config.channel_mix.clone())
+            .with_restart_policy(config.restart_policy)
+            .with_stability(config.stability)
+            .with_sentence_segmentation(config.sentence_segmentation)
+            .with_alternatives_stream(config.alternatives_stream)
+            .with_resample_quality(config.resample_quality)
+            .with_strict_parsing(config.strict_parsing)
+            .with_audio_passthrough(config.audio_passthrough)
+            .with_backpressure(config.backpressure)
+            .with_stderr(config.stderr)
+            .with_skip_silence(config.skip_silence)
+            .with_final_carryover(config.final_carryover)
+            .with_volatile_tail(config.volatile_tail)
+            .with_extra_args(&config.extra_args)
+            .with_discovery_retry(config.discovery_attempts, config.discovery_delay())
+            .with_backlog_limit(
+                Duration::try_from_secs_f64(config.backlog_limit_secs).unwrap_or(Duration::MAX),
+            );
+        builder = match config.input_mode {
+            AudioInputMode::Microphone => builder.with_microphone(),
+            AudioInputMode::Programmatic => builder.with_programmatic_input(),
+        };
+        if let (Some(sample_rate), Some(channels)) =
+            (config.input_sample_rate, config.input_channels)
+        {
+            builder = builder.with_input_format(sample_rate, channels);
+        }
+        if let Some(secs) = config.max_duration_secs {
+            builder = builder
+                .with_max_duration(Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX));
+        }
+        if let Some(path) = &config.capture_fed_audio {
+            builder = builder.with_capture_fed_audio(path);
+        }
+        if let Some(hz) = config.partial_rate {
+            builder = builder.with_partial_rate(hz);
+        }
+        if let Some(frames) = config.mic_buffer_frames {
+            builder = builder.with_mic_buffer_frames(frames);
+        }
+        if let Some(path) = &config.helper_path {
+            builder = builder.with_helper_path(path);
+        }
+        if let Some(path) = &config.socket_path {
+            builder = builder.with_socket(path);
+        }
+        if let Some(hex) = &config.expected_helper_sha256 {
+            builder = builder.with_expected_helper_sha256(hex);
+        }
+        if let Some(locale) = &config.locale {
+            builder = builder.with_locale(locale);
+        }
+        builder.build()
+    }
+
+    /// Returns the settings this transcriber actually uses
+    ///
+    /// The builder options as applied, with the discovered helper path filled
+    /// in (the socket path for [`connect_socket`](Self::connect_socket)
+    /// connections). File-only fields keep their defaults. An absolute
+    /// [`with_session_deadline`](crate::StreamingTranscriberBuilder::with_session_deadline)
+    /// has no config field and isn't included.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{AudioInputMode, StreamingTranscriber};
+    ///
+    /// let transcriber = StreamingTranscriber::builder()
+    ///     .with_microphone()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(transcriber.config().input_mode, AudioInputMode::Programmatic);
+    /// ```
+    pub fn config(&self) -> TranscriptionConfig {
+        TranscriptionConfig {
+            helper_path: Some(self.helper_path.clone()),
+            expected_helper_sha256: self.helper_checksum.as_ref().map(HelperChecksum::hex),
+            discovery_attempts: self.discovery_retry.attempts,
+            discovery_delay_secs: self.discovery_retry.delay.as_secs_f64(),
+            priority: self.priority,
+            task_hint: self.task_hint,
+            locale: self.locale.clone(),
+            extra_args: self.extra_args.clone(),
+            input_mode: self.input_mode,
+            input_sample_rate: self.declared_format.map(|(sample_rate, _)| sample_rate),
+            input_channels: self.declared_format.map(|(_, channels)| channels),
+            format_change: self.format_change,
+            channel_mix: self.channel_mix.clone(),
+            restart_policy: self.restart,
+            sentence_segmentation: self.segmenter.is_some(),
+            alternatives_stream: self.alternatives_stream,
+            resample_quality: self.resampler.quality(),
+            strict_parsing: self.strict_parsing,
+            partial_rate: self.partial_rate,
+            stability: if self.stability.is_some() {
+                StabilityMode::Stable
+            } else {
+                StabilityMode::Responsive
+            },
+            audio_passthrough: self.audio_passthrough,
+            mic_buffer_frames: self.mic_buffer_frames,
+            backpressure: self.backpressure,
+            backlog_limit_secs: self.backlog_limit.as_secs_f64(),
+            skip_silence: self.skip_silence,
+            capture_fed_audio: self.fed_audio_path.clone(),
+            final_carryover: self.final_carryover,
+            volatile_tail: self.volatile_tail,
+            max_duration_secs: self.max_duration.map(|max| max.as_secs_f64()),
+            stderr: self.stderr_mode,
+            socket_path: self.socket_path.clone(),
+            ..TranscriptionConfig::default()
+        }
+    }
+}
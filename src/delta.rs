@@ -0,0 +1,135 @@
+//! Incremental text updates for live displays
+
+use crate::{ResultKind, StreamingResult};
+
+/// An edit to apply to a display buffer holding the transcript shown so far
+///
+/// Truncate the buffer to `replaced_from` (a byte offset, when present), then
+/// append `appended`. Applying every delta in order reproduces the full
+/// transcript: all final segments followed by the current partial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextDelta {
+    /// Text to append after any truncation
+    pub appended: String,
+    /// Byte offset to truncate the buffer to first, when earlier text was revised
+    pub replaced_from: Option<usize>,
+}
+
+impl TextDelta {
+    /// Applies this delta to a display buffer
+    pub fn apply_to(&self, buffer: &mut String) {
+        if let Some(offset) = self.replaced_from {
+            buffer.truncate(offset);
+        }
+        buffer.push_str(&self.appended);
+    }
+}
+
+/// Tracks the text emitted so far and diffs each new result against it
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeltaTracker {
+    /// All final segments, space separated
+    committed: String,
+    /// What the caller's buffer holds after the last delta
+    emitted: String,
+}
+
+impl DeltaTracker {
+    /// Returns the edit that brings the caller's buffer up to date, if anything changed
+    pub(crate) fn update(&mut self, result: &StreamingResult) -> Option<TextDelta> {
+        if result.kind == ResultKind::EndOfSpeech {
+            return None;
+        }
+        let text = result.text.trim();
+        let display = match (self.committed.is_empty(), text.is_empty()) {
+            (true, _) => text.to_string(),
+            (_, true) => self.committed.clone(),
+            _ => format!("{} {}", self.committed, text),
+        };
+        if result.is_final {
+            self.committed = display.clone();
+        }
+        if display == self.emitted {
+            return None;
+        }
+
+        let common = common_prefix_len(&self.emitted, &display);
+        let delta = TextDelta {
+            appended: display[common..].to_string(),
+            replaced_from: (common < self.emitted.len()).then_some(common),
+        };
+        self.emitted = display;
+        Some(delta)
+    }
+}
+
+/// Length in bytes of the longest common prefix, ending on a char boundary
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, ca), cb)| ca != cb)
+        .map(|((idx, _), _)| idx)
+        .unwrap_or_else(|| a.len().min(b.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str, is_final: bool) -> StreamingResult {
+        StreamingResult {
+            text: text.to_string(),
+            is_final,
+            kind: Default::default(),
+            timestamp: 0.0,
+            start_timestamp: None,
+            alternatives: Vec::new(),
+            is_revision: false,
+            stable_text: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn deltas_rebuild_the_transcript() {
+        let mut tracker = DeltaTracker::default();
+        let mut buffer = String::new();
+        let updates = [
+            ("hello", false),
+            ("hello wor", false),
+            ("hello world", true),
+            ("how", false),
+            ("who are", false),
+            ("who are you", true),
+        ];
+
+        let mut deltas = Vec::new();
+        for (text, is_final) in updates {
+            if let Some(delta) = tracker.update(&result(text, is_final)) {
+                delta.apply_to(&mut buffer);
+                deltas.push(delta);
+            }
+        }
+
+        assert_eq!(buffer, "hello world who are you");
+        // Growing a partial only appends
+        assert_eq!(deltas[1].appended, " wor");
+        assert_eq!(deltas[1].replaced_from, None);
+        // Revising "how" to "who are" rewrites from the first differing byte
+        assert_eq!(deltas[4].replaced_from, Some("hello world ".len()));
+        assert_eq!(deltas[4].appended, "who are");
+    }
+
+    #[test]
+    fn unchanged_text_produces_no_delta() {
+        let mut tracker = DeltaTracker::default();
+        assert!(tracker.update(&result("same", false)).is_some());
+        assert!(tracker.update(&result("same", true)).is_none());
+    }
+
+    #[test]
+    fn prefix_respects_char_boundaries() {
+        assert_eq!(common_prefix_len("café", "cafe"), 3);
+        assert_eq!(common_prefix_len("naïve", "naïveté"), "naïve".len());
+    }
+}
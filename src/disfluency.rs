@@ -0,0 +1,106 @@
+//! Removal of filler words and false starts for cleaned transcripts
+
+/// Hesitation sounds dropped in cleaned mode
+///
+/// Only pure fillers are listed; words like "like" or "so" carry meaning too
+/// often to remove blindly, and "uh-huh" is an answer, not a hesitation.
+const FILLERS: &[&str] = &[
+    "ah", "eh", "er", "erm", "hm", "hmm", "mm", "mhm", "uh", "uhm", "um", "umm",
+];
+
+/// Strips filler words and immediately repeated words from `text`
+///
+/// Punctuation attached to a dropped filler is handled so the sentence still
+/// reads naturally: a sentence-ending mark moves onto the previous word, and a
+/// capitalized filler that opened a sentence passes its capital on.
+pub(crate) fn remove_disfluencies(text: &str) -> String {
+    let mut kept: Vec<String> = Vec::new();
+    let mut capitalize_next = false;
+
+    for token in text.split_whitespace() {
+        let core = core_word(token);
+        if is_filler(core) {
+            let terminal: String = token
+                .chars()
+                .rev()
+                .take_while(|c| matches!(c, '.' | '!' | '?'))
+                .collect();
+            if let Some(prev) = kept.last_mut() {
+                if !terminal.is_empty() {
+                    prev.truncate(prev.trim_end_matches([',', ';', ':']).len());
+                    prev.extend(terminal.chars().rev());
+                }
+            }
+            capitalize_next |= core.starts_with(char::is_uppercase);
+            continue;
+        }
+
+        // "I I think" or "the- the" is a false start; keep the last attempt
+        let false_start = kept.last().is_some_and(|prev| {
+            !prev.ends_with(['.', '!', '?', ',', ';', ':'])
+                && !core.is_empty()
+                && core_word(prev).eq_ignore_ascii_case(core)
+        });
+        if false_start {
+            capitalize_next |= kept
+                .pop()
+                .is_some_and(|prev| prev.starts_with(char::is_uppercase));
+        }
+
+        let mut word = token.to_string();
+        if std::mem::take(&mut capitalize_next) {
+            word = capitalize(&word);
+        }
+        kept.push(word);
+    }
+    kept.join(" ")
+}
+
+/// The word in `token` without surrounding punctuation or a trailing hyphen
+fn core_word(token: &str) -> &str {
+    token.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+}
+
+fn is_filler(word: &str) -> bool {
+    FILLERS
+        .iter()
+        .any(|filler| filler.eq_ignore_ascii_case(word))
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_fillers_and_keeps_punctuation_readable() {
+        assert_eq!(
+            remove_disfluencies("Um, so, uh, we ship it"),
+            "So, we ship it"
+        );
+        assert_eq!(remove_disfluencies("That works, um."), "That works.");
+        assert_eq!(remove_disfluencies("Hmm"), "");
+        assert_eq!(
+            remove_disfluencies("uh-huh, sounds good"),
+            "uh-huh, sounds good"
+        );
+    }
+
+    #[test]
+    fn collapses_false_starts() {
+        assert_eq!(
+            remove_disfluencies("I I think the- the plan"),
+            "I think the plan"
+        );
+        assert_eq!(remove_disfluencies("The the meeting"), "The meeting");
+        // A repeat across a sentence boundary is not a false start
+        assert_eq!(remove_disfluencies("Go. Go now"), "Go. Go now");
+    }
+}
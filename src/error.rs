@@ -0,0 +1,228 @@
+//! The error type returned by the transcribers
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+/// Why a transcriber call failed
+///
+/// The variants cover the failures callers commonly handle on their own, like
+/// a missing helper or audio file; everything else is [`Other`](Self::Other),
+/// with a message. The `Display` text reads the same as the plain messages
+/// earlier versions returned, and `String::from` turns an error back into one,
+/// so code that only prints or logs errors keeps working.
+///
+/// # Examples
+///
+/// ```no_run
+/// use swift_scribe::{ScribeError, Transcriber};
+/// use std::path::Path;
+///
+/// let transcriber = Transcriber::new().unwrap();
+/// match transcriber.transcribe_file(Path::new("memo.m4a")) {
+///     Ok(text) => println!("{}", text),
+///     Err(ScribeError::AudioFileMissing(path)) => eprintln!("No such file: {}", path.display()),
+///     Err(e) => eprintln!("Error: {}", e),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ScribeError {
+    /// The helper binary isn't at the configured path, or, for `None`, in any
+    /// of the default locations
+    HelperNotFound(Option<PathBuf>),
+    /// The helper couldn't be started
+    HelperSpawn {
+        /// The command line that failed, quoted for a shell
+        command: String,
+        /// Why it failed
+        source: io::Error,
+    },
+    /// The helper exited while audio was still being sent to it
+    HelperExited {
+        /// How it exited
+        status: ExitStatus,
+        /// The last of what it wrote to stderr, trimmed
+        stderr: String,
+    },
+    /// The audio file to transcribe doesn't exist
+    AudioFileMissing(PathBuf),
+    /// A path can't be passed to the helper because it isn't valid UTF-8
+    InvalidUtf8Path,
+    /// The helper wrote a result that isn't valid JSON for it
+    ParseResult {
+        /// The line as the helper wrote it
+        line: String,
+        /// What's wrong with it
+        source: serde_json::Error,
+    },
+    /// The call needs the other input mode, microphone or programmatic, than
+    /// the transcriber was built with
+    WrongInputMode(String),
+    /// The configured [`ChannelMix`](crate::ChannelMix) doesn't fit the
+    /// channel count of the fed audio
+    InvalidChannelMix(String),
+    /// Nothing was recognized in the audio file and
+    /// [`EmptyOutputPolicy::AsError`](crate::EmptyOutputPolicy::AsError) is
+    /// configured
+    NoSpeechDetected(PathBuf),
+    /// The file has no playable audio track, such as a video-only `.mov`
+    NoAudioTrack(PathBuf),
+    /// A streaming method that needs a running session was called before
+    /// `start`, or after the session was stopped
+    NotStarted,
+    /// The operation, named here, isn't available on a transcriber connected
+    /// to a socket
+    UnsupportedOnSocket(&'static str),
+    /// Microphone access was withdrawn while a streaming session was running
+    ///
+    /// The session can be restarted once the user grants access again.
+    PermissionRevoked,
+    /// The streaming helper closed its output after writing all of its results
+    ///
+    /// This is how a session ends normally, once the audio has ended or the
+    /// session was asked to finish.
+    StreamEnded,
+    /// Any other failure, described by its message
+    Other(String),
+}
+
+impl fmt::Display for ScribeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScribeError::HelperNotFound(Some(path)) => {
+                write!(f, "Helper binary not found at: {}", path.display())
+            }
+            ScribeError::HelperNotFound(None) => f.write_str(
+                "Helper binary not found. Please compile with 'make helpers' or install system-wide.",
+            ),
+            ScribeError::HelperSpawn { command, source } => {
+                write!(f, "Failed to execute helper `{}`: {}", command, source)
+            }
+            ScribeError::HelperExited { status, stderr } => {
+                write!(f, "Helper exited ({}) while receiving audio", status)?;
+                if !stderr.is_empty() {
+                    write!(f, ": {}", stderr)?;
+                }
+                Ok(())
+            }
+            ScribeError::AudioFileMissing(path) => {
+                // Stray whitespace or control characters would go unnoticed
+                let raw = path.to_string_lossy();
+                if raw.contains(char::is_control) || raw.ends_with(char::is_whitespace) {
+                    write!(f, "Audio file not found: {:?}", raw)
+                } else {
+                    write!(f, "Audio file not found: {}", path.display())
+                }
+            }
+            ScribeError::InvalidUtf8Path => f.write_str("Invalid UTF-8 path"),
+            ScribeError::NoSpeechDetected(path) => {
+                write!(f, "No speech detected in {}", path.display())
+            }
+            ScribeError::NoAudioTrack(path) => write!(f, "No audio track in {}", path.display()),
+            ScribeError::NotStarted => f.write_str("Transcriber not started"),
+            ScribeError::UnsupportedOnSocket(operation) => {
+                write!(f, "{} is not supported on socket connections", operation)
+            }
+            ScribeError::StreamEnded => f.write_str("Streaming process ended"),
+            ScribeError::PermissionRevoked => f.write_str(
+                "Permission revoked: microphone access was withdrawn during the session",
+            ),
+            ScribeError::ParseResult { line, source } => {
+                write!(f, "Failed to parse result: {} (line: {})", source, line)
+            }
+            ScribeError::WrongInputMode(message)
+            | ScribeError::InvalidChannelMix(message)
+            | ScribeError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+impl ScribeError {
+    /// The error again, for reporting it on every call it applies to
+    ///
+    /// Variants holding an `io::Error` or `serde_json::Error` can't be copied,
+    /// so those come back as [`Other`](Self::Other) with the same message.
+    pub(crate) fn replay(&self) -> ScribeError {
+        match self {
+            ScribeError::HelperNotFound(path) => ScribeError::HelperNotFound(path.clone()),
+            ScribeError::AudioFileMissing(path) => ScribeError::AudioFileMissing(path.clone()),
+            ScribeError::InvalidUtf8Path => ScribeError::InvalidUtf8Path,
+            ScribeError::NotStarted => ScribeError::NotStarted,
+            ScribeError::UnsupportedOnSocket(operation) => {
+                ScribeError::UnsupportedOnSocket(operation)
+            }
+            other => ScribeError::Other(other.to_string()),
+        }
+    }
+}
+
+impl std::error::Error for ScribeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScribeError::HelperSpawn { source, .. } => Some(source),
+            ScribeError::ParseResult { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<String> for ScribeError {
+    fn from(message: String) -> Self {
+        ScribeError::Other(message)
+    }
+}
+
+impl From<&str> for ScribeError {
+    fn from(message: &str) -> Self {
+        ScribeError::Other(message.to_string())
+    }
+}
+
+impl From<ScribeError> for String {
+    fn from(error: ScribeError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn messages_match_the_plain_string_errors() {
+        let missing = ScribeError::AudioFileMissing(PathBuf::from("/tmp/memo.m4a"));
+        assert_eq!(missing.to_string(), "Audio file not found: /tmp/memo.m4a");
+        let pasted = ScribeError::AudioFileMissing(PathBuf::from("/tmp/memo.m4a\n"));
+        assert_eq!(
+            pasted.to_string(),
+            r#"Audio file not found: "/tmp/memo.m4a\n""#
+        );
+        let not_found = ScribeError::HelperNotFound(Some(Path::new("/opt/transcribe").into()));
+        assert_eq!(
+            not_found.to_string(),
+            "Helper binary not found at: /opt/transcribe"
+        );
+        assert_eq!(
+            ScribeError::NoAudioTrack(PathBuf::from("/tmp/clip.mov")).to_string(),
+            "No audio track in /tmp/clip.mov"
+        );
+        assert_eq!(
+            String::from(ScribeError::from("Not started")),
+            "Not started"
+        );
+    }
+
+    #[test]
+    fn parse_errors_keep_their_source() {
+        let source = serde_json::from_str::<u32>("x").unwrap_err();
+        let error = ScribeError::ParseResult {
+            line: "x".to_string(),
+            source,
+        };
+        assert!(error.to_string().ends_with("(line: x)"), "{}", error);
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
@@ -0,0 +1,54 @@
+//! Recording the exact audio sent to the streaming helper
+
+use crate::{write_pcm, write_wav_header, StreamingTranscriber, TARGET_SAMPLE_RATE};
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// A 16kHz mono WAV file that grows as audio is fed
+pub(crate) struct FedAudioRecorder {
+    file: File,
+    /// Bytes of PCM data written so far
+    data_len: u32,
+}
+
+impl FedAudioRecorder {
+    /// Creates (or truncates) the WAV file at `path`
+    pub(crate) fn create(path: &Path) -> std::io::Result<Self> {
+        let mut file = File::create(path)?;
+        write_wav_header(&mut file, 0, TARGET_SAMPLE_RATE, 1)?;
+        Ok(Self { file, data_len: 0 })
+    }
+
+    /// Appends samples, keeping the header's sizes up to date
+    ///
+    /// The header is rewritten after every chunk so the file stays playable
+    /// even if the process dies mid-session.
+    fn append(&mut self, samples: &[i16]) -> std::io::Result<()> {
+        write_pcm(&mut self.file, samples)?;
+        let added = u32::try_from(samples.len() * 2).unwrap_or(u32::MAX);
+        self.data_len = self.data_len.saturating_add(added);
+        self.file.seek(SeekFrom::Start(4))?;
+        self.file
+            .write_all(&self.data_len.saturating_add(36).to_le_bytes())?;
+        self.file.seek(SeekFrom::Start(40))?;
+        self.file.write_all(&self.data_len.to_le_bytes())?;
+        self.file.seek(SeekFrom::End(0))?;
+        Ok(())
+    }
+}
+
+impl StreamingTranscriber {
+    /// Tees audio the helper accepted into the fed audio capture, if enabled
+    ///
+    /// A failed write is logged and ends the capture rather than the session.
+    pub(crate) fn record_fed_audio(&mut self, samples: &[i16]) {
+        let Some(recorder) = self.fed_audio.as_mut() else {
+            return;
+        };
+        if let Err(e) = recorder.append(samples) {
+            log::warn!("Stopped capturing fed audio: {}", e);
+            self.fed_audio = None;
+        }
+    }
+}
@@ -0,0 +1,693 @@
+//! Inverse text normalization: spelled-out numbers, times and amounts to digits
+
+use crate::TranscriptionSegment;
+
+/// Number words by value, space-separated
+const UNITS: &str = "zero one two three four five six seven eight nine ten eleven twelve \
+    thirteen fourteen fifteen sixteen seventeen eighteen nineteen";
+
+const UNIT_ORDINALS: &str = "zeroth first second third fourth fifth sixth seventh eighth \
+    ninth tenth eleventh twelfth thirteenth fourteenth fifteenth sixteenth seventeenth \
+    eighteenth nineteenth";
+
+/// Multiples of ten by value, from twenty
+const TENS: &str = "twenty thirty forty fifty sixty seventy eighty ninety";
+
+const TENS_ORDINALS: &str =
+    "twentieth thirtieth fortieth fiftieth sixtieth seventieth eightieth ninetieth";
+
+const MONTHS: &str =
+    "january february march april may june july august september october november december";
+
+/// Units that a number before them is always written in digits for, kept as words
+const UNIT_WORDS: &str = "cent cents day days feet foot hour hours inch inches kg kilogram \
+    kilograms kilometer kilometers km meter meters mile miles minute minutes percentile \
+    second seconds week weeks year years";
+
+/// One word of the text, with the punctuation around it
+#[derive(Clone, Copy)]
+struct Token<'a> {
+    /// The word as written, punctuation included
+    raw: &'a str,
+    lead: &'a str,
+    /// The word without surrounding punctuation
+    core: &'a str,
+    trail: &'a str,
+    /// Joined to the previous token by a hyphen rather than a space
+    glued: bool,
+}
+
+impl Token<'_> {
+    fn is(&self, word: &str) -> bool {
+        self.core.eq_ignore_ascii_case(word)
+    }
+}
+
+/// A run of number words read as one number
+struct Cardinal {
+    value: u64,
+    /// Index of the token after the number
+    end: usize,
+    /// Spoken as a two-digit pair like "nineteen" or "twenty five", as in years
+    pair: bool,
+    /// Ended with an ordinal word like "first"
+    ordinal: bool,
+    /// Written in digits already
+    digits: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    Start,
+    Unit,
+    Tens,
+    Hundred,
+    Scale,
+    And,
+}
+
+/// Rewrites spelled-out numbers in `text` as digits
+///
+/// Covers cardinals ("two thousand five hundred" to "2500"), decimals
+/// ("three point five" to "3.5"), years spoken in pairs ("twenty twenty five"
+/// to "2025"), clock times with a.m. or p.m. ("three thirty p m" to
+/// "3:30 PM"), percentages, dollar and euro amounts, degrees, ordinals of ten
+/// and over or after a month name ("March twenty first" to "March 21st"), and
+/// numbers before units like "minutes" or "kilometers".
+///
+/// Following common style, a bare number below ten stays a word ("two ideas"),
+/// and numbers of 10,000 and over are grouped with commas. Adjacent numbers
+/// that don't form a year or a time ("five thirty") are left as spoken, since
+/// their meaning is ambiguous. A number never spans punctuation.
+pub(crate) fn normalize(text: &str) -> String {
+    let tokens = tokenize(text);
+    let mut out = String::with_capacity(text.len());
+    for piece in pieces(&tokens) {
+        write_piece(&mut out, &tokens, &piece);
+    }
+    out
+}
+
+/// Rewrites spelled-out numbers across timed segments, as [`normalize`] does
+///
+/// The recognizers report about one segment per word, so a number is usually
+/// spread over several. Segments that a rewritten number spans are merged into
+/// one, running from the first one's start to the last one's end; the others
+/// keep their timings.
+pub(crate) fn normalize_segments(segments: &[TranscriptionSegment]) -> Vec<TranscriptionSegment> {
+    let mut tokens = Vec::new();
+    // The segment each token came from
+    let mut owners = Vec::new();
+    for (n, segment) in segments.iter().enumerate() {
+        let segment_tokens = tokenize(&segment.text);
+        owners.extend(std::iter::repeat_n(n, segment_tokens.len()));
+        tokens.extend(segment_tokens);
+    }
+
+    let mut pieces = pieces(&tokens).into_iter().peekable();
+    let mut normalized = Vec::with_capacity(segments.len());
+    let mut first = 0;
+    while first < segments.len() {
+        let mut last = first;
+        let mut text = String::new();
+        while let Some(piece) = pieces.next_if(|piece| owners[piece.start] <= last) {
+            last = last.max(owners[piece.end - 1]);
+            write_piece(&mut text, &tokens, &piece);
+        }
+        normalized.push(match first == last {
+            // A segment without words keeps its text as it was
+            true if text.is_empty() => segments[first].clone(),
+            true => TranscriptionSegment {
+                text,
+                ..segments[first].clone()
+            },
+            false => TranscriptionSegment::merged(&segments[first..=last], text),
+        });
+        first = last + 1;
+    }
+    normalized
+}
+
+/// Whether no number can run on from the end of `text` into what follows
+///
+/// True once `text` ends in punctuation, which a number never spans, unless the
+/// punctuation belongs to an abbreviation like "p.m.".
+pub(crate) fn closes_numbers(text: &str) -> bool {
+    tokenize(text).last().is_some_and(|last| {
+        !last.trail.is_empty() && last.core.chars().count() > 1 && !last.core.contains('.')
+    })
+}
+
+/// A stretch of the output: one token as written, or the digits for a run of them
+struct Piece {
+    start: usize,
+    /// Index of the token after the piece
+    end: usize,
+    /// The rewritten text, or `None` for the token as written
+    written: Option<String>,
+}
+
+/// Splits `tokens` into pieces, rewriting each number that should be in digits
+fn pieces(tokens: &[Token]) -> Vec<Piece> {
+    let mut pieces = Vec::with_capacity(tokens.len());
+    let mut after_spoken_number = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let as_written = |i: usize| Piece {
+            start: i,
+            end: i + 1,
+            written: None,
+        };
+        let cardinal = match read_cardinal(tokens, i) {
+            Some(cardinal) => cardinal,
+            None => {
+                pieces.push(as_written(i));
+                after_spoken_number = false;
+                i += 1;
+                continue;
+            }
+        };
+        match rewrite(tokens, i, &cardinal, after_spoken_number) {
+            Some((written, end)) => {
+                let lead = tokens[i].lead;
+                let trail = trail_of(tokens, end);
+                pieces.push(Piece {
+                    start: i,
+                    end,
+                    written: Some(format!("{}{}{}", lead, written, trail)),
+                });
+                after_spoken_number = false;
+                i = end;
+            }
+            None => {
+                pieces.extend((i..cardinal.end).map(as_written));
+                after_spoken_number = !cardinal.digits
+                    && !cardinal.ordinal
+                    && tokens[cardinal.end - 1].trail.is_empty();
+                i = cardinal.end;
+            }
+        }
+    }
+    pieces
+}
+
+/// Appends a piece to `out`, separated as its first token was
+fn write_piece(out: &mut String, tokens: &[Token], piece: &Piece) {
+    let token = &tokens[piece.start];
+    push(out, token, piece.written.as_deref().unwrap_or(token.raw));
+}
+
+/// Appends a token's replacement, separated as the token was
+fn push(out: &mut String, token: &Token, written: &str) {
+    if !out.is_empty() && !token.glued {
+        out.push(' ');
+    }
+    out.push_str(written);
+}
+
+/// Splits `text` into words, hyphenated numbers like "twenty-five" into their parts
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    for raw in text.split_whitespace() {
+        let core = raw.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'');
+        let start = raw.find(core).unwrap_or(0);
+        let lead = &raw[..start];
+        let trail = &raw[start + core.len()..];
+        let parts: Vec<&str> = core.split('-').collect();
+        if parts.len() > 1 && parts.iter().all(|part| is_number_word(part)) {
+            let mut offset = start;
+            for (n, part) in parts.iter().enumerate() {
+                let last = n + 1 == parts.len();
+                let end = if last {
+                    raw.len()
+                } else {
+                    offset + part.len() + 1
+                };
+                tokens.push(Token {
+                    raw: &raw[if n == 0 { 0 } else { offset }..end],
+                    lead: if n == 0 { lead } else { "" },
+                    core: part,
+                    trail: if last { trail } else { "" },
+                    glued: n > 0,
+                });
+                offset = end;
+            }
+        } else {
+            tokens.push(Token {
+                raw,
+                lead,
+                core,
+                trail,
+                glued: false,
+            });
+        }
+    }
+    tokens
+}
+
+fn is_number_word(word: &str) -> bool {
+    let word = word.to_ascii_lowercase();
+    position(UNITS, &word).is_some()
+        || position(TENS, &word).is_some()
+        || position(UNIT_ORDINALS, &word).is_some()
+        || position(TENS_ORDINALS, &word).is_some()
+        || word == "hundred"
+        || scale(&word).is_some()
+}
+
+/// Index of `word` in a space-separated word list
+fn position(words: &str, word: &str) -> Option<u64> {
+    words
+        .split_whitespace()
+        .position(|candidate| candidate.eq_ignore_ascii_case(word))
+        .map(|n| n as u64)
+}
+
+fn scale(word: &str) -> Option<u64> {
+    match word.to_ascii_lowercase().as_str() {
+        "thousand" => Some(1_000),
+        "million" => Some(1_000_000),
+        "billion" => Some(1_000_000_000),
+        _ => None,
+    }
+}
+
+/// Reads the number starting at token `start`, if one does
+fn read_cardinal(tokens: &[Token], start: usize) -> Option<Cardinal> {
+    let first = tokens.get(start)?;
+    if first.core.starts_with(|c: char| c.is_ascii_digit())
+        && first.core.chars().all(|c| c.is_ascii_digit() || c == ',')
+    {
+        let value = first.core.replace(',', "").parse().ok()?;
+        return Some(Cardinal {
+            value,
+            end: start + 1,
+            pair: false,
+            ordinal: false,
+            digits: true,
+        });
+    }
+
+    let (mut total, mut current) = (0u64, 0u64);
+    let mut last = Part::Start;
+    let mut last_scale = u64::MAX;
+    let mut scaled = false;
+    let mut ordinal = false;
+    let mut i = start;
+    while let Some(token) = tokens.get(i) {
+        if i > start && !tokens[i - 1].trail.is_empty() {
+            break;
+        }
+        let word = token.core;
+        let next_is_number = tokens.get(i + 1).is_some_and(|next| {
+            position(UNITS, next.core).is_some() || position(TENS, next.core).is_some()
+        });
+        if let Some(n) = position(UNITS, word).or_else(|| position(UNIT_ORDINALS, word)) {
+            let fits = match last {
+                Part::Start => true,
+                Part::Tens => (1..=9).contains(&n),
+                Part::Hundred | Part::Scale | Part::And => n > 0,
+                Part::Unit => false,
+            };
+            if !fits
+                || (n == 0
+                    && tokens
+                        .get(i + 1)
+                        .is_some_and(|next| is_number_word(next.core)))
+            {
+                break;
+            }
+            current += n;
+            last = Part::Unit;
+            ordinal = position(UNIT_ORDINALS, word).is_some();
+        } else if let Some(n) = position(TENS, word)
+            .or_else(|| position(TENS_ORDINALS, word))
+            .map(|n| n + 2)
+        {
+            if !matches!(last, Part::Start | Part::Hundred | Part::Scale | Part::And) {
+                break;
+            }
+            current += n * 10;
+            last = Part::Tens;
+            ordinal = position(TENS_ORDINALS, word).is_some();
+        } else if token.is("hundred") {
+            if !matches!(last, Part::Unit | Part::Tens) || current == 0 || current >= 100 {
+                break;
+            }
+            current *= 100;
+            last = Part::Hundred;
+            scaled = true;
+        } else if let Some(n) = scale(word) {
+            if !matches!(last, Part::Unit | Part::Tens | Part::Hundred)
+                || current == 0
+                || n >= last_scale
+            {
+                break;
+            }
+            total += current * n;
+            current = 0;
+            last_scale = n;
+            last = Part::Scale;
+            scaled = true;
+        } else if token.is("a")
+            && last == Part::Start
+            && tokens
+                .get(i + 1)
+                .is_some_and(|next| next.is("hundred") || scale(next.core).is_some())
+            && token.trail.is_empty()
+        {
+            current = 1;
+            last = Part::Unit;
+        } else if token.is("and")
+            && matches!(last, Part::Hundred | Part::Scale)
+            && next_is_number
+            && token.trail.is_empty()
+        {
+            last = Part::And;
+        } else {
+            break;
+        }
+        i += 1;
+        if ordinal {
+            break;
+        }
+    }
+    // A trailing "and" belongs to the sentence, not the number
+    while i > start && tokens[i - 1].is("and") {
+        i -= 1;
+    }
+    if i == start {
+        return None;
+    }
+    let value = total + current;
+    Some(Cardinal {
+        value,
+        end: i,
+        pair: !scaled && (10..=99).contains(&value),
+        ordinal,
+        digits: false,
+    })
+}
+
+/// The digits for the number at `start` and the index of the token after them,
+/// or `None` to leave it as spoken
+fn rewrite(
+    tokens: &[Token],
+    start: usize,
+    cardinal: &Cardinal,
+    after_spoken_number: bool,
+) -> Option<(String, usize)> {
+    let open = |end: usize| end < tokens.len() && tokens[end - 1].trail.is_empty();
+    let mut end = cardinal.end;
+
+    if cardinal.ordinal {
+        let after_month = start > 0
+            && tokens[start - 1].trail.is_empty()
+            && tokens[start - 1].core.starts_with(char::is_uppercase)
+            && position(MONTHS, tokens[start - 1].core).is_some();
+        if cardinal.value < 10 && !after_month {
+            return None;
+        }
+        return Some((
+            format!("{}{}", cardinal.value, ordinal_suffix(cardinal.value)),
+            end,
+        ));
+    }
+
+    // Clock time: an hour, maybe minutes, then a.m. or p.m.
+    if (1..=12).contains(&cardinal.value) && open(end) {
+        let minutes = minutes_at(tokens, end);
+        let after_minutes = minutes.map_or(end, |(_, minutes_end)| minutes_end);
+        if minutes.is_none() || open(after_minutes) {
+            if let Some((meridiem, meridiem_end)) = meridiem_at(tokens, after_minutes) {
+                let time = match minutes {
+                    Some((minutes, _)) => format!("{}:{:02} {}", cardinal.value, minutes, meridiem),
+                    None => format!("{} {}", cardinal.value, meridiem),
+                };
+                return Some((time, meridiem_end));
+            }
+        }
+    }
+
+    // Year spoken in pairs: "nineteen ninety nine", "twenty oh five"
+    if cardinal.pair && open(end) {
+        if let Some((second, second_end)) = year_half_at(tokens, end) {
+            let followed_by_number = open(second_end)
+                && tokens
+                    .get(second_end)
+                    .is_some_and(|next| is_number_word(next.core));
+            if !followed_by_number {
+                return Some((format!("{}{:02}", cardinal.value, second), second_end));
+            }
+        }
+    }
+
+    let mut number = format_number(cardinal.value);
+    let mut decimal = false;
+    if !cardinal.digits && open(end) && tokens[end].is("point") {
+        let mut digits = String::new();
+        let mut next = end + 1;
+        while tokens[next - 1].trail.is_empty() {
+            match tokens.get(next).and_then(|token| digit(token.core)) {
+                Some(d) => digits.push(d),
+                None => break,
+            }
+            next += 1;
+        }
+        if !digits.is_empty() {
+            number = format!("{}.{}", number, digits);
+            end = next;
+            decimal = true;
+        }
+    }
+
+    if open(end) {
+        let unit = tokens[end];
+        let lower = unit.core.to_ascii_lowercase();
+        match lower.as_str() {
+            "percent" => return Some((format!("{}%", number), end + 1)),
+            "per" if open(end + 1) && tokens[end + 1].is("cent") => {
+                return Some((format!("{}%", number), end + 2))
+            }
+            "degree" | "degrees" => return Some((format!("{}°", number), end + 1)),
+            "dollar" | "dollars" => {
+                let (cents, amount_end) = cents_at(tokens, end + 1).unwrap_or((None, end + 1));
+                return Some(match cents {
+                    Some(cents) if !decimal => (format!("${}.{:02}", number, cents), amount_end),
+                    _ => (format!("${}", number), end + 1),
+                });
+            }
+            "euro" | "euros" => return Some((format!("€{}", number), end + 1)),
+            _ if position(UNIT_WORDS, &lower).is_some() => {
+                // The unit stays a word of its own
+                return (!cardinal.digits || decimal).then_some((number, end));
+            }
+            _ => {}
+        }
+    }
+
+    let next_is_number = open(end) && is_number_word(tokens[end].core);
+    if cardinal.digits || after_spoken_number || next_is_number {
+        return None;
+    }
+    (decimal || cardinal.value >= 10).then_some((number, end))
+}
+
+/// Minutes after an hour: "thirty", "forty five" or "oh five"
+fn minutes_at(tokens: &[Token], start: usize) -> Option<(u64, usize)> {
+    let token = tokens.get(start)?;
+    if token.is("oh") && token.trail.is_empty() {
+        let minutes =
+            position(UNITS, tokens.get(start + 1)?.core).filter(|n| (1..=9).contains(n))?;
+        return Some((minutes, start + 2));
+    }
+    let minutes = read_cardinal(tokens, start)?;
+    (minutes.pair && !minutes.ordinal && minutes.value < 60).then_some((minutes.value, minutes.end))
+}
+
+/// The second half of a year: a pair like "ninety nine", or "oh" and a digit
+fn year_half_at(tokens: &[Token], start: usize) -> Option<(u64, usize)> {
+    let token = tokens.get(start)?;
+    if token.is("oh") && token.trail.is_empty() {
+        let digit = position(UNITS, tokens.get(start + 1)?.core).filter(|n| (1..=9).contains(n))?;
+        return Some((digit, start + 2));
+    }
+    let half = read_cardinal(tokens, start)?;
+    (half.pair && !half.ordinal).then_some((half.value, half.end))
+}
+
+/// "a.m." or "p.m." in any of the ways the recognizer writes it
+fn meridiem_at(tokens: &[Token], start: usize) -> Option<(&'static str, usize)> {
+    let letters = |token: &Token| token.core.replace('.', "").to_ascii_lowercase();
+    let first = letters(tokens.get(start)?);
+    let (first, end) = match first.as_str() {
+        "am" | "pm" => (first, start + 1),
+        "a" | "p" => {
+            let second = tokens.get(start + 1)?;
+            if !matches!(tokens[start].trail, "" | ".") || letters(second) != "m" {
+                return None;
+            }
+            (first, start + 2)
+        }
+        _ => return None,
+    };
+    Some((if first.starts_with('a') { "AM" } else { "PM" }, end))
+}
+
+/// Cents after a dollar amount: "and fifty cents"
+fn cents_at(tokens: &[Token], start: usize) -> Option<(Option<u64>, usize)> {
+    let and = tokens.get(start)?;
+    if !tokens[start - 1].trail.is_empty() || !and.is("and") || !and.trail.is_empty() {
+        return None;
+    }
+    let cents = read_cardinal(tokens, start + 1)?;
+    let unit = tokens.get(cents.end)?;
+    if cents.ordinal || cents.value >= 100 || !tokens[cents.end - 1].trail.is_empty() {
+        return None;
+    }
+    (unit.is("cent") || unit.is("cents")).then_some((Some(cents.value), cents.end + 1))
+}
+
+/// The trailing punctuation of the token before `end`
+///
+/// A period that is part of "a.m." or "p.m." is kept only at the end of the
+/// text, where it also ends the sentence.
+fn trail_of<'a>(tokens: &[Token<'a>], end: usize) -> &'a str {
+    let last = &tokens[end - 1];
+    let abbreviated = last.core.eq_ignore_ascii_case("m") || last.core.contains('.');
+    if abbreviated && last.trail.starts_with('.') && end < tokens.len() {
+        &last.trail[1..]
+    } else {
+        last.trail
+    }
+}
+
+fn digit(word: &str) -> Option<char> {
+    if word.eq_ignore_ascii_case("oh") {
+        return Some('0');
+    }
+    position(UNITS, word)
+        .filter(|n| *n <= 9)
+        .and_then(|n| char::from_digit(n as u32, 10))
+}
+
+/// Writes `value` in digits, grouping thousands from 10,000 up
+fn format_number(value: u64) -> String {
+    let digits = value.to_string();
+    if value < 10_000 {
+        return digits;
+    }
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (n, c) in digits.chars().enumerate() {
+        if n > 0 && (digits.len() - n).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+fn ordinal_suffix(value: u64) -> &'static str {
+    match (value % 10, value % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_numbers_years_and_decimals_in_digits() {
+        assert_eq!(normalize("It was twenty twenty five."), "It was 2025.");
+        assert_eq!(normalize("Back in nineteen oh five"), "Back in 1905");
+        assert_eq!(
+            normalize("two thousand five hundred and twelve people"),
+            "2512 people"
+        );
+        assert_eq!(normalize("forty-two thousand visitors"), "42,000 visitors");
+        assert_eq!(normalize("a hundred times"), "100 times");
+        assert_eq!(normalize("about three point five"), "about 3.5");
+        // Small bare numbers and ambiguous runs stay as spoken
+        assert_eq!(normalize("I have two ideas"), "I have two ideas");
+        assert_eq!(normalize("meet at five thirty"), "meet at five thirty");
+        assert_eq!(normalize("one, two, twenty"), "one, two, 20");
+    }
+
+    #[test]
+    fn writes_times_amounts_and_ordinals() {
+        assert_eq!(normalize("Call at three p m"), "Call at 3 PM");
+        assert_eq!(
+            normalize("at three thirty p.m. we meet"),
+            "at 3:30 PM we meet"
+        );
+        assert_eq!(normalize("By nine oh five a.m."), "By 9:05 AM.");
+        assert_eq!(normalize("up ten percent"), "up 10%");
+        assert_eq!(
+            normalize("It costs five dollars and fifty cents, or twenty euros"),
+            "It costs $5.50, or €20"
+        );
+        assert_eq!(normalize("seventy degrees"), "70°");
+        assert_eq!(normalize("for five minutes"), "for 5 minutes");
+        assert_eq!(normalize("March twenty first"), "March 21st");
+        assert_eq!(normalize("On May second"), "On May 2nd");
+        assert_eq!(normalize("you may first check"), "you may first check");
+    }
+
+    fn word(text: &str, start_secs: f64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_secs,
+            duration_secs: 0.5,
+            confidence: Some(0.9),
+            script: None,
+            locale: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn numbers_split_across_segments_are_merged() {
+        let words = [
+            word("In", 0.0),
+            word("twenty", 0.5),
+            word("twenty", 1.0),
+            word("five", 1.5),
+            word("sales", 2.0),
+            word("rose", 2.5),
+            word("ten", 3.0),
+            word("percent.", 3.5),
+        ];
+        let segments = normalize_segments(&words);
+        let texts: Vec<&str> = segments.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["In", "2025", "sales", "rose", "10%."]);
+        assert_eq!(
+            texts.join(" "),
+            normalize("In twenty twenty five sales rose ten percent.")
+        );
+        assert_eq!(
+            (segments[1].start_secs, segments[1].duration_secs),
+            (0.5, 1.5)
+        );
+        assert_eq!(
+            (segments[4].start_secs, segments[4].duration_secs),
+            (3.0, 1.0)
+        );
+        assert_eq!(
+            (segments[2].start_secs, segments[2].duration_secs),
+            (2.0, 0.5)
+        );
+    }
+
+    #[test]
+    fn punctuation_closes_numbers_unless_abbreviated() {
+        assert!(closes_numbers("twenty five."));
+        assert!(!closes_numbers("twenty"));
+        assert!(!closes_numbers("p."));
+        assert!(!closes_numbers("three p.m."));
+    }
+}
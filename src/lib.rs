@@ -33,13 +33,12 @@
 //! let mut transcriber = StreamingTranscriber::new().expect("Failed to create transcriber");
 //! transcriber.start().expect("Failed to start transcription");
 //!
-//! loop {
-//!     if let Some(result) = transcriber.poll_result().expect("Failed to poll") {
-//!         if result.is_final {
-//!             println!("Final: {}", result.text);
-//!         } else {
-//!             println!("Partial: {}", result.text);
-//!         }
+//! for result in transcriber.results() {
+//!     let result = result.expect("Failed to poll");
+//!     if result.is_final {
+//!         println!("Final: {}", result.text);
+//!     } else {
+//!         println!("Partial: {}", result.text);
 //!     }
 //! }
 //! ```
@@ -76,9 +75,132 @@
 //! See the [repository README](https://github.com/NimbleAINinja/swift-scribe-rs) for build instructions.
 
 use serde::{Deserialize, Serialize};
-use std::io::Write;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::io::{BufRead, Read, Write};
+use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, TryLockError};
+use std::time::{Duration, Instant};
+
+mod align;
+#[cfg(feature = "async")]
+mod async_io;
+mod batch;
+mod checksum;
+mod config;
+mod delta;
+mod disfluency;
+mod error;
+mod fed_audio;
+mod itn;
+mod notes;
+mod output;
+mod pool;
+mod preflight;
+mod resample;
+mod resident;
+mod results;
+mod script;
+mod sentences;
+mod split;
+mod stability;
+mod stderr_tail;
+#[cfg(all(feature = "system-audio", target_os = "macos"))]
+mod system_audio;
+mod tail;
+mod telemetry;
+mod transcript;
+#[cfg(feature = "hound")]
+mod wav;
+#[cfg(feature = "ws-server")]
+mod ws_server;
+
+pub use align::WordSegment;
+pub use batch::{
+    BatchEntry, BatchOptions, BatchOutcome, BatchOutput, DirectoryResult, LineEnding, SkipReason,
+    COMBINED_TRANSCRIPT,
+};
+pub use config::TranscriptionConfig;
+pub use delta::TextDelta;
+pub use error::ScribeError;
+pub use notes::{format_transcript, FormatOptions};
+pub use output::OutputFormat;
+pub use pool::TranscriberPool;
+pub use preflight::{PreflightIssue, PreflightReport};
+pub use resample::ResampleQuality;
+pub use results::ResultIter;
+pub use script::{detect_script, Script};
+pub use stability::StabilityMode;
+
+use checksum::HelperChecksum;
+use delta::DeltaTracker;
+use fed_audio::FedAudioRecorder;
+use resample::Resampler;
+use resident::{ResidentHelper, ResidentSlot};
+use sentences::SentenceSegmenter;
+use stability::StabilityGate;
+use stderr_tail::StderrTail;
+use transcript::SessionTranscript;
+#[cfg(feature = "ws-server")]
+use ws_server::WsServer;
+
+/// Sample encoding of a headerless PCM file, for [`Transcriber::transcribe_raw_file`]
+///
+/// Samples are little-endian and interleaved by channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcmFormat {
+    /// Signed 16-bit integer
+    S16Le,
+    /// Signed 24-bit integer, packed in 3 bytes
+    S24Le,
+    /// Signed 32-bit integer
+    S32Le,
+    /// 32-bit IEEE float
+    F32Le,
+}
+
+impl PcmFormat {
+    /// Size of one sample in bytes
+    pub fn bytes_per_sample(&self) -> usize {
+        match self {
+            PcmFormat::S16Le => 2,
+            PcmFormat::S24Le => 3,
+            PcmFormat::S32Le | PcmFormat::F32Le => 4,
+        }
+    }
+
+    /// The helper's `--raw-format` value
+    fn flag(&self) -> &'static str {
+        match self {
+            PcmFormat::S16Le => "s16le",
+            PcmFormat::S24Le => "s24le",
+            PcmFormat::S32Le => "s32le",
+            PcmFormat::F32Le => "f32le",
+        }
+    }
+}
+
+/// An audio track of a media file, as reported by [`Transcriber::list_tracks`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackInfo {
+    /// Index to pass to [`Transcriber::transcribe_track`]
+    pub index: usize,
+    /// Number of audio channels in the track
+    #[serde(rename = "channels")]
+    pub channel_count: u32,
+    /// Sample rate of the track, in Hz
+    #[serde(rename = "sampleRate")]
+    pub sample_rate: f64,
+    /// Length of the track, in seconds
+    #[serde(rename = "duration")]
+    pub duration_secs: f64,
+    /// Language tag of the track, when the file declares one
+    #[serde(default)]
+    pub language: Option<String>,
+}
 
 /// Result of a transcription operation with optional metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,512 +208,4737 @@ pub struct TranscriptionResult {
     /// The transcribed text
     pub text: String,
     /// Optional confidence score (0.0-1.0)
+    ///
+    /// Combined from the segment confidences as configured with
+    /// [`TranscriberBuilder::with_confidence_aggregation`].
     pub confidence: Option<f32>,
+    /// Recognized segments with timing and per-segment confidence
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<TranscriptionSegment>,
+    /// Seconds of leading silence skipped before recognition, when trimming is enabled
+    #[serde(
+        rename = "trimmedLeading",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub trimmed_leading_secs: Option<f64>,
+    /// Seconds of trailing silence skipped before recognition, when trimming is enabled
+    #[serde(
+        rename = "trimmedTrailing",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub trimmed_trailing_secs: Option<f64>,
+    /// Recognition engine the helper used, when reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<Backend>,
+    /// Locale the audio was recognized in, when reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Length of the audio in seconds, before any trimming, when reported
+    #[serde(rename = "duration", default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    /// Total length of the recognized segments
+    ///
+    /// How much of the audio held speech, e.g. for billing by speech time or
+    /// flagging mostly silent recordings against `total_duration`. Filled in
+    /// by [`Transcriber::transcribe_file_with_metadata`]; not part of the
+    /// serialized form, as it follows from `segments`.
+    #[serde(skip)]
+    pub speech_duration: Duration,
+    /// Length of the audio, before any trimming, when the helper reports it
+    ///
+    /// `duration_secs` as a [`Duration`]; not part of the serialized form.
+    #[serde(skip)]
+    pub total_duration: Option<Duration>,
 }
 
-/// Main transcriber interface for speech-to-text conversion
+/// Recognition engine used by the helper
 ///
-/// # Examples
-///
-/// ```no_run
-/// use swift_scribe::Transcriber;
-/// use std::path::Path;
+/// Returned by [`Transcriber::backend`], and reported with file transcriptions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Backend {
+    /// `SpeechAnalyzer` (macOS 26+)
+    #[serde(alias = "SpeechAnalyzer")]
+    SpeechAnalyzer,
+    /// `SFSpeechRecognizer`, used on older systems
+    #[serde(alias = "SFSpeechRecognizer")]
+    SfSpeechRecognizer,
+}
+
+/// The helper's `--backend-info` output
+#[derive(Deserialize)]
+struct BackendInfo {
+    backend: Backend,
+    #[serde(default)]
+    os_version: Option<String>,
+}
+
+/// A file transcription in the same structured shape as streaming output
 ///
-/// let transcriber = Transcriber::new().unwrap();
-/// let result = transcriber.transcribe_file(Path::new("audio.m4a")).unwrap();
-/// println!("Transcription: {}", result);
-/// ```
-pub struct Transcriber {
-    helper_path: PathBuf,
+/// Returned by [`Transcriber::transcribe_file_json`]. Serializes with the
+/// camelCase keys the streaming results use, so file and streaming transcripts
+/// can share a storage format.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTranscription {
+    /// The complete transcript
+    pub full_text: String,
+    /// Recognized segments with timing and per-segment confidence
+    pub segments: Vec<TranscriptionSegment>,
+    /// Overall confidence (0.0-1.0), combined from the segments
+    pub confidence: Option<f32>,
+    /// Recognition engine the helper used, when reported
+    pub backend: Option<Backend>,
+    /// Locale the audio was recognized in, when reported
+    pub locale: Option<String>,
+    /// Length of the audio in seconds, when reported
+    #[serde(rename = "duration")]
+    pub duration_secs: Option<f64>,
 }
 
-impl Transcriber {
-    /// Creates a new transcriber with default helper path
+/// A recognized span of a file transcription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptionSegment {
+    /// The recognized text
+    pub text: String,
+    /// Offset of the segment from the start of the file, in seconds
+    #[serde(rename = "start")]
+    pub start_secs: f64,
+    /// Length of the segment, in seconds
+    #[serde(rename = "duration")]
+    pub duration_secs: f64,
+    /// Recognizer confidence (0.0-1.0), when reported
+    #[serde(default)]
+    pub confidence: Option<f32>,
+    /// Writing system of `text`, from [`detect_script`]
     ///
-    /// Looks for the helper binary in the following locations (in order):
-    /// 1. `./helpers/transcribe` (local development)
-    /// 2. `~/.local/bin/transcribe` (user install)
-    /// 3. `/usr/local/bin/transcribe` (system install)
+    /// Filled in for segments returned by
+    /// [`Transcriber::transcribe_file_with_metadata`]; a script the locale doesn't
+    /// use points at a misrecognized stretch of audio.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<Script>,
+    /// Locale the segment was recognized in, when reported
     ///
-    /// # Errors
+    /// See [`TranscriberBuilder::with_candidate_locales`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Timing of each word of the segment, when reported
     ///
-    /// Returns an error if the helper binary cannot be found in any of the default locations.
-    pub fn new() -> Result<Self, String> {
-        let default_paths = vec![
-            PathBuf::from("./helpers/transcribe"),
-            dirs::home_dir()
-                .map(|h| h.join(".local/bin/transcribe"))
-                .unwrap_or_default(),
-            PathBuf::from("/usr/local/bin/transcribe"),
-        ];
-
-        for path in default_paths {
-            if path.exists() {
-                return Ok(Self { helper_path: path });
-            }
-        }
-
-        Err(
-            "Helper binary not found. Please compile with 'make helpers' or install system-wide."
-                .to_string(),
-        )
-    }
+    /// The segments of [`StreamingTranscriber::finish_transcript`] carry the
+    /// words of their [`StreamingResult`]; the file helper already reports one
+    /// segment per word, so its segments have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTiming>>,
+}
 
-    /// Creates a new transcriber with a custom helper binary path
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - Path to the transcribe helper binary
+impl TranscriptionSegment {
+    /// Combines adjacent segments into cues of at most `max_chars` characters and
+    /// `max_duration_secs` seconds
     ///
-    /// # Errors
+    /// Segments are merged greedily in order: each one joins the cue being built
+    /// if the combined text and span stay within both limits and the cue doesn't
+    /// already end a sentence. A single segment that exceeds a limit on its own is
+    /// kept as is. Merged cues span from the first segment's start to the last
+    /// one's end, with duration-weighted confidence.
     ///
-    /// Returns an error if the specified path does not exist.
+    /// This turns word- or phrase-level recognizer output into readable subtitle
+    /// cues, e.g. `merge_to(&result.segments, 42, 4.0)`.
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// use swift_scribe::Transcriber;
+    /// ```
+    /// use swift_scribe::TranscriptionSegment;
     ///
-    /// let transcriber = Transcriber::with_helper_path("/custom/path/transcribe").unwrap();
+    /// let word = |text: &str, start: f64| TranscriptionSegment {
+    ///     text: text.to_string(),
+    ///     start_secs: start,
+    ///     duration_secs: 0.4,
+    ///     confidence: None,
+    ///     script: None,
+    ///     locale: None,
+    ///     words: None,
+    /// };
+    /// let words = [word("Hello", 0.0), word("there.", 0.5), word("Bye", 1.0)];
+    /// let cues = TranscriptionSegment::merge_to(&words, 40, 3.0);
+    /// assert_eq!(cues[0].text, "Hello there.");
+    /// assert_eq!(cues[1].text, "Bye");
     /// ```
-    pub fn with_helper_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
-        let path = path.as_ref().to_path_buf();
-        if !path.exists() {
-            return Err(format!("Helper binary not found at: {}", path.display()));
+    pub fn merge_to(
+        segments: &[TranscriptionSegment],
+        max_chars: usize,
+        max_duration_secs: f64,
+    ) -> Vec<TranscriptionSegment> {
+        let mut cues = Vec::new();
+        let mut group: &[TranscriptionSegment] = &[];
+        let mut text = String::new();
+
+        for (idx, segment) in segments.iter().enumerate() {
+            if Self::fits_cue(group, &text, segment, max_chars, max_duration_secs) {
+                group = &segments[idx - group.len()..=idx];
+                text.push(' ');
+            } else {
+                if !group.is_empty() {
+                    cues.push(Self::merged(group, std::mem::take(&mut text)));
+                }
+                group = &segments[idx..=idx];
+            }
+            text.push_str(segment.text.trim());
+        }
+        if !group.is_empty() {
+            cues.push(Self::merged(group, text));
         }
-        Ok(Self { helper_path: path })
+        cues
     }
 
-    /// Transcribes an audio file to text
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - Path to the audio file (supports M4A, WAV, MP3, AAC, FLAC, AIFF)
-    ///
-    /// # Returns
+    /// Whether `segment` can join the cue of `group`, which reads `text` so far
     ///
-    /// The transcribed text as a `String`.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if:
-    /// - The file doesn't exist
-    /// - The audio format is unsupported
-    /// - The transcription fails
-    /// - Speech recognition permissions haven't been granted
-    ///
-    /// # Examples
+    /// An empty group starts a cue of its own.
+    fn fits_cue(
+        group: &[TranscriptionSegment],
+        text: &str,
+        segment: &TranscriptionSegment,
+        max_chars: usize,
+        max_duration_secs: f64,
+    ) -> bool {
+        group.first().is_some_and(|first| {
+            let end = segment.start_secs + segment.duration_secs;
+            !sentences::ends_sentence(text)
+                && text.chars().count() + 1 + segment.text.trim().chars().count() <= max_chars
+                && end - first.start_secs <= max_duration_secs
+        })
+    }
+
+    /// A cue spanning all of `group`, which must not be empty
+    fn merged(group: &[TranscriptionSegment], text: String) -> TranscriptionSegment {
+        let first = &group[0];
+        let last = &group[group.len() - 1];
+        TranscriptionSegment {
+            start_secs: first.start_secs,
+            duration_secs: last.start_secs + last.duration_secs - first.start_secs,
+            confidence: ConfidenceAggregation::DurationWeighted.aggregate(group),
+            script: first.script.map(|_| detect_script(&text)),
+            locale: group
+                .iter()
+                .all(|segment| segment.locale == first.locale)
+                .then(|| first.locale.clone())
+                .flatten(),
+            words: group
+                .iter()
+                .map(|segment| segment.words.clone())
+                .collect::<Option<Vec<_>>>()
+                .map(|words| words.concat()),
+            text,
+        }
+    }
+}
+
+/// How segment confidences are combined into a single file confidence
+///
+/// Segments without a confidence are ignored; if none has one, the result has
+/// no confidence. With `c` the segment confidences and `d` their durations:
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceAggregation {
+    /// Plain mean, `Σc / n`, where every segment counts equally
+    Mean,
+    /// Lowest segment confidence, `min(c)`, to flag any low-confidence region
+    Min,
+    /// Duration-weighted mean, `Σ(c·d) / Σd` (default)
     ///
-    /// ```no_run
-    /// use swift_scribe::Transcriber;
-    /// use std::path::Path;
+    /// Long segments count proportionally more than short ones. Falls back to the
+    /// plain mean when the segments have no duration.
+    #[default]
+    DurationWeighted,
+}
+
+impl ConfidenceAggregation {
+    /// Combines the confidences of `segments` according to this method
+    pub fn aggregate(&self, segments: &[TranscriptionSegment]) -> Option<f32> {
+        let scored: Vec<(f64, f64)> = segments
+            .iter()
+            .filter_map(|s| s.confidence.map(|c| (c as f64, s.duration_secs.max(0.0))))
+            .collect();
+        if scored.is_empty() {
+            return None;
+        }
+
+        let mean = || scored.iter().map(|(c, _)| c).sum::<f64>() / scored.len() as f64;
+        let value = match self {
+            ConfidenceAggregation::Mean => mean(),
+            ConfidenceAggregation::Min => {
+                scored.iter().map(|(c, _)| *c).fold(f64::INFINITY, f64::min)
+            }
+            ConfidenceAggregation::DurationWeighted => {
+                let total: f64 = scored.iter().map(|(_, d)| d).sum();
+                if total > 0.0 {
+                    scored.iter().map(|(c, d)| c * d).sum::<f64>() / total
+                } else {
+                    mean()
+                }
+            }
+        };
+        Some(value as f32)
+    }
+}
+
+/// What file transcription returns when the helper recognizes no speech
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyOutputPolicy {
+    /// Return `Ok` with an empty transcript (default)
+    #[default]
+    AsEmpty,
+    /// Return a [`ScribeError::NoSpeechDetected`] error
+    AsError,
+}
+
+/// How the temporary files a [`Transcriber`] stages audio in are named
+///
+/// See [`TranscriberBuilder::with_temp_naming`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TempNaming {
+    /// `swift-scribe-<pid>-<n>.<ext>`, unique across processes and
+    /// transcribers sharing a directory (default)
+    #[default]
+    Unique,
+    /// `<prefix>-<n>.<ext>`, with `n` counting up from 0 for each transcriber
     ///
-    /// let transcriber = Transcriber::new().unwrap();
-    /// match transcriber.transcribe_file(Path::new("recording.m4a")) {
-    ///     Ok(text) => println!("Transcription: {}", text),
-    ///     Err(e) => eprintln!("Error: {}", e),
-    /// }
-    /// ```
-    pub fn transcribe_file(&self, path: &Path) -> Result<String, String> {
-        if !path.exists() {
-            return Err(format!("Audio file not found: {}", path.display()));
+    /// Predictable paths for tests that check intermediate files. Names only
+    /// stay unique if no other transcriber uses the same prefix in the same
+    /// directory.
+    Sequential {
+        /// File name prefix; must not contain a path separator
+        prefix: String,
+    },
+}
+
+/// Availability of on-device recognition assets for a locale
+///
+/// Returned by [`Transcriber::ensure_locale_available`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LocaleStatus {
+    /// Assets are installed; recognition works offline
+    Installed,
+    /// The locale is supported but its assets aren't installed
+    NotInstalled,
+    /// The recognizer doesn't support the locale on this system
+    Unsupported,
+}
+
+/// One line of the helper's `--locale-status` output
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum LocaleLine {
+    Progress { progress: f64 },
+    Status { status: LocaleStatus },
+}
+
+/// Reads `--locale-status` output, reporting progress lines as they arrive
+///
+/// Returns the last status reported, if any.
+fn read_locale_lines(
+    output: impl Read,
+    on_progress: &mut impl FnMut(f64),
+) -> Result<Option<LocaleStatus>, String> {
+    let mut status = None;
+    for line in std::io::BufReader::new(output).lines() {
+        let line = line.map_err(|e| format!("Failed to read from helper: {}", e))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
         }
+        match serde_json::from_str(line)
+            .map_err(|e| format!("Failed to parse locale status: {} (line: {})", e, line))?
+        {
+            LocaleLine::Progress { progress } => on_progress(progress),
+            LocaleLine::Status { status: reported } => status = Some(reported),
+        }
+    }
+    Ok(status)
+}
 
-        let path_str = path
-            .to_str()
-            .ok_or_else(|| "Invalid UTF-8 path".to_string())?;
+/// Scheduling priority for the spawned helper process
+///
+/// Lowering the priority trades throughput for responsiveness: the helper yields
+/// CPU to foreground work, so transcription takes longer but the rest of the
+/// system stays snappy. Useful for background batch jobs on battery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Run the helper with its nice value raised by 10
+    Low,
+    /// Run the helper at the caller's priority (default)
+    #[default]
+    Normal,
+}
 
-        let output = Command::new(&self.helper_path)
-            .arg(path_str)
-            .output()
-            .map_err(|e| {
-                format!(
-                    "Failed to execute helper at {}: {}",
-                    self.helper_path.display(),
-                    e
-                )
-            })?;
+/// Increment added to the nice value of helpers spawned with [`Priority::Low`]
+const LOW_PRIORITY_NICE: libc::c_int = 10;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("Transcription failed: {}", stderr));
+/// Configures the spawned helper's scheduling priority before it executes
+fn apply_priority(cmd: &mut Command, priority: Priority) {
+    #[cfg(unix)]
+    if priority == Priority::Low {
+        use std::os::unix::process::CommandExt;
+        // SAFETY: nice is async-signal-safe and touches no parent state.
+        unsafe {
+            cmd.pre_exec(|| {
+                // Relative to the caller's nice value, and capped by the system.
+                // A helper that can't be lowered still runs, at the caller's
+                // priority.
+                libc::nice(LOW_PRIORITY_NICE);
+                Ok(())
+            });
         }
+    }
+}
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        Ok(stdout.trim().to_string())
+/// Kind of speech the recognizer should expect
+///
+/// Maps to `SFSpeechRecognitionTaskHint`. Short utterances such as voice
+/// commands are recognized more accurately with `Search` or `Confirmation`.
+/// SpeechAnalyzer (macOS 26+) has no task hints, so there the hint has no
+/// effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskHint {
+    /// No particular kind of speech (default)
+    #[default]
+    Unspecified,
+    /// Free-form dictation, as in a keyboard's dictation feature
+    Dictation,
+    /// Short search queries
+    Search,
+    /// Short commands or answers such as "yes", "cancel" or "call Mom"
+    Confirmation,
+}
+
+impl TaskHint {
+    /// Value of the helper's `--task-hint` flag, or `None` to leave it unset
+    fn flag(self) -> Option<&'static str> {
+        match self {
+            TaskHint::Unspecified => None,
+            TaskHint::Dictation => Some("dictation"),
+            TaskHint::Search => Some("search"),
+            TaskHint::Confirmation => Some("confirmation"),
+        }
     }
+}
 
-    /// Returns the path to the helper binary being used
-    pub fn helper_path(&self) -> &Path {
-        &self.helper_path
+/// Renders `cmd` as a shell-style command line for error messages and logs
+///
+/// Arguments containing whitespace or quotes are single-quoted so the line can be
+/// pasted into a terminal to reproduce the invocation.
+fn command_line(cmd: &Command) -> String {
+    std::iter::once(cmd.get_program())
+        .chain(cmd.get_args())
+        .map(|part| {
+            let part = part.to_string_lossy();
+            let quote =
+                part.is_empty() || part.contains(char::is_whitespace) || part.contains(['\'', '"']);
+            if quote {
+                format!("'{}'", part.replace('\'', r"'\''"))
+            } else {
+                part.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds the audio file at `path`, forgiving a trailing newline or whitespace
+///
+/// Paths copied from a terminal often carry a trailing newline. When `path`
+/// doesn't exist but the path without its trailing whitespace and control
+/// characters does, that path is used instead.
+fn locate_audio(path: &Path) -> Result<Cow<'_, Path>, ScribeError> {
+    if path.exists() {
+        return Ok(Cow::Borrowed(path));
+    }
+
+    let stray = |c: char| c.is_whitespace() || c.is_control();
+    if let Some(raw) = path.to_str() {
+        let trimmed = raw.trim_end_matches(stray);
+        if trimmed.len() != raw.len() && !trimmed.is_empty() && Path::new(trimmed).exists() {
+            return Ok(Cow::Owned(PathBuf::from(trimmed)));
+        }
     }
+    Err(ScribeError::AudioFileMissing(path.to_path_buf()))
 }
 
-impl Default for Transcriber {
+/// How many times helper discovery scans the default locations before giving up
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DiscoveryRetry {
+    attempts: u32,
+    delay: Duration,
+}
+
+impl Default for DiscoveryRetry {
     fn default() -> Self {
-        Self::new().unwrap()
+        Self {
+            attempts: 1,
+            delay: Duration::ZERO,
+        }
     }
 }
 
-/// Result from streaming transcription with real-time updates
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StreamingResult {
-    /// The transcribed text
-    pub text: String,
-    /// Whether this is a final result (true) or volatile/partial (false)
-    #[serde(rename = "isFinal")]
-    pub is_final: bool,
-    /// Unix timestamp when the result was generated
-    pub timestamp: f64,
+/// Checks a locale identifier given to a builder
+fn check_locale(locale: &str) -> Result<(), ScribeError> {
+    if locale.is_empty() {
+        return Err("Locale must not be empty".into());
+    }
+    if locale.contains(|c: char| c == ',' || c.is_whitespace()) {
+        return Err(format!("Invalid locale identifier: {:?}", locale).into());
+    }
+    Ok(())
 }
 
-/// Audio input mode for streaming transcription
-#[derive(Debug, Clone, Copy)]
-pub enum AudioInputMode {
-    /// Capture audio from the microphone
-    Microphone,
-    /// Accept audio programmatically via feed_audio methods
-    Programmatic,
+/// Looks for a helper binary in the default install locations
+///
+/// Searches `./helpers/<name>`, `~/.local/bin/<name>`, then `/usr/local/bin/<name>`.
+fn find_helper(name: &str, retry: DiscoveryRetry) -> Option<PathBuf> {
+    first_existing(&helper_search_paths(name, dirs::home_dir()), retry)
 }
 
-/// Builder for StreamingTranscriber with flexible configuration
-pub struct StreamingTranscriberBuilder {
+/// Returns the first of `paths` that exists, rescanning them as `retry` allows
+fn first_existing(paths: &[PathBuf], retry: DiscoveryRetry) -> Option<PathBuf> {
+    for attempt in 1..=retry.attempts.max(1) {
+        if let Some(path) = paths.iter().find(|path| path.exists()) {
+            return Some(path.clone());
+        }
+        if attempt < retry.attempts {
+            log::debug!("Helper not found (attempt {}); retrying", attempt);
+            std::thread::sleep(retry.delay);
+        }
+    }
+    None
+}
+
+/// Candidate helper locations, in search order
+///
+/// The user install location is skipped when there is no home directory (some
+/// daemon and service contexts) rather than searched under a degenerate path.
+fn helper_search_paths(name: &str, home: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from("./helpers").join(name)];
+    if let Some(home) = home.filter(|h| !h.as_os_str().is_empty()) {
+        paths.push(home.join(".local/bin").join(name));
+    }
+    paths.push(PathBuf::from("/usr/local/bin").join(name));
+    paths
+}
+
+/// Builder for Transcriber with flexible configuration
+pub struct TranscriberBuilder {
     helper_path: Option<PathBuf>,
-    input_mode: AudioInputMode,
+    priority: Priority,
+    temp_dir: Option<PathBuf>,
+    temp_naming: TempNaming,
+    trim_silence: bool,
+    confidence_aggregation: ConfidenceAggregation,
+    empty_output: EmptyOutputPolicy,
+    verbatim: bool,
+    inverse_text_normalization: bool,
+    task_hint: TaskHint,
+    timestamp_offset_secs: f64,
+    max_segment_duration: Option<Duration>,
+    line_ending: LineEnding,
+    bom: bool,
+    locale: Option<String>,
+    candidate_locales: Vec<String>,
+    extra_args: Vec<String>,
+    helper_idle_timeout: Option<Duration>,
+    discovery_retry: DiscoveryRetry,
+    expected_helper_sha256: Option<String>,
 }
 
-impl StreamingTranscriberBuilder {
-    /// Creates a new builder with default settings (microphone input)
+impl TranscriberBuilder {
+    /// Creates a new builder with default settings
     pub fn new() -> Self {
         Self {
             helper_path: None,
-            input_mode: AudioInputMode::Microphone,
+            priority: Priority::Normal,
+            temp_dir: None,
+            temp_naming: TempNaming::Unique,
+            trim_silence: false,
+            confidence_aggregation: ConfidenceAggregation::DurationWeighted,
+            empty_output: EmptyOutputPolicy::AsEmpty,
+            verbatim: true,
+            inverse_text_normalization: false,
+            task_hint: TaskHint::Unspecified,
+            timestamp_offset_secs: 0.0,
+            max_segment_duration: None,
+            line_ending: LineEnding::Lf,
+            bom: false,
+            locale: None,
+            candidate_locales: Vec::new(),
+            extra_args: Vec::new(),
+            helper_idle_timeout: None,
+            discovery_retry: DiscoveryRetry::default(),
+            expected_helper_sha256: None,
         }
     }
 
-    /// Set the input mode to microphone (default)
-    pub fn with_microphone(mut self) -> Self {
-        self.input_mode = AudioInputMode::Microphone;
+    /// Set a custom path to the helper binary
+    pub fn with_helper_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.helper_path = Some(path.as_ref().to_path_buf());
         self
     }
 
-    /// Set the input mode to programmatic (feed audio via API)
-    pub fn with_programmatic_input(mut self) -> Self {
-        self.input_mode = AudioInputMode::Programmatic;
+    /// Refuse to run a helper whose SHA-256 differs from `hex` (default: unchecked)
+    ///
+    /// Pins the exact helper an app shipped with. The helper is hashed the first
+    /// time it would run and the outcome is kept for the transcriber's lifetime;
+    /// on a mismatch every call fails with a "Helper checksum mismatch" error
+    /// naming both digests. `hex` is 64 hex digits, as printed by `shasum -a 256`.
+    pub fn with_expected_helper_sha256(mut self, hex: impl Into<String>) -> Self {
+        self.expected_helper_sha256 = Some(hex.into());
         self
     }
 
-    /// Set a custom path to the helper binary
-    pub fn with_helper_path<P: AsRef<Path>>(mut self, path: P) -> Self {
-        self.helper_path = Some(path.as_ref().to_path_buf());
+    /// Set the scheduling priority of the spawned helper (default: `Normal`)
+    ///
+    /// `Priority::Low` keeps foreground apps responsive during background
+    /// transcription at the cost of slower results.
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
         self
     }
 
-    /// Build the StreamingTranscriber
-    pub fn build(self) -> Result<StreamingTranscriber, String> {
-        let helper_path = if let Some(path) = self.helper_path {
-            if !path.exists() {
-                return Err(format!(
-                    "Streaming helper binary not found at: {}",
-                    path.display()
-                ));
-            }
-            path
-        } else {
-            let default_paths = vec![
-                PathBuf::from("./helpers/transcribe_stream"),
-                dirs::home_dir()
-                    .map(|h| h.join(".local/bin/transcribe_stream"))
-                    .unwrap_or_default(),
-                PathBuf::from("/usr/local/bin/transcribe_stream"),
-            ];
-
-            let mut found = None;
-            for path in default_paths {
-                if path.exists() {
-                    found = Some(path);
-                    break;
-                }
-            }
+    /// Set the directory for temporary files (default: the system temp directory)
+    ///
+    /// Operations that stage audio on disk before invoking the helper write their
+    /// temporary files here. Sandboxed apps (e.g. Mac App Store builds, where `/tmp`
+    /// isn't writable) can point this at a directory their entitlements allow.
+    /// The helper is given the same directory through `TMPDIR`.
+    pub fn with_temp_dir<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.temp_dir = Some(path.as_ref().to_path_buf());
+        self
+    }
 
-            found.ok_or_else(|| {
-                "Streaming helper binary not found. Please compile with 'make helpers'.".to_string()
-            })?
-        };
+    /// Set how temporary files are named (default: `Unique`)
+    ///
+    /// [`TempNaming::Sequential`] gives predictable paths, so integration tests
+    /// can check that staged files are created and cleaned up where expected.
+    /// Keep the default in production, where several processes may share the
+    /// temp directory.
+    pub fn with_temp_naming(mut self, naming: TempNaming) -> Self {
+        self.temp_naming = naming;
+        self
+    }
 
-        Ok(StreamingTranscriber {
-            helper_path,
-            input_mode: self.input_mode,
-            process: None,
-            stdout: None,
-            stdin: None,
-            line_buffer: String::new(),
-        })
+    /// Skip leading and trailing silence before recognition (default: off)
+    ///
+    /// The helper drops padding quieter than -45 dBFS from both ends of the file,
+    /// which speeds up mostly-silent recordings such as voicemail and keeps the
+    /// recognizer from producing spurious text on the noise floor. Use
+    /// [`Transcriber::transcribe_file_with_metadata`] to see how much was trimmed.
+    pub fn with_trim_silence(mut self, enabled: bool) -> Self {
+        self.trim_silence = enabled;
+        self
     }
-}
 
-impl Default for StreamingTranscriberBuilder {
-    fn default() -> Self {
-        Self::new()
+    /// Set how segment confidences combine into the file confidence (default: `DurationWeighted`)
+    ///
+    /// Applies to [`Transcriber::transcribe_file_with_metadata`]. See
+    /// [`ConfidenceAggregation`] for the formulas.
+    pub fn with_confidence_aggregation(mut self, aggregation: ConfidenceAggregation) -> Self {
+        self.confidence_aggregation = aggregation;
+        self
     }
-}
 
-/// Streaming transcriber for live microphone or audio stream input
-///
-/// Provides real-time transcription with both partial (volatile) and final results.
-/// Uses progressive transcription mode for low-latency feedback.
-///
+    /// Set what happens when the helper returns an empty transcript (default: `AsEmpty`)
+    ///
+    /// Silence and unintelligible audio produce no text. `AsError` turns that into
+    /// a [`ScribeError::NoSpeechDetected`] error for pipelines that should fail loudly;
+    /// `AsEmpty` returns `Ok` with an empty string.
+    pub fn with_empty_output_policy(mut self, policy: EmptyOutputPolicy) -> Self {
+        self.empty_output = policy;
+        self
+    }
+
+    /// Keep filler words and false starts in the transcript (default: `true`)
+    ///
+    /// The Speech framework has no switch for disfluencies, so the transcript is
+    /// passed through as recognized when this is `true`. With `false`, the library
+    /// cleans the text and each segment after recognition: hesitations such as
+    /// "um" and "uh" are dropped and immediately repeated words ("I I think") are
+    /// collapsed. Use verbatim output for legal or research transcripts and
+    /// cleaned output for meeting notes.
+    pub fn with_verbatim(mut self, verbatim: bool) -> Self {
+        self.verbatim = verbatim;
+        self
+    }
+
+    /// Write spelled-out numbers in digits (default: `false`)
+    ///
+    /// The recognizer's own normalization is inconsistent: it may return
+    /// "twenty twenty five" for a year or "three p m" for a time. With `true`,
+    /// the library rewrites the text and each segment after recognition:
+    /// numbers, decimals and years become digits ("2025"), clock times are
+    /// written "3:30 PM", and percentages, dollar and euro amounts and degrees
+    /// take their symbols ("10%", "$5.50", "70°"). Ordinals of ten and over,
+    /// or after a month name, become "21st".
+    ///
+    /// Bare numbers below ten stay words ("two ideas"), as most style guides
+    /// prefer, and adjacent numbers that form neither a year nor a time, such
+    /// as "five thirty", are left as spoken rather than guessed at.
+    pub fn with_inverse_text_normalization(mut self, enabled: bool) -> Self {
+        self.inverse_text_normalization = enabled;
+        self
+    }
+
+    /// Tell the recognizer what kind of speech to expect (default: `Unspecified`)
+    ///
+    /// See [`TaskHint`]. Only the SFSpeechRecognizer backend uses the hint.
+    pub fn with_task_hint(mut self, hint: TaskHint) -> Self {
+        self.task_hint = hint;
+        self
+    }
+
+    /// Shift every segment timing by `offset_secs` seconds (default: `0.0`)
+    ///
+    /// Corrects a constant lag between the helper's timings and the audio, e.g.
+    /// to line subtitles up with a player. This is a linear shift: every start
+    /// moves by the same amount and durations are unchanged, so it can't correct
+    /// drift that grows over the file. Negative offsets move timings earlier;
+    /// a start that would fall before zero is clamped to zero. Applies to
+    /// [`Transcriber::transcribe_file_with_metadata`] and everything built on it,
+    /// and to [`Transcriber::align`]. [`build`](Self::build) fails if
+    /// `offset_secs` is NaN or infinite.
+    pub fn with_timestamp_offset(mut self, offset_secs: f64) -> Self {
+        self.timestamp_offset_secs = offset_secs;
+        self
+    }
+
+    /// Split segments longer than `max` into shorter ones (default: unlimited)
+    ///
+    /// Continuous speech without pauses can come back as one segment far too
+    /// long for a subtitle cue. Such segments are cut at the word gaps nearest
+    /// the limit, never inside a word; since the recognizer only times whole
+    /// segments, each word is given a share of its segment's span by length. A
+    /// single word longer than `max` is kept whole. Applies to
+    /// [`Transcriber::transcribe_file_with_metadata`] and everything built on
+    /// it, and to [`Transcriber::transcribe_file_to_writer`].
+    pub fn with_max_segment_duration(mut self, max: Duration) -> Self {
+        self.max_segment_duration = Some(max);
+        self
+    }
+
+    /// Set the line terminator of written subtitles (default: `Lf`)
+    ///
+    /// Applies to [`Transcriber::transcribe_file_to_writer`]; use `CrLf` for
+    /// consumers like Notepad. Batch output has its own
+    /// [`BatchOptions::line_ending`].
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Start written subtitles with a UTF-8 byte order mark (default: `false`)
+    ///
+    /// Applies to [`Transcriber::transcribe_file_to_writer`], for consumers
+    /// that otherwise guess a legacy or UTF-16 encoding. Batch output has its
+    /// own [`BatchOptions::bom`].
+    pub fn with_bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Set the locale the audio is spoken in (default: `en-US`)
+    ///
+    /// `locale` is a BCP 47 identifier such as `"es-ES"` or `"ja-JP"`, passed to
+    /// the helper as `--locale`. Unlike
+    /// [`with_candidate_locales`](Self::with_candidate_locales) there is no
+    /// fallback: a locale this Mac doesn't support makes transcription fail.
+    /// [`build`](Self::build) fails if `locale` is empty or both are set.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    /// Set the locales the audio may be spoken in, most likely first
+    /// (default: `en-US` only)
+    ///
+    /// Identifiers are BCP 47, such as `"en-US"` or `"zh-CN"`. The helper
+    /// recognizes in the first candidate this Mac supports, and every segment
+    /// of [`Transcriber::transcribe_file_with_metadata`] is tagged with that
+    /// locale in [`TranscriptionSegment::locale`].
+    ///
+    /// Apple's recognizers handle one locale per recognition, so code-switched
+    /// audio that mixes languages within a recording is recognized entirely in
+    /// the primary locale and the other language is approximated in it. The
+    /// later candidates are fallbacks for a primary locale that isn't
+    /// supported, not a second language recognized alongside it.
+    pub fn with_candidate_locales<I, S>(mut self, locales: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.candidate_locales = locales.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Pass extra arguments to the helper, verbatim (default: none)
+    ///
+    /// An escape hatch for helper flags this crate doesn't expose yet, such as
+    /// those of a custom helper build. The arguments go into every
+    /// request on an audio file, track listing included, after the crate's own
+    /// flags and just before the audio path, so a flag that takes a value must
+    /// be followed by it here. They reach the resident helper of
+    /// [`Transcriber::warm_up`] the same way; locale and authorization checks
+    /// don't get them.
+    ///
+    /// Nothing is validated. An argument the helper doesn't recognize is taken
+    /// as the audio path, and repeating a flag the crate already manages, such
+    /// as `--locales` or `--trim-silence`, conflicts with the builder option
+    /// that sets it; which one wins is up to the helper.
+    pub fn with_extra_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra_args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Stop the resident helper after it goes unused for `timeout` (default: never)
+    ///
+    /// A helper loaded by [`Transcriber::warm_up`] otherwise stays resident,
+    /// holding its model in memory and on the Neural Engine, until the
+    /// transcriber is dropped. With a timeout, a background thread stops it
+    /// once no file has been transcribed for that long, and the next
+    /// transcription loads it again before running, paying the startup cost
+    /// once. Choose a timeout longer than the usual gap between files to keep
+    /// warm starts. Workers of a [`Transcriber::pool`] each time out on their
+    /// own. [`Transcriber::helper_idle_time`] reports how long the helper has
+    /// been unused.
+    pub fn with_helper_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.helper_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Scan for the helper up to `attempts` times, `delay` apart (default: once)
+    ///
+    /// For apps that install the helper in the background at first launch,
+    /// where building a transcriber right after the install can race it. Each
+    /// attempt rescans every default location; [`build`](Self::build) blocks
+    /// until the helper turns up or the attempts run out. A path set with
+    /// [`with_helper_path`](Self::with_helper_path) is checked only once. An
+    /// `attempts` of 0 counts as 1.
+    pub fn with_discovery_retry(mut self, attempts: u32, delay: Duration) -> Self {
+        self.discovery_retry = DiscoveryRetry { attempts, delay };
+        self
+    }
+
+    /// Build the Transcriber
+    pub fn build(self) -> Result<Transcriber, ScribeError> {
+        let helper_path = if let Some(path) = self.helper_path {
+            if !path.exists() {
+                return Err(ScribeError::HelperNotFound(Some(path)));
+            }
+            path
+        } else {
+            find_helper("transcribe", self.discovery_retry)
+                .ok_or(ScribeError::HelperNotFound(None))?
+        };
+
+        if let Some(dir) = &self.temp_dir {
+            if !dir.is_dir() {
+                return Err(format!("Temp directory not found: {}", dir.display()).into());
+            }
+        }
+        if let TempNaming::Sequential { prefix } = &self.temp_naming {
+            if prefix.is_empty() || prefix.contains(std::path::is_separator) {
+                return Err(format!("Invalid temp file prefix: {:?}", prefix).into());
+            }
+        }
+        if let Some(locale) = self.candidate_locales.iter().find(|locale| {
+            locale.is_empty() || locale.contains(|c: char| c == ',' || c.is_whitespace())
+        }) {
+            return Err(format!("Invalid locale identifier: {:?}", locale).into());
+        }
+        if !self.timestamp_offset_secs.is_finite() {
+            return Err(format!(
+                "Timestamp offset must be finite, got {}",
+                self.timestamp_offset_secs
+            )
+            .into());
+        }
+        if let Some(locale) = &self.locale {
+            check_locale(locale)?;
+            if !self.candidate_locales.is_empty() {
+                return Err("A locale and candidate locales can't be combined".into());
+            }
+        }
+        let helper_checksum = self
+            .expected_helper_sha256
+            .as_deref()
+            .map(HelperChecksum::parse)
+            .transpose()?;
+
+        Ok(Transcriber {
+            helper_path,
+            priority: self.priority,
+            temp_dir: self.temp_dir,
+            temp_naming: self.temp_naming,
+            temp_counter: AtomicUsize::new(0),
+            trim_silence: self.trim_silence,
+            confidence_aggregation: self.confidence_aggregation,
+            empty_output: self.empty_output,
+            verbatim: self.verbatim,
+            inverse_text_normalization: self.inverse_text_normalization,
+            task_hint: self.task_hint,
+            timestamp_offset_secs: self.timestamp_offset_secs,
+            max_segment_duration: self.max_segment_duration,
+            line_ending: self.line_ending,
+            bom: self.bom,
+            locale: self.locale,
+            candidate_locales: self.candidate_locales.join(","),
+            extra_args: self.extra_args,
+            helper_idle_timeout: self.helper_idle_timeout,
+            discovery_retry: self.discovery_retry,
+            helper_checksum,
+            resident: Arc::new(Mutex::new(None)),
+            reaped: Arc::new(AtomicBool::new(false)),
+            backend: OnceLock::new(),
+            unavailable: None,
+        })
+    }
+}
+
+impl Default for TranscriberBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Main transcriber interface for speech-to-text conversion
+///
 /// # Examples
 ///
 /// ```no_run
-/// use swift_scribe::StreamingTranscriber;
-///
-/// let mut transcriber = StreamingTranscriber::new().unwrap();
-/// transcriber.start().unwrap();
+/// use swift_scribe::Transcriber;
+/// use std::path::Path;
 ///
-/// // Poll for results in a loop
-/// while let Some(result) = transcriber.poll_result().unwrap() {
-///     if result.is_final {
-///         println!("Final: {}", result.text);
-///     } else {
-///         print!("\rPartial: {}", result.text);
-///     }
-/// }
+/// let transcriber = Transcriber::new().unwrap();
+/// let result = transcriber.transcribe_file(Path::new("audio.m4a")).unwrap();
+/// println!("Transcription: {}", result);
 /// ```
-pub struct StreamingTranscriber {
+pub struct Transcriber {
     helper_path: PathBuf,
-    input_mode: AudioInputMode,
-    process: Option<Child>,
-    stdout: Option<std::process::ChildStdout>,
-    stdin: Option<std::process::ChildStdin>,
-    line_buffer: String,
+    priority: Priority,
+    temp_dir: Option<PathBuf>,
+    temp_naming: TempNaming,
+    /// Temp files named so far, for [`TempNaming::Sequential`]
+    temp_counter: AtomicUsize,
+    trim_silence: bool,
+    confidence_aggregation: ConfidenceAggregation,
+    empty_output: EmptyOutputPolicy,
+    verbatim: bool,
+    inverse_text_normalization: bool,
+    task_hint: TaskHint,
+    /// Seconds added to every segment start
+    timestamp_offset_secs: f64,
+    /// Segments longer than this are split at word gaps
+    max_segment_duration: Option<Duration>,
+    /// Line terminator for [`Transcriber::transcribe_file_to_writer`]
+    line_ending: LineEnding,
+    /// Whether [`Transcriber::transcribe_file_to_writer`] starts with a BOM
+    bom: bool,
+    /// Locale for `--locale`, which takes no fallbacks
+    locale: Option<String>,
+    /// Comma-separated locales for `--locales`; empty for the helper's default
+    candidate_locales: String,
+    /// Passed to the helper before the audio path of every request
+    extra_args: Vec<String>,
+    discovery_retry: DiscoveryRetry,
+    helper_checksum: Option<HelperChecksum>,
+    /// Helper kept loaded by [`Transcriber::warm_up`]
+    resident: ResidentSlot,
+    /// How long the resident helper may go unused before it is stopped
+    helper_idle_timeout: Option<Duration>,
+    /// Whether the resident helper was stopped for idling
+    reaped: Arc<AtomicBool>,
+    /// Engine the helper reported, once [`Transcriber::backend`] has asked
+    backend: OnceLock<Backend>,
+    /// Why the helper couldn't be found, for a transcriber made by `Default`
+    unavailable: Option<ScribeError>,
 }
 
-impl StreamingTranscriber {
-    /// Creates a new builder for configuring a StreamingTranscriber
+impl Transcriber {
+    /// Creates a new builder for configuring a Transcriber
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{Priority, Transcriber};
+    ///
+    /// let transcriber = Transcriber::builder()
+    ///     .with_priority(Priority::Low)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> TranscriberBuilder {
+        TranscriberBuilder::new()
+    }
+
+    /// Creates a new transcriber with default helper path
+    ///
+    /// This is a convenience method equivalent to `Transcriber::builder().build()`.
+    ///
+    /// Looks for the helper binary in the following locations (in order):
+    /// 1. `./helpers/transcribe` (local development)
+    /// 2. `~/.local/bin/transcribe` (user install)
+    /// 3. `/usr/local/bin/transcribe` (system install)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the helper binary cannot be found in any of the default locations.
+    pub fn new() -> Result<Self, ScribeError> {
+        Self::builder().build()
+    }
+
+    /// Creates a new transcriber with a custom helper binary path
+    ///
+    /// This is a convenience method equivalent to `Transcriber::builder().with_helper_path(path).build()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the transcribe helper binary
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the specified path does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    ///
+    /// let transcriber = Transcriber::with_helper_path("/custom/path/transcribe").unwrap();
+    /// ```
+    pub fn with_helper_path<P: AsRef<Path>>(path: P) -> Result<Self, ScribeError> {
+        Self::builder().with_helper_path(path).build()
+    }
+
+    /// Transcribes an audio file to text
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the audio file (supports M4A, WAV, MP3, AAC, FLAC, AIFF).
+    ///   A trailing newline or whitespace, as in a path pasted from a terminal, is
+    ///   ignored when the path doesn't exist with it.
+    ///
+    /// # Returns
+    ///
+    /// The transcribed text as a `String`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file doesn't exist
+    /// - The audio format is unsupported
+    /// - The file has no playable audio track, such as a video-only `.mov`:
+    ///   [`ScribeError::NoAudioTrack`]
+    /// - The transcription fails
+    /// - Speech recognition permissions haven't been granted
+    /// - No speech was recognized and [`EmptyOutputPolicy::AsError`] is configured:
+    ///   [`ScribeError::NoSpeechDetected`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// match transcriber.transcribe_file(Path::new("recording.m4a")) {
+    ///     Ok(text) => println!("Transcription: {}", text),
+    ///     Err(e) => eprintln!("Error: {}", e),
+    /// }
+    /// ```
+    pub fn transcribe_file(&self, path: &Path) -> Result<String, ScribeError> {
+        self.transcribe_text(path, &[])
+    }
+
+    /// Transcribes an audio file, returning the raw and the formatted transcript
+    ///
+    /// The formatted transcript is what [`transcribe_file`](Self::transcribe_file)
+    /// returns, capitalized and punctuated for display. The raw one is the same
+    /// words lowercased with the punctuation around them removed, for search
+    /// indexes and matching. The recognizers only report formatted text, so the
+    /// raw form is derived from it: numbers stay as digits and apostrophes and
+    /// hyphens inside words are kept. One recognition yields both.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`transcribe_file`](Self::transcribe_file).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let (raw, formatted) = transcriber.transcribe_file_dual(Path::new("memo.m4a")).unwrap();
+    /// println!("index: {}\nshow: {}", raw, formatted);
+    /// ```
+    pub fn transcribe_file_dual(&self, path: &Path) -> Result<(String, String), ScribeError> {
+        let formatted = self.transcribe_file(path)?;
+        Ok((raw_text(&formatted), formatted))
+    }
+
+    /// Transcribes one audio track of a multi-track file
+    ///
+    /// Files such as `.caf` or `.m4a` recordings can hold several audio tracks,
+    /// e.g. one per microphone. [`transcribe_file`](Self::transcribe_file) always
+    /// uses the first track (index 0); this selects another by its index from
+    /// [`list_tracks`](Self::list_tracks), so each speaker's isolated track can be
+    /// transcribed separately without demuxing the file first.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`transcribe_file`](Self::transcribe_file), or an
+    /// error if the file has no audio track at `track`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let path = Path::new("interview.caf");
+    /// for track in transcriber.list_tracks(path).unwrap() {
+    ///     let text = transcriber.transcribe_track(path, track.index).unwrap();
+    ///     println!("Track {}: {}", track.index, text);
+    /// }
+    /// ```
+    pub fn transcribe_track(&self, path: &Path, track: usize) -> Result<String, ScribeError> {
+        let track = track.to_string();
+        self.transcribe_text(path, &["--track", &track])
+    }
+
+    /// Transcribes a headerless PCM file
+    ///
+    /// Raw `.raw`/`.pcm` dumps from low-level capture tools have no container for
+    /// the helper to read the format from, so it is given explicitly. The helper
+    /// interprets the bytes as `format` samples at `sample_rate` Hz, interleaved
+    /// across `channels`, without any header parsing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file doesn't exist, `sample_rate` or `channels` is
+    /// zero, the file size isn't a whole number of frames
+    /// (`format.bytes_per_sample() * channels` bytes each), or the transcription
+    /// fails as for [`transcribe_file`](Self::transcribe_file).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{PcmFormat, Transcriber};
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let text = transcriber
+    ///     .transcribe_raw_file(Path::new("capture.pcm"), PcmFormat::S16Le, 48000, 2)
+    ///     .unwrap();
+    /// ```
+    pub fn transcribe_raw_file(
+        &self,
+        path: &Path,
+        format: PcmFormat,
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<String, ScribeError> {
+        if sample_rate == 0 || channels == 0 {
+            return Err(format!(
+                "Invalid raw PCM layout: {} Hz, {} channels",
+                sample_rate, channels
+            )
+            .into());
+        }
+        let path = locate_audio(path)?;
+        let size = std::fs::metadata(&path)
+            .map_err(|_| ScribeError::AudioFileMissing(path.to_path_buf()))?
+            .len();
+        let frame_size = (format.bytes_per_sample() * channels as usize) as u64;
+        if size % frame_size != 0 {
+            return Err(format!(
+                "Raw PCM file size {} is not a multiple of the {}-byte frame size",
+                size, frame_size
+            )
+            .into());
+        }
+
+        let sample_rate = sample_rate.to_string();
+        let channels = channels.to_string();
+        let text = self.transcribe_text(
+            &path,
+            &[
+                "--raw-format",
+                format.flag(),
+                "--sample-rate",
+                &sample_rate,
+                "--channels",
+                &channels,
+            ],
+        )?;
+        Ok(text)
+    }
+
+    /// Transcribes audio read from standard input
+    ///
+    /// For shell pipelines such as `curl ... | my-tool`: stdin is read to the
+    /// end, staged as a `format` file in [`temp_dir`](Self::temp_dir) that is
+    /// removed afterwards, and transcribed in one shot as with
+    /// [`transcribe_file`](Self::transcribe_file). The helper needs the whole
+    /// file to decode it, so nothing is recognized before stdin closes; for
+    /// live audio use a [`StreamingTranscriber`] with programmatic input.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if stdin can't be read or is empty, doesn't start like
+    /// a `format` file (checked for CAF, AIFF and WAV), the audio can't be
+    /// written to the temp directory, or the transcription fails as for
+    /// `transcribe_file`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{AudioFormat, Transcriber};
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let text = transcriber.transcribe_stdin(AudioFormat::Wav).unwrap();
+    /// println!("{}", text);
+    /// ```
+    pub fn transcribe_stdin(&self, format: AudioFormat) -> Result<String, ScribeError> {
+        self.transcribe_reader(std::io::stdin().lock(), format)
+    }
+
+    /// Stages everything `reader` yields as a temporary `format` file and transcribes it
+    fn transcribe_reader(
+        &self,
+        mut reader: impl Read,
+        format: AudioFormat,
+    ) -> Result<String, ScribeError> {
+        let read_error = |e: std::io::Error| format!("Failed to read audio from stdin: {}", e);
+        let mut head = Vec::with_capacity(4);
+        reader
+            .by_ref()
+            .take(4)
+            .read_to_end(&mut head)
+            .map_err(read_error)?;
+        if head.is_empty() {
+            return Err("No audio on stdin".into());
+        }
+        if format.magic().is_some_and(|magic| !head.starts_with(magic)) {
+            return Err(format!("Audio on stdin is not {}", format.name()).into());
+        }
+
+        let stage_error = |e: std::io::Error| format!("Failed to stage audio: {}", e);
+        let (staged, mut file) = self.temp_file(format.extension()).map_err(stage_error)?;
+        file.write_all(&head).map_err(stage_error)?;
+        std::io::copy(&mut reader, &mut file).map_err(stage_error)?;
+        drop(file);
+        self.transcribe_file(staged.path())
+    }
+
+    /// Transcribes encoded audio held in memory
+    ///
+    /// For audio that was downloaded or produced in memory: the helper only
+    /// reads files, so `data` is written to a `format` file in
+    /// [`temp_dir`](Self::temp_dir), transcribed as with
+    /// [`transcribe_file`](Self::transcribe_file), and removed afterwards,
+    /// whether or not the transcription succeeds. That round trip through the
+    /// disk costs a write of `data` on top of the transcription; for audio that
+    /// is already in a file, call `transcribe_file` on it directly.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `data` is empty, doesn't start like a `format` file
+    /// (checked for CAF, AIFF and WAV), can't be written to the temp
+    /// directory, or the transcription fails as for `transcribe_file`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{AudioFormat, Transcriber};
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let mp3 = std::fs::read("podcast.mp3").unwrap();
+    /// let text = transcriber.transcribe_bytes(&mp3, AudioFormat::Mp3).unwrap();
+    /// println!("{}", text);
+    /// ```
+    pub fn transcribe_bytes(
+        &self,
+        data: &[u8],
+        format: AudioFormat,
+    ) -> Result<String, ScribeError> {
+        if data.is_empty() {
+            return Err("No audio data".into());
+        }
+        if format.magic().is_some_and(|magic| !data.starts_with(magic)) {
+            return Err(format!("Audio data is not {}", format.name()).into());
+        }
+
+        let staged = self
+            .temp_file(format.extension())
+            .and_then(|(staged, mut file)| file.write_all(data).map(|()| staged))
+            .map_err(|e| format!("Failed to stage audio: {}", e))?;
+        self.transcribe_file(staged.path())
+    }
+
+    /// Transcribes a time window of an in-memory 16-bit PCM buffer
+    ///
+    /// For transcribing just the highlighted region of a recording that is
+    /// already in memory. `pcm` holds samples at `sample_rate` Hz interleaved
+    /// across `channels`; only the frames from `start` up to `end` are staged
+    /// for the helper, as a short WAV file in [`temp_dir`](Self::temp_dir) that
+    /// is removed afterwards. Times are rounded to the nearest frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `sample_rate` or `channels` is zero, `pcm` isn't a
+    /// whole number of frames, `start` isn't before `end`, `end` is past the end
+    /// of the buffer, the window can't be written to the temp directory, or the
+    /// transcription fails as for [`transcribe_file`](Self::transcribe_file).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::time::Duration;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let recording = vec![0i16; 16000 * 60];
+    /// let text = transcriber
+    ///     .transcribe_pcm_range(&recording, 16000, 1, Duration::from_secs(10), Duration::from_secs(15))
+    ///     .unwrap();
+    /// ```
+    pub fn transcribe_pcm_range(
+        &self,
+        pcm: &[i16],
+        sample_rate: u32,
+        channels: u16,
+        start: Duration,
+        end: Duration,
+    ) -> Result<String, ScribeError> {
+        if sample_rate == 0 || channels == 0 {
+            return Err(format!(
+                "Invalid PCM layout: {} Hz, {} channels",
+                sample_rate, channels
+            )
+            .into());
+        }
+        let channels_len = channels as usize;
+        if !pcm.len().is_multiple_of(channels_len) {
+            return Err(format!(
+                "PCM buffer of {} samples is not a whole number of {}-channel frames",
+                pcm.len(),
+                channels
+            )
+            .into());
+        }
+        let frames = pcm.len() / channels_len;
+        let frame_at = |time: Duration| (time.as_secs_f64() * sample_rate as f64).round() as usize;
+        let (first, last) = (frame_at(start), frame_at(end));
+        if first >= last || last > frames {
+            return Err(format!(
+                "Invalid PCM range {:?}..{:?}: buffer holds {:?} of audio",
+                start,
+                end,
+                Duration::from_secs_f64(frames as f64 / sample_rate as f64)
+            )
+            .into());
+        }
+
+        self.transcribe_samples(
+            &pcm[first * channels_len..last * channels_len],
+            sample_rate,
+            channels,
+        )
+    }
+
+    /// Stages interleaved 16-bit samples as a temporary WAV file and transcribes it
+    fn transcribe_samples(
+        &self,
+        samples: &[i16],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<String, ScribeError> {
+        let staged = self
+            .temp_file("wav")
+            .and_then(|(staged, file)| {
+                write_wav(file, samples, sample_rate, channels).map(|()| staged)
+            })
+            .map_err(|e| format!("Failed to stage audio: {}", e))?;
+        self.transcribe_file(staged.path())
+    }
+
+    /// Lists the audio tracks of a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file doesn't exist, can't be opened as media, or the
+    /// helper's output can't be parsed.
+    pub fn list_tracks(&self, path: &Path) -> Result<Vec<TrackInfo>, ScribeError> {
+        let output = self.run_helper(path, &["--list-tracks"])?;
+        serde_json::from_str(&output).map_err(|source| ScribeError::ParseResult {
+            line: output.clone(),
+            source,
+        })
+    }
+
+    /// Runs a plain-text transcription and applies the text post-processing options
+    fn transcribe_text(&self, path: &Path, args: &[&str]) -> Result<String, ScribeError> {
+        let started = Instant::now();
+        let mut text = self.run_helper(path, args)?;
+        if !self.verbatim {
+            text = disfluency::remove_disfluencies(&text);
+        }
+        if self.inverse_text_normalization {
+            text = itn::normalize(&text);
+        }
+        self.check_empty(path, &text)?;
+        telemetry::file_transcribed(started.elapsed());
+        Ok(text)
+    }
+
+    /// Transcribes an audio file and returns the text with metadata
+    ///
+    /// Like [`transcribe_file`](Self::transcribe_file), but asks the helper for a
+    /// structured result: the recognized segments with timing and confidence, and
+    /// an overall confidence combined as configured with
+    /// [`TranscriberBuilder::with_confidence_aggregation`]. With silence trimming
+    /// enabled, the result also reports how many seconds were skipped at each end.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`transcribe_file`](Self::transcribe_file), or an
+    /// error if the helper's output can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::builder().with_trim_silence(true).build().unwrap();
+    /// let result = transcriber.transcribe_file_with_metadata(Path::new("voicemail.m4a")).unwrap();
+    /// println!("{} (skipped {:?}s of leading silence)", result.text, result.trimmed_leading_secs);
+    /// ```
+    pub fn transcribe_file_with_metadata(
+        &self,
+        path: &Path,
+    ) -> Result<TranscriptionResult, ScribeError> {
+        let started = Instant::now();
+        let output = self.run_helper(path, &["--json"])?;
+        let mut result: TranscriptionResult =
+            serde_json::from_str(&output).map_err(|source| ScribeError::ParseResult {
+                line: output.clone(),
+                source,
+            })?;
+        if !self.verbatim {
+            result.text = disfluency::remove_disfluencies(&result.text);
+            for segment in &mut result.segments {
+                segment.text = disfluency::remove_disfluencies(&segment.text);
+            }
+            result.segments.retain(|segment| !segment.text.is_empty());
+        }
+        if self.inverse_text_normalization {
+            result.text = itn::normalize(&result.text);
+            result.segments = itn::normalize_segments(&result.segments);
+        }
+        result.segments = self.split_long_segments(result.segments);
+        for segment in &mut result.segments {
+            segment.script = Some(detect_script(&segment.text));
+            // Helpers that predate per-segment locales report one for the whole file
+            if segment.locale.is_none() {
+                segment.locale = result.locale.clone();
+            }
+        }
+        self.shift_timings(&mut result.segments);
+        result.speech_duration = result
+            .segments
+            .iter()
+            .map(|segment| Duration::try_from_secs_f64(segment.duration_secs).unwrap_or_default())
+            .sum();
+        result.total_duration = result
+            .duration_secs
+            .and_then(|secs| Duration::try_from_secs_f64(secs).ok());
+        self.check_empty(path, &result.text)?;
+        result.confidence = self.confidence_aggregation.aggregate(&result.segments);
+        telemetry::file_transcribed(started.elapsed());
+        Ok(result)
+    }
+
+    /// Transcribes an audio file into a [`FileTranscription`]
+    ///
+    /// Runs [`transcribe_file_with_metadata`](Self::transcribe_file_with_metadata)
+    /// and returns the transcript, timed segments, confidence, backend, locale and
+    /// audio duration as one serializable value.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as
+    /// [`transcribe_file_with_metadata`](Self::transcribe_file_with_metadata).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let transcription = transcriber.transcribe_file_json(Path::new("meeting.m4a")).unwrap();
+    /// println!("{}", serde_json::to_string(&transcription).unwrap());
+    /// ```
+    pub fn transcribe_file_json(&self, path: &Path) -> Result<FileTranscription, ScribeError> {
+        let result = self.transcribe_file_with_metadata(path)?;
+        Ok(FileTranscription {
+            full_text: result.text,
+            segments: result.segments,
+            confidence: result.confidence,
+            backend: result.backend,
+            locale: result.locale,
+            duration_secs: result.duration_secs,
+        })
+    }
+
+    /// The candidate locales, in order
+    pub(crate) fn candidate_locales(&self) -> Vec<String> {
+        self.candidate_locales
+            .split(',')
+            .filter(|locale| !locale.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Applies the timestamp offset to the helper's segment timings
+    fn shift_timings(&self, segments: &mut [TranscriptionSegment]) {
+        if self.timestamp_offset_secs == 0.0 {
+            return;
+        }
+        for segment in segments {
+            segment.start_secs = (segment.start_secs + self.timestamp_offset_secs).max(0.0);
+            for word in segment.words.iter_mut().flatten() {
+                word.start = (word.start + self.timestamp_offset_secs).max(0.0);
+                word.end = (word.end + self.timestamp_offset_secs).max(0.0);
+            }
+        }
+    }
+
+    /// Splits segments longer than the configured maximum duration
+    fn split_long_segments(
+        &self,
+        segments: Vec<TranscriptionSegment>,
+    ) -> Vec<TranscriptionSegment> {
+        match self.max_segment_duration {
+            Some(max) => split::split_long(segments, max),
+            None => segments,
+        }
+    }
+
+    /// Applies the empty output policy to a transcript of `path`
+    fn check_empty(&self, path: &Path, text: &str) -> Result<(), ScribeError> {
+        if self.empty_output == EmptyOutputPolicy::AsError && text.trim().is_empty() {
+            return Err(ScribeError::NoSpeechDetected(path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    /// Runs the helper on `path` with `args` ahead of it and returns its trimmed stdout
+    fn run_helper(&self, path: &Path, args: &[&str]) -> Result<String, ScribeError> {
+        let path = locate_audio(path)?;
+        let path_str = path.to_str().ok_or(ScribeError::InvalidUtf8Path)?;
+
+        let request = self.request(path_str, args);
+
+        // A resident helper runs the same request, so errors quote the one-shot
+        // command line that reproduces it either way
+        let mut cmd = self.command()?;
+        cmd.args(&request);
+
+        if let Some(result) = self.run_resident(&request) {
+            return result
+                .map(|output| output.trim().to_string())
+                .map_err(|e| helper_failure(&path, &e, &cmd));
+        }
+
+        let output = cmd.output().map_err(|e| {
+            telemetry::helper_spawn_failed();
+            ScribeError::HelperSpawn {
+                command: command_line(&cmd),
+                source: e,
+            }
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(helper_failure(&path, &stderr, &cmd));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout.trim().to_string())
+    }
+
+    /// The helper arguments that run `args` on the audio at `path` as configured
+    fn request<'a>(&'a self, path: &'a str, args: &[&'a str]) -> Vec<&'a str> {
+        let mut request = Vec::with_capacity(args.len() + 6);
+        if self.trim_silence {
+            request.push("--trim-silence");
+        }
+        if let Some(locale) = &self.locale {
+            request.extend(["--locale", locale.as_str()]);
+        }
+        if !self.candidate_locales.is_empty() {
+            request.extend(["--locales", self.candidate_locales.as_str()]);
+        }
+        if let Some(hint) = self.task_hint.flag() {
+            request.extend(["--task-hint", hint]);
+        }
+        request.extend_from_slice(args);
+        request.extend(self.extra_args.iter().map(String::as_str));
+        request.push(path);
+        request
+    }
+
+    /// Sends a request to the resident helper, if one is loaded and idle
+    ///
+    /// Returns `None` when the request should go to a freshly spawned helper
+    /// instead: nothing is resident, it is busy with another thread's request, or
+    /// it has died (in which case it is discarded).
+    fn run_resident(&self, request: &[&str]) -> Option<Result<String, String>> {
+        let mut resident = match self.resident.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return None,
+        };
+        if resident.is_none() && self.reaped.swap(false, Ordering::Relaxed) {
+            if let Err(e) = self.load_resident(&mut resident) {
+                log::warn!("Failed to reload resident helper: {}", e);
+            }
+        }
+        match resident.as_mut()?.request(request) {
+            Ok(result) => Some(result),
+            Err(_) => {
+                *resident = None;
+                None
+            }
+        }
+    }
+
+    /// Builds a helper command with the configured environment and priority
+    ///
+    /// Fails with the discovery error for a transcriber whose helper wasn't found,
+    /// and for a helper that doesn't match its pinned checksum.
+    fn command(&self) -> Result<Command, ScribeError> {
+        if let Some(error) = &self.unavailable {
+            return Err(error.replay());
+        }
+        if let Some(checksum) = &self.helper_checksum {
+            checksum.verify(&self.helper_path)?;
+        }
+        let mut cmd = Command::new(&self.helper_path);
+        if let Some(dir) = &self.temp_dir {
+            cmd.env("TMPDIR", dir);
+        }
+        apply_priority(&mut cmd, self.priority);
+        Ok(cmd)
+    }
+
+    /// Starts the helper ahead of time and keeps it loaded for later calls
+    ///
+    /// The first transcription otherwise pays for process startup and model
+    /// loading. Calling this during app launch spawns the helper in a resident
+    /// mode, waits until its model is ready, and routes subsequent
+    /// [`transcribe_file`](Self::transcribe_file) calls through it. Calls made
+    /// while the resident helper is busy fall back to a one-shot helper, and a
+    /// resident helper that dies, or takes over an hour on one file, is stopped
+    /// and replaced by one-shot helpers transparently.
+    ///
+    /// Calling this again while a helper is resident does nothing. The helper is
+    /// stopped when the `Transcriber` is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the helper can't be spawned or fails to load its model,
+    /// quoting the end of its stderr, or if loading takes over two minutes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// transcriber.warm_up().unwrap();
+    /// // No model-load pause here
+    /// let text = transcriber.transcribe_file(Path::new("note.m4a")).unwrap();
+    /// ```
+    pub fn warm_up(&self) -> Result<(), ScribeError> {
+        let mut resident = self.resident.lock().unwrap_or_else(|e| e.into_inner());
+        if resident.is_none() {
+            self.load_resident(&mut resident)?;
+        }
+        Ok(())
+    }
+
+    /// How long the resident helper has gone without transcribing a file
+    ///
+    /// `None` when no helper is resident: before [`warm_up`](Self::warm_up),
+    /// after it died, or after
+    /// [`with_helper_idle_timeout`](TranscriberBuilder::with_helper_idle_timeout)
+    /// stopped it. Zero while it is transcribing.
+    pub fn helper_idle_time(&self) -> Option<Duration> {
+        let resident = match self.resident.try_lock() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return Some(Duration::ZERO),
+        };
+        resident.as_ref().map(ResidentHelper::idle_time)
+    }
+
+    /// Spawns the resident helper into the locked `slot`, with its idle timer
+    fn load_resident(&self, slot: &mut Option<ResidentHelper>) -> Result<(), ScribeError> {
+        let mut cmd = self.command()?;
+        // The model it loads is for the configured locale
+        if let Some(locale) = &self.locale {
+            cmd.args(["--locale", locale]);
+        }
+        if !self.candidate_locales.is_empty() {
+            cmd.args(["--locales", &self.candidate_locales]);
+        }
+        let helper = ResidentHelper::spawn(cmd)?;
+        if let Some(timeout) = self.helper_idle_timeout {
+            resident::reap_when_idle(&self.resident, helper.id(), &self.reaped, timeout);
+        }
+        *slot = Some(helper);
+        Ok(())
+    }
+
+    /// Checks whether recognition assets for `locale` are installed, optionally
+    /// downloading them
+    ///
+    /// On-device recognition needs per-locale assets that macOS may have to
+    /// download before first use; without them the first transcription stalls or
+    /// fails offline. `locale` is an identifier such as `"en-US"` or `"fr-FR"`.
+    /// With `download` set, missing assets for a supported locale are downloaded
+    /// and installed before this returns [`LocaleStatus::Installed`]. Installer
+    /// flows can use this to pre-provision the user's language.
+    ///
+    /// Downloads require macOS 26; on older systems the status is only reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the helper can't be executed, the download fails, or the
+    /// helper's output can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{LocaleStatus, Transcriber};
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// match transcriber.ensure_locale_available("de-DE", true).unwrap() {
+    ///     LocaleStatus::Installed => println!("Ready for offline use"),
+    ///     LocaleStatus::NotInstalled => println!("Assets will download on first use"),
+    ///     LocaleStatus::Unsupported => println!("German isn't supported here"),
+    /// }
+    /// ```
+    pub fn ensure_locale_available(
+        &self,
+        locale: &str,
+        download: bool,
+    ) -> Result<LocaleStatus, ScribeError> {
+        self.ensure_locale_available_with_progress(locale, download, |_| {})
+    }
+
+    /// Like [`ensure_locale_available`](Self::ensure_locale_available), reporting
+    /// download progress
+    ///
+    /// `on_progress` is called with the completed fraction (0.0-1.0) periodically
+    /// while assets download. It isn't called when nothing needs downloading.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// transcriber
+    ///     .ensure_locale_available_with_progress("ja-JP", true, |fraction| {
+    ///         println!("Downloading speech model: {:.0}%", fraction * 100.0);
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn ensure_locale_available_with_progress<F: FnMut(f64)>(
+        &self,
+        locale: &str,
+        download: bool,
+        mut on_progress: F,
+    ) -> Result<LocaleStatus, ScribeError> {
+        let mut cmd = self.command()?;
+        cmd.args(["--locale-status", locale]);
+        if download {
+            cmd.arg("--download");
+        }
+
+        let mut child = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                telemetry::helper_spawn_failed();
+                ScribeError::HelperSpawn {
+                    command: command_line(&cmd),
+                    source: e,
+                }
+            })?;
+        // Drained as it's written, so a chatty download can't fill the pipe and stall
+        let mut stderr = child
+            .stderr
+            .take()
+            .map(|err| StderrTail::capture(err, false));
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let read = read_locale_lines(stdout, &mut on_progress);
+        if read.is_err() {
+            let _ = child.kill();
+        }
+        let exit = child
+            .wait()
+            .map_err(|e| format!("Failed to wait for helper: {}", e))?;
+        let stderr = stderr.as_mut().map(StderrTail::finish).unwrap_or_default();
+        let status = read?;
+        if !exit.success() {
+            return Err(format!(
+                "Locale check failed: {} (command: {})",
+                stderr.trim(),
+                command_line(&cmd)
+            )
+            .into());
+        }
+        status.ok_or_else(|| format!("Helper reported no status for locale {}", locale).into())
+    }
+
+    /// Returns the recognition engine the helper uses on this system
+    ///
+    /// The helper picks `SpeechAnalyzer` on macOS 26 and later and
+    /// `SFSpeechRecognizer` before that, which differ in accuracy and latency.
+    /// The first call asks the helper; later calls return the cached answer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the helper can't be executed, fails, or reports a
+    /// backend that can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{Backend, Transcriber};
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// if transcriber.backend().unwrap() == Backend::SfSpeechRecognizer {
+    ///     eprintln!("Using the legacy recognizer; expect lower accuracy");
+    /// }
+    /// ```
+    pub fn backend(&self) -> Result<Backend, ScribeError> {
+        if let Some(backend) = self.backend.get() {
+            return Ok(*backend);
+        }
+        let mut cmd = self.command()?;
+        cmd.arg("--backend-info");
+        let output = cmd.output().map_err(|e| {
+            telemetry::helper_spawn_failed();
+            ScribeError::HelperSpawn {
+                command: command_line(&cmd),
+                source: e,
+            }
+        })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "Helper failed: {} (command: {})",
+                stderr.trim(),
+                command_line(&cmd)
+            )
+            .into());
+        }
+        let line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let info = serde_json::from_str::<BackendInfo>(&line)
+            .map_err(|source| ScribeError::ParseResult { line, source })?;
+        if let Some(os_version) = &info.os_version {
+            log::debug!("Helper uses {:?} on macOS {}", info.backend, os_version);
+        }
+        Ok(*self.backend.get_or_init(|| info.backend))
+    }
+
+    /// Returns the path to the helper binary being used
+    pub fn helper_path(&self) -> &Path {
+        &self.helper_path
+    }
+
+    /// Estimates the real-time factor of file transcription on this machine
+    ///
+    /// Runs the helper on a short synthesized reference clip and returns audio
+    /// seconds processed per wall-clock second. A value below `1.0` means
+    /// transcription is slower than real time, so live transcription is likely to
+    /// lag.
+    ///
+    /// The clip is generated rather than bundled, to keep recorded audio out of
+    /// the crate: a voiced buzz with a syllable rhythm, which the recognizer
+    /// analyzes like speech even though it holds no words. Only the helper run is
+    /// timed; the transcript is discarded without post-processing, so the
+    /// [`EmptyOutputPolicy`] doesn't apply.
+    ///
+    /// The measurement includes helper startup and model loading, so it is a
+    /// conservative, lightweight capability probe rather than a full benchmark.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference clip can't be written to the temp
+    /// directory or the transcription fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// if transcriber.quick_benchmark().unwrap() < 1.0 {
+    ///     eprintln!("Warning: live transcription may lag on this machine");
+    /// }
+    /// ```
+    pub fn quick_benchmark(&self) -> Result<f32, ScribeError> {
+        let samples = reference_clip();
+        let audio_secs = samples.len() as f32 / TARGET_SAMPLE_RATE as f32;
+
+        let clip = self
+            .temp_file("wav")
+            .and_then(|(clip, file)| {
+                write_wav(file, &samples, TARGET_SAMPLE_RATE, 1).map(|()| clip)
+            })
+            .map_err(|e| format!("Failed to write reference clip: {}", e))?;
+
+        // Only recognition is timed: the transcript is thrown away, so neither
+        // post-processing nor the empty output policy applies
+        let start = Instant::now();
+        self.run_helper(clip.path(), &[])?;
+        Ok(audio_secs / start.elapsed().as_secs_f32())
+    }
+
+    /// Creates a new temp file, named as configured, that is removed when dropped
+    ///
+    /// The file is created exclusively, so a file or symlink already at the name
+    /// is never written through; the next name is tried instead.
+    fn temp_file(&self, extension: &str) -> std::io::Result<(TempFile, std::fs::File)> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let mut attempts = 0;
+        loop {
+            let name = match &self.temp_naming {
+                TempNaming::Unique => format!(
+                    "swift-scribe-{}-{}.{}",
+                    std::process::id(),
+                    COUNTER.fetch_add(1, Ordering::Relaxed),
+                    extension
+                ),
+                TempNaming::Sequential { prefix } => format!(
+                    "{}-{}.{}",
+                    prefix,
+                    self.temp_counter.fetch_add(1, Ordering::Relaxed),
+                    extension
+                ),
+            };
+            let path = self.temp_dir().join(name);
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(file) => return Ok((TempFile(path), file)),
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::AlreadyExists
+                        && attempts < TEMP_FILE_ATTEMPTS =>
+                {
+                    attempts += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Returns the directory temporary files are written to
+    ///
+    /// This is the directory set with [`TranscriberBuilder::with_temp_dir`], or the
+    /// system temp directory if none was configured.
+    pub fn temp_dir(&self) -> PathBuf {
+        self.temp_dir.clone().unwrap_or_else(std::env::temp_dir)
+    }
+}
+
+/// How the file helper starts the error for a file without a playable audio track
+const NO_AUDIO_TRACK: &str = "Error: No audio track";
+
+/// The error for a file helper that failed on `path`, given its stderr
+///
+/// Files without audio get their own variant, so batches can skip them.
+fn helper_failure(path: &Path, stderr: &str, cmd: &Command) -> ScribeError {
+    if stderr.lines().any(|line| line.starts_with(NO_AUDIO_TRACK)) {
+        return ScribeError::NoAudioTrack(path.to_path_buf());
+    }
+    ScribeError::Other(format!(
+        "Transcription failed: {} (command: {})",
+        stderr.trim(),
+        command_line(cmd)
+    ))
+}
+
+/// Creates a transcriber with default settings without panicking
+///
+/// Unlike [`Transcriber::new`], this can't report a missing helper binary, so the
+/// discovery error is kept and returned by the first call that needs the helper
+/// (`transcribe_file`, `warm_up`, and so on). [`Transcriber::helper_path`] is
+/// empty in that case. Prefer `new` or the builder where the error can be
+/// handled up front.
+impl Default for Transcriber {
+    fn default() -> Self {
+        Self::new().unwrap_or_else(Self::unavailable)
+    }
+}
+
+impl Transcriber {
+    /// A default-configured transcriber that fails every helper call with `error`
+    fn unavailable(error: ScribeError) -> Self {
+        let builder = TranscriberBuilder::new();
+        Transcriber {
+            helper_path: PathBuf::new(),
+            priority: builder.priority,
+            temp_dir: builder.temp_dir,
+            temp_naming: builder.temp_naming,
+            temp_counter: AtomicUsize::new(0),
+            trim_silence: builder.trim_silence,
+            confidence_aggregation: builder.confidence_aggregation,
+            empty_output: builder.empty_output,
+            verbatim: builder.verbatim,
+            inverse_text_normalization: builder.inverse_text_normalization,
+            task_hint: builder.task_hint,
+            timestamp_offset_secs: builder.timestamp_offset_secs,
+            max_segment_duration: builder.max_segment_duration,
+            line_ending: builder.line_ending,
+            bom: builder.bom,
+            locale: None,
+            candidate_locales: String::new(),
+            extra_args: builder.extra_args,
+            helper_idle_timeout: builder.helper_idle_timeout,
+            discovery_retry: builder.discovery_retry,
+            helper_checksum: None,
+            resident: Arc::new(Mutex::new(None)),
+            reaped: Arc::new(AtomicBool::new(false)),
+            backend: OnceLock::new(),
+            unavailable: Some(error),
+        }
+    }
+}
+
+/// Lowercases `text` and strips the punctuation around its words
+///
+/// Punctuation inside a word ("don't", "3.5") is kept.
+fn raw_text(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Names tried for a temp file before giving up on finding a free one
+const TEMP_FILE_ATTEMPTS: usize = 100;
+
+/// Temporary file that is deleted when the guard goes out of scope
+///
+/// Covers both success and error paths of operations that stage audio on disk.
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// What to do with a chunk of fed audio, as decided by `prepare_pcm`
+enum PcmFeed<'a> {
+    /// Nothing to write; return the warning to the caller
+    Done(Option<BackpressureWarning>),
+    /// Write `pcm` (16kHz mono) to the helper, then record `fed` and return `warning`
+    Write {
+        pcm: Cow<'a, [i16]>,
+        fed: u64,
+        warning: Option<BackpressureWarning>,
+    },
+}
+
+/// Writes 16-bit PCM samples to `writer` as a WAV file
+fn write_wav<W: Write>(
+    writer: W,
+    samples: &[i16],
+    sample_rate: u32,
+    channels: u16,
+) -> std::io::Result<()> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut file = std::io::BufWriter::new(writer);
+    write_wav_header(&mut file, data_len, sample_rate, channels)?;
+    for sample in samples {
+        file.write_all(&sample.to_le_bytes())?;
+    }
+    file.flush()
+}
+
+/// Writes the 44-byte header of a 16-bit PCM WAV file with `data_len` bytes of samples
+fn write_wav_header<W: Write>(
+    file: &mut W,
+    data_len: u32,
+    sample_rate: u32,
+    channels: u16,
+) -> std::io::Result<()> {
+    let block_align = channels * 2;
+    file.write_all(b"RIFF")?;
+    file.write_all(&(36 + data_len).to_le_bytes())?;
+    file.write_all(b"WAVEfmt ")?;
+    file.write_all(&16u32.to_le_bytes())?;
+    file.write_all(&1u16.to_le_bytes())?; // PCM
+    file.write_all(&channels.to_le_bytes())?;
+    file.write_all(&sample_rate.to_le_bytes())?;
+    file.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+    file.write_all(&block_align.to_le_bytes())?;
+    file.write_all(&16u16.to_le_bytes())?;
+    file.write_all(b"data")?;
+    file.write_all(&data_len.to_le_bytes())
+}
+
+/// Synthesizes a short speech-like reference clip (16kHz mono)
+///
+/// A 140Hz voiced tone with harmonics, amplitude-modulated at a syllable-like
+/// 4Hz rate, so the recognizer analyzes it much like real speech.
+fn reference_clip() -> Vec<i16> {
+    const SECONDS: usize = 3;
+    let rate = TARGET_SAMPLE_RATE as f32;
+
+    (0..SECONDS * TARGET_SAMPLE_RATE as usize)
+        .map(|i| {
+            let t = i as f32 / rate;
+            let voiced: f32 = (1..=5)
+                .map(|h| (2.0 * std::f32::consts::PI * 140.0 * h as f32 * t).sin() / h as f32)
+                .sum();
+            let envelope = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * 4.0 * t).cos();
+            (voiced * envelope * 6000.0) as i16
+        })
+        .collect()
+}
+
+/// Result from streaming transcription with real-time updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingResult {
+    /// The transcribed text
+    pub text: String,
+    /// Whether this is a final result (true) or volatile/partial (false)
+    #[serde(rename = "isFinal")]
+    pub is_final: bool,
+    /// How settled the result is; see [`ResultKind`]
+    ///
+    /// Helpers that don't report it get [`ResultKind::Final`] or
+    /// [`ResultKind::Volatile`] according to `is_final`.
+    #[serde(default)]
+    pub kind: ResultKind,
+    /// Unix timestamp when the result was generated
+    pub timestamp: f64,
+    /// Unix timestamp of the earliest recognizer result merged into this one
+    ///
+    /// Only set when several results are combined, e.g. by sentence segmentation;
+    /// `timestamp` is then the time the last of them arrived.
+    #[serde(
+        rename = "startTimestamp",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub start_timestamp: Option<f64>,
+    /// Other readings of a final result, most likely first
+    ///
+    /// Only filled in on finals, and only when requested with
+    /// [`StreamingTranscriberBuilder::with_alternatives_stream`]; `text` stays
+    /// the recognizer's best guess and isn't repeated here.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternatives: Vec<String>,
+    /// Whether this result rewrites the previous partial instead of extending it
+    ///
+    /// Set by [`StreamingTranscriber::poll_result`] when the previous partial
+    /// returned since the last final isn't a prefix of this result's text
+    /// (ignoring surrounding whitespace). A display can then append to what it
+    /// shows when this is `false` and redraw the line when it's `true`. Always
+    /// `false` for the first result after a final.
+    #[serde(rename = "isRevision", default)]
+    pub is_revision: bool,
+    /// Leading part of a partial's `text` that the recognizer has settled on
+    ///
+    /// Only reported when requested with
+    /// [`StreamingTranscriberBuilder::with_volatile_tail`]`(false)`; the rest of
+    /// `text` is the volatile tail. `None` on finals, which are settled as a
+    /// whole.
+    #[serde(
+        rename = "stableText",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub stable_text: Option<String>,
+    /// Timing of each word of `text`, for aligning captions
+    ///
+    /// `None` from helpers that don't report word timings. With
+    /// [`StreamingTranscriberBuilder::with_sentence_segmentation`], only
+    /// results that are a whole recognizer result keep them, and partials
+    /// trimmed to their settled text keep the timings of the settled words.
+    /// Text carried over with
+    /// [`StreamingTranscriberBuilder::with_final_carryover`] has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub words: Option<Vec<WordTiming>>,
+}
+
+/// Timing of one word of a [`StreamingResult`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    /// The word as recognized
+    pub text: String,
+    /// Offset of the word's start from the start of the session's audio, in seconds
+    pub start: f64,
+    /// Offset of the word's end from the start of the session's audio, in seconds
+    pub end: f64,
+    /// Recognizer confidence (0.0-1.0), when reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// Stability of a [`StreamingResult`]
+///
+/// A final segment is done, but the recognizer may still reinterpret it once
+/// later audio arrives. It is committed when the analyzer's finalization point
+/// has moved past it, after which it will never be revised.
+///
+/// The kind is a snapshot taken when the result is reported: a `Final` result
+/// isn't reported again once it becomes committed. Since the finalization
+/// point only moves forward, a `Committed` result also commits every final
+/// reported before it, and every final is settled once the session ends. An
+/// append-only consumer should therefore hold `Final` results and persist them
+/// when the next `Committed` result arrives or the session ends, rather than
+/// persist only the results marked `Committed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultKind {
+    /// A partial hypothesis that will be replaced (default)
+    #[default]
+    Volatile,
+    /// A finished segment that may still be revised, as of when it was reported
+    Final,
+    /// A stable checkpoint the recognizer won't revise, nor any final before it
+    Committed,
+    /// The speaker stopped talking; carries no text and isn't final
+    ///
+    /// Reported when the SpeechAnalyzer backend detects the end of a speech
+    /// region, usually just before the final result for that speech arrives, so
+    /// a voice UI can stop listening and act at once. Never held back by
+    /// [`StreamingTranscriberBuilder::with_stability`] or the partial rate, and
+    /// ignored by [`StreamingTranscriber::poll_delta`]. The legacy
+    /// SFSpeechRecognizer backend doesn't report it.
+    EndOfSpeech,
+}
+
+/// Audio input mode for streaming transcription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioInputMode {
+    /// Capture audio from the microphone (default)
+    #[default]
+    Microphone,
+    /// Accept audio programmatically via feed_audio methods
+    Programmatic,
+}
+
+/// What to do when the sample rate or channel count changes between feed_audio calls
+///
+/// The helper always receives 16kHz mono, so a change such as a device switch from
+/// 48kHz stereo to 44.1kHz mono can be absorbed by restarting the resampler.
+/// Callers that would rather treat it as a fault can opt into an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatChangePolicy {
+    /// Reset resampler state and continue at the new format (default)
+    #[default]
+    Adapt,
+    /// Reject the chunk with a "Input format changed" error
+    Error,
+}
+
+/// How the channels of fed multi-channel audio are mixed down to mono
+///
+/// The helper always receives mono. Averaging suits most sources, but a
+/// silent or noisy channel, such as an unused input of an audio interface,
+/// drags the average down or adds its noise; picking or weighting channels
+/// keeps it out. Mono audio is passed through as is, whatever the mix.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelMix {
+    /// Average all channels equally (default)
+    #[default]
+    Average,
+    /// Keep only the first channel
+    Left,
+    /// Keep only the second channel
+    Right,
+    /// Sum the channels scaled by one weight each, in channel order
+    ///
+    /// Fed audio must have as many channels as there are weights.
+    Weighted(Vec<f32>),
+}
+
+impl ChannelMix {
+    /// Checks that the mix can be applied to audio with `channels` channels
+    fn check(&self, channels: usize) -> Result<(), ScribeError> {
+        match self {
+            ChannelMix::Weighted(weights) if channels > 1 && weights.len() != channels => {
+                Err(ScribeError::InvalidChannelMix(format!(
+                    "Channel mix has {} weights, but the audio has {} channels",
+                    weights.len(),
+                    channels
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Weight of each channel in the mono mix of audio with `channels` channels
+    fn weights(&self, channels: usize) -> Cow<'_, [f32]> {
+        if channels <= 1 {
+            return Cow::Owned(vec![1.0; channels]);
+        }
+        let only = |channel: usize| {
+            (0..channels)
+                .map(|ch| (ch == channel) as u8 as f32)
+                .collect()
+        };
+        match self {
+            ChannelMix::Average => Cow::Owned(vec![1.0 / channels as f32; channels]),
+            ChannelMix::Left => Cow::Owned(only(0)),
+            ChannelMix::Right => Cow::Owned(only(1)),
+            ChannelMix::Weighted(weights) => Cow::Borrowed(weights),
+        }
+    }
+}
+
+/// What [`StreamingTranscriber::start`] does when a session is already running
+///
+/// Starting again without [`stop`](StreamingTranscriber::stop) is usually a
+/// lifecycle bug in the caller, but it is also a convenient way to recover after
+/// the helper exits. Either way the old helper is never left running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RestartPolicy {
+    /// Stop the running session, then start a new one (default)
+    #[default]
+    Restart,
+    /// Fail with an "Already started" error and leave the session running
+    Error,
+}
+
+/// Where the streaming helper's stderr goes
+///
+/// See [`StreamingTranscriberBuilder::with_stderr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StderrMode {
+    /// Shown on this process's stderr, as for a command-line tool (default)
+    #[default]
+    Inherit,
+    /// Discarded
+    Null,
+    /// Kept for [`StreamingTranscriber::helper_stderr`] instead of being shown
+    Capture,
+}
+
+/// What `feed_audio_*` does with a chunk once the helper's backlog exceeds the limit
+///
+/// See [`StreamingTranscriberBuilder::with_backlog_limit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackpressurePolicy {
+    /// Write the chunk anyway and return a warning (default)
+    ///
+    /// Once the pipe to the helper fills up, the write blocks until the helper
+    /// catches up.
+    #[default]
+    Block,
+    /// Reject the chunk with a "Helper backlog" error
+    Error,
+    /// Discard the chunk and return a warning with `dropped` set
+    Drop,
+}
+
+/// Non-fatal notice from `feed_audio_*` that audio is arriving faster than the
+/// helper consumes it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackpressureWarning {
+    /// How far the fed audio is ahead of the helper's results
+    pub backlog: Duration,
+    /// Whether the chunk was discarded ([`BackpressurePolicy::Drop`])
+    pub dropped: bool,
+}
+
+/// Backlog above which `feed_audio_*` reports backpressure by default
+pub(crate) const DEFAULT_BACKLOG_LIMIT: Duration = Duration::from_secs(10);
+
+/// Microphone tap buffer sizes accepted by
+/// [`StreamingTranscriberBuilder::with_mic_buffer_frames`]
+const MIC_BUFFER_FRAMES: std::ops::RangeInclusive<u32> = 256..=16384;
+
+/// Container of encoded audio passed to [`StreamingTranscriber::feed_encoded`],
+/// [`Transcriber::transcribe_stdin`] or [`Transcriber::transcribe_bytes`]
+///
+/// Only CAF, AIFF and WAV can be decoded on the fly; `feed_encoded` rejects
+/// the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AudioFormat {
+    /// Core Audio Format (`.caf`)
+    Caf,
+    /// Audio Interchange File Format (`.aif`, `.aiff`)
+    Aiff,
+    /// RIFF WAVE (`.wav`)
+    Wav,
+    /// MPEG-4 audio (`.m4a`)
+    M4a,
+    /// MPEG audio layer III (`.mp3`)
+    Mp3,
+    /// Free Lossless Audio Codec (`.flac`)
+    Flac,
+    /// Raw AAC in ADTS frames (`.aac`)
+    Aac,
+}
+
+impl AudioFormat {
+    /// Leading bytes of a stream in this container, for the streamable formats
+    fn magic(&self) -> Option<&'static [u8; 4]> {
+        match self {
+            AudioFormat::Caf => Some(b"caff"),
+            AudioFormat::Aiff => Some(b"FORM"),
+            AudioFormat::Wav => Some(b"RIFF"),
+            AudioFormat::M4a | AudioFormat::Mp3 | AudioFormat::Flac | AudioFormat::Aac => None,
+        }
+    }
+
+    /// The streamable format whose header `head` starts with
+    fn from_magic(head: &[u8]) -> Option<Self> {
+        [AudioFormat::Caf, AudioFormat::Aiff, AudioFormat::Wav]
+            .into_iter()
+            .find(|format| format.magic().is_some_and(|magic| head.starts_with(magic)))
+    }
+
+    /// File extension the helper recognizes the container by
+    fn extension(&self) -> &'static str {
+        match self {
+            AudioFormat::Caf => "caf",
+            AudioFormat::Aiff => "aiff",
+            AudioFormat::Wav => "wav",
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Aac => "aac",
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            AudioFormat::Caf => "CAF",
+            AudioFormat::Aiff => "AIFF",
+            AudioFormat::Wav => "WAV",
+            AudioFormat::M4a => "M4A",
+            AudioFormat::Mp3 => "MP3",
+            AudioFormat::Flac => "FLAC",
+            AudioFormat::Aac => "AAC",
+        }
+    }
+}
+
+/// Builder for StreamingTranscriber with flexible configuration
+pub struct StreamingTranscriberBuilder {
+    helper_path: Option<PathBuf>,
+    input_mode: AudioInputMode,
+    declared_format: Option<(u32, u16)>,
+    priority: Priority,
+    format_change: FormatChangePolicy,
+    channel_mix: ChannelMix,
+    restart: RestartPolicy,
+    sentence_segmentation: bool,
+    partial_rate: Option<f32>,
+    stability: StabilityMode,
+    audio_passthrough: bool,
+    mic_buffer_frames: Option<u32>,
+    backpressure: BackpressurePolicy,
+    backlog_limit: Duration,
+    skip_silence: bool,
+    capture_fed_audio: Option<PathBuf>,
+    final_carryover: bool,
+    volatile_tail: bool,
+    max_duration: Option<Duration>,
+    session_deadline: Option<Instant>,
+    stderr: StderrMode,
+    locale: Option<String>,
+    task_hint: TaskHint,
+    alternatives_stream: bool,
+    extra_args: Vec<String>,
+    resample_quality: ResampleQuality,
+    strict_parsing: bool,
+    discovery_retry: DiscoveryRetry,
+    expected_helper_sha256: Option<String>,
+    socket_path: Option<PathBuf>,
+}
+
+impl StreamingTranscriberBuilder {
+    /// Creates a new builder with default settings (microphone input)
+    pub fn new() -> Self {
+        Self {
+            helper_path: None,
+            input_mode: AudioInputMode::Microphone,
+            declared_format: None,
+            priority: Priority::Normal,
+            format_change: FormatChangePolicy::Adapt,
+            channel_mix: ChannelMix::Average,
+            restart: RestartPolicy::Restart,
+            sentence_segmentation: false,
+            partial_rate: None,
+            stability: StabilityMode::Responsive,
+            audio_passthrough: false,
+            mic_buffer_frames: None,
+            backpressure: BackpressurePolicy::Block,
+            backlog_limit: DEFAULT_BACKLOG_LIMIT,
+            skip_silence: false,
+            capture_fed_audio: None,
+            final_carryover: false,
+            volatile_tail: true,
+            max_duration: None,
+            session_deadline: None,
+            stderr: StderrMode::Inherit,
+            locale: None,
+            task_hint: TaskHint::Unspecified,
+            alternatives_stream: false,
+            extra_args: Vec::new(),
+            resample_quality: ResampleQuality::default(),
+            strict_parsing: false,
+            discovery_retry: DiscoveryRetry::default(),
+            expected_helper_sha256: None,
+            socket_path: None,
+        }
+    }
+
+    /// Set the input mode to microphone (default)
+    pub fn with_microphone(mut self) -> Self {
+        self.input_mode = AudioInputMode::Microphone;
+        self
+    }
+
+    /// Set the input mode to programmatic (feed audio via API)
+    pub fn with_programmatic_input(mut self) -> Self {
+        self.input_mode = AudioInputMode::Programmatic;
+        self
+    }
+
+    /// Declare the sample rate and channel count of fed audio up front (default: none)
+    ///
+    /// For sources whose format never changes, such as a fixed capture device.
+    /// [`StreamingTranscriber::feed_audio_i16_fmt`] and
+    /// [`feed_audio_f32_fmt`](StreamingTranscriber::feed_audio_f32_fmt) then
+    /// take just the samples, so every chunk is fed with the same format. The
+    /// explicit `feed_audio_*` methods still accept any format. Requires
+    /// programmatic input.
+    pub fn with_input_format(mut self, sample_rate: u32, channels: u16) -> Self {
+        self.declared_format = Some((sample_rate, channels));
+        self
+    }
+
+    /// Set a custom path to the helper binary
+    pub fn with_helper_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.helper_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Connect to a helper already listening on the Unix socket at `path`
+    /// instead of spawning one (default: spawn)
+    ///
+    /// See [`StreamingTranscriber::connect_socket`], which is this with
+    /// programmatic input, started right away. Each
+    /// [`start`](StreamingTranscriber::start) connects, and the helper path and
+    /// discovery are ignored. Options that configure the helper process, such
+    /// as the locale, have no effect. [`build`](Self::build) fails unless input
+    /// is programmatic.
+    pub fn with_socket<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.socket_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Refuse to run a helper whose SHA-256 differs from `hex` (default: unchecked)
+    ///
+    /// See [`TranscriberBuilder::with_expected_helper_sha256`]. The helper is
+    /// hashed when a session or warm-up first spawns it. Doesn't apply to
+    /// [`StreamingTranscriber::connect_socket`] connections.
+    pub fn with_expected_helper_sha256(mut self, hex: impl Into<String>) -> Self {
+        self.expected_helper_sha256 = Some(hex.into());
+        self
+    }
+
+    /// Scan for the helper up to `attempts` times, `delay` apart (default: once)
+    ///
+    /// See [`TranscriberBuilder::with_discovery_retry`].
+    pub fn with_discovery_retry(mut self, attempts: u32, delay: Duration) -> Self {
+        self.discovery_retry = DiscoveryRetry { attempts, delay };
+        self
+    }
+
+    /// Set the scheduling priority of the spawned helper (default: `Normal`)
+    ///
+    /// `Priority::Low` keeps foreground apps responsive during background
+    /// transcription at the cost of slower results.
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set how a mid-session change of input format is handled (default: `Adapt`)
+    pub fn with_format_change_policy(mut self, policy: FormatChangePolicy) -> Self {
+        self.format_change = policy;
+        self
+    }
+
+    /// Set how multi-channel fed audio is mixed down to mono (default: `Average`)
+    ///
+    /// See [`ChannelMix`]. Only applies to programmatic input; a
+    /// [`ChannelMix::Weighted`] mix makes feeding audio with a different
+    /// number of channels than weights fail.
+    pub fn with_channel_mix(mut self, mix: ChannelMix) -> Self {
+        self.channel_mix = mix;
+        self
+    }
+
+    /// Set what `start` does while a session is running (default: `Restart`)
+    ///
+    /// See [`RestartPolicy`].
+    pub fn with_restart_policy(mut self, policy: RestartPolicy) -> Self {
+        self.restart = policy;
+        self
+    }
+
+    /// Emit one final result per sentence instead of the recognizer's segments (default: off)
+    ///
+    /// Finals are buffered and re-emitted split or merged on `.`, `!` and `?`, with
+    /// [`StreamingResult::start_timestamp`] marking when the sentence began. Partials
+    /// include the sentence in progress. When the helper exits, the trailing
+    /// unfinished sentence is returned as a final before the end-of-stream error.
+    pub fn with_sentence_segmentation(mut self, enabled: bool) -> Self {
+        self.sentence_segmentation = enabled;
+        self
+    }
+
+    /// Return at most `hz` partial results per second (default: unthrottled)
+    ///
+    /// A partial arriving sooner than `1 / hz` after the previous one is held
+    /// back; newer partials replace it, and the latest is returned by the first
+    /// poll after the interval has passed. Finals are never held and discard any
+    /// held partial. Gives captions a steady refresh rate, e.g. 10 updates per
+    /// second. `hz` must be positive.
+    pub fn with_partial_rate(mut self, hz: f32) -> Self {
+        self.partial_rate = Some(hz);
+        self
+    }
+
+    /// Choose between fast and stable partial results (default: `Responsive`)
+    ///
+    /// With [`StabilityMode::Stable`], a partial is only returned once the
+    /// recognizer has left its text unchanged for 300 ms, so captions update
+    /// less often and are rarely taken back, at the cost of that much latency.
+    /// Combines with [`with_partial_rate`](Self::with_partial_rate), which then
+    /// limits how often the stable partials are returned.
+    pub fn with_stability(mut self, mode: StabilityMode) -> Self {
+        self.stability = mode;
+        self
+    }
+
+    /// Also deliver the captured microphone audio to the caller (default: off)
+    ///
+    /// In microphone mode the helper owns the audio device, so the audio can't be
+    /// tapped from Rust. With passthrough enabled the helper writes what it
+    /// captures to a second pipe as 16kHz mono s16le PCM, read with
+    /// [`StreamingTranscriber::read_captured_audio`] for playback or recording.
+    ///
+    /// This costs 32 KB/s of extra pipe traffic and a format conversion per
+    /// capture buffer in the helper, and the audio arrives one capture buffer
+    /// (roughly 100 ms) after it was spoken. Audio the caller doesn't read in time
+    /// is dropped rather than stalling capture. Only microphone input supports
+    /// passthrough; with programmatic input the caller already has the audio.
+    pub fn with_audio_passthrough(mut self, enabled: bool) -> Self {
+        self.audio_passthrough = enabled;
+        self
+    }
+
+    /// Set the microphone capture buffer size in frames (default: the helper's choice)
+    ///
+    /// This is the buffer size requested for the audio engine tap, so it sets how
+    /// often captured audio reaches the recognizer. Smaller buffers give lower
+    /// latency at higher CPU cost, which suits a responsive dictation UI; larger
+    /// ones suit background transcription. Values from 256 to 16384 frames are
+    /// accepted. At 48kHz, 1024 frames is about 21 ms and 8192 about 170 ms. The
+    /// audio engine treats the size as a request and may deliver buffers of
+    /// another size, typically no smaller than about 100 ms.
+    pub fn with_mic_buffer_frames(mut self, frames: u32) -> Self {
+        self.mic_buffer_frames = Some(frames);
+        self
+    }
+
+    /// Set what `feed_audio_*` does when the helper falls behind (default: `Block`)
+    pub fn with_backpressure(mut self, policy: BackpressurePolicy) -> Self {
+        self.backpressure = policy;
+        self
+    }
+
+    /// Set how far fed audio may run ahead of the helper before backpressure applies
+    /// (default: 10 seconds)
+    ///
+    /// The backlog is the audio fed since the helper last produced a result, minus
+    /// the time that has passed since then. Feeding in real time keeps it near
+    /// zero even through silence; replaying a file at full speed grows it quickly.
+    /// Past the limit, `feed_audio_*` returns a [`BackpressureWarning`] or applies
+    /// the configured [`BackpressurePolicy`], instead of stalling the caller's
+    /// thread on a full pipe without explanation.
+    pub fn with_backlog_limit(mut self, limit: Duration) -> Self {
+        self.backlog_limit = limit;
+        self
+    }
+
+    /// Don't send fed chunks that are entirely silent to the helper (default: off)
+    ///
+    /// A chunk whose samples all stay within ±64 (about -54 dBFS) is dropped before
+    /// resampling, which saves the work for sources that send silent keep-alive
+    /// chunks. The recognizer then hears the speech around a skipped chunk back to
+    /// back, so a pause may finalize later. The total skipped is reported by
+    /// [`StreamingTranscriber::skipped_silence`]; result timestamps are wall-clock
+    /// times and are unaffected.
+    pub fn with_skip_silence(mut self, enabled: bool) -> Self {
+        self.skip_silence = enabled;
+        self
+    }
+
+    /// Record the audio sent to the helper as a WAV file at `path` (default: off)
+    ///
+    /// The recording is the 16kHz mono PCM the helper actually received, after
+    /// downmixing and resampling and without chunks dropped by backpressure or
+    /// silence skipping, so it can be replayed through another helper version
+    /// to reproduce a transcription exactly. Each session overwrites the file.
+    /// Audio fed with `feed_encoded` isn't recorded. Requires programmatic
+    /// input.
+    pub fn with_capture_fed_audio<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.capture_fed_audio = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set where the helper's stderr goes (default: `Inherit`)
+    ///
+    /// The helper reports progress and problems on stderr. `Inherit` suits
+    /// command-line tools; GUI apps without a console and daemons whose logs
+    /// shouldn't be polluted can use `Null`, or `Capture` to keep the output for
+    /// [`StreamingTranscriber::helper_stderr`]. With `Inherit` and `Capture`, the
+    /// last lines are also included in the error when the helper exits while
+    /// receiving audio.
+    pub fn with_stderr(mut self, mode: StderrMode) -> Self {
+        self.stderr = mode;
+        self
+    }
+
+    /// End each session this long after [`StreamingTranscriber::start`] (default: no limit)
+    ///
+    /// Once the time is up the helper stops taking audio, emits its remaining final
+    /// results and exits, regardless of activity. [`poll_result`] returns those
+    /// results and then a "Session deadline reached" error in place of the usual
+    /// end-of-stream error. Feeding audio after the deadline also returns that error.
+    /// A helper that doesn't finish within ten seconds is killed.
+    ///
+    /// Combined with [`with_session_deadline`](Self::with_session_deadline), the
+    /// earlier of the two applies. The deadline is checked whenever results are
+    /// polled or audio is fed.
+    ///
+    /// [`poll_result`]: StreamingTranscriber::poll_result
+    pub fn with_max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// End sessions at a fixed point in time (default: no deadline)
+    ///
+    /// Like [`with_max_duration`](Self::with_max_duration), but absolute, for
+    /// scheduling against a wall-clock appointment: "listen until the meeting slot
+    /// ends". A session started after the deadline ends right away.
+    pub fn with_session_deadline(mut self, deadline: Instant) -> Self {
+        self.session_deadline = Some(deadline);
+        self
+    }
+
+    /// Set the locale the speech is in (default: `en-US`)
+    ///
+    /// `locale` is a BCP 47 identifier such as `"es-ES"` or `"ja-JP"`, passed to
+    /// the helper as `--locale` for every session; a session that can't
+    /// recognize it fails to start. [`StreamingTranscriber::set_locale`]
+    /// switches a running session. [`build`](Self::build) fails if `locale` is
+    /// empty. Has no effect on [`StreamingTranscriber::connect_socket`]
+    /// connections, whose helper is already running.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    /// Tell the recognizer what kind of speech to expect (default: `Unspecified`)
+    ///
+    /// See [`TaskHint`]. Only the SFSpeechRecognizer backend uses the hint. Has
+    /// no effect on [`StreamingTranscriber::connect_socket`] connections, whose
+    /// helper is already running.
+    pub fn with_task_hint(mut self, hint: TaskHint) -> Self {
+        self.task_hint = hint;
+        self
+    }
+
+    /// Report alternative readings with each final result (default: `false`)
+    ///
+    /// Fills [`StreamingResult::alternatives`] on finals, for "did you mean"
+    /// corrections. Partials never carry alternatives, since they are replaced
+    /// too quickly to offer. With sentence segmentation, alternatives are kept
+    /// only for sentences that came from a single final.
+    ///
+    /// Requesting alternatives makes the recognizer keep several hypotheses
+    /// alive, which costs some CPU and can delay finals slightly; partials are
+    /// unaffected. Has no effect on [`StreamingTranscriber::connect_socket`]
+    /// connections, whose helper is already running.
+    pub fn with_alternatives_stream(mut self, enabled: bool) -> Self {
+        self.alternatives_stream = enabled;
+        self
+    }
+
+    /// Set how fed audio is resampled to 16kHz (default: `Medium`)
+    ///
+    /// See [`ResampleQuality`]. Only applies to programmatic input that isn't
+    /// already at 16kHz; microphone audio is resampled by the helper.
+    pub fn with_resample_quality(mut self, quality: ResampleQuality) -> Self {
+        self.resample_quality = quality;
+        self
+    }
+
+    /// End the session on a malformed helper output line (default: `false`)
+    ///
+    /// By default a line that isn't a valid result, such as one that is
+    /// truncated, lacks `isFinal` or `timestamp`, or runs past 64 KB, is logged
+    /// and skipped, and [`StreamingTranscriber::poll_result`] carries on with
+    /// the next line. With strict parsing it returns a "Failed to parse result"
+    /// error quoting the line instead; useful when developing a helper.
+    pub fn with_strict_parsing(mut self, strict: bool) -> Self {
+        self.strict_parsing = strict;
+        self
+    }
+
+    /// Start partials with the text of the final before them (default: `false`)
+    ///
+    /// After a final, the recognizer begins the next segment from nothing, so
+    /// its first partials are often empty or a single word, and a caption line
+    /// that shows the latest result goes blank at every segment boundary. With
+    /// carryover, each partial's `text` is the previous final's text followed
+    /// by the new partial's, until the next final; finals themselves are left
+    /// as recognized, so a transcript built from finals is unchanged.
+    /// [`StreamingResult::is_revision`] compares the carried text, and
+    /// session transcripts record results as returned.
+    pub fn with_final_carryover(mut self, enabled: bool) -> Self {
+        self.final_carryover = enabled;
+        self
+    }
+
+    /// Include the volatile tail of partial results (default: `true`)
+    ///
+    /// A partial's text ends in words the recognizer may still revise. By
+    /// default partials carry all of it, for the lowest latency. Turned off,
+    /// the helper marks each partial's settled prefix in
+    /// [`StreamingResult::stable_text`] and partials carry only that, so a
+    /// display never shows a word that is later taken back; a partial whose
+    /// settled text hasn't grown is skipped. Finals are unaffected.
+    ///
+    /// Neither backend reports word stability directly, so the helper treats a
+    /// word as settled once the recognizer has kept it, and everything before
+    /// it, through a revision of its hypothesis. This holds text back by about
+    /// one partial. Has no effect on [`StreamingTranscriber::connect_socket`]
+    /// connections, whose helper is already running; partials from a helper
+    /// that doesn't mark the split are passed on whole.
+    pub fn with_volatile_tail(mut self, enabled: bool) -> Self {
+        self.volatile_tail = enabled;
+        self
+    }
+
+    /// Pass extra arguments to the helper, verbatim (default: none)
+    ///
+    /// An escape hatch for helper flags this crate doesn't expose yet, such as
+    /// those of a custom helper build. The arguments are appended after all of
+    /// the crate's own, on every spawn including restarts. Has no effect on
+    /// [`StreamingTranscriber::connect_socket`] connections, whose helper is
+    /// already running.
+    ///
+    /// Nothing is validated. The helper reads the first occurrence of each
+    /// flag, so repeating one the crate already manages, such as
+    /// `--task-hint`, is ignored, while adding one the crate leaves out, such
+    /// as `--stdin` with microphone input, changes what the helper expects
+    /// from this transcriber and can break the session.
+    pub fn with_extra_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extra_args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Build the StreamingTranscriber
+    pub fn build(self) -> Result<StreamingTranscriber, ScribeError> {
+        if self.audio_passthrough && self.input_mode != AudioInputMode::Microphone {
+            return Err(ScribeError::WrongInputMode(
+                "Audio passthrough requires microphone input".to_string(),
+            ));
+        }
+        if self.capture_fed_audio.is_some() && self.input_mode != AudioInputMode::Programmatic {
+            return Err(ScribeError::WrongInputMode(
+                "Fed audio capture requires programmatic input".to_string(),
+            ));
+        }
+        if let Some((sample_rate, channels)) = self.declared_format {
+            if self.input_mode != AudioInputMode::Programmatic {
+                return Err(ScribeError::WrongInputMode(
+                    "Input format requires programmatic input".to_string(),
+                ));
+            }
+            if sample_rate == 0 || channels == 0 {
+                return Err(format!(
+                    "Invalid input format: {} Hz, {} channels",
+                    sample_rate, channels
+                )
+                .into());
+            }
+        }
+        if let Some(frames) = self.mic_buffer_frames {
+            if self.input_mode != AudioInputMode::Microphone {
+                return Err(ScribeError::WrongInputMode(
+                    "Mic buffer size requires microphone input".to_string(),
+                ));
+            }
+            if !MIC_BUFFER_FRAMES.contains(&frames) {
+                return Err(format!(
+                    "Mic buffer size of {} frames is outside {}..={}",
+                    frames,
+                    MIC_BUFFER_FRAMES.start(),
+                    MIC_BUFFER_FRAMES.end()
+                )
+                .into());
+            }
+        }
+
+        if self.socket_path.is_some() && self.input_mode != AudioInputMode::Programmatic {
+            return Err(ScribeError::WrongInputMode(
+                "Socket connections require programmatic input".to_string(),
+            ));
+        }
+
+        let helper_path = if let Some(socket) = &self.socket_path {
+            // Reported as the helper path; there is no binary to find
+            socket.clone()
+        } else if let Some(path) = self.helper_path {
+            if !path.exists() {
+                return Err(ScribeError::HelperNotFound(Some(path)));
+            }
+            path
+        } else {
+            find_helper("transcribe_stream", self.discovery_retry)
+                .ok_or(ScribeError::HelperNotFound(None))?
+        };
+        let helper_checksum = self
+            .expected_helper_sha256
+            .as_deref()
+            .map(HelperChecksum::parse)
+            .transpose()?;
+        if let Some(hz) = self
+            .partial_rate
+            .filter(|hz| !(hz.is_finite() && *hz > 0.0))
+        {
+            return Err(format!("Partial rate must be positive, got {}", hz).into());
+        }
+        if let Some(locale) = &self.locale {
+            check_locale(locale)?;
+        }
+
+        Ok(StreamingTranscriber {
+            helper_path,
+            input_mode: self.input_mode,
+            declared_format: self.declared_format,
+            priority: self.priority,
+            format_change: self.format_change,
+            channel_mix: self.channel_mix,
+            restart: self.restart,
+            audio_passthrough: self.audio_passthrough,
+            mic_buffer_frames: self.mic_buffer_frames,
+            max_duration: self.max_duration,
+            session_deadline: self.session_deadline,
+            stderr_mode: self.stderr,
+            locale: self.locale,
+            task_hint: self.task_hint,
+            alternatives_stream: self.alternatives_stream,
+            extra_args: self.extra_args,
+            discovery_retry: self.discovery_retry,
+            helper_checksum,
+            deadline: None,
+            input_finished: false,
+            finish_by: None,
+            backpressure: self.backpressure,
+            backlog_limit: self.backlog_limit,
+            skip_silence: self.skip_silence,
+            skipped_silence: Duration::ZERO,
+            fed_audio_path: self.capture_fed_audio,
+            fed_audio: None,
+            final_carryover: self.final_carryover,
+            carried_final: None,
+            volatile_tail: self.volatile_tail,
+            stable_partial: String::new(),
+            process: None,
+            stderr: None,
+            reader: None,
+            output_fd: None,
+            writer: None,
+            socket_path: self.socket_path,
+            protocol_version: None,
+            transcript: None,
+            output_ended: false,
+            partial_line: Vec::new(),
+            line_overflowed: false,
+            strict_parsing: self.strict_parsing,
+            last_raw_line: None,
+            resampler: Resampler::new(self.resample_quality),
+            input_format: None,
+            encoded_format: None,
+            fed_since_result: 0,
+            last_result_at: Instant::now(),
+            captured_audio: None,
+            control: None,
+            pings_sent: 0,
+            last_pong: 0,
+            locale_reply: None,
+            context: None,
+            segmenter: self.sentence_segmentation.then(SentenceSegmenter::default),
+            stability: (self.stability == StabilityMode::Stable).then(StabilityGate::default),
+            partial_rate: self.partial_rate,
+            last_partial_at: None,
+            last_partial: None,
+            held_partial: None,
+            pending: VecDeque::new(),
+            delta: DeltaTracker::default(),
+            #[cfg(feature = "async")]
+            pipe_fds: None,
+            #[cfg(feature = "async")]
+            async_pipes: None,
+            #[cfg(all(feature = "system-audio", target_os = "macos"))]
+            system_audio: None,
+            tail: None,
+            #[cfg(feature = "ws-server")]
+            ws_server: None,
+        })
+    }
+}
+
+impl Default for StreamingTranscriberBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Streaming transcriber for live microphone or audio stream input
+///
+/// Provides real-time transcription with both partial (volatile) and final results.
+/// Uses progressive transcription mode for low-latency feedback.
+///
+/// # Examples
+///
+/// ```no_run
+/// use swift_scribe::StreamingTranscriber;
+///
+/// let mut transcriber = StreamingTranscriber::new().unwrap();
+/// transcriber.start().unwrap();
+///
+/// // Poll for results in a loop
+/// while let Some(result) = transcriber.poll_result().unwrap() {
+///     if result.is_final {
+///         println!("Final: {}", result.text);
+///     } else {
+///         print!("\rPartial: {}", result.text);
+///     }
+/// }
+/// ```
+pub struct StreamingTranscriber {
+    helper_path: PathBuf,
+    input_mode: AudioInputMode,
+    /// Format used by the `feed_audio_*_fmt` methods
+    declared_format: Option<(u32, u16)>,
+    priority: Priority,
+    format_change: FormatChangePolicy,
+    channel_mix: ChannelMix,
+    restart: RestartPolicy,
+    audio_passthrough: bool,
+    mic_buffer_frames: Option<u32>,
+    max_duration: Option<Duration>,
+    session_deadline: Option<Instant>,
+    stderr_mode: StderrMode,
+    /// Locale for `--locale`; the helper's default when unset
+    locale: Option<String>,
+    task_hint: TaskHint,
+    alternatives_stream: bool,
+    /// Passed to the helper after the crate's own arguments
+    extra_args: Vec<String>,
+    discovery_retry: DiscoveryRetry,
+    helper_checksum: Option<HelperChecksum>,
+    /// When the current session ends: the earlier of the two limits above
+    deadline: Option<Instant>,
+    /// The audio input was ended, by `finish_input` or otherwise
+    input_finished: bool,
+    /// Once the helper was asked to finish, when it is killed if it hasn't
+    finish_by: Option<Instant>,
+    backpressure: BackpressurePolicy,
+    backlog_limit: Duration,
+    skip_silence: bool,
+    /// Audio left out of the current session by `skip_silence`
+    skipped_silence: Duration,
+    /// Where the audio sent to the helper is recorded, if anywhere
+    fed_audio_path: Option<PathBuf>,
+    /// Recording of the current session's audio, at `fed_audio_path`
+    fed_audio: Option<FedAudioRecorder>,
+    final_carryover: bool,
+    /// Text of the last final, prefixed to partials with `final_carryover`
+    carried_final: Option<String>,
+    volatile_tail: bool,
+    /// Settled text of the last partial passed on without its volatile tail
+    stable_partial: String,
+    process: Option<Child>,
+    /// Forwards the spawned helper's stderr, keeping its last lines for errors
+    stderr: Option<StderrTail>,
+    /// Helper output: the child's stdout, or the socket's read side
+    reader: Option<Box<dyn Read + Send>>,
+    /// Descriptor `reader` reads from, for waiting on it in `poll_result_timeout`
+    output_fd: Option<std::os::unix::io::RawFd>,
+    /// Helper audio input: the child's stdin, or the socket's write side
+    writer: Option<Box<dyn Write + Send>>,
+    /// Set for transcribers connecting to a socket, see
+    /// [`StreamingTranscriberBuilder::with_socket`]
+    socket_path: Option<PathBuf>,
+    /// Protocol version the helper announced for the current session
+    protocol_version: Option<u32>,
+    /// Final results of the current session, for [`finish_transcript`](Self::finish_transcript)
+    transcript: Option<SessionTranscript>,
+    /// Whether the helper's output reached EOF in the current session
+    output_ended: bool,
+    /// Bytes of the output line read so far, kept across polls until its
+    /// newline arrives; bytes, so a character split between reads isn't garbled
+    partial_line: Vec<u8>,
+    /// Whether the line being read outgrew [`MAX_LINE_LEN`] and is being dropped
+    line_overflowed: bool,
+    /// Whether a malformed output line ends the session instead of being skipped
+    strict_parsing: bool,
+    last_raw_line: Option<String>,
+    resampler: Resampler,
+    /// Sample rate and channel count of the most recently fed chunk
+    input_format: Option<(u32, u16)>,
+    /// Container of the encoded stream, once `feed_encoded` has been used
+    encoded_format: Option<AudioFormat>,
+    /// 16kHz samples written since the helper last produced a result
+    fed_since_result: u64,
+    /// When the helper last produced a result (or the session started)
+    last_result_at: Instant,
+    /// Read end of the passthrough pipe while a session with passthrough runs
+    captured_audio: Option<std::fs::File>,
+    /// Write end of the control pipe while a spawned session runs
+    control: Option<std::fs::File>,
+    /// Id of the most recent ping sent, and of the most recent pong received
+    pings_sent: u64,
+    last_pong: u64,
+    /// The helper's answer to the last `set_locale`, until it is read
+    locale_reply: Option<LocaleReply>,
+    /// Tail of an earlier transcript for the session being started, for `--context`
+    context: Option<String>,
+    segmenter: Option<SentenceSegmenter>,
+    /// Partial held until confirmed, with `StabilityMode::Stable`
+    stability: Option<StabilityGate>,
+    /// Maximum partials returned per second, from `with_partial_rate`
+    partial_rate: Option<f32>,
+    last_partial_at: Option<Instant>,
+    /// Text of the partial last returned, until a final follows it
+    last_partial: Option<String>,
+    /// Newest partial held back by the partial rate
+    held_partial: Option<StreamingResult>,
+    /// Post-processed results not yet returned by `poll_result`
+    pending: VecDeque<StreamingResult>,
+    delta: DeltaTracker,
+    /// Raw stdout and stdin descriptors of the spawned helper, for `start_async`
+    #[cfg(feature = "async")]
+    pipe_fds: Option<(std::os::unix::io::RawFd, Option<std::os::unix::io::RawFd>)>,
+    /// The helper's pipes registered with the tokio reactor, after `start_async`
+    #[cfg(feature = "async")]
+    async_pipes: Option<async_io::AsyncPipes>,
+    /// System audio being captured and fed, after `start_system_audio`
+    #[cfg(all(feature = "system-audio", target_os = "macos"))]
+    system_audio: Option<system_audio::SystemAudioCapture>,
+    /// File being followed and fed as it grows, after `start_tailing_file`
+    tail: Option<tail::FileTail>,
+    /// Server pushing results to WebSocket clients, after `serve_ws`
+    #[cfg(feature = "ws-server")]
+    ws_server: Option<WsServer>,
+}
+
+impl StreamingTranscriber {
+    /// Creates a new builder for configuring a StreamingTranscriber
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> StreamingTranscriberBuilder {
+        StreamingTranscriberBuilder::new()
+    }
+
+    /// Creates a new streaming transcriber with default settings (microphone input)
+    ///
+    /// This is a convenience method equivalent to `StreamingTranscriber::builder().build()`.
+    ///
+    /// Looks for the helper binary in the following locations (in order):
+    /// 1. `./helpers/transcribe_stream` (local development)
+    /// 2. `~/.local/bin/transcribe_stream` (user install)
+    /// 3. `/usr/local/bin/transcribe_stream` (system install)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the helper binary cannot be found.
+    pub fn new() -> Result<Self, ScribeError> {
+        Self::builder().build()
+    }
+
+    /// Creates a new streaming transcriber with a custom helper binary path and microphone input
+    ///
+    /// This is a convenience method equivalent to `StreamingTranscriber::builder().with_helper_path(path).build()`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the transcribe_stream helper binary
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the specified path does not exist.
+    pub fn with_helper_path<P: AsRef<Path>>(path: P) -> Result<Self, ScribeError> {
+        Self::builder().with_helper_path(path).build()
+    }
+
+    /// Starts the streaming transcription
+    ///
+    /// - For microphone input: Launches the helper process and begins capturing from the microphone
+    /// - For programmatic input: Launches the helper in stdin mode, ready to receive audio samples
+    ///
+    /// Call `poll_result()` to retrieve transcription results.
+    /// For programmatic input, call `feed_audio_*()` methods to send audio samples.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The helper process fails to start
+    /// - Permissions haven't been granted (for microphone input)
+    /// - The helper doesn't announce a protocol version in
+    ///   [`SUPPORTED_PROTOCOL_VERSIONS`]; the message starts with
+    ///   "Incompatible helper protocol". This is checked before any audio is
+    ///   fed, so a stale helper fails here rather than with parse errors later.
+    /// - A session is already running and the builder's [`RestartPolicy`] is
+    ///   `Error`; the message starts with "Already started". With the default
+    ///   `Restart`, the running session is stopped first instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// // Microphone input
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// // Programmatic input
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    /// ```
+    pub fn start(&mut self) -> Result<(), ScribeError> {
+        self.begin_session()?;
+        match self.handshake() {
+            Ok(version) => self.protocol_version = Some(version),
+            Err(e) => {
+                let _ = self.stop();
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts a session that continues an earlier transcript
+    ///
+    /// For long dictation that spans restarts of the app or the session: pass
+    /// the text recognized so far, such as the saved text of a
+    /// [`finish_transcript`](Self::finish_transcript), and the new session is
+    /// recognized with its last 100 words as context. Apple's recognizers take
+    /// no text prompt, so the words are given to the helper as contextual
+    /// strings, as [`Transcriber::align`] does with its reference: names and
+    /// terms already used are favored where the audio is ambiguous, but the
+    /// recognizer isn't conditioned on how the previous sentence ended.
+    ///
+    /// The context lasts for this session only; a later
+    /// [`start`](Self::start) begins without it. An empty `prior_text` starts
+    /// an ordinary session.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`start`](Self::start), or an error if the
+    /// transcriber is connected to a socket, whose helper is already running.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let saved = std::fs::read_to_string("draft.txt").unwrap_or_default();
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start_with_context(&saved).unwrap();
+    /// ```
+    pub fn start_with_context(&mut self, prior_text: &str) -> Result<(), ScribeError> {
+        if self.socket_path.is_some() {
+            return Err(ScribeError::UnsupportedOnSocket("Transcript context"));
+        }
+        let words: Vec<&str> = prior_text.split_whitespace().collect();
+        let tail = &words[words.len().saturating_sub(CONTEXT_WORDS)..];
+        self.context = (!tail.is_empty()).then(|| tail.join(" "));
+        let started = self.start();
+        self.context = None;
+        started
+    }
+
+    /// Launches or connects to the helper and resets per-session state, up to
+    /// the protocol handshake
+    fn begin_session(&mut self) -> Result<(), String> {
+        if self.reader.is_some() || self.process.is_some() {
+            if self.restart == RestartPolicy::Error {
+                return Err(
+                    "Already started: call stop() before starting a new session".to_string()
+                );
+            }
+            log::debug!("Session already running; stopping it before starting again");
+            self.stop()?;
+        }
+        if let Some(path) = &self.fed_audio_path {
+            let recorder = FedAudioRecorder::create(path).map_err(|e| {
+                format!(
+                    "Failed to create fed audio capture {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            self.fed_audio = Some(recorder);
+        }
+        match self.socket_path.clone() {
+            Some(path) => self.connect(&path)?,
+            None => self.spawn()?,
+        }
+
+        self.partial_line.clear();
+        self.line_overflowed = false;
+        self.last_raw_line = None;
+        self.resampler.reset();
+        self.input_format = None;
+        self.encoded_format = None;
+        self.fed_since_result = 0;
+        self.skipped_silence = Duration::ZERO;
+        self.last_result_at = Instant::now();
+        self.pings_sent = 0;
+        self.last_pong = 0;
+        self.locale_reply = None;
+        let started = Instant::now();
+        let max_end = self.max_duration.and_then(|max| started.checked_add(max));
+        self.deadline = match (self.session_deadline, max_end) {
+            (Some(deadline), Some(max_end)) => Some(deadline.min(max_end)),
+            (deadline, max_end) => deadline.or(max_end),
+        };
+        self.input_finished = false;
+        self.finish_by = None;
+        self.transcript = Some(SessionTranscript::begin());
+        self.output_ended = false;
+        if let Some(segmenter) = self.segmenter.as_mut() {
+            *segmenter = SentenceSegmenter::default();
+        }
+        if let Some(gate) = self.stability.as_mut() {
+            *gate = StabilityGate::default();
+        }
+        self.pending.clear();
+        self.delta = DeltaTracker::default();
+        self.last_partial_at = None;
+        self.last_partial = None;
+        self.carried_final = None;
+        self.stable_partial.clear();
+        self.held_partial = None;
+        #[cfg(all(feature = "system-audio", target_os = "macos"))]
+        {
+            self.system_audio = None;
+        }
+        self.tail = None;
+        self.protocol_version = None;
+        Ok(())
+    }
+
+    /// Reads the helper's protocol announcement and checks that it is supported
+    ///
+    /// The helper writes `{"protocol":N}` before anything else. A helper whose
+    /// first line is anything else predates the announcement and counts as
+    /// version 0.
+    fn handshake(&mut self) -> Result<u32, String> {
+        let started = Instant::now();
+        let mut line = Vec::new();
+        while !self.read_announcement(&mut line)? {
+            if started.elapsed() >= HANDSHAKE_TIMEOUT {
+                return Err(format!(
+                    "Helper did not announce its protocol version within {:?}",
+                    HANDSHAKE_TIMEOUT
+                ));
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        self.check_announcement(&line)
+    }
+
+    /// Reads the announcement line into `line` a byte at a time
+    ///
+    /// Returns `Ok(false)` when the helper has nothing more to read yet, and
+    /// `Ok(true)` once the whole line (without its newline) is in `line`. Reads
+    /// byte by byte so nothing after the announcement is consumed.
+    fn read_announcement(&mut self, line: &mut Vec<u8>) -> Result<bool, String> {
+        let reader = self.reader.as_mut().ok_or(ScribeError::NotStarted)?;
+
+        let mut byte = [0u8; 1];
+        loop {
+            match reader.read(&mut byte) {
+                Ok(0) => {
+                    return Err("Helper exited before announcing its protocol version".to_string())
+                }
+                Ok(_) if byte[0] == b'\n' => return Ok(true),
+                Ok(_) => line.push(byte[0]),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Ok(false),
+                Err(e) => return Err(format!("Failed to read from helper: {}", e)),
+            }
+        }
+    }
+
+    /// Parses the announcement line, checking that its version is supported
+    fn check_announcement(&self, line: &[u8]) -> Result<u32, String> {
+        let line = String::from_utf8_lossy(line);
+        let version = serde_json::from_str::<ProtocolAnnouncement>(line.trim())
+            .map_or(0, |announcement| announcement.protocol);
+        if !SUPPORTED_PROTOCOL_VERSIONS.contains(&version) {
+            return Err(format!(
+                "Incompatible helper protocol: {} speaks version {}, this crate supports versions \
+                 {}..{}; rebuild the helpers that came with this version of the crate",
+                self.helper_path.display(),
+                version,
+                SUPPORTED_PROTOCOL_VERSIONS.start,
+                SUPPORTED_PROTOCOL_VERSIONS.end
+            ));
+        }
+        Ok(version)
+    }
+
+    /// Returns the protocol version the helper announced for the current session
+    ///
+    /// `None` until [`start`](Self::start) succeeds. Always within
+    /// [`SUPPORTED_PROTOCOL_VERSIONS`], as other versions fail the start.
+    pub fn protocol_version(&self) -> Option<u32> {
+        self.protocol_version
+    }
+
+    /// Starts a session that transcribes the audio the system is playing
+    ///
+    /// Captures the output of the main display with ScreenCaptureKit (macOS 13+)
+    /// and feeds it to the helper as [`feed_audio_f32`](Self::feed_audio_f32)
+    /// would. Captured audio is handed over whenever results are polled, so keep
+    /// calling [`poll_result`](Self::poll_result). Audio this process plays
+    /// itself is left out. [`stop`](Self::stop) ends the capture.
+    ///
+    /// Captured audio not yet handed over is capped at the backlog limit (see
+    /// [`StreamingTranscriberBuilder::with_backlog_limit`]); capture beyond it is
+    /// dropped until results are polled again.
+    ///
+    /// Requires the `system-audio` feature, on macOS.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The transcriber doesn't use programmatic input
+    /// - Screen Recording permission hasn't been granted, which ScreenCaptureKit
+    ///   requires even for audio alone
+    /// - The helper process fails to start
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start_system_audio().unwrap();
+    /// while let Ok(result) = transcriber.poll_result() {
+    ///     if let Some(result) = result {
+    ///         println!("{}", result.text);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(all(feature = "system-audio", target_os = "macos"))]
+    pub fn start_system_audio(&mut self) -> Result<(), ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "System audio capture requires programmatic input mode".to_string(),
+            ));
+        }
+
+        self.start()?;
+        match system_audio::SystemAudioCapture::start(self.backlog_limit) {
+            Ok(capture) => {
+                self.system_audio = Some(capture);
+                Ok(())
+            }
+            Err(e) => {
+                let _ = self.stop();
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Feeds the system audio captured since the last poll
+    #[cfg(all(feature = "system-audio", target_os = "macos"))]
+    fn feed_system_audio(&mut self) -> Result<(), String> {
+        let Some(capture) = &self.system_audio else {
+            return Ok(());
+        };
+        let samples = capture.drain()?;
+        if !samples.is_empty() {
+            self.feed_audio_f32(&samples, system_audio::CAPTURE_SAMPLE_RATE, 1)?;
+        }
+        Ok(())
+    }
+
+    /// Launches the helper process and wires up its pipes
+    fn spawn(&mut self) -> Result<(), ScribeError> {
+        if let Some(checksum) = &self.helper_checksum {
+            checksum.verify(&self.helper_path)?;
+        }
+        let mut cmd = Command::new(&self.helper_path);
+        cmd.stdout(Stdio::piped()).stderr(match self.stderr_mode {
+            StderrMode::Null => Stdio::null(),
+            StderrMode::Inherit | StderrMode::Capture => Stdio::piped(),
+        });
+
+        match self.input_mode {
+            AudioInputMode::Microphone => {
+                if let Some(frames) = self.mic_buffer_frames {
+                    cmd.arg("--buffer-frames").arg(frames.to_string());
+                }
+            }
+            AudioInputMode::Programmatic => {
+                cmd.arg("--stdin").stdin(Stdio::piped());
+            }
+        }
+        if let Some(locale) = &self.locale {
+            cmd.arg("--locale").arg(locale);
+        }
+        if let Some(hint) = self.task_hint.flag() {
+            cmd.arg("--task-hint").arg(hint);
+        }
+        if self.alternatives_stream {
+            cmd.arg("--alternatives");
+        }
+        if !self.volatile_tail {
+            cmd.arg("--stable-text");
+        }
+        if let Some(context) = &self.context {
+            cmd.arg("--context").arg(context);
+        }
+        apply_priority(&mut cmd, self.priority);
+
+        // The child's ends of the pipes are closed in the parent once the child
+        // has its own copies, so EOF follows the helper's exit
+        let mut passthrough_write = None;
+        if self.audio_passthrough {
+            let (read, write) = passthrough_pipe()
+                .map_err(|e| format!("Failed to create audio passthrough pipe: {}", e))?;
+            cmd.arg("--audio-out").arg(PASSTHROUGH_FD.to_string());
+            inherit_fd(&mut cmd, &write, PASSTHROUGH_FD);
+            self.captured_audio = Some(read);
+            passthrough_write = Some(write);
+        }
+        let (control_read, control_write) =
+            pipe().map_err(|e| format!("Failed to create control pipe: {}", e))?;
+        cmd.arg("--control").arg(CONTROL_FD.to_string());
+        inherit_fd(&mut cmd, &control_read, CONTROL_FD);
+        cmd.args(&self.extra_args);
+        self.control = Some(std::fs::File::from(control_write));
+
+        log::debug!("Starting streaming helper: {}", command_line(&cmd));
+        let spawned = cmd.spawn();
+        drop(passthrough_write);
+        drop(control_read);
+        let mut child = spawned.map_err(|e| {
+            telemetry::helper_spawn_failed();
+            ScribeError::HelperSpawn {
+                command: command_line(&cmd),
+                source: e,
+            }
+        })?;
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "Failed to capture stdout".to_string())?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+            let fd = stdout.as_raw_fd();
+            unsafe {
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+                if flags != -1 {
+                    libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+                }
+            }
+        }
+
+        #[cfg(feature = "async")]
+        {
+            use std::os::unix::io::AsRawFd;
+            let stdin = child.stdin.as_ref().map(|stdin| stdin.as_raw_fd());
+            self.pipe_fds = Some((stdout.as_raw_fd(), stdin));
+        }
+        self.output_fd = Some(std::os::unix::io::AsRawFd::as_raw_fd(&stdout));
+        self.reader = Some(Box::new(stdout));
+        let forward = self.stderr_mode == StderrMode::Inherit;
+        self.stderr = child
+            .stderr
+            .take()
+            .map(|stderr| StderrTail::capture(stderr, forward));
+
+        if matches!(self.input_mode, AudioInputMode::Programmatic) {
+            let stdin = child
+                .stdin
+                .take()
+                .ok_or_else(|| "Failed to capture stdin".to_string())?;
+            self.writer = Some(Box::new(stdin));
+        }
+
+        self.process = Some(child);
+        Ok(())
+    }
+
+    /// Connects to a helper listening on a Unix domain socket
+    fn connect(&mut self, path: &Path) -> Result<(), String> {
+        log::debug!("Connecting to streaming helper at {}", path.display());
+        let stream = UnixStream::connect(path)
+            .map_err(|e| format!("Failed to connect to {}: {}", path.display(), e))?;
+        let writer = stream
+            .try_clone()
+            .map_err(|e| format!("Failed to clone socket: {}", e))?;
+
+        self.output_fd = Some(std::os::unix::io::AsRawFd::as_raw_fd(&stream));
+        self.reader = Some(Box::new(SocketReader(stream)));
+        self.writer = Some(Box::new(writer));
+        Ok(())
+    }
+
+    /// Connects to a helper that is already running and listening on a Unix socket
+    ///
+    /// For multi-process setups where the helper runs elsewhere, e.g. under a
+    /// separate launchd agent, and listens on `path`. The socket carries the same
+    /// protocol as the helper's stdin/stdout in programmatic mode: 16kHz mono
+    /// s16le audio in, one JSON result per line out. `feed_audio_*` writes to the
+    /// socket and [`poll_result`](Self::poll_result) reads from it, so the
+    /// transcriber's lifecycle is independent of the helper process.
+    ///
+    /// The returned transcriber is already connected. [`stop`](Self::stop) closes
+    /// the connection without affecting the helper, and [`start`](Self::start)
+    /// reconnects. [`helper_path`](Self::helper_path) returns the socket path.
+    /// To combine a socket with other options, such as sentence segmentation,
+    /// use [`StreamingTranscriberBuilder::with_socket`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if nothing is listening on `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    /// use std::path::Path;
+    ///
+    /// let mut transcriber = StreamingTranscriber::connect_socket(Path::new("/tmp/scribe.sock")).unwrap();
+    /// transcriber.feed_audio_i16(&[0; 1600], 16000, 1).unwrap();
+    /// let result = transcriber.poll_result().unwrap();
+    /// ```
+    pub fn connect_socket(path: &Path) -> Result<Self, ScribeError> {
+        let mut transcriber = Self::builder()
+            .with_programmatic_input()
+            .with_socket(path)
+            .build()?;
+        transcriber.start()?;
+        Ok(transcriber)
+    }
+
+    /// Confirms the helper is ready and loads its model ahead of the first session
+    ///
+    /// Runs the helper once in warm-up mode, which makes sure the on-device model
+    /// is installed and loaded, then exits. Call this during app launch so the
+    /// first [`start`](Self::start) doesn't pause for model loading.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the helper can't be executed or reports that speech
+    /// recognition isn't available.
+    pub fn warm_up(&self) -> Result<(), ScribeError> {
+        // A helper behind a socket manages its own model
+        if self.socket_path.is_some() {
+            return Ok(());
+        }
+
+        if let Some(checksum) = &self.helper_checksum {
+            checksum.verify(&self.helper_path)?;
+        }
+        let mut cmd = Command::new(&self.helper_path);
+        cmd.arg("--warm-up");
+        if let Some(locale) = &self.locale {
+            cmd.arg("--locale").arg(locale);
+        }
+        apply_priority(&mut cmd, self.priority);
+
+        let output = cmd.output().map_err(|e| {
+            telemetry::helper_spawn_failed();
+            ScribeError::HelperSpawn {
+                command: command_line(&cmd),
+                source: e,
+            }
+        })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "Helper warm-up failed: {} (command: {})",
+                stderr.trim(),
+                command_line(&cmd)
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Polls for the next transcription result
+    ///
+    /// This is a non-blocking call that returns immediately:
+    /// - `Ok(Some(result))` if a new result is available
+    /// - `Ok(None)` if no result is ready yet
+    /// - `Err(_)` if an error occurred
+    ///
+    /// A line the helper has only partly written is kept until the rest of it
+    /// arrives, so a result is returned once it is complete.
+    ///
+    /// Results can be partial (volatile) or final. Check `result.is_final`
+    /// to determine if the transcription is complete for that segment.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(Some(StreamingResult))` - New transcription result available
+    /// - `Ok(None)` - No new result, try again later
+    /// - `Err(ScribeError)` - Error occurred during polling
+    ///
+    /// # Errors
+    ///
+    /// When the user revokes microphone access mid-session the helper stops and
+    /// the error is [`ScribeError::PermissionRevoked`]. This is recoverable:
+    /// [`stop`](Self::stop) the session, ask the user to grant access again, and
+    /// [`start`](Self::start) a new one.
+    ///
+    /// Once the helper has written its last result and closed its output, the
+    /// error is [`ScribeError::StreamEnded`]: the session is over, normally.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// loop {
+    ///     match transcriber.poll_result() {
+    ///         Ok(Some(result)) => {
+    ///             println!("[{}] {}", if result.is_final { "FINAL" } else { "partial" }, result.text);
+    ///         }
+    ///         Ok(None) => thread::sleep(Duration::from_millis(10)),
+    ///         Err(e) => {
+    ///             eprintln!("Error: {}", e);
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn poll_result(&mut self) -> Result<Option<StreamingResult>, ScribeError> {
+        let mut result = self.next_recorded_result()?;
+        if let Some(result) = &mut result {
+            // Only the caller's copy carries the final; the transcript, the
+            // broadcast and the delta tracking already hold it
+            if self.final_carryover && result.kind != ResultKind::EndOfSpeech {
+                self.carry_final(result);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Takes the next result and records it in the transcript and broadcast
+    fn next_recorded_result(&mut self) -> Result<Option<StreamingResult>, ScribeError> {
+        let mut result = self.next_throttled_result()?;
+        if let Some(result) = &mut result {
+            // An end of speech event says nothing about the text shown so far
+            if result.kind != ResultKind::EndOfSpeech {
+                if self.final_carryover && result.is_final {
+                    self.remember_final(result);
+                }
+                result.is_revision = self
+                    .last_partial
+                    .as_deref()
+                    .is_some_and(|previous| !result.text.trim().starts_with(previous));
+                self.last_partial = (!result.is_final).then(|| result.text.trim().to_string());
+            }
+            telemetry::stream_result(result.is_final);
+            if let Some(session) = self.transcript.as_mut() {
+                session.record(result);
+            }
+            #[cfg(feature = "ws-server")]
+            if let Some(server) = &self.ws_server {
+                server.broadcast(result);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Waits up to `timeout` for the next transcription result
+    ///
+    /// Like [`poll_result`](Self::poll_result), but instead of returning
+    /// `Ok(None)` right away it blocks until a result arrives, returning
+    /// `Ok(None)` only once `timeout` has passed without one. The helper's
+    /// output is waited on with `poll(2)`, so a loop waiting for the next final
+    /// result needs no sleeps of its own. While waiting, it wakes at least
+    /// every 50ms to release results held back by
+    /// [`with_partial_rate`](StreamingTranscriberBuilder::with_partial_rate)
+    /// and to feed a tailed file or captured system audio.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`poll_result`](Self::poll_result).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    /// use std::time::Duration;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// loop {
+    ///     match transcriber.poll_result_timeout(Duration::from_secs(1)) {
+    ///         Ok(Some(result)) if result.is_final => println!("{}", result.text),
+    ///         Ok(_) => {}
+    ///         Err(e) => {
+    ///             eprintln!("Error: {}", e);
+    ///             break;
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn poll_result_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<StreamingResult>, ScribeError> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(result) = self.poll_result()? {
+                return Ok(Some(result));
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+            let wait = remaining.min(POLL_WAIT_SLICE);
+            match self.output_fd {
+                Some(fd) => {
+                    wait_readable(fd, wait);
+                }
+                None => std::thread::sleep(wait),
+            }
+        }
+    }
+
+    /// Remembers a final's text for carrying over to the partials after it
+    fn remember_final(&mut self, result: &StreamingResult) {
+        let text = result.text.trim();
+        if !text.is_empty() {
+            self.carried_final = Some(text.to_string());
+        }
+    }
+
+    /// Prefixes the last final's text to a partial
+    fn carry_final(&self, result: &mut StreamingResult) {
+        if result.is_final {
+            return;
+        }
+        let Some(carried) = &self.carried_final else {
+            return;
+        };
+        let carry = |partial: &str| match partial.trim() {
+            "" => carried.clone(),
+            partial => format!("{} {}", carried, partial),
+        };
+        if let Some(stable) = &mut result.stable_text {
+            *stable = carry(stable);
+        }
+        result.text = carry(&result.text);
+    }
+
+    /// Takes the next result, holding back partials that exceed the partial rate
+    fn next_throttled_result(&mut self) -> Result<Option<StreamingResult>, ScribeError> {
+        let Some(hz) = self.partial_rate else {
+            return self.next_stable_result();
+        };
+        let interval = Duration::from_secs_f64(1.0 / f64::from(hz));
+        let due = |last: Option<Instant>| last.is_none_or(|last| last.elapsed() >= interval);
+
+        while let Some(result) = self.next_stable_result()? {
+            if result.is_final {
+                self.held_partial = None;
+                return Ok(Some(result));
+            }
+            if result.kind == ResultKind::EndOfSpeech {
+                return Ok(Some(result));
+            }
+            if due(self.last_partial_at) {
+                self.held_partial = None;
+                self.last_partial_at = Some(Instant::now());
+                return Ok(Some(result));
+            }
+            self.held_partial = Some(result);
+        }
+
+        if self.held_partial.is_some() && due(self.last_partial_at) {
+            self.last_partial_at = Some(Instant::now());
+            return Ok(self.held_partial.take());
+        }
+        Ok(None)
+    }
+
+    /// Takes the next result, holding back partials that haven't settled yet
+    fn next_stable_result(&mut self) -> Result<Option<StreamingResult>, ScribeError> {
+        if self.stability.is_none() {
+            return self.next_result();
+        }
+        while let Some(result) = self.next_result()? {
+            let gate = self.stability.as_mut().expect("stability gate is set");
+            if let Some(result) = gate.push(result, Instant::now()) {
+                return Ok(Some(result));
+            }
+        }
+        Ok(self
+            .stability
+            .as_mut()
+            .and_then(|gate| gate.confirmed(Instant::now())))
+    }
+
+    /// Asks the helper to finish once the session deadline has passed
+    ///
+    /// Returns whether the session is past its deadline. A helper that is still
+    /// running [`FINISH_GRACE`] after being asked to finish, for whatever
+    /// reason, is killed.
+    fn deadline_passed(&mut self) -> bool {
+        let now = Instant::now();
+        let passed = self.deadline.is_some_and(|deadline| now >= deadline);
+        if passed && self.finish_by.is_none() {
+            log::debug!("Session deadline reached; finalizing");
+            self.request_finish(Some(FINISH_GRACE));
+        }
+        if self.finish_by.is_some_and(|finish_by| now >= finish_by) {
+            if let Some(process) = self.process.as_mut() {
+                let _ = process.kill();
+            }
+        }
+        passed
+    }
+
+    /// Ends the audio and asks the helper to finish recognizing what it has
+    ///
+    /// With a `grace` period, a helper still running that long afterwards is
+    /// killed. A helper that has no control pipe can only be told through its
+    /// input, so it is then given no grace period. On a socket connection the
+    /// sending half of the socket is shut down.
+    fn request_finish(&mut self, mut grace: Option<Duration>) {
+        if !self.input_finished {
+            self.input_finished = true;
+            #[cfg(feature = "async")]
+            if let Some(pipes) = self.async_pipes.as_mut() {
+                pipes.input = None;
+            }
+            self.writer = None;
+            let requested = match (&self.socket_path, self.output_fd) {
+                // The peer sees the audio end as the end of its input. Dropping
+                // the writer alone doesn't do that, as the reader shares the socket
+                // SAFETY: `fd` is the connected socket, open until `stop`.
+                (Some(_), Some(fd)) => unsafe { libc::shutdown(fd, libc::SHUT_WR) == 0 },
+                _ => self
+                    .control
+                    .as_mut()
+                    .is_some_and(|control| writeln!(control, "finish").is_ok()),
+            };
+            if !requested {
+                grace = grace.map(|_| Duration::ZERO);
+            }
+        }
+        if self.finish_by.is_none() {
+            self.finish_by = grace.map(|grace| Instant::now() + grace);
+        }
+    }
+
+    /// Takes the next result from the pending queue or the helper's output
+    fn next_result(&mut self) -> Result<Option<StreamingResult>, ScribeError> {
+        #[cfg(all(feature = "system-audio", target_os = "macos"))]
+        self.feed_system_audio()?;
+        self.feed_tailed_file()?;
+        if let Some(result) = self.pending.pop_front() {
+            return Ok(Some(result));
+        }
+        let deadline_passed = self.deadline_passed();
+
+        let reader = self.reader.as_mut().ok_or(ScribeError::NotStarted)?;
+
+        let mut byte = [0u8; 1];
+        loop {
+            match reader.read(&mut byte) {
+                Ok(0) => {
+                    // EOF - process ended; hand over any sentence still being assembled
+                    self.output_ended = true;
+                    if let Some(rest) = self.segmenter.as_mut().and_then(SentenceSegmenter::flush) {
+                        return Ok(Some(rest));
+                    }
+                    if self.tail_replaced() {
+                        self.restart_tail()?;
+                        return Ok(None);
+                    }
+                    if deadline_passed {
+                        return Err("Session deadline reached".into());
+                    }
+                    if self.permission_revoked() {
+                        return Err(ScribeError::PermissionRevoked);
+                    }
+                    return Err(ScribeError::StreamEnded);
+                }
+                Ok(_) => {
+                    if byte[0] == b'\n' {
+                        let line = String::from_utf8_lossy(&self.partial_line)
+                            .trim()
+                            .to_string();
+                        self.partial_line.clear();
+                        if std::mem::take(&mut self.line_overflowed) {
+                            Self::malformed_line(self.strict_parsing, format!(
+                                "Helper output line exceeds {} bytes; the stream is likely corrupted",
+                                MAX_LINE_LEN
+                            ).into())?;
+                            continue;
+                        }
+                        if let Ok(Pong { pong }) = serde_json::from_str(&line) {
+                            self.last_pong = pong;
+                            continue;
+                        }
+                        if let Ok(reply) = serde_json::from_str::<LocaleReply>(&line) {
+                            self.locale_reply = Some(reply);
+                            continue;
+                        }
+                        if !line.is_empty() {
+                            self.fed_since_result = 0;
+                            self.last_result_at = Instant::now();
+                            let parsed =
+                                serde_json::from_str::<StreamingResult>(&line).map_err(|source| {
+                                    ScribeError::ParseResult {
+                                        line: line.clone(),
+                                        source,
+                                    }
+                                });
+                            self.last_raw_line = Some(line);
+                            let mut result = match parsed {
+                                Ok(result) => result,
+                                Err(e) => {
+                                    Self::malformed_line(self.strict_parsing, e)?;
+                                    continue;
+                                }
+                            };
+                            if result.is_final && result.kind == ResultKind::Volatile {
+                                result.kind = ResultKind::Final;
+                            }
+                            if !result.is_final {
+                                result.alternatives.clear();
+                            }
+                            if !self.volatile_tail
+                                && !Self::strip_volatile_tail(&mut self.stable_partial, &mut result)
+                            {
+                                continue;
+                            }
+
+                            let Some(segmenter) = self.segmenter.as_mut() else {
+                                return Ok(Some(result));
+                            };
+                            self.pending.extend(segmenter.push(result));
+                            if let Some(result) = self.pending.pop_front() {
+                                return Ok(Some(result));
+                            }
+                        }
+                    } else if self.partial_line.len() < MAX_LINE_LEN {
+                        self.partial_line.push(byte[0]);
+                    } else {
+                        self.line_overflowed = true;
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    // No data available yet - return immediately (non-blocking)
+                    return Ok(None);
+                }
+                Err(e) => return Err(format!("Failed to read from helper: {}", e).into()),
+            }
+        }
+    }
+
+    /// Cuts a partial down to its settled text, returning whether it adds anything
+    ///
+    /// `previous` is the settled text last passed on in the current segment.
+    /// Finals, end of speech events and partials the helper didn't mark are kept
+    /// as they are.
+    fn strip_volatile_tail(previous: &mut String, result: &mut StreamingResult) -> bool {
+        if result.is_final {
+            previous.clear();
+            return true;
+        }
+        let Some(stable) = &result.stable_text else {
+            return true;
+        };
+        if stable == previous {
+            return false;
+        }
+        if let Some(words) = &mut result.words {
+            words.truncate(stable.split_whitespace().count());
+        }
+        result.text = stable.clone();
+        previous.clone_from(stable);
+        true
+    }
+
+    /// Fails on a malformed output line when parsing strictly, or logs it to be skipped
+    fn malformed_line(strict: bool, error: ScribeError) -> Result<(), ScribeError> {
+        if strict {
+            return Err(error);
+        }
+        log::warn!("Skipping malformed helper output: {}", error);
+        Ok(())
+    }
+
+    /// Checks that the helper is still responsive and measures its round-trip time
+    ///
+    /// [`is_running`](Self::is_running) only reports that a session exists; a
+    /// helper that has deadlocked still counts as running. This sends a ping over
+    /// a control pipe, separate from the audio, and waits up to five seconds for
+    /// the helper to answer on its output. Results that arrive meanwhile are kept
+    /// and returned by the next [`poll_result`](Self::poll_result) calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transcriber isn't started, is connected to a socket
+    /// (which has no control channel), or the helper exits or doesn't answer in
+    /// time. A supervisor can then [`stop`](Self::stop) and
+    /// [`start`](Self::start) a fresh helper.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// if let Err(e) = transcriber.ping() {
+    ///     eprintln!("Helper is unresponsive, restarting: {}", e);
+    ///     transcriber.stop().unwrap();
+    ///     transcriber.start().unwrap();
+    /// }
+    /// ```
+    pub fn ping(&mut self) -> Result<Duration, ScribeError> {
+        if self.socket_path.is_some() {
+            return Err(ScribeError::UnsupportedOnSocket("Ping"));
+        }
+        let control = self.control.as_mut().ok_or(ScribeError::NotStarted)?;
+
+        self.pings_sent += 1;
+        let id = self.pings_sent;
+        let sent = Instant::now();
+        writeln!(control, "ping {}", id).map_err(|e| format!("Failed to send ping: {}", e))?;
+        let rtt = self.await_control_reply(sent, PING_TIMEOUT, "ping", |transcriber| {
+            transcriber.last_pong >= id
+        })?;
+        Ok(rtt)
+    }
+
+    /// Switches the recognition locale without ending the session
+    ///
+    /// For live multilingual audio where the caller detects a change of
+    /// language. The helper finalizes everything it has recognized so far,
+    /// then recognizes from there on in `locale` (a BCP 47 identifier such as
+    /// `"zh-CN"`), keeping the microphone or audio input open, so there is no
+    /// gap as with [`stop`](Self::stop) and [`start`](Self::start). Results of
+    /// the old locale arrive before this returns or are kept for
+    /// [`poll_result`](Self::poll_result), ahead of any in the new one. Audio
+    /// fed before the call that the helper hasn't analyzed yet is recognized in
+    /// the new locale.
+    ///
+    /// The locale's speech model must already be installed, as downloading it
+    /// would stall the session; check with
+    /// [`Transcriber::ensure_locale_available`] beforehand. The switch lasts
+    /// for the current session. Only the SpeechAnalyzer backend (macOS 26+)
+    /// can switch; older systems return an error and keep the old locale.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transcriber isn't started, is connected to a
+    /// socket (which has no control channel), `locale` isn't a valid
+    /// identifier, or the helper can't switch or doesn't answer within ten
+    /// seconds. The session keeps running in the old locale after a refused
+    /// switch.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    /// // ... the speakers move to Mandarin ...
+    /// transcriber.set_locale("zh-CN").unwrap();
+    /// ```
+    pub fn set_locale(&mut self, locale: &str) -> Result<(), ScribeError> {
+        if self.socket_path.is_some() {
+            return Err(ScribeError::UnsupportedOnSocket("Switching locale"));
+        }
+        if locale.is_empty() || locale.contains(char::is_whitespace) {
+            return Err(format!("Invalid locale identifier: {:?}", locale).into());
+        }
+        let control = self.control.as_mut().ok_or(ScribeError::NotStarted)?;
+
+        self.locale_reply = None;
+        let sent = Instant::now();
+        writeln!(control, "locale {}", locale)
+            .map_err(|e| format!("Failed to send locale switch: {}", e))?;
+        self.await_control_reply(
+            sent,
+            LOCALE_SWITCH_TIMEOUT,
+            "locale switch",
+            |transcriber| transcriber.locale_reply.is_some(),
+        )?;
+        match self.locale_reply.take().and_then(|reply| reply.error) {
+            Some(error) => {
+                Err(format!("Helper could not switch to locale {}: {}", locale, error).into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Reads helper output until `answered` holds, returning how long it took
+    ///
+    /// Results that arrive meanwhile are kept for `poll_result`.
+    fn await_control_reply(
+        &mut self,
+        sent: Instant,
+        timeout: Duration,
+        what: &str,
+        answered: impl Fn(&Self) -> bool,
+    ) -> Result<Duration, ScribeError> {
+        let mut held = Vec::new();
+        let outcome = loop {
+            if answered(self) {
+                break Ok(sent.elapsed());
+            }
+            if sent.elapsed() >= timeout {
+                break Err(format!("Helper did not answer {} within {:?}", what, timeout).into());
+            }
+            match self.next_result() {
+                Ok(Some(result)) => held.push(result),
+                Ok(None) => std::thread::sleep(Duration::from_millis(1)),
+                Err(e) => break Err(e),
+            }
+        };
+
+        // Queued results were taken first, so this restores the original order
+        for result in held.into_iter().rev() {
+            self.pending.push_front(result);
+        }
+        outcome
+    }
+
+    /// Keeps the helper's recognition session alive through a gap in the audio
+    ///
+    /// When a source pauses for minutes, a recognizer that hears nothing for
+    /// long enough may end its session, and audio fed afterwards is lost. Call
+    /// this periodically while the source is quiet, e.g. every few seconds: it
+    /// sends 100ms of digital silence, which keeps the session going without
+    /// producing results. It bypasses
+    /// [`with_skip_silence`](StreamingTranscriberBuilder::with_skip_silence)
+    /// and the input format check, and is recorded by
+    /// [`with_capture_fed_audio`](StreamingTranscriberBuilder::with_capture_fed_audio)
+    /// like any other audio the helper receives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transcriber isn't started or isn't in
+    /// programmatic mode, if the session is fed with
+    /// [`feed_encoded`](Self::feed_encoded), or if the write fails as in
+    /// [`feed_audio_i16`](Self::feed_audio_i16).
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use swift_scribe::StreamingTranscriber;
     ///
-    /// let transcriber = StreamingTranscriber::builder()
+    /// let mut transcriber = StreamingTranscriber::builder()
     ///     .with_programmatic_input()
     ///     .build()
     ///     .unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// // While the stream is paused
+    /// transcriber.keep_alive().unwrap();
     /// ```
-    pub fn builder() -> StreamingTranscriberBuilder {
-        StreamingTranscriberBuilder::new()
+    pub fn keep_alive(&mut self) -> Result<(), ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "keep_alive can only be used with programmatic input mode".to_string(),
+            ));
+        }
+        if self.deadline_passed() {
+            return Err("Session deadline reached".into());
+        }
+        if self.input_finished {
+            return Err("Audio input already finished".into());
+        }
+        #[cfg(feature = "async")]
+        self.ensure_blocking_input("keep_alive")?;
+        if self.encoded_format.is_some() {
+            return Err("keep_alive can't be used in a session fed with feed_encoded".into());
+        }
+        let writer = self.writer.as_mut().ok_or(ScribeError::NotStarted)?;
+
+        let silence = [0i16; KEEP_ALIVE_SAMPLES];
+        let written = write_pcm(writer, &silence).and_then(|()| writer.flush());
+        written.map_err(|e| self.write_error(e))?;
+        self.record_fed_audio(&silence);
+        self.fed_since_result += silence.len() as u64;
+        Ok(())
     }
 
-    /// Creates a new streaming transcriber with default settings (microphone input)
+    /// Polls for the next change to the displayed transcript
     ///
-    /// This is a convenience method equivalent to `StreamingTranscriber::builder().build()`.
+    /// Instead of the full (possibly revised) partial, returns a [`TextDelta`]
+    /// describing how to edit a display buffer holding everything shown so far:
+    /// final segments followed by the current partial. Results that don't change
+    /// the text are skipped. This is what a terminal or text widget needs for
+    /// flicker-free incremental updates.
     ///
-    /// Looks for the helper binary in the following locations (in order):
-    /// 1. `./helpers/transcribe_stream` (local development)
-    /// 2. `~/.local/bin/transcribe_stream` (user install)
-    /// 3. `/usr/local/bin/transcribe_stream` (system install)
+    /// Results taken with [`poll_result`](Self::poll_result) are not seen by the
+    /// delta tracking, so use one or the other for a session.
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// Returns an error if the helper binary cannot be found.
-    pub fn new() -> Result<Self, String> {
-        Self::builder().build()
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// let mut display = String::new();
+    /// loop {
+    ///     if let Some(delta) = transcriber.poll_delta().unwrap() {
+    ///         delta.apply_to(&mut display);
+    ///         println!("{}", display);
+    ///     }
+    /// }
+    /// ```
+    pub fn poll_delta(&mut self) -> Result<Option<TextDelta>, ScribeError> {
+        // The tracker keeps the finals itself, so it takes results uncarried
+        while let Some(result) = self.next_recorded_result()? {
+            if let Some(delta) = self.delta.update(&result) {
+                return Ok(Some(delta));
+            }
+        }
+        Ok(None)
     }
 
-    /// Creates a new streaming transcriber with a custom helper binary path and microphone input
+    /// Feeds i16 PCM audio samples to the transcriber
     ///
-    /// This is a convenience method equivalent to `StreamingTranscriber::builder().with_helper_path(path).build()`.
+    /// Only available when using programmatic audio input mode.
+    /// Audio is automatically converted to mono and resampled to 16kHz if needed.
+    /// Consecutive calls are treated as one continuous stream, so resampling stays
+    /// continuous across chunk boundaries. If `sample_rate` or `channels` changes
+    /// mid-session, the configured [`FormatChangePolicy`] decides whether the
+    /// resampler restarts at the new format or the chunk is rejected.
     ///
     /// # Arguments
     ///
-    /// * `path` - Path to the transcribe_stream helper binary
+    /// * `samples` - Audio samples in i16 PCM format
+    /// * `sample_rate` - Sample rate in Hz (e.g., 16000, 48000)
+    /// * `channels` - Number of audio channels (1 for mono, 2 for stereo, etc.)
     ///
     /// # Errors
     ///
-    /// Returns an error if the specified path does not exist.
-    pub fn with_helper_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
-        Self::builder().with_helper_path(path).build()
-    }
-
-    /// Starts the streaming transcription
-    ///
-    /// - For microphone input: Launches the helper process and begins capturing from the microphone
-    /// - For programmatic input: Launches the helper in stdin mode, ready to receive audio samples
-    ///
-    /// Call `poll_result()` to retrieve transcription results.
-    /// For programmatic input, call `feed_audio_*()` methods to send audio samples.
+    /// Returns an error if:
+    /// - Transcriber is in microphone mode (not programmatic)
+    /// - Transcriber hasn't been started
+    /// - The input format changed and the policy is [`FormatChangePolicy::Error`]
+    /// - The helper's backlog exceeds the limit and the policy is
+    ///   [`BackpressurePolicy::Error`]
+    /// - The helper has exited: [`ScribeError::HelperExited`], with its exit
+    ///   status and last stderr lines
+    /// - Writing to the helper process fails for another reason
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// Returns an error if:
-    /// - The helper process fails to start
-    /// - Permissions haven't been granted (for microphone input)
+    /// A [`BackpressureWarning`] when audio is being fed faster than the helper
+    /// keeps up with (see [`StreamingTranscriberBuilder::with_backlog_limit`]);
+    /// throttle feeding until it stops appearing. An empty chunk, or a silent one
+    /// with [`StreamingTranscriberBuilder::with_skip_silence`], returns `Ok(None)`
+    /// without writing anything.
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use swift_scribe::StreamingTranscriber;
     ///
-    /// // Microphone input
-    /// let mut transcriber = StreamingTranscriber::new().unwrap();
-    /// transcriber.start().unwrap();
-    ///
-    /// // Programmatic input
     /// let mut transcriber = StreamingTranscriber::builder()
     ///     .with_programmatic_input()
     ///     .build()
     ///     .unwrap();
     /// transcriber.start().unwrap();
+    ///
+    /// let samples = vec![0i16; 4096];
+    /// if let Some(warning) = transcriber.feed_audio_i16(&samples, 48000, 2).unwrap() {
+    ///     eprintln!("Helper is {:?} behind, slowing down", warning.backlog);
+    /// }
     /// ```
-    pub fn start(&mut self) -> Result<(), String> {
-        let mut cmd = Command::new(&self.helper_path);
-        cmd.stdout(Stdio::piped()).stderr(Stdio::inherit());
+    pub fn feed_audio_i16(
+        &mut self,
+        samples: &[i16],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        #[cfg(feature = "async")]
+        self.ensure_blocking_input("feed_audio_i16")?;
+        let (pcm, fed, warning) =
+            match self.prepare_pcm(samples, sample_rate, channels, "feed_audio_i16")? {
+                PcmFeed::Done(warning) => return Ok(warning),
+                PcmFeed::Write { pcm, fed, warning } => (pcm, fed, warning),
+            };
+        let writer = self.writer.as_mut().expect("checked when preparing");
+        let written = write_pcm(writer, &pcm).and_then(|()| writer.flush());
+        written.map_err(|e| self.write_error(e))?;
+        self.record_fed_audio(&pcm);
+        self.fed_since_result = fed;
+        Ok(warning)
+    }
 
-        match self.input_mode {
-            AudioInputMode::Microphone => {}
-            AudioInputMode::Programmatic => {
-                cmd.arg("--stdin").stdin(Stdio::piped());
-            }
+    /// Checks and converts fed i16 audio, deciding what to write to the helper
+    ///
+    /// `method` names the public method for the input mode error.
+    fn prepare_pcm<'a>(
+        &mut self,
+        samples: &'a [i16],
+        sample_rate: u32,
+        channels: u16,
+        method: &str,
+    ) -> Result<PcmFeed<'a>, ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(format!(
+                "{} can only be used with programmatic input mode",
+                method
+            )));
+        }
+        if self.deadline_passed() {
+            return Err("Session deadline reached".into());
+        }
+        if self.input_finished {
+            return Err("Audio input already finished".into());
         }
 
-        let mut child = cmd.spawn().map_err(|e| {
-            format!(
-                "Failed to start streaming helper at {}: {}",
-                self.helper_path.display(),
-                e
+        if self.writer.is_none() {
+            return Err(ScribeError::NotStarted);
+        }
+        if let Some(encoded) = self.encoded_format {
+            return Err(format!(
+                "Can't feed PCM after {} audio in the same session",
+                encoded.name()
             )
-        })?;
+            .into());
+        }
+        if samples.is_empty() {
+            return Ok(PcmFeed::Done(None));
+        }
 
-        let stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| "Failed to capture stdout".to_string())?;
+        let format = (sample_rate, channels);
+        if let Some(previous) = self.input_format.filter(|&previous| previous != format) {
+            if self.format_change == FormatChangePolicy::Error {
+                return Err(format!(
+                    "Input format changed mid-session from {} Hz/{} ch to {} Hz/{} ch",
+                    previous.0, previous.1, sample_rate, channels
+                )
+                .into());
+            }
+            self.resampler.reset();
+        }
+        self.channel_mix.check(usize::from(channels))?;
+        self.input_format = Some(format);
 
-        #[cfg(unix)]
+        if self.skip_silence
+            && samples
+                .iter()
+                .all(|s| s.unsigned_abs() <= SILENCE_THRESHOLD)
         {
-            use std::os::unix::io::AsRawFd;
-            let fd = stdout.as_raw_fd();
-            unsafe {
-                let flags = libc::fcntl(fd, libc::F_GETFL);
-                if flags != -1 {
-                    libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
-                }
-            }
+            let frames = samples.len() / usize::from(channels.max(1));
+            self.skipped_silence += Duration::from_secs_f64(frames as f64 / sample_rate as f64);
+            return Ok(PcmFeed::Done(None));
         }
 
-        self.stdout = Some(stdout);
+        // 16kHz mono input is borrowed all the way through to the write
+        let resampled = match Self::to_mono_i16(samples, channels, &self.channel_mix) {
+            Cow::Borrowed(mono) => self.resampler.process(mono, sample_rate),
+            Cow::Owned(mono) => Cow::Owned(self.resampler.process(&mono, sample_rate).into_owned()),
+        };
 
-        if matches!(self.input_mode, AudioInputMode::Programmatic) {
-            let stdin = child
-                .stdin
-                .take()
-                .ok_or_else(|| "Failed to capture stdin".to_string())?;
-            self.stdin = Some(stdin);
+        let fed = self.fed_since_result + resampled.len() as u64;
+        let fed_secs = fed as f64 / TARGET_SAMPLE_RATE as f64;
+        let backlog = fed_secs - self.last_result_at.elapsed().as_secs_f64();
+        let mut warning = None;
+        if backlog > self.backlog_limit.as_secs_f64() {
+            let backlog = Duration::from_secs_f64(backlog);
+            match self.backpressure {
+                BackpressurePolicy::Block => {}
+                BackpressurePolicy::Error => {
+                    return Err(format!(
+                        "Helper backlog of {:.1}s exceeds the {:.1}s limit; feed audio more slowly",
+                        backlog.as_secs_f64(),
+                        self.backlog_limit.as_secs_f64()
+                    )
+                    .into());
+                }
+                BackpressurePolicy::Drop => {
+                    return Ok(PcmFeed::Done(Some(BackpressureWarning {
+                        backlog,
+                        dropped: true,
+                    })));
+                }
+            }
+            warning = Some(BackpressureWarning {
+                backlog,
+                dropped: false,
+            });
         }
 
-        self.process = Some(child);
-        self.line_buffer.clear();
-
-        Ok(())
+        Ok(PcmFeed::Write {
+            pcm: resampled,
+            fed,
+            warning,
+        })
     }
 
-    /// Polls for the next transcription result
+    /// Feeds f32 audio samples to the transcriber
     ///
-    /// This is a non-blocking call that returns immediately:
-    /// - `Ok(Some(result))` if a new result is available
-    /// - `Ok(None)` if no result is ready yet
-    /// - `Err(_)` if an error occurred
+    /// Only available when using programmatic audio input mode.
+    /// Audio is automatically converted from f32 (-1.0 to 1.0) to i16 PCM,
+    /// resampled to 16kHz, and converted to mono if needed.
     ///
-    /// Results can be partial (volatile) or final. Check `result.is_final`
-    /// to determine if the transcription is complete for that segment.
+    /// # Arguments
+    ///
+    /// * `samples` - Audio samples in f32 format (range: -1.0 to 1.0)
+    /// * `sample_rate` - Sample rate in Hz (e.g., 16000, 48000)
+    /// * `channels` - Number of audio channels (1 for mono, 2 for stereo, etc.)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Transcriber is in microphone mode (not programmatic)
+    /// - Transcriber hasn't been started
+    /// - The input format changed and the policy is [`FormatChangePolicy::Error`]
+    /// - The helper's backlog exceeds the limit and the policy is
+    ///   [`BackpressurePolicy::Error`]
+    /// - The helper has exited: [`ScribeError::HelperExited`], with its exit
+    ///   status and last stderr lines
+    /// - Writing to the helper process fails for another reason
     ///
     /// # Returns
     ///
-    /// - `Ok(Some(StreamingResult))` - New transcription result available
-    /// - `Ok(None)` - No new result, try again later
-    /// - `Err(String)` - Error occurred during polling
+    /// A [`BackpressureWarning`] when the helper is falling behind, as for
+    /// [`feed_audio_i16`](Self::feed_audio_i16).
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use swift_scribe::StreamingTranscriber;
-    /// use std::thread;
-    /// use std::time::Duration;
     ///
-    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
     /// transcriber.start().unwrap();
     ///
-    /// loop {
-    ///     match transcriber.poll_result() {
-    ///         Ok(Some(result)) => {
-    ///             println!("[{}] {}", if result.is_final { "FINAL" } else { "partial" }, result.text);
-    ///         }
-    ///         Ok(None) => thread::sleep(Duration::from_millis(10)),
-    ///         Err(e) => {
-    ///             eprintln!("Error: {}", e);
-    ///             break;
-    ///         }
-    ///     }
-    /// }
+    /// let samples = vec![0.0f32; 4096];
+    /// transcriber.feed_audio_f32(&samples, 48000, 2).unwrap();
     /// ```
-    pub fn poll_result(&mut self) -> Result<Option<StreamingResult>, String> {
-        use std::io::Read;
+    pub fn feed_audio_f32(
+        &mut self,
+        samples: &[f32],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "feed_audio_f32 can only be used with programmatic input mode".to_string(),
+            ));
+        }
 
-        let stdout = self
-            .stdout
-            .as_mut()
-            .ok_or_else(|| "Transcriber not started".to_string())?;
+        let i16_samples = Self::f32_to_i16(samples);
+        self.feed_audio_i16(&i16_samples, sample_rate, channels)
+    }
 
-        let mut byte = [0u8; 1];
-        loop {
-            match stdout.read(&mut byte) {
-                Ok(0) => {
-                    // EOF - process ended
-                    return Err("Streaming process ended".to_string());
-                }
-                Ok(_) => {
-                    let ch = byte[0] as char;
-                    if ch == '\n' {
-                        let line = self.line_buffer.trim();
-                        if !line.is_empty() {
-                            let result: StreamingResult = serde_json::from_str(line)
-                                .map_err(|e| format!("Failed to parse result: {}", e))?;
-                            self.line_buffer.clear();
-                            return Ok(Some(result));
-                        }
-                        self.line_buffer.clear();
-                    } else {
-                        self.line_buffer.push(ch);
-                    }
-                }
-                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    // No data available yet - return immediately (non-blocking)
-                    return Ok(None);
-                }
-                Err(e) => return Err(format!("Failed to read from helper: {}", e)),
-            }
-        }
+    /// Feeds i16 PCM audio in the format declared with
+    /// [`StreamingTranscriberBuilder::with_input_format`]
+    ///
+    /// Same as [`feed_audio_i16`](Self::feed_audio_i16) with the declared
+    /// sample rate and channel count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no input format was declared, or for the same
+    /// reasons as [`feed_audio_i16`](Self::feed_audio_i16).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .with_input_format(48000, 2)
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// let samples = vec![0i16; 4096];
+    /// transcriber.feed_audio_i16_fmt(&samples).unwrap();
+    /// ```
+    pub fn feed_audio_i16_fmt(
+        &mut self,
+        samples: &[i16],
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        let (sample_rate, channels) = self.declared_format()?;
+        self.feed_audio_i16(samples, sample_rate, channels)
     }
 
-    /// Feeds i16 PCM audio samples to the transcriber
+    /// Feeds f32 audio in the format declared with
+    /// [`StreamingTranscriberBuilder::with_input_format`]
     ///
-    /// Only available when using programmatic audio input mode.
-    /// Audio is automatically resampled to 16kHz and converted to mono if needed.
+    /// Same as [`feed_audio_f32`](Self::feed_audio_f32) with the declared
+    /// sample rate and channel count.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no input format was declared, or for the same
+    /// reasons as [`feed_audio_f32`](Self::feed_audio_f32).
+    pub fn feed_audio_f32_fmt(
+        &mut self,
+        samples: &[f32],
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        let (sample_rate, channels) = self.declared_format()?;
+        self.feed_audio_f32(samples, sample_rate, channels)
+    }
+
+    fn declared_format(&self) -> Result<(u32, u16), String> {
+        self.declared_format.ok_or_else(|| {
+            "No input format declared; use with_input_format or pass the format explicitly"
+                .to_string()
+        })
+    }
+
+    /// Feeds planar (non-interleaved) f32 audio to the transcriber
+    ///
+    /// Only available when using programmatic audio input mode. Takes one slice
+    /// per channel, as CoreAudio and AVFoundation hand out multichannel buffers,
+    /// and averages them straight to mono without interleaving first. The mono
+    /// audio is then converted to i16 PCM and resampled to 16kHz.
     ///
     /// # Arguments
     ///
-    /// * `samples` - Audio samples in i16 PCM format
+    /// * `channels` - One slice of samples per channel (range: -1.0 to 1.0)
     /// * `sample_rate` - Sample rate in Hz (e.g., 16000, 48000)
-    /// * `channels` - Number of audio channels (1 for mono, 2 for stereo, etc.)
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - Transcriber is in microphone mode (not programmatic)
-    /// - Transcriber hasn't been started
-    /// - Writing to the helper process fails
+    /// Returns an error if `channels` is empty or the slices differ in length,
+    /// or for the same reasons as [`feed_audio_f32`](Self::feed_audio_f32).
+    ///
+    /// # Returns
+    ///
+    /// A [`BackpressureWarning`] when the helper is falling behind, as for
+    /// [`feed_audio_i16`](Self::feed_audio_i16).
     ///
     /// # Examples
     ///
@@ -604,58 +4951,108 @@ impl StreamingTranscriber {
     ///     .unwrap();
     /// transcriber.start().unwrap();
     ///
-    /// let samples = vec![0i16; 4096];
-    /// transcriber.feed_audio_i16(&samples, 48000, 2).unwrap();
+    /// let left = vec![0.0f32; 4096];
+    /// let right = vec![0.0f32; 4096];
+    /// transcriber.feed_audio_f32_planar(&[&left, &right], 48000).unwrap();
     /// ```
-    pub fn feed_audio_i16(&mut self, samples: &[i16], sample_rate: u32, channels: u16) -> Result<(), String> {
+    pub fn feed_audio_f32_planar(
+        &mut self,
+        channels: &[&[f32]],
+        sample_rate: u32,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
         if !matches!(self.input_mode, AudioInputMode::Programmatic) {
-            return Err("feed_audio_i16 can only be used with programmatic input mode".to_string());
+            return Err(ScribeError::WrongInputMode(
+                "feed_audio_f32_planar can only be used with programmatic input mode".to_string(),
+            ));
         }
 
-        let stdin = self
-            .stdin
-            .as_mut()
-            .ok_or_else(|| "Transcriber not started".to_string())?;
-
-        let resampled = Self::resample_i16(samples, sample_rate, channels);
-        let mono = Self::to_mono_i16(&resampled, channels);
+        self.channel_mix.check(channels.len())?;
+        let mono = Self::planar_to_mono(channels, &self.channel_mix)?;
+        let i16_samples = Self::f32_to_i16(&mono);
+        self.feed_audio_i16(&i16_samples, sample_rate, 1)
+    }
 
-        let bytes: Vec<u8> = mono
-            .iter()
-            .flat_map(|&sample| sample.to_le_bytes().to_vec())
-            .collect();
+    /// Feeds 8-bit unsigned audio samples to the transcriber
+    ///
+    /// Only available when using programmatic audio input mode.
+    /// Audio is automatically converted from unsigned 8-bit (silence at 128) to
+    /// i16 PCM, resampled to 16kHz, and converted to mono if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `samples` - Audio samples in unsigned 8-bit format (range: 0 to 255)
+    /// * `sample_rate` - Sample rate in Hz (e.g., 8000, 11025)
+    /// * `channels` - Number of audio channels (1 for mono, 2 for stereo, etc.)
+    ///
+    /// # Errors
+    ///
+    /// The same as [`feed_audio_f32`](Self::feed_audio_f32).
+    ///
+    /// # Returns
+    ///
+    /// A [`BackpressureWarning`] when the helper is falling behind, as for
+    /// [`feed_audio_i16`](Self::feed_audio_i16).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// let samples = vec![128u8; 4096];
+    /// transcriber.feed_audio_u8(&samples, 8000, 1).unwrap();
+    /// ```
+    pub fn feed_audio_u8(
+        &mut self,
+        samples: &[u8],
+        sample_rate: u32,
+        channels: u16,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "feed_audio_u8 can only be used with programmatic input mode".to_string(),
+            ));
+        }
 
-        stdin
-            .write_all(&bytes)
-            .map_err(|e| format!("Failed to write audio to helper: {}", e))?;
-        stdin
-            .flush()
-            .map_err(|e| format!("Failed to flush audio: {}", e))
+        let i16_samples = Self::u8_to_i16(samples);
+        self.feed_audio_i16(&i16_samples, sample_rate, channels)
     }
 
-    /// Feeds f32 audio samples to the transcriber
-    ///
-    /// Only available when using programmatic audio input mode.
-    /// Audio is automatically converted from f32 (-1.0 to 1.0) to i16 PCM,
-    /// resampled to 16kHz, and converted to mono if needed.
+    /// Feeds encoded audio for the helper to decode
     ///
-    /// # Arguments
+    /// Only available when using programmatic audio input mode. `data` is the next
+    /// chunk of a CAF, AIFF or WAV byte stream, split anywhere: the first chunk of
+    /// the session must start with the container header, and later chunks
+    /// continue it. The helper detects the header and decodes the stream itself,
+    /// so audio from a capture library that produces encoded chunks doesn't need
+    /// to be decoded to PCM in Rust first.
     ///
-    /// * `samples` - Audio samples in f32 format (range: -1.0 to 1.0)
-    /// * `sample_rate` - Sample rate in Hz (e.g., 16000, 48000)
-    /// * `channels` - Number of audio channels (1 for mono, 2 for stereo, etc.)
+    /// A session carries either PCM from `feed_audio_*` or a single encoded
+    /// stream; [`start`](Self::start) begins a new session.
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - Transcriber is in microphone mode (not programmatic)
     /// - Transcriber hasn't been started
-    /// - Writing to the helper process fails
+    /// - `format` can't be decoded on the fly: anything but CAF, AIFF and WAV,
+    ///   such as [`AudioFormat::M4a`], whose index may follow the audio
+    /// - The first chunk doesn't start with a `format` header, or the format
+    ///   differs from the session's stream
+    /// - PCM was already fed in this session
+    /// - The helper has exited: [`ScribeError::HelperExited`], with its exit
+    ///   status and last stderr lines
+    /// - Writing to the helper process fails for another reason
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use swift_scribe::StreamingTranscriber;
+    /// use swift_scribe::{AudioFormat, StreamingTranscriber};
     ///
     /// let mut transcriber = StreamingTranscriber::builder()
     ///     .with_programmatic_input()
@@ -663,16 +5060,132 @@ impl StreamingTranscriber {
     ///     .unwrap();
     /// transcriber.start().unwrap();
     ///
-    /// let samples = vec![0.0f32; 4096];
-    /// transcriber.feed_audio_f32(&samples, 48000, 2).unwrap();
+    /// let caf = std::fs::read("dictation.caf").unwrap();
+    /// for chunk in caf.chunks(8192) {
+    ///     transcriber.feed_encoded(chunk, AudioFormat::Caf).unwrap();
+    /// }
     /// ```
-    pub fn feed_audio_f32(&mut self, samples: &[f32], sample_rate: u32, channels: u16) -> Result<(), String> {
+    pub fn feed_encoded(&mut self, data: &[u8], format: AudioFormat) -> Result<(), ScribeError> {
         if !matches!(self.input_mode, AudioInputMode::Programmatic) {
-            return Err("feed_audio_f32 can only be used with programmatic input mode".to_string());
+            return Err(ScribeError::WrongInputMode(
+                "feed_encoded can only be used with programmatic input mode".to_string(),
+            ));
         }
+        if self.deadline_passed() {
+            return Err("Session deadline reached".into());
+        }
+        if self.input_finished {
+            return Err("Audio input already finished".into());
+        }
+        #[cfg(feature = "async")]
+        self.ensure_blocking_input("feed_encoded")?;
 
-        let i16_samples = Self::f32_to_i16(samples);
-        self.feed_audio_i16(&i16_samples, sample_rate, channels)
+        let writer = self.writer.as_mut().ok_or(ScribeError::NotStarted)?;
+
+        let Some(magic) = format.magic() else {
+            return Err(format!(
+                "The streaming helper can't decode {} on the fly; decode it to PCM and use feed_audio_i16",
+                format.name()
+            ).into());
+        };
+        match self.encoded_format {
+            Some(current) if current != format => {
+                return Err(format!(
+                    "Encoded format changed mid-session from {} to {}",
+                    current.name(),
+                    format.name()
+                )
+                .into());
+            }
+            Some(_) => {}
+            None if self.input_format.is_some() => {
+                return Err(format!(
+                    "Can't feed {} audio after PCM in the same session",
+                    format.name()
+                )
+                .into());
+            }
+            None if !data.starts_with(magic) => {
+                return Err(
+                    format!("Encoded audio must start with a {} header", format.name()).into(),
+                );
+            }
+            None => self.encoded_format = Some(format),
+        }
+
+        let written = writer.write_all(data).and_then(|()| writer.flush());
+        written.map_err(|e| self.write_error(e))
+    }
+
+    /// Whether the helper stopped because microphone access was revoked
+    ///
+    /// Called once its output has ended, so the helper is exiting; it reports the
+    /// revocation with [`PERMISSION_REVOKED_EXIT`] and a line on stderr.
+    fn permission_revoked(&mut self) -> bool {
+        let Some(process) = self.process.as_mut() else {
+            return false;
+        };
+        let deadline = Instant::now() + Duration::from_millis(500);
+        let status = loop {
+            match process.try_wait() {
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10))
+                }
+                Ok(status) => break status,
+                Err(_) => break None,
+            }
+        };
+        let Some(status) = status else {
+            return false;
+        };
+        if status.code() == Some(PERMISSION_REVOKED_EXIT) {
+            return true;
+        }
+        self.stderr
+            .as_mut()
+            .is_some_and(|stderr| stderr.finish().contains(PERMISSION_REVOKED_MESSAGE))
+    }
+
+    /// Explains a failed audio write, reaping the helper if it has exited
+    ///
+    /// A broken pipe almost always means the helper is gone, which callers need
+    /// to tell apart from other write failures to decide whether to restart.
+    fn write_error(&mut self, error: std::io::Error) -> ScribeError {
+        if error.kind() != std::io::ErrorKind::BrokenPipe {
+            return format!("Failed to write audio to helper: {}", error).into();
+        }
+        let Some(process) = self.process.as_mut() else {
+            return format!(
+                "Helper at {} closed the connection",
+                self.helper_path.display()
+            )
+            .into();
+        };
+
+        // The helper closes its input as it exits, so give it a moment to finish
+        let deadline = Instant::now() + Duration::from_millis(500);
+        let status = loop {
+            match process.try_wait() {
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10))
+                }
+                Ok(status) => break status,
+                Err(_) => break None,
+            }
+        };
+        let Some(status) = status else {
+            return "Helper closed its audio input but is still running".into();
+        };
+
+        let stderr = self
+            .stderr
+            .as_mut()
+            .map(StderrTail::finish)
+            .unwrap_or_default();
+        ScribeError::HelperExited {
+            status,
+            stderr: stderr.trim().to_string(),
+        }
     }
 
     fn f32_to_i16(samples: &[f32]) -> Vec<i16> {
@@ -685,59 +5198,176 @@ impl StreamingTranscriber {
             .collect()
     }
 
-    fn resample_i16(samples: &[i16], from_rate: u32, _channels: u16) -> Vec<i16> {
-        const TARGET_RATE: u32 = 16000;
-
-        if from_rate == TARGET_RATE {
-            return samples.to_vec();
+    fn planar_to_mono(channels: &[&[f32]], mix: &ChannelMix) -> Result<Vec<f32>, String> {
+        let Some(first) = channels.first() else {
+            return Err("Planar audio needs at least one channel".to_string());
+        };
+        if let Some((idx, channel)) = channels
+            .iter()
+            .enumerate()
+            .find(|(_, channel)| channel.len() != first.len())
+        {
+            return Err(format!(
+                "Planar channel {} has {} samples, but channel 0 has {}",
+                idx,
+                channel.len(),
+                first.len()
+            ));
         }
 
-        let ratio = TARGET_RATE as f64 / from_rate as f64;
-        let output_len = ((samples.len() as f64) * ratio).ceil() as usize;
-        let mut output = Vec::with_capacity(output_len);
-
-        for i in 0..output_len {
-            let src_pos = (i as f64) / ratio;
-            let src_idx = src_pos as usize;
-
-            if src_idx >= samples.len() {
-                break;
-            }
-
-            let frac = src_pos - src_idx as f64;
-
-            if src_idx + 1 < samples.len() {
-                let s0 = samples[src_idx] as f64;
-                let s1 = samples[src_idx + 1] as f64;
-                let interpolated = s0 + (s1 - s0) * frac;
-                output.push(interpolated.clamp(-32768.0, 32767.0) as i16);
-            } else {
-                output.push(samples[src_idx]);
-            }
-        }
+        let weights = mix.weights(channels.len());
+        Ok((0..first.len())
+            .map(|i| {
+                channels
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(channel, w)| channel[i] * w)
+                    .sum()
+            })
+            .collect())
+    }
 
-        output
+    fn u8_to_i16(samples: &[u8]) -> Vec<i16> {
+        samples.iter().map(|&s| (s as i16 - 128) << 8).collect()
     }
 
-    fn to_mono_i16(samples: &[i16], channels: u16) -> Vec<i16> {
+    /// Mixes interleaved audio down to mono; `mix` was checked against `channels`
+    fn to_mono_i16<'a>(samples: &'a [i16], channels: u16, mix: &ChannelMix) -> Cow<'a, [i16]> {
         if channels <= 1 {
-            return samples.to_vec();
+            return Cow::Borrowed(samples);
         }
 
         let channels = channels as usize;
         let frames = samples.len() / channels;
         let mut mono = Vec::with_capacity(frames);
 
-        for frame_idx in 0..frames {
-            let mut sum = 0i32;
-            for ch in 0..channels {
-                sum += samples[frame_idx * channels + ch] as i32;
+        if *mix == ChannelMix::Average {
+            for frame_idx in 0..frames {
+                let mut sum = 0i32;
+                for ch in 0..channels {
+                    sum += samples[frame_idx * channels + ch] as i32;
+                }
+                let avg = (sum / channels as i32).clamp(-32768, 32767) as i16;
+                mono.push(avg);
+            }
+        } else {
+            let weights = mix.weights(channels);
+            for frame in samples.chunks_exact(channels) {
+                let sum: f32 = frame
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(&s, w)| s as f32 * w)
+                    .sum();
+                mono.push(sum.round().clamp(-32768.0, 32767.0) as i16);
             }
-            let avg = (sum / channels as i32).clamp(-32768, 32767) as i16;
-            mono.push(avg);
         }
 
-        mono
+        Cow::Owned(mono)
+    }
+
+    /// Reads captured microphone audio into `buf`, returning the number of bytes read
+    ///
+    /// Requires [`StreamingTranscriberBuilder::with_audio_passthrough`]. The bytes
+    /// are 16kHz mono signed 16-bit little-endian PCM. Like
+    /// [`poll_result`](Self::poll_result) this never blocks: it returns `Ok(0)`
+    /// when no audio is waiting or after the helper has exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if passthrough isn't enabled, the transcriber isn't
+    /// started, or reading from the pipe fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_audio_passthrough(true)
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// let mut recording = Vec::new();
+    /// let mut buf = [0u8; 4096];
+    /// loop {
+    ///     let n = transcriber.read_captured_audio(&mut buf).unwrap();
+    ///     recording.extend_from_slice(&buf[..n]);
+    ///     while let Some(result) = transcriber.poll_result().unwrap() {
+    ///         println!("{}", result.text);
+    ///     }
+    /// #   break;
+    /// }
+    /// ```
+    pub fn read_captured_audio(&mut self, buf: &mut [u8]) -> Result<usize, ScribeError> {
+        if !self.audio_passthrough {
+            return Err("Audio passthrough is not enabled".into());
+        }
+        let pipe = self
+            .captured_audio
+            .as_mut()
+            .ok_or(ScribeError::NotStarted)?;
+        match pipe.read(buf) {
+            Ok(n) => Ok(n),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(0),
+            Err(e) => Err(format!("Failed to read captured audio: {}", e).into()),
+        }
+    }
+
+    /// Ends the audio input, so the helper finishes transcribing what it has
+    ///
+    /// Closes the helper's stdin, which tells it no more audio is coming,
+    /// while its output stays open: keep calling
+    /// [`poll_result`](Self::poll_result) to receive the last final results,
+    /// until it returns an error once the helper has written them all and
+    /// exited, then call [`stop`](Self::stop). [`results`](Self::results) does
+    /// the polling and ends at that point. Calling [`stop`](Self::stop) right
+    /// away instead would kill the helper before the last results are read.
+    ///
+    /// The helper is given as long as it needs: a backlog of fed audio can take
+    /// a while to recognize, and the helper is never killed for taking it. A
+    /// session deadline set with
+    /// [`with_max_duration`](StreamingTranscriberBuilder::with_max_duration)
+    /// still applies, ending the session with "Session deadline reached".
+    ///
+    /// Feeding audio after this returns an error; start a new session to
+    /// transcribe more. Calling it again does nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transcriber doesn't use programmatic input or
+    /// hasn't been started.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    /// transcriber.feed_audio_i16(&[0; 16000], 16000, 1).unwrap();
+    ///
+    /// transcriber.finish_input().unwrap();
+    /// for result in transcriber.results() {
+    ///     println!("{}", result.unwrap().text);
+    /// }
+    /// transcriber.stop().unwrap();
+    /// ```
+    pub fn finish_input(&mut self) -> Result<(), ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "finish_input can only be used with programmatic input mode".to_string(),
+            ));
+        }
+        if self.reader.is_none() {
+            return Err(ScribeError::NotStarted);
+        }
+        // No kill deadline: the helper may still be working through a backlog
+        self.request_finish(None);
+        Ok(())
     }
 
     /// Stops the streaming transcription and cleans up resources
@@ -755,10 +5385,28 @@ impl StreamingTranscriber {
     /// // ... do transcription ...
     /// transcriber.stop().unwrap();
     /// ```
-    pub fn stop(&mut self) -> Result<(), String> {
-        self.stdin = None;
-        self.stdout = None;
-        self.line_buffer.clear();
+    pub fn stop(&mut self) -> Result<(), ScribeError> {
+        // Deregister from the reactor before the descriptors are closed
+        #[cfg(feature = "async")]
+        {
+            self.async_pipes = None;
+            self.pipe_fds = None;
+        }
+        self.writer = None;
+        self.reader = None;
+        self.output_fd = None;
+        self.stderr = None;
+        self.captured_audio = None;
+        self.control = None;
+        self.fed_audio = None;
+        #[cfg(all(feature = "system-audio", target_os = "macos"))]
+        {
+            self.system_audio = None;
+        }
+        self.tail = None;
+        self.partial_line.clear();
+        self.line_overflowed = false;
+        self.pending.clear();
 
         if let Some(mut process) = self.process.take() {
             let _ = process.kill();
@@ -773,9 +5421,35 @@ impl StreamingTranscriber {
         &self.helper_path
     }
 
+    /// Returns the last raw line read from the helper's stdout
+    ///
+    /// This is the exact JSON the helper emitted for the most recent result, or the
+    /// line that failed to parse. Useful when the helper and the crate disagree on
+    /// the result schema.
+    pub fn last_raw_line(&self) -> Option<&str> {
+        self.last_raw_line.as_deref()
+    }
+
+    /// Returns how much fed audio the current session skipped as silence
+    ///
+    /// Always zero unless [`StreamingTranscriberBuilder::with_skip_silence`] is
+    /// enabled. Add it to the helper's view of elapsed audio to get the position
+    /// in the fed stream.
+    pub fn skipped_silence(&self) -> Duration {
+        self.skipped_silence
+    }
+
+    /// Returns the last lines the helper wrote to stderr in the current session
+    ///
+    /// Up to 20 lines are kept. Returns `None` with [`StderrMode::Null`], before
+    /// [`start`](Self::start), and for socket connections.
+    pub fn helper_stderr(&self) -> Option<String> {
+        self.stderr.as_ref().map(StderrTail::lines)
+    }
+
     /// Checks if the transcription is currently running
     pub fn is_running(&self) -> bool {
-        self.process.is_some()
+        self.reader.is_some()
     }
 }
 
@@ -784,3 +5458,530 @@ impl Drop for StreamingTranscriber {
         let _ = self.stop();
     }
 }
+
+/// Versions of the streaming helper's output protocol this crate understands
+///
+/// The helper announces its version as the first line of every session; see
+/// [`StreamingTranscriber::start`].
+pub const SUPPORTED_PROTOCOL_VERSIONS: std::ops::Range<u32> = 1..2;
+
+/// How long [`StreamingTranscriber::start`] waits for the helper's protocol announcement
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest helper output line accepted; real results are far shorter, so a
+/// longer line means the stream is corrupted
+const MAX_LINE_LEN: usize = 64 * 1024;
+
+/// The helper's first line, announcing its protocol version
+#[derive(Deserialize)]
+struct ProtocolAnnouncement {
+    protocol: u32,
+}
+
+/// How long [`StreamingTranscriber::ping`] waits for the helper to answer
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Samples of silence [`StreamingTranscriber::keep_alive`] sends: 100ms at 16kHz
+const KEEP_ALIVE_SAMPLES: usize = TARGET_SAMPLE_RATE as usize / 10;
+
+/// How long a helper may take to finish after the session deadline before it is killed
+const FINISH_GRACE: Duration = Duration::from_secs(10);
+
+/// Exit code of a helper that lost microphone access mid-session (`EX_NOPERM`)
+const PERMISSION_REVOKED_EXIT: i32 = 77;
+
+/// What the helper writes to stderr when microphone access is revoked
+const PERMISSION_REVOKED_MESSAGE: &str = "Microphone permission revoked";
+
+/// The helper's answer to a ping on the control pipe
+#[derive(Deserialize)]
+struct Pong {
+    pong: u64,
+}
+
+/// How long [`StreamingTranscriber::set_locale`] waits for the helper to switch
+const LOCALE_SWITCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Words of the prior transcript given as context by
+/// [`StreamingTranscriber::start_with_context`]
+const CONTEXT_WORDS: usize = 100;
+
+/// The helper's answer to a locale switch on the control pipe
+#[derive(Deserialize)]
+struct LocaleReply {
+    #[allow(dead_code)]
+    locale: String,
+    /// Why the switch failed, if it did
+    #[serde(default)]
+    error: Option<String>,
+}
+
+/// Sample rate the streaming helper expects on stdin
+const TARGET_SAMPLE_RATE: u32 = 16000;
+
+/// Loudest sample of a chunk that `with_skip_silence` treats as silent
+const SILENCE_THRESHOLD: u16 = 64;
+
+/// Read side of a helper socket that never blocks
+///
+/// Uses `MSG_DONTWAIT` per read instead of `O_NONBLOCK`, which would also make
+/// writes through the cloned write side non-blocking.
+struct SocketReader(UnixStream);
+
+impl Read for SocketReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::os::unix::io::AsRawFd;
+
+        let n = unsafe {
+            libc::recv(
+                self.0.as_raw_fd(),
+                buf.as_mut_ptr() as *mut libc::c_void,
+                buf.len(),
+                libc::MSG_DONTWAIT,
+            )
+        };
+        if n < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(n as usize)
+        }
+    }
+}
+
+/// Longest `poll_result_timeout` waits on the helper's output between polls
+const POLL_WAIT_SLICE: Duration = Duration::from_millis(50);
+
+/// Waits up to `timeout` for `fd` to have input to read, or to hang up
+///
+/// Returns whether it did; `false` after a timeout or an interrupted wait.
+fn wait_readable(fd: std::os::unix::io::RawFd, timeout: Duration) -> bool {
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // Rounded up, so a sub-millisecond remainder still waits
+    let millis = timeout
+        .as_nanos()
+        .div_ceil(1_000_000)
+        .min(libc::c_int::MAX as u128);
+    // SAFETY: `pollfd` is a single valid entry for the duration of the call.
+    unsafe { libc::poll(&mut pollfd, 1, millis as libc::c_int) > 0 }
+}
+
+/// File descriptor the helper writes passthrough audio to
+const PASSTHROUGH_FD: libc::c_int = 3;
+
+/// File descriptor the helper reads control messages (pings) from
+const CONTROL_FD: libc::c_int = 4;
+
+/// Creates a pipe as (read end, write end)
+///
+/// Both ends are close-on-exec so only copies placed with [`inherit_fd`] reach
+/// the helper. They are also moved above the descriptor numbers handed to the
+/// helper, so placing one pipe can never overwrite the other in the child.
+fn pipe() -> std::io::Result<(std::os::fd::OwnedFd, std::os::fd::OwnedFd)> {
+    use std::os::fd::{FromRawFd, OwnedFd};
+
+    let mut fds = [0; 2];
+    // SAFETY: `fds` has room for the two descriptors pipe() writes.
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: pipe() succeeded, so both descriptors are open and owned by us.
+    let (low_read, low_write) =
+        unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+    let raise = |fd: &OwnedFd| {
+        use std::os::fd::AsRawFd;
+        // SAFETY: fcntl duplicates a descriptor we own; the copy is ours too.
+        match unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_DUPFD_CLOEXEC, CONTROL_FD + 1) } {
+            -1 => Err(std::io::Error::last_os_error()),
+            raised => Ok(unsafe { OwnedFd::from_raw_fd(raised) }),
+        }
+    };
+    Ok((raise(&low_read)?, raise(&low_write)?))
+}
+
+/// Creates the passthrough pipe as (non-blocking read end, write end)
+fn passthrough_pipe() -> std::io::Result<(std::fs::File, std::os::fd::OwnedFd)> {
+    use std::os::fd::AsRawFd;
+
+    let (read, write) = pipe()?;
+    // SAFETY: fcntl on a descriptor we own.
+    unsafe {
+        let flags = libc::fcntl(read.as_raw_fd(), libc::F_GETFL);
+        if flags != -1 {
+            libc::fcntl(read.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK);
+        }
+    }
+    Ok((std::fs::File::from(read), write))
+}
+
+/// Hands `fd` to the helper as descriptor `target`
+fn inherit_fd(cmd: &mut Command, fd: &std::os::fd::OwnedFd, target: libc::c_int) {
+    use std::os::fd::AsRawFd;
+    use std::os::unix::process::CommandExt;
+
+    let fd = fd.as_raw_fd();
+    // SAFETY: dup2 is async-signal-safe and touches no parent state. The
+    // duplicate doesn't inherit close-on-exec, so it survives exec.
+    unsafe {
+        cmd.pre_exec(move || {
+            if libc::dup2(fd, target) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Writes samples as 16-bit little-endian PCM through a fixed stack buffer
+///
+/// Avoids allocating a byte vector per chunk in real-time feed loops.
+fn write_pcm<W: Write>(writer: &mut W, samples: &[i16]) -> std::io::Result<()> {
+    let mut buffer = [0u8; 4096];
+    for chunk in samples.chunks(buffer.len() / 2) {
+        for (bytes, sample) in buffer.chunks_exact_mut(2).zip(chunk) {
+            bytes.copy_from_slice(&sample.to_le_bytes());
+        }
+        writer.write_all(&buffer[..chunk.len() * 2])?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads `output` as if the helper had written it, returning the results and final error
+    fn read_output(output: Vec<u8>, strict: bool) -> (Vec<StreamingResult>, String) {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path("/bin/sh")
+            .with_strict_parsing(strict)
+            .build()
+            .unwrap();
+        transcriber.reader = Some(Box::new(std::io::Cursor::new(output)));
+        let mut results = Vec::new();
+        loop {
+            match transcriber.next_result() {
+                Ok(Some(result)) => results.push(result),
+                Ok(None) => {}
+                Err(e) => return (results, e.to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn piped_audio_is_staged_for_the_helper() {
+        // `cat` echoes the staged file back as the transcript
+        let transcriber = Transcriber::with_helper_path("/bin/cat").unwrap();
+        let piped = std::io::Cursor::new(b"RIFF and the rest".to_vec());
+        let text = transcriber
+            .transcribe_reader(piped, AudioFormat::Wav)
+            .unwrap();
+        assert_eq!(text, "RIFF and the rest");
+
+        let empty = std::io::Cursor::new(Vec::new());
+        let err = transcriber
+            .transcribe_reader(empty, AudioFormat::Wav)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No audio on stdin");
+        let wav = std::io::Cursor::new(b"RIFF".to_vec());
+        let err = transcriber
+            .transcribe_reader(wav, AudioFormat::Caf)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Audio on stdin is not CAF");
+    }
+
+    #[test]
+    fn malformed_output_lines_are_skipped_unless_strict() {
+        let good = r#"{"text":"hi","isFinal":true,"timestamp":1}"#;
+        let output = format!(
+            "{{\"text\":\"trunc\n{{\"text\":\"no flag\",\"timestamp\":1}}\n{}\n{}\n",
+            "x".repeat(MAX_LINE_LEN + 10),
+            good
+        );
+
+        let (results, err) = read_output(output.clone().into_bytes(), false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].text, "hi");
+        assert_eq!(err, "Streaming process ended");
+
+        let (results, err) = read_output(output.into_bytes(), true);
+        assert!(results.is_empty());
+        assert!(
+            err.starts_with("Failed to parse result"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn random_output_never_panics() {
+        // xorshift, so failures reproduce
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let fragments: [&[u8]; 8] = [
+            br#"{"text":"a","isFinal":true,"timestamp":1}"#,
+            br#"{"text":"#,
+            br#""isFinal":"#,
+            b"\n",
+            b"}",
+            br#"{"pong":"#,
+            "\u{e9}\u{1f600}".as_bytes(),
+            b"\xff\xfe\x00",
+        ];
+        for _ in 0..300 {
+            let mut output = Vec::new();
+            for _ in 0..next() % 40 {
+                match next() % 3 {
+                    0 => output.push(next() as u8),
+                    _ => output.extend_from_slice(fragments[next() as usize % fragments.len()]),
+                }
+            }
+            let (_, err) = read_output(output, false);
+            assert_eq!(err, "Streaming process ended");
+        }
+    }
+
+    #[test]
+    fn finishing_input_leaves_the_helper_to_finish() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let helper = dir.path().join("transcribe_stream");
+        std::fs::write(
+            &helper,
+            "#!/bin/sh\necho '{\"protocol\":1}'\ncat > /dev/null\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path(&helper)
+            .with_programmatic_input()
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+
+        transcriber.finish_input().unwrap();
+        assert!(transcriber.input_finished);
+        assert_eq!(transcriber.finish_by, None);
+        // A deadline passing afterwards still sets one
+        transcriber.request_finish(Some(FINISH_GRACE));
+        assert!(transcriber.finish_by.is_some());
+        transcriber.stop().unwrap();
+    }
+
+    #[test]
+    fn planar_channels_average_to_mono() {
+        let left = [1.0, 0.5, -1.0];
+        let right = [0.0, 0.5, 1.0];
+        assert_eq!(
+            StreamingTranscriber::planar_to_mono(&[&left, &right], &ChannelMix::Average).unwrap(),
+            [0.5, 0.5, 0.0]
+        );
+        assert!(StreamingTranscriber::planar_to_mono(&[], &ChannelMix::Average).is_err());
+        let err = StreamingTranscriber::planar_to_mono(&[&left, &right[..2]], &ChannelMix::Average)
+            .unwrap_err();
+        assert!(
+            err.contains("channel 1 has 2 samples"),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn interleaved_channels_mix_as_configured() {
+        let stereo = [100, -40, 200, 60, -300, 20];
+        let mix =
+            |mix: ChannelMix| StreamingTranscriber::to_mono_i16(&stereo, 2, &mix).into_owned();
+        assert_eq!(mix(ChannelMix::Average), [30, 130, -140]);
+        assert_eq!(mix(ChannelMix::Left), [100, 200, -300]);
+        assert_eq!(mix(ChannelMix::Right), [-40, 60, 20]);
+        assert_eq!(mix(ChannelMix::Weighted(vec![0.5, 2.0])), [-30, 220, -110]);
+        assert_eq!(
+            mix(ChannelMix::Weighted(vec![300.0, 0.0])),
+            [30000, 32767, -32768]
+        );
+
+        assert!(ChannelMix::Weighted(vec![0.5, 0.5]).check(2).is_ok());
+        let err = ChannelMix::Weighted(vec![0.5, 0.5]).check(3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Channel mix has 2 weights, but the audio has 3 channels"
+        );
+        // Mono needs no mixing
+        assert!(ChannelMix::Weighted(vec![0.5, 0.5]).check(1).is_ok());
+        let mono = StreamingTranscriber::to_mono_i16(&stereo, 1, &ChannelMix::Right);
+        assert!(matches!(mono, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn planar_channels_follow_the_mix() {
+        let left = [1.0, 0.5];
+        let right = [0.0, -0.5];
+        let mono = StreamingTranscriber::planar_to_mono(&[&left, &right], &ChannelMix::Left);
+        assert_eq!(mono.unwrap(), left);
+        let mono = StreamingTranscriber::planar_to_mono(&[&right], &ChannelMix::Right);
+        assert_eq!(mono.unwrap(), right);
+    }
+
+    #[test]
+    fn u8_samples_convert_around_midpoint() {
+        assert_eq!(
+            StreamingTranscriber::u8_to_i16(&[128, 255, 0, 129]),
+            [0, 32512, -32768, 256]
+        );
+    }
+
+    #[test]
+    fn helper_search_skips_missing_home() {
+        let without_home = helper_search_paths("transcribe", None);
+        assert_eq!(
+            without_home,
+            [
+                PathBuf::from("./helpers/transcribe"),
+                PathBuf::from("/usr/local/bin/transcribe"),
+            ]
+        );
+        assert_eq!(
+            helper_search_paths("transcribe", Some(PathBuf::new())),
+            without_home
+        );
+
+        let with_home = helper_search_paths("transcribe", Some(PathBuf::from("/home/me")));
+        assert_eq!(
+            with_home[1],
+            PathBuf::from("/home/me/.local/bin/transcribe")
+        );
+    }
+
+    #[test]
+    fn helper_discovery_retries_until_installed() {
+        let dir = tempfile::tempdir().unwrap();
+        let helper = dir.path().join("transcribe");
+        let paths = [dir.path().join("missing"), helper.clone()];
+
+        let once = DiscoveryRetry::default();
+        assert_eq!(first_existing(&paths, once), None);
+
+        let installer = {
+            let helper = helper.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                std::fs::write(helper, "").unwrap();
+            })
+        };
+        let retry = DiscoveryRetry {
+            attempts: 50,
+            delay: Duration::from_millis(20),
+        };
+        assert_eq!(first_existing(&paths, retry), Some(helper));
+        installer.join().unwrap();
+    }
+
+    #[test]
+    fn helper_search_with_home_unset() {
+        // The environment is shared by every test thread, so HOME is only ever
+        // missing in a copy of this test run as a child process
+        if std::env::var_os("SWIFT_SCRIBE_TEST_HOME_UNSET").is_some() {
+            assert!(std::env::var_os("HOME").is_none());
+            let paths = helper_search_paths("transcribe", dirs::home_dir());
+            // Either the password database supplies a home or the candidate is
+            // dropped; never a path rooted at nothing
+            assert!(paths
+                .iter()
+                .all(|p| p.is_absolute() || p.starts_with("./helpers")));
+            return;
+        }
+
+        let output = Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::helper_search_with_home_unset"])
+            .env("SWIFT_SCRIBE_TEST_HOME_UNSET", "1")
+            .env_remove("HOME")
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(output.status.success(), "child test failed: {}", stdout);
+        assert!(
+            stdout.contains("1 passed"),
+            "child test didn't run: {}",
+            stdout
+        );
+    }
+
+    #[test]
+    fn unavailable_transcriber_errors_on_use() {
+        let transcriber = Transcriber::unavailable(ScribeError::HelperNotFound(None));
+        let audio = std::env::current_exe().unwrap();
+        assert!(matches!(
+            transcriber.transcribe_file(&audio),
+            Err(ScribeError::HelperNotFound(None))
+        ));
+        assert!(matches!(
+            transcriber.warm_up(),
+            Err(ScribeError::HelperNotFound(None))
+        ));
+        assert_eq!(transcriber.helper_path(), Path::new(""));
+    }
+
+    #[test]
+    fn command_line_quotes_awkward_arguments() {
+        let mut cmd = Command::new("/opt/scribe/transcribe");
+        cmd.args(["--json", "my notes.m4a", "it's", ""]);
+        assert_eq!(
+            command_line(&cmd),
+            r"/opt/scribe/transcribe --json 'my notes.m4a' 'it'\''s' ''"
+        );
+    }
+
+    #[test]
+    fn confidence_aggregation_formulas() {
+        let segment = |confidence, duration_secs| TranscriptionSegment {
+            text: String::new(),
+            start_secs: 0.0,
+            duration_secs,
+            confidence,
+            script: None,
+            locale: None,
+            words: None,
+        };
+        let segments = [
+            segment(Some(0.9), 3.0),
+            segment(Some(0.3), 1.0),
+            segment(None, 5.0),
+        ];
+
+        let close = |a: Option<f32>, b: f32| (a.unwrap() - b).abs() < 1e-6;
+        assert!(close(ConfidenceAggregation::Mean.aggregate(&segments), 0.6));
+        assert!(close(ConfidenceAggregation::Min.aggregate(&segments), 0.3));
+        let weighted = ConfidenceAggregation::DurationWeighted.aggregate(&segments);
+        assert!(close(weighted, 0.75));
+
+        // Zero total duration falls back to the plain mean
+        let instant = [segment(Some(0.2), 0.0), segment(Some(0.4), 0.0)];
+        assert!(close(
+            ConfidenceAggregation::DurationWeighted.aggregate(&instant),
+            0.3
+        ));
+        assert_eq!(
+            ConfidenceAggregation::Mean.aggregate(&[segment(None, 1.0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn write_pcm_spans_buffer_boundaries() {
+        let samples: Vec<i16> = (0..5000).map(|i| (i * 7 - 16000) as i16).collect();
+        let mut written = Vec::new();
+        write_pcm(&mut written, &samples).unwrap();
+
+        let expected: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        assert_eq!(written, expected);
+    }
+}
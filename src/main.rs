@@ -1,8 +1,8 @@
-use swift_scribe::{StreamingTranscriber, Transcriber};
 use std::io::{self, Write};
 use std::path::Path;
 use std::thread;
 use std::time::Duration;
+use swift_scribe::{AudioFormat, StreamingTranscriber, Transcriber};
 
 fn main() {
     println!("swift-scribe: Speech-to-Text Transcription Tool");
@@ -16,6 +16,8 @@ fn main() {
     // Check for --mic flag for live microphone transcription
     if args[1] == "--mic" || args[1] == "-m" {
         run_microphone_mode();
+    } else if args[1] == "--stdin" {
+        run_stdin_mode(args.get(2).map(String::as_str));
     } else {
         run_file_mode(&args);
     }
@@ -23,8 +25,18 @@ fn main() {
 
 fn print_usage(program_name: &str) {
     eprintln!("Usage:");
-    eprintln!("  {} <audio-file-path>  - Transcribe an audio file", program_name);
-    eprintln!("  {} --mic              - Live microphone transcription", program_name);
+    eprintln!(
+        "  {} <audio-file-path>  - Transcribe an audio file",
+        program_name
+    );
+    eprintln!(
+        "  {} --mic              - Live microphone transcription",
+        program_name
+    );
+    eprintln!(
+        "  {} --stdin [format]    - Transcribe audio piped to stdin (wav, caf, aiff, m4a, mp3, flac or aac; default wav)",
+        program_name
+    );
     eprintln!();
     eprintln!("Make sure to build the Swift helpers first:");
     eprintln!("  make helpers");
@@ -59,6 +71,39 @@ fn run_file_mode(args: &[String]) {
     }
 }
 
+fn run_stdin_mode(format: Option<&str>) {
+    let format = match format.unwrap_or("wav") {
+        "wav" => AudioFormat::Wav,
+        "caf" => AudioFormat::Caf,
+        "aiff" | "aif" => AudioFormat::Aiff,
+        "m4a" => AudioFormat::M4a,
+        "mp3" => AudioFormat::Mp3,
+        "flac" => AudioFormat::Flac,
+        "aac" => AudioFormat::Aac,
+        other => {
+            eprintln!("Error: Unknown audio format: {}", other);
+            return;
+        }
+    };
+    let transcriber = match Transcriber::new() {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Error initializing transcriber: {}", e);
+            return;
+        }
+    };
+
+    match transcriber.transcribe_stdin(format) {
+        Ok(text) => {
+            println!("--- Transcription ---");
+            println!("{}", text);
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+        }
+    }
+}
+
 fn run_microphone_mode() {
     println!("\n🎤 Live Microphone Transcription Mode");
     println!("=====================================");
@@ -0,0 +1,198 @@
+//! Human-readable transcripts for pasting into notes and chats
+
+use crate::{sentences, TranscriptionSegment};
+
+/// A pause between segments at least this long starts a new line
+const PARAGRAPH_PAUSE_SECS: f64 = 2.0;
+
+/// Layout of the text produced by [`format_transcript`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Start each line with the time it begins at, as `[HH:MM:SS]`
+    pub timestamps: bool,
+    /// Label each line with a speaker, e.g. the owner of a
+    /// [`Transcriber::transcribe_track`](crate::Transcriber::transcribe_track) track
+    pub speaker: Option<String>,
+    /// Wrap lines to at most this many characters; continuation lines are
+    /// indented to line up with the text after the prefix
+    pub wrap_width: Option<usize>,
+}
+
+impl FormatOptions {
+    /// Bare text, one unwrapped line per sentence or stretch of speech
+    pub fn plain() -> Self {
+        Self::default()
+    }
+
+    /// Timestamped lines wrapped at 80 characters
+    pub fn meeting_notes() -> Self {
+        Self {
+            timestamps: true,
+            speaker: None,
+            wrap_width: Some(80),
+        }
+    }
+}
+
+/// Formats timed segments as readable text, e.g. `[00:01:23] Speaker 1: ...`
+///
+/// Segments are joined into lines that end at a sentence boundary or a pause
+/// of two seconds or more, so word-level segments read as prose. Each line gets
+/// the prefixes chosen in `options`, with the timestamp taken from its first
+/// segment.
+///
+/// # Examples
+///
+/// ```
+/// use swift_scribe::{format_transcript, FormatOptions, TranscriptionSegment};
+///
+/// let segment = |text: &str, start_secs: f64| TranscriptionSegment {
+///     text: text.to_string(),
+///     start_secs,
+///     duration_secs: 0.5,
+///     confidence: None,
+///     script: None,
+///     locale: None,
+///     words: None,
+/// };
+/// let segments = [segment("Welcome", 83.0), segment("everyone.", 83.5), segment("Let's start.", 85.0)];
+/// let options = FormatOptions {
+///     speaker: Some("Speaker 1".to_string()),
+///     ..FormatOptions::meeting_notes()
+/// };
+/// assert_eq!(
+///     format_transcript(&segments, &options),
+///     "[00:01:23] Speaker 1: Welcome everyone.\n[00:01:25] Speaker 1: Let's start.\n"
+/// );
+/// ```
+pub fn format_transcript(segments: &[TranscriptionSegment], options: &FormatOptions) -> String {
+    let mut out = String::new();
+    let mut line = String::new();
+    let mut line_start = 0.0;
+    let mut previous_end = None;
+
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let paused =
+            previous_end.is_some_and(|end| segment.start_secs - end >= PARAGRAPH_PAUSE_SECS);
+        if paused && !line.is_empty() {
+            write_line(&mut out, &line, line_start, options);
+            line.clear();
+        }
+        if line.is_empty() {
+            line_start = segment.start_secs;
+        } else {
+            line.push(' ');
+        }
+        line.push_str(text);
+        previous_end = Some(segment.start_secs + segment.duration_secs);
+
+        if sentences::ends_sentence(&line) {
+            write_line(&mut out, &line, line_start, options);
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        write_line(&mut out, &line, line_start, options);
+    }
+    out
+}
+
+/// Appends one prefixed, wrapped line of text to `out`
+fn write_line(out: &mut String, text: &str, start_secs: f64, options: &FormatOptions) {
+    let mut prefix = String::new();
+    if options.timestamps {
+        prefix.push_str(&format!("[{}] ", clock(start_secs)));
+    }
+    if let Some(speaker) = &options.speaker {
+        prefix.push_str(&format!("{}: ", speaker));
+    }
+    out.push_str(&prefix);
+
+    let Some(width) = options.wrap_width else {
+        out.push_str(text);
+        out.push('\n');
+        return;
+    };
+    let indent = prefix.chars().count();
+    let mut column = indent;
+    for (idx, word) in text.split_whitespace().enumerate() {
+        let len = word.chars().count();
+        if idx > 0 {
+            if column + 1 + len > width {
+                out.push('\n');
+                out.push_str(&" ".repeat(indent));
+                column = indent;
+            } else {
+                out.push(' ');
+                column += 1;
+            }
+        }
+        out.push_str(word);
+        column += len;
+    }
+    out.push('\n');
+}
+
+/// Formats seconds as `HH:MM:SS`
+fn clock(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_secs: f64, duration_secs: f64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_secs,
+            duration_secs,
+            confidence: None,
+            script: None,
+            locale: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn breaks_lines_at_sentences_and_pauses() {
+        let segments = [
+            segment("so", 0.0, 0.5),
+            segment("anyway", 0.5, 0.5),
+            segment("next", 4.0, 0.5),
+            segment("item.", 4.5, 0.5),
+            segment("Done", 3725.0, 1.0),
+        ];
+        assert_eq!(
+            format_transcript(&segments, &FormatOptions::plain()),
+            "so anyway\nnext item.\nDone\n"
+        );
+        assert_eq!(
+            format_transcript(&segments, &FormatOptions::meeting_notes()),
+            "[00:00:00] so anyway\n[00:00:04] next item.\n[01:02:05] Done\n"
+        );
+    }
+
+    #[test]
+    fn wraps_under_the_prefix() {
+        let segments = [segment(
+            "the quick brown fox jumps over the lazy dog",
+            61.0,
+            3.0,
+        )];
+        let options = FormatOptions {
+            speaker: Some("Ann".to_string()),
+            wrap_width: Some(31),
+            ..FormatOptions::meeting_notes()
+        };
+        assert_eq!(
+            format_transcript(&segments, &options),
+            "[00:01:01] Ann: the quick brown\n                fox jumps over\n                the lazy dog\n"
+        );
+    }
+}
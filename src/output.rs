@@ -0,0 +1,397 @@
+//! Streaming of timed segments to subtitle and JSON Lines writers
+
+use crate::batch::BOM;
+use crate::stderr_tail::StderrTail;
+use crate::{
+    command_line, detect_script, disfluency, itn, locate_audio, telemetry, LineEnding, Transcriber,
+    TranscriptionSegment,
+};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Instant;
+
+/// Format of the segments written by [`Transcriber::transcribe_file_to_writer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// SubRip subtitles, numbered cues of merged segments
+    Srt,
+    /// WebVTT subtitles, cues of merged segments
+    Vtt,
+    /// One JSON [`TranscriptionSegment`] per line
+    Jsonl,
+}
+
+/// Longest subtitle cue, in characters, that segments are merged into
+const CUE_MAX_CHARS: usize = 42;
+
+/// Longest subtitle cue, in seconds, that segments are merged into
+const CUE_MAX_SECS: f64 = 4.0;
+
+/// Writes segments one at a time in an [`OutputFormat`]
+///
+/// Subtitle segments are merged into caption-length cues as with
+/// [`TranscriptionSegment::merge_to`], so only the cue being built is held.
+struct SegmentWriter<W> {
+    writer: W,
+    format: OutputFormat,
+    line_ending: LineEnding,
+    /// Segments of the subtitle cue not yet written
+    cue: Vec<TranscriptionSegment>,
+    /// Text of `cue` so far
+    cue_text: String,
+    written: usize,
+}
+
+impl<W: Write> SegmentWriter<W> {
+    fn new(
+        mut writer: W,
+        format: OutputFormat,
+        line_ending: LineEnding,
+        bom: bool,
+    ) -> std::io::Result<Self> {
+        if bom {
+            writer.write_all(BOM)?;
+        }
+        if format == OutputFormat::Vtt {
+            writer.write_all(line_ending.apply("WEBVTT\n\n").as_bytes())?;
+        }
+        Ok(Self {
+            writer,
+            format,
+            line_ending,
+            cue: Vec::new(),
+            cue_text: String::new(),
+            written: 0,
+        })
+    }
+
+    fn write(&mut self, segment: &TranscriptionSegment) -> std::io::Result<()> {
+        if self.format == OutputFormat::Jsonl {
+            serde_json::to_writer(&mut self.writer, segment)?;
+            self.writer
+                .write_all(self.line_ending.apply("\n").as_bytes())?;
+            self.written += 1;
+            // Readers of a long transcription see each segment as it's recognized
+            return self.writer.flush();
+        }
+
+        let fits = TranscriptionSegment::fits_cue(
+            &self.cue,
+            &self.cue_text,
+            segment,
+            CUE_MAX_CHARS,
+            CUE_MAX_SECS,
+        );
+        if fits {
+            self.cue_text.push(' ');
+        } else {
+            self.write_cue()?;
+        }
+        self.cue.push(segment.clone());
+        self.cue_text.push_str(segment.text.trim());
+        Ok(())
+    }
+
+    /// Writes the cue being built, returning the number of cues or lines written
+    fn finish(&mut self) -> std::io::Result<usize> {
+        self.write_cue()?;
+        Ok(self.written)
+    }
+
+    /// Writes and clears the cue being built, if there is one
+    fn write_cue(&mut self) -> std::io::Result<()> {
+        if self.cue.is_empty() {
+            return Ok(());
+        }
+        let cue = TranscriptionSegment::merged(&self.cue, std::mem::take(&mut self.cue_text));
+        self.cue.clear();
+        let end = cue.start_secs + cue.duration_secs;
+        let text = match self.format {
+            OutputFormat::Srt => format!(
+                "{}\n{} --> {}\n{}\n\n",
+                self.written + 1,
+                cue_time(cue.start_secs, ','),
+                cue_time(end, ','),
+                cue.text
+            ),
+            OutputFormat::Vtt => format!(
+                "{} --> {}\n{}\n\n",
+                cue_time(cue.start_secs, '.'),
+                cue_time(end, '.'),
+                cue.text
+            ),
+            OutputFormat::Jsonl => unreachable!("JSON Lines segments are written as they arrive"),
+        };
+        self.writer
+            .write_all(self.line_ending.apply(&text).as_bytes())?;
+        self.written += 1;
+        // Readers of a long transcription see each cue as it's complete
+        self.writer.flush()
+    }
+}
+
+/// Formats seconds as `HH:MM:SS` and milliseconds after `separator`
+fn cue_time(secs: f64, separator: char) -> String {
+    let millis = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        separator,
+        millis % 1000
+    )
+}
+
+impl Transcriber {
+    /// Transcribes an audio file, writing each timed segment as soon as it's recognized
+    ///
+    /// For long recordings whose transcript shouldn't be held in memory: the
+    /// helper reports segments as the recognizer finalizes them, and each one is
+    /// cleaned up as configured, written to `writer` in `format`, and flushed
+    /// before the next is read. JSON Lines gets one line per segment; for SRT
+    /// and VTT, segments are merged into cues of up to 42 characters and 4
+    /// seconds as with [`TranscriptionSegment::merge_to`], each written once
+    /// complete. Lines end as set by
+    /// [`with_line_ending`](crate::TranscriberBuilder::with_line_ending), after a
+    /// byte order mark if [`with_bom`](crate::TranscriberBuilder::with_bom) is
+    /// set.
+    ///
+    /// The SFSpeechRecognizer backend only finalizes at the end of the file, so
+    /// there all segments arrive at once. With inverse text normalization, a
+    /// segment ending in a spoken number is held until the number is complete,
+    /// since it can run on into the next segment.
+    ///
+    /// Always runs a fresh helper, even after [`warm_up`](Self::warm_up).
+    /// Returns the number of cues, or JSON lines, written.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`transcribe_file`](Self::transcribe_file), or
+    /// an error if writing to `writer` fails, which stops the helper. Segments
+    /// written before a failure stay written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::{OutputFormat, Transcriber};
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    /// use std::path::Path;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let subtitles = BufWriter::new(File::create("lecture.srt").unwrap());
+    /// let count = transcriber
+    ///     .transcribe_file_to_writer(Path::new("lecture.m4a"), subtitles, OutputFormat::Srt)
+    ///     .unwrap();
+    /// println!("Wrote {} cues", count);
+    /// ```
+    pub fn transcribe_file_to_writer<W: Write>(
+        &self,
+        path: &Path,
+        writer: W,
+        format: OutputFormat,
+    ) -> Result<usize, String> {
+        let started = Instant::now();
+        let path = locate_audio(path)?;
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| "Invalid UTF-8 path".to_string())?;
+        let mut cmd = self.command()?;
+        cmd.args(self.request(path_str, &["--segments"]));
+
+        let mut child = cmd
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                telemetry::helper_spawn_failed();
+                format!("Failed to execute helper `{}`: {}", command_line(&cmd), e)
+            })?;
+        let mut stderr = child
+            .stderr
+            .take()
+            .map(|err| StderrTail::capture(err, false));
+        let stdout = child.stdout.take().expect("stdout is piped");
+
+        let streamed = self.stream_segments(stdout, writer, format);
+        if streamed.is_err() {
+            let _ = child.kill();
+        }
+        let status = child
+            .wait()
+            .map_err(|e| format!("Failed to wait for helper: {}", e))?;
+        let stderr = stderr.as_mut().map(StderrTail::finish).unwrap_or_default();
+        let written = streamed?;
+        if !status.success() {
+            return Err(format!(
+                "Transcription failed: {} (command: {})",
+                stderr.trim(),
+                command_line(&cmd)
+            ));
+        }
+
+        if written == 0 {
+            self.check_empty(&path, "")?;
+        }
+        telemetry::file_transcribed(started.elapsed());
+        Ok(written)
+    }
+
+    /// Writes each segment line of the helper's output as it arrives
+    fn stream_segments<W: Write>(
+        &self,
+        output: impl Read,
+        writer: W,
+        format: OutputFormat,
+    ) -> Result<usize, String> {
+        let mut segments = SegmentWriter::new(writer, format, self.line_ending, self.bom)
+            .map_err(|e| format!("Failed to write transcript: {}", e))?;
+        let mut pending = Vec::new();
+        for line in BufReader::new(output).lines() {
+            let line = line.map_err(|e| format!("Failed to read from helper: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(segment) = self.parse_segment(line.trim())? {
+                // A spoken number can run on into the next segment
+                let closed = !self.inverse_text_normalization || itn::closes_numbers(&segment.text);
+                pending.push(segment);
+                if closed {
+                    self.write_segments(&mut segments, std::mem::take(&mut pending))?;
+                }
+            }
+        }
+        self.write_segments(&mut segments, pending)?;
+        segments
+            .finish()
+            .map_err(|e| format!("Failed to write transcript: {}", e))
+    }
+
+    /// Parses one segment line from the helper, cleaned up as configured
+    ///
+    /// Returns `None` for a segment that cleanup leaves empty.
+    fn parse_segment(&self, line: &str) -> Result<Option<TranscriptionSegment>, String> {
+        let mut segment: TranscriptionSegment = serde_json::from_str(line)
+            .map_err(|e| format!("Failed to parse result: {} (line: {})", e, line))?;
+        if !self.verbatim {
+            segment.text = disfluency::remove_disfluencies(&segment.text);
+            if segment.text.is_empty() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(segment))
+    }
+
+    /// Writes parsed segments, normalized and split as configured
+    fn write_segments<W: Write>(
+        &self,
+        segments: &mut SegmentWriter<W>,
+        parsed: Vec<TranscriptionSegment>,
+    ) -> Result<(), String> {
+        let parsed = if self.inverse_text_normalization {
+            itn::normalize_segments(&parsed)
+        } else {
+            parsed
+        };
+        let mut pieces = self.split_long_segments(parsed);
+        for piece in &mut pieces {
+            piece.script = Some(detect_script(&piece.text));
+        }
+        self.shift_timings(&mut pieces);
+        for piece in &pieces {
+            segments
+                .write(piece)
+                .map_err(|e| format!("Failed to write transcript: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_secs: f64, duration_secs: f64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_secs,
+            duration_secs,
+            confidence: None,
+            script: None,
+            locale: None,
+            words: None,
+        }
+    }
+
+    fn render(format: OutputFormat, line_ending: LineEnding, bom: bool) -> String {
+        let mut out = Vec::new();
+        let mut writer = SegmentWriter::new(&mut out, format, line_ending, bom).unwrap();
+        writer.write(&segment("Hello", 1.5, 0.75)).unwrap();
+        writer.write(&segment("there.", 2.5, 0.5)).unwrap();
+        writer.write(&segment("world", 3725.0, 1.0)).unwrap();
+        writer.finish().unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn writes_subtitle_cues() {
+        assert_eq!(
+            render(OutputFormat::Srt, LineEnding::Lf, false),
+            "1\n00:00:01,500 --> 00:00:03,000\nHello there.\n\n\
+             2\n01:02:05,000 --> 01:02:06,000\nworld\n\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Vtt, LineEnding::Lf, false),
+            "WEBVTT\n\n00:00:01.500 --> 00:00:03.000\nHello there.\n\n\
+             01:02:05.000 --> 01:02:06.000\nworld\n\n"
+        );
+    }
+
+    #[test]
+    fn ends_cues_at_sentences_and_limits() {
+        let mut out = Vec::new();
+        let mut writer =
+            SegmentWriter::new(&mut out, OutputFormat::Vtt, LineEnding::Lf, false).unwrap();
+        for (text, start) in [("One.", 0.0), ("Two", 1.0), ("three", 2.0), ("four", 5.0)] {
+            writer.write(&segment(text, start, 0.5)).unwrap();
+        }
+        assert_eq!(writer.finish().unwrap(), 3);
+        let cues: Vec<&str> = std::str::from_utf8(&out)
+            .unwrap()
+            .lines()
+            .filter(|line| line.chars().next().is_some_and(char::is_alphabetic))
+            .collect();
+        assert_eq!(cues, ["WEBVTT", "One.", "Two three", "four"]);
+    }
+
+    #[test]
+    fn writes_crlf_after_a_bom() {
+        let out = render(OutputFormat::Srt, LineEnding::CrLf, true);
+        assert!(out.starts_with("\u{feff}1\r\n00:00:01,500"));
+        assert_eq!(out.matches("\r\n").count(), out.matches('\n').count());
+
+        let out = render(OutputFormat::Jsonl, LineEnding::CrLf, false);
+        assert_eq!(out.matches("}\r\n").count(), 3);
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let out = render(OutputFormat::Jsonl, LineEnding::Lf, false);
+        let lines: Vec<TranscriptionSegment> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                segment("Hello", 1.5, 0.75),
+                segment("there.", 2.5, 0.5),
+                segment("world", 3725.0, 1.0)
+            ]
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! A fixed set of resident file helpers shared between threads
+
+use crate::{ScribeError, Transcriber, TranscriptionResult};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::{Condvar, Mutex, PoisonError};
+
+/// Transcribes files on a fixed number of warm helpers
+///
+/// Created by [`Transcriber::pool`]. Each worker keeps a resident helper with
+/// its model loaded, as [`Transcriber::warm_up`] does, and every call borrows
+/// an idle worker for the length of one file. Calls made while all workers are
+/// busy wait for one to finish instead of spawning another helper, so at most
+/// `size` helpers run at a time. Share the pool between threads by reference
+/// or in an `Arc`; workers are handed out round-robin.
+///
+/// A worker whose helper dies is reloaded before its next file. If reloading
+/// fails, that file goes to a one-shot helper, exactly as
+/// [`Transcriber::transcribe_file`] would.
+pub struct TranscriberPool {
+    workers: Vec<Transcriber>,
+    /// Indices of the workers not transcribing, longest idle first
+    idle: Mutex<VecDeque<usize>>,
+    returned: Condvar,
+}
+
+impl Transcriber {
+    /// Starts `size` resident helpers for transcribing many files in parallel
+    ///
+    /// Every worker uses this transcriber's settings. Each helper is spawned
+    /// and its model loaded before this returns, so the first files don't pay
+    /// for startup. For large batches this combines the warm start of
+    /// [`warm_up`](Self::warm_up) with parallelism, while bounding the number
+    /// of helper processes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `size` is zero, or if any helper can't be spawned or
+    /// fails to load its model; helpers already started are stopped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::PathBuf;
+    ///
+    /// let pool = Transcriber::new().unwrap().pool(4).unwrap();
+    /// let files: Vec<PathBuf> = vec!["a.m4a".into(), "b.m4a".into()];
+    /// std::thread::scope(|scope| {
+    ///     for file in &files {
+    ///         let pool = &pool;
+    ///         scope.spawn(move || println!("{}", pool.transcribe(file).unwrap()));
+    ///     }
+    /// });
+    /// ```
+    pub fn pool(&self, size: usize) -> Result<TranscriberPool, ScribeError> {
+        if size == 0 {
+            return Err("Pool size must be at least 1".into());
+        }
+        let config = self.config();
+        let workers = (0..size)
+            .map(|_| {
+                let worker = Transcriber::from_config(&config)?;
+                worker.warm_up()?;
+                Ok(worker)
+            })
+            .collect::<Result<Vec<_>, ScribeError>>()?;
+        Ok(TranscriberPool {
+            workers,
+            idle: Mutex::new((0..size).collect()),
+            returned: Condvar::new(),
+        })
+    }
+}
+
+impl TranscriberPool {
+    /// Transcribes an audio file on the next idle worker
+    ///
+    /// Waits while every worker is busy. See [`Transcriber::transcribe_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Transcriber::transcribe_file`].
+    pub fn transcribe(&self, path: &Path) -> Result<String, ScribeError> {
+        self.with_worker(|worker| worker.transcribe_file(path))
+    }
+
+    /// Transcribes an audio file with metadata on the next idle worker
+    ///
+    /// Waits while every worker is busy. See
+    /// [`Transcriber::transcribe_file_with_metadata`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as
+    /// [`Transcriber::transcribe_file_with_metadata`].
+    pub fn transcribe_with_metadata(
+        &self,
+        path: &Path,
+    ) -> Result<TranscriptionResult, ScribeError> {
+        self.with_worker(|worker| worker.transcribe_file_with_metadata(path))
+    }
+
+    /// Number of workers in the pool
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Runs `f` on a borrowed worker, waiting for one to become idle
+    fn with_worker<T>(&self, f: impl FnOnce(&Transcriber) -> T) -> T {
+        let mut idle = self.idle.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = loop {
+            match idle.pop_front() {
+                Some(index) => break index,
+                None => {
+                    idle = self
+                        .returned
+                        .wait(idle)
+                        .unwrap_or_else(PoisonError::into_inner)
+                }
+            }
+        };
+        drop(idle);
+
+        let _borrow = Borrow { pool: self, index };
+        let worker = &self.workers[index];
+        // A helper that died on an earlier file was discarded; reload it, or
+        // let this file fall back to a one-shot helper
+        if let Err(e) = worker.warm_up() {
+            log::warn!("Failed to reload pool helper: {}", e);
+        }
+        f(worker)
+    }
+}
+
+/// A worker taken from the idle queue, returned when dropped
+struct Borrow<'a> {
+    pool: &'a TranscriberPool,
+    index: usize,
+}
+
+impl Drop for Borrow<'_> {
+    fn drop(&mut self) {
+        self.pool
+            .idle
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push_back(self.index);
+        self.pool.returned.notify_one();
+    }
+}
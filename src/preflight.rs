@@ -0,0 +1,224 @@
+//! Checking that a batch can run before transcribing any of it
+
+use crate::{command_line, locate_audio, telemetry, LocaleStatus, Transcriber};
+use serde::Deserialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Locale the helper recognizes in when no candidates are configured
+const DEFAULT_LOCALE: &str = "en-US";
+
+/// Problems found by [`Transcriber::preflight`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreflightReport {
+    /// Every problem found, helper and permission issues first, then inputs in order
+    pub issues: Vec<PreflightIssue>,
+}
+
+impl PreflightReport {
+    /// Whether nothing stands in the way of transcribing the inputs
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// One problem found by [`Transcriber::preflight`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreflightIssue {
+    /// The helper is missing, fails its checksum, or can't be run
+    Helper(String),
+    /// Speech recognition hasn't been authorized for the helper
+    ///
+    /// Only the SFSpeechRecognizer backend, used before macOS 26, needs
+    /// authorization. The helper never asks for it, so it has to be granted in
+    /// System Settings beforehand.
+    Permission(String),
+    /// No candidate locale can be recognized offline right away
+    ///
+    /// `status` is [`LocaleStatus::Unsupported`] when none of the candidates is
+    /// supported, or [`LocaleStatus::NotInstalled`] when the first supported
+    /// one would download its speech model on first use; see
+    /// [`Transcriber::ensure_locale_available`].
+    Locale {
+        /// The locale that would be used
+        locale: String,
+        /// Its status
+        status: LocaleStatus,
+    },
+    /// An input can't be transcribed
+    Input {
+        /// The input as given
+        path: PathBuf,
+        /// Why: missing, unreadable, or not an audio file the helper can open
+        reason: String,
+    },
+}
+
+impl fmt::Display for PreflightIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PreflightIssue::Helper(message) | PreflightIssue::Permission(message) => {
+                f.write_str(message)
+            }
+            PreflightIssue::Locale {
+                locale,
+                status: LocaleStatus::Unsupported,
+            } => write!(f, "Locale {} is not supported", locale),
+            PreflightIssue::Locale { locale, .. } => {
+                write!(f, "Speech model for {} is not installed", locale)
+            }
+            PreflightIssue::Input { path, reason } => write!(f, "{}: {}", path.display(), reason),
+        }
+    }
+}
+
+/// The helper's `--authorization` output
+#[derive(Deserialize)]
+struct Authorization {
+    authorization: String,
+}
+
+impl Transcriber {
+    /// Checks that `inputs` can be transcribed, without transcribing them
+    ///
+    /// For validating a large batch up front rather than failing file by file
+    /// deep into it. Checks that the helper is present, matches its pinned
+    /// checksum and runs; that speech recognition is authorized where the
+    /// backend needs it; that the locale the helper would pick from the
+    /// candidates (en-US by default) is supported and its model installed; and
+    /// that every input exists, is readable, and holds an audio track the
+    /// helper can open. Reading each file's track list is cheap compared to
+    /// transcribing it.
+    ///
+    /// When the helper can't run, only the file system checks are made on the
+    /// inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::path::PathBuf;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let inputs: Vec<PathBuf> = vec!["a.m4a".into(), "b.wav".into()];
+    /// let report = transcriber.preflight(&inputs);
+    /// for issue in &report.issues {
+    ///     eprintln!("{}", issue);
+    /// }
+    /// if report.is_ok() {
+    ///     // start the batch
+    /// }
+    /// ```
+    pub fn preflight(&self, inputs: &[PathBuf]) -> PreflightReport {
+        let mut issues = Vec::new();
+        let helper_runs = match self.authorization() {
+            Ok(authorization) => {
+                if let Some(message) = authorization_problem(&authorization) {
+                    issues.push(PreflightIssue::Permission(message));
+                }
+                true
+            }
+            Err(e) => {
+                issues.push(PreflightIssue::Helper(e));
+                false
+            }
+        };
+        if helper_runs {
+            match self.preflight_locale() {
+                Ok(Some(issue)) => issues.push(issue),
+                Ok(None) => {}
+                Err(e) => issues.push(PreflightIssue::Helper(e)),
+            }
+        }
+        for path in inputs {
+            if let Err(reason) = self.check_input(path, helper_runs) {
+                issues.push(PreflightIssue::Input {
+                    path: path.clone(),
+                    reason,
+                });
+            }
+        }
+        PreflightReport { issues }
+    }
+
+    /// Asks the helper for its speech recognition authorization
+    fn authorization(&self) -> Result<String, String> {
+        let mut cmd = self.command()?;
+        cmd.arg("--authorization");
+        let output = cmd.output().map_err(|e| {
+            telemetry::helper_spawn_failed();
+            format!("Failed to execute helper `{}`: {}", command_line(&cmd), e)
+        })?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "Helper failed: {} (command: {})",
+                stderr.trim(),
+                command_line(&cmd)
+            ));
+        }
+        serde_json::from_str::<Authorization>(stdout.trim())
+            .map(|reply| reply.authorization)
+            .map_err(|e| {
+                format!(
+                    "Failed to parse authorization: {} (output: {})",
+                    e,
+                    stdout.trim()
+                )
+            })
+    }
+
+    /// Checks the locale the helper would pick, as it does: the first supported candidate
+    fn preflight_locale(&self) -> Result<Option<PreflightIssue>, String> {
+        let mut candidates = match &self.locale {
+            Some(locale) => vec![locale.clone()],
+            None => self.candidate_locales(),
+        };
+        if candidates.is_empty() {
+            candidates.push(DEFAULT_LOCALE.to_string());
+        }
+        for locale in &candidates {
+            match self.ensure_locale_available(locale, false)? {
+                LocaleStatus::Installed => return Ok(None),
+                LocaleStatus::NotInstalled => {
+                    return Ok(Some(PreflightIssue::Locale {
+                        locale: locale.clone(),
+                        status: LocaleStatus::NotInstalled,
+                    }))
+                }
+                LocaleStatus::Unsupported => {}
+            }
+        }
+        Ok(Some(PreflightIssue::Locale {
+            locale: candidates.swap_remove(0),
+            status: LocaleStatus::Unsupported,
+        }))
+    }
+
+    /// Checks that an input is a readable file, and that the helper finds audio in it
+    fn check_input(&self, path: &Path, ask_helper: bool) -> Result<(), String> {
+        let located = locate_audio(path)?;
+        std::fs::File::open(&located).map_err(|e| format!("Can't read file: {}", e))?;
+        if !ask_helper {
+            return Ok(());
+        }
+        let tracks = self
+            .list_tracks(&located)
+            .map_err(|e| format!("Can't open as audio: {}", e))?;
+        if tracks.is_empty() {
+            return Err("No audio track; not a supported audio file".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Describes an authorization that keeps the helper from recognizing speech
+fn authorization_problem(authorization: &str) -> Option<String> {
+    match authorization {
+        "authorized" | "not_required" => None,
+        "denied" => Some("Speech recognition permission was denied".to_string()),
+        "restricted" => Some("Speech recognition is restricted on this device".to_string()),
+        _ => Some("Speech recognition permission hasn't been granted".to_string()),
+    }
+}
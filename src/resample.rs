@@ -0,0 +1,334 @@
+//! Sample-rate conversion of fed audio to the helper's 16kHz
+
+use crate::TARGET_SAMPLE_RATE;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::f64::consts::PI;
+
+/// How fed audio is resampled to 16kHz
+///
+/// Better filters cost more CPU per chunk. The filtered qualities remove content
+/// above 8kHz before downsampling, which would otherwise fold back as noise and
+/// blur sibilants. `Fast` suits real-time callbacks with tight budgets that can
+/// accept that; `Best` suits offline processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+    /// Linear interpolation, with no anti-aliasing filter
+    Fast,
+    /// A short windowed-sinc FIR filter (default)
+    #[default]
+    Medium,
+    /// A long windowed-sinc FIR filter with a sharp cutoff
+    Best,
+}
+
+impl ResampleQuality {
+    /// Zero crossings of the sinc on each side, and the cutoff relative to Nyquist
+    fn filter_shape(self) -> Option<(usize, f64)> {
+        match self {
+            ResampleQuality::Fast => None,
+            ResampleQuality::Medium => Some((4, 0.85)),
+            ResampleQuality::Best => Some((24, 0.95)),
+        }
+    }
+}
+
+/// Resampler that carries its state across chunks
+///
+/// Programmatic input arrives as a sequence of chunks from one continuous
+/// stream. Keeping the input samples later outputs still need and the
+/// fractional read position between calls makes the output identical to
+/// resampling the whole stream at once, instead of restarting interpolation at
+/// every chunk boundary.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Resampler {
+    quality: ResampleQuality,
+    /// Input carried over from earlier chunks
+    history: Vec<f32>,
+    /// Read position, relative to the start of `history`
+    position: f64,
+    /// Filter for the current input rate; unset for `Fast` and before the first chunk
+    kernel: Option<Kernel>,
+}
+
+impl Resampler {
+    pub(crate) fn new(quality: ResampleQuality) -> Self {
+        Self {
+            quality,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn quality(&self) -> ResampleQuality {
+        self.quality
+    }
+
+    /// Forgets the stream so far, as when the input format changes
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new(self.quality);
+    }
+
+    /// Resamples one mono chunk from `from_rate` to [`TARGET_SAMPLE_RATE`]
+    ///
+    /// Input already at the target rate is passed through without copying. The
+    /// filters look ahead by half their length, so their last few outputs wait
+    /// for the next chunk.
+    pub(crate) fn process<'a>(&mut self, samples: &'a [i16], from_rate: u32) -> Cow<'a, [i16]> {
+        if from_rate == TARGET_SAMPLE_RATE {
+            self.reset();
+            return Cow::Borrowed(samples);
+        }
+        if samples.is_empty() {
+            return Cow::Borrowed(samples);
+        }
+        if let Some((zero_crossings, rolloff)) = self.quality.filter_shape() {
+            if self.kernel.as_ref().is_none_or(|k| k.rate != from_rate) {
+                let kernel = Kernel::windowed_sinc(from_rate, zero_crossings, rolloff);
+                if self.kernel.is_none() {
+                    // Silence before the stream, so the first output is centered on the first input
+                    self.history = vec![0.0; kernel.half - 1];
+                    self.position = (kernel.half - 1) as f64;
+                }
+                self.kernel = Some(kernel);
+            }
+        }
+        let half = self.kernel.as_ref().map_or(1, |k| k.half);
+
+        let mut input = std::mem::take(&mut self.history);
+        input.extend(samples.iter().map(|&s| f32::from(s)));
+        let len = input.len();
+
+        let step = from_rate as f64 / TARGET_SAMPLE_RATE as f64;
+        let mut output = Vec::with_capacity((samples.len() as f64 / step).ceil() as usize + 1);
+        let mut position = self.position;
+
+        while (position as usize) + half < len {
+            let idx = position as usize;
+            let frac = position - idx as f64;
+            let value = match &self.kernel {
+                None => {
+                    let s0 = input[idx] as f64;
+                    let s1 = input[idx + 1] as f64;
+                    s0 + (s1 - s0) * frac
+                }
+                Some(kernel) => kernel.apply(&input[idx + 1 - half..=idx + half], frac),
+            };
+            output.push(value.clamp(-32768.0, 32767.0) as i16);
+            position += step;
+        }
+
+        // Keep what the next output reads, re-anchoring the position on it
+        let keep_from = ((position as usize + 1).saturating_sub(half)).min(len);
+        input.drain(..keep_from);
+        self.history = input;
+        self.position = position - keep_from as f64;
+
+        Cow::Owned(output)
+    }
+}
+
+/// Fractional positions between input samples with precomputed filter taps
+const PHASES: usize = 256;
+
+/// Windowed-sinc low-pass filter, tabulated for [`PHASES`] fractional offsets
+#[derive(Debug, Clone)]
+struct Kernel {
+    /// Input rate the cutoff was chosen for
+    rate: u32,
+    /// Taps on each side of the read position
+    half: usize,
+    /// `PHASES + 1` rows of `2 * half` taps, each row summing to one
+    taps: Vec<f32>,
+}
+
+impl Kernel {
+    fn windowed_sinc(rate: u32, zero_crossings: usize, rolloff: f64) -> Self {
+        // Below the lower of the two Nyquist frequencies, in input-sample units
+        let cutoff = rolloff * (TARGET_SAMPLE_RATE as f64 / rate as f64).min(1.0);
+        let half = (zero_crossings as f64 / cutoff).ceil() as usize;
+        let width = 2 * half;
+
+        let mut taps = Vec::with_capacity((PHASES + 1) * width);
+        for phase in 0..=PHASES {
+            let frac = phase as f64 / PHASES as f64;
+            let row: Vec<f64> = (0..width)
+                .map(|j| {
+                    let t = (j as f64 + 1.0 - half as f64) - frac;
+                    cutoff * sinc(cutoff * t) * blackman(t / half as f64)
+                })
+                .collect();
+            let sum: f64 = row.iter().sum();
+            taps.extend(row.iter().map(|tap| (tap / sum) as f32));
+        }
+        Self { rate, half, taps }
+    }
+
+    /// Filters the `2 * half` inputs around a read position `frac` past the middle
+    fn apply(&self, window: &[f32], frac: f64) -> f64 {
+        let width = 2 * self.half;
+        let phase = (frac * PHASES as f64).round() as usize;
+        let row = &self.taps[phase * width..(phase + 1) * width];
+        let sum: f32 = window.iter().zip(row).map(|(x, tap)| x * tap).sum();
+        sum as f64
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        (PI * x).sin() / (PI * x)
+    }
+}
+
+/// Blackman window over -1..=1
+fn blackman(u: f64) -> f64 {
+    if u.abs() >= 1.0 {
+        return 0.0;
+    }
+    0.42 + 0.5 * (PI * u).cos() + 0.08 * (2.0 * PI * u).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const QUALITIES: [ResampleQuality; 3] = [
+        ResampleQuality::Fast,
+        ResampleQuality::Medium,
+        ResampleQuality::Best,
+    ];
+
+    fn tone(freq: f64, rate: u32, secs: f64) -> Vec<i16> {
+        let len = (rate as f64 * secs) as usize;
+        (0..len)
+            .map(|i| (10000.0 * (2.0 * PI * freq * i as f64 / rate as f64).sin()) as i16)
+            .collect()
+    }
+
+    /// RMS of the output, skipping the filter's start-up
+    fn rms(samples: &[i16]) -> f64 {
+        let settled = &samples[samples.len() / 10..];
+        let power: f64 = settled.iter().map(|&s| (s as f64).powi(2)).sum();
+        (power / settled.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn resampler_is_continuous_across_chunks() {
+        let ramp: Vec<i16> = (0..4800).map(|i| i as i16).collect();
+
+        let mut whole = Resampler::new(ResampleQuality::Fast);
+        let expected = whole.process(&ramp, 48000);
+
+        let mut chunked = Resampler::new(ResampleQuality::Fast);
+        let (first, second) = ramp.split_at(1237);
+        let mut output = chunked.process(first, 48000).into_owned();
+        output.extend_from_slice(&chunked.process(second, 48000));
+
+        assert_eq!(output, &*expected);
+        // Downsampling a ramp by 3x steps by exactly 3 with no seam at the boundary
+        assert!(
+            output.windows(2).all(|w| w[1] - w[0] == 3),
+            "output has a discontinuity"
+        );
+    }
+
+    #[test]
+    fn resampler_passes_through_target_rate() {
+        let mut resampler = Resampler::default();
+        let samples = vec![1, -2, 3, -4];
+        let output = resampler.process(&samples, TARGET_SAMPLE_RATE);
+        assert!(
+            matches!(output, Cow::Borrowed(_)),
+            "passthrough should not copy"
+        );
+        assert_eq!(output, samples);
+    }
+
+    #[test]
+    fn filters_are_continuous_across_chunks() {
+        let input = tone(440.0, 44100, 0.5);
+        for quality in [ResampleQuality::Medium, ResampleQuality::Best] {
+            let mut whole = Resampler::new(quality);
+            let expected = whole.process(&input, 44100).into_owned();
+
+            let mut chunked = Resampler::new(quality);
+            let mut output = Vec::new();
+            for chunk in input.chunks(333) {
+                output.extend_from_slice(&chunked.process(chunk, 44100));
+            }
+            assert_eq!(output, expected, "{:?}", quality);
+        }
+    }
+
+    #[test]
+    fn filters_keep_speech_and_remove_aliases() {
+        for quality in QUALITIES {
+            // 1kHz is well inside the speech band and must survive
+            let kept = rms(&Resampler::new(quality).process(&tone(1000.0, 48000, 0.5), 48000));
+            assert!(
+                (kept / 7071.0 - 1.0).abs() < 0.05,
+                "{:?} passband: {}",
+                quality,
+                kept
+            );
+        }
+
+        // 12kHz can't be represented at 16kHz and would fold back to 4kHz
+        let alias =
+            |quality| rms(&Resampler::new(quality).process(&tone(12000.0, 48000, 0.5), 48000));
+        assert!(alias(ResampleQuality::Fast) > 1000.0);
+        assert!(alias(ResampleQuality::Medium) < 300.0);
+        assert!(alias(ResampleQuality::Best) < 30.0);
+    }
+
+    #[test]
+    fn sweep_keeps_its_length_and_loses_what_would_alias() {
+        // One second sweeping linearly from 100Hz to 20kHz
+        let rate = 48000;
+        let sweep: Vec<i16> = (0..rate)
+            .map(|i| {
+                let t = i as f64 / rate as f64;
+                (10000.0 * (2.0 * PI * (100.0 * t + 9950.0 * t * t)).sin()) as i16
+            })
+            .collect();
+
+        // RMS over a span of the sweep's seconds, in output samples
+        let rms_between = |output: &[i16], from: f64, to: f64| {
+            let span = &output[(from * 16000.0) as usize..(to * 16000.0) as usize];
+            let power: f64 = span.iter().map(|&s| (s as f64).powi(2)).sum();
+            (power / span.len() as f64).sqrt()
+        };
+        // Every quality, and the default, which has to filter
+        let resamplers = QUALITIES.map(Resampler::new).into_iter();
+        for mut resampler in resamplers.chain([Resampler::default()]) {
+            let quality = resampler.quality();
+            let mut output = Vec::new();
+            for chunk in sweep.chunks(480) {
+                output.extend_from_slice(&resampler.process(chunk, rate));
+            }
+            // A third as many samples, less the few the filter still waits on
+            assert!(
+                output.len() <= 16000 && output.len() >= 16000 - 32,
+                "{:?} output {} samples",
+                quality,
+                output.len()
+            );
+            // 1kHz to 5kHz passes, at the sweep's amplitude
+            let kept = rms_between(&output, 0.05, 0.25);
+            assert!(
+                (kept / 7071.0 - 1.0).abs() < 0.05,
+                "{:?} passband: {}",
+                quality,
+                kept
+            );
+            // From 10kHz on the sweep can only come out as aliases
+            let folded = rms_between(&output, 0.5, 0.99);
+            match quality {
+                ResampleQuality::Fast => assert!(folded > 1000.0, "{}", folded),
+                _ => assert!(folded < 30.0, "{:?} aliases: {}", quality, folded),
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! File helper kept running between transcriptions
+
+use crate::stderr_tail::StderrTail;
+use crate::{command_line, telemetry, wait_readable, ScribeError};
+use serde::Deserialize;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::io::AsRawFd;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Where a transcriber keeps its resident helper, shared with the idle reaper
+pub(crate) type ResidentSlot = Arc<Mutex<Option<ResidentHelper>>>;
+
+/// How long the helper may take to load its model
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long the helper may take to answer a request
+///
+/// Generous, as a request transcribes a whole file; it only bounds a helper
+/// that has hung.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// A `transcribe --serve` process with its model already loaded
+///
+/// Requests are JSON arrays of the arguments a one-shot helper would take, one
+/// per line; each gets a single JSON response line.
+pub(crate) struct ResidentHelper {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    stderr: StderrTail,
+    /// When the last request was answered, or the model finished loading
+    last_used: Instant,
+}
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    ok: bool,
+    #[serde(default)]
+    output: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl ResidentHelper {
+    /// Spawns `cmd` in serve mode and waits until the helper reports it is ready
+    pub(crate) fn spawn(mut cmd: Command) -> Result<Self, ScribeError> {
+        let mut child = cmd
+            .arg("--serve")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                telemetry::helper_spawn_failed();
+                ScribeError::HelperSpawn {
+                    command: command_line(&cmd),
+                    source: e,
+                }
+            })?;
+
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("Failed to open resident helper pipes".into());
+        };
+
+        let mut helper = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+            stderr: StderrTail::capture(stderr, false),
+            last_used: Instant::now(),
+        };
+        helper
+            .read_response(READY_TIMEOUT)?
+            .map_err(|e| format!("Helper warm-up failed: {}", e))?;
+        Ok(helper)
+    }
+
+    /// Sends one request
+    ///
+    /// The outer error means the helper is no longer usable; the inner result is
+    /// the helper's own answer to the request.
+    pub(crate) fn request(&mut self, args: &[&str]) -> Result<Result<String, String>, String> {
+        let line =
+            serde_json::to_string(args).map_err(|e| format!("Failed to encode request: {}", e))?;
+        writeln!(self.stdin, "{}", line)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("Failed to send request to resident helper: {}", e))?;
+        let response = self.read_response(REQUEST_TIMEOUT);
+        self.last_used = Instant::now();
+        response
+    }
+
+    /// Process ID of the helper
+    pub(crate) fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// How long the helper has gone without a request
+    pub(crate) fn idle_time(&self) -> Duration {
+        self.last_used.elapsed()
+    }
+
+    /// Reads the next response line, stopping the helper if none comes within
+    /// `timeout`
+    fn read_response(&mut self, timeout: Duration) -> Result<Result<String, String>, String> {
+        let deadline = Instant::now() + timeout;
+        let mut line = Vec::new();
+        loop {
+            // Only wait on the pipe once everything already read is used up
+            if self.stdout.buffer().is_empty() {
+                let left = deadline.saturating_duration_since(Instant::now());
+                if left.is_zero() {
+                    return Err(self.stop(&format!(
+                        "Resident helper did not answer within {:?}",
+                        timeout
+                    )));
+                }
+                if !wait_readable(self.stdout.get_ref().as_raw_fd(), left) {
+                    continue;
+                }
+            }
+            let available = match self.stdout.fill_buf() {
+                Ok([]) => return Err(self.stop("Resident helper exited")),
+                Ok(available) => available,
+                Err(e) => return Err(format!("Failed to read from resident helper: {}", e)),
+            };
+            let (taken, complete) = match available.iter().position(|&b| b == b'\n') {
+                Some(end) => (end + 1, true),
+                None => (available.len(), false),
+            };
+            line.extend_from_slice(&available[..taken]);
+            self.stdout.consume(taken);
+            if complete {
+                break;
+            }
+        }
+
+        let line = String::from_utf8_lossy(&line);
+        let line = line.trim();
+        let response: Response = serde_json::from_str(line)
+            .map_err(|e| format!("Failed to parse helper response: {} (line: {})", e, line))?;
+        Ok(if response.ok {
+            Ok(response.output.unwrap_or_default())
+        } else {
+            Err(response.error.unwrap_or_default())
+        })
+    }
+
+    /// Stops the helper and returns `reason` with the last of its stderr
+    fn stop(&mut self, reason: &str) -> String {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let stderr = self.stderr.finish();
+        if stderr.trim().is_empty() {
+            reason.to_string()
+        } else {
+            format!("{}: {}", reason, stderr.trim())
+        }
+    }
+}
+
+impl Drop for ResidentHelper {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Stops the helper with process ID `pid` in `slot` once it has gone `timeout`
+/// without a request
+///
+/// Runs on a thread of its own that ends when the helper is stopped or
+/// replaced, or when the transcriber owning `slot` is dropped. Sets `reaped`
+/// when it stops the helper, so the next request can load it again.
+pub(crate) fn reap_when_idle(
+    slot: &ResidentSlot,
+    pid: u32,
+    reaped: &Arc<AtomicBool>,
+    timeout: Duration,
+) {
+    let slot = Arc::downgrade(slot);
+    let reaped = Arc::clone(reaped);
+    let spawned = std::thread::Builder::new()
+        .name("swift-scribe-idle-helper".to_string())
+        .spawn(move || loop {
+            let Some(slot) = slot.upgrade() else {
+                return;
+            };
+            let wait = {
+                let mut resident = slot.lock().unwrap_or_else(PoisonError::into_inner);
+                match resident.as_ref() {
+                    Some(helper) if helper.id() == pid => {
+                        match timeout.checked_sub(helper.idle_time()) {
+                            Some(left) if !left.is_zero() => left,
+                            _ => {
+                                log::debug!("Stopping resident helper after {:?} idle", timeout);
+                                *resident = None;
+                                reaped.store(true, Ordering::Relaxed);
+                                return;
+                            }
+                        }
+                    }
+                    _ => return,
+                }
+            };
+            drop(slot);
+            std::thread::sleep(wait);
+        });
+    if let Err(e) = spawned {
+        log::warn!("Failed to start idle helper timer: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", script]);
+        cmd
+    }
+
+    #[test]
+    fn stops_a_helper_that_does_not_answer() {
+        let mut resident = ResidentHelper::spawn(helper(
+            r#"echo '{"ok":true}'; printf '{"ok":'; echo stuck >&2; exec sleep 30"#,
+        ))
+        .unwrap();
+        let started = Instant::now();
+        let err = resident
+            .read_response(Duration::from_millis(200))
+            .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(err, "Resident helper did not answer within 200ms: stuck");
+        assert!(resident.child.try_wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn reports_stderr_when_loading_fails() {
+        let err = ResidentHelper::spawn(helper("echo no model for xx-XX >&2; exit 1"))
+            .map(|_| ())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Resident helper exited: no model for xx-XX"
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Blocking iteration over a streaming session's results
+
+use crate::{ScribeError, StreamingResult, StreamingTranscriber};
+use std::time::Duration;
+
+/// How long each wait inside [`ResultIter::next`] lasts before polling again
+const NEXT_WAIT: Duration = Duration::from_secs(1);
+
+/// Iterator over a session's results, from
+/// [`StreamingTranscriber::results`]
+///
+/// Each `next()` blocks until the helper writes a result. Iteration ends once
+/// the helper closes its output; any other error is yielded once, after which
+/// the iterator ends too.
+pub struct ResultIter<'a> {
+    transcriber: &'a mut StreamingTranscriber,
+    done: bool,
+}
+
+impl Iterator for ResultIter<'_> {
+    type Item = Result<StreamingResult, ScribeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            match self.transcriber.poll_result_timeout(NEXT_WAIT) {
+                Ok(Some(result)) => return Some(Ok(result)),
+                Ok(None) => continue,
+                Err(ScribeError::StreamEnded) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for ResultIter<'_> {}
+
+impl StreamingTranscriber {
+    /// Iterates over the session's results, blocking for each one
+    ///
+    /// The iterator reads through the same buffered output as
+    /// [`poll_result`](Self::poll_result), so the two can be mixed: a line the
+    /// helper was partway through writing is picked up where the last poll
+    /// left it. Results go through the same processing too, such as
+    /// [`with_partial_rate`](crate::StreamingTranscriberBuilder::with_partial_rate)
+    /// throttling and transcript recording.
+    ///
+    /// Iteration ends when the helper process closes its output, once it has
+    /// written the results for all of the audio. Errors, such as a revoked
+    /// microphone permission or a reached session deadline, are yielded as
+    /// `Some(Err(_))` and end the iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// transcriber.start().unwrap();
+    ///
+    /// for result in transcriber.results() {
+    ///     match result {
+    ///         Ok(result) if result.is_final => println!("{}", result.text),
+    ///         Ok(_) => {}
+    ///         Err(e) => eprintln!("Error: {}", e),
+    ///     }
+    /// }
+    /// ```
+    pub fn results(&mut self) -> ResultIter<'_> {
+        ResultIter {
+            transcriber: self,
+            done: false,
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Writing system detection for recognized text
+
+use serde::{Deserialize, Serialize};
+
+/// Writing system of a piece of text, as found by [`detect_script`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Script {
+    /// Latin alphabet, including accented letters
+    Latin,
+    /// Greek alphabet
+    Greek,
+    /// Cyrillic alphabet
+    Cyrillic,
+    /// Armenian alphabet
+    Armenian,
+    /// Georgian alphabet
+    Georgian,
+    /// Hebrew abjad
+    Hebrew,
+    /// Arabic abjad
+    Arabic,
+    /// Devanagari, as used for Hindi or Marathi
+    Devanagari,
+    /// Thai script
+    Thai,
+    /// Korean Hangul
+    Hangul,
+    /// Japanese: kana, with any Chinese characters mixed in
+    Japanese,
+    /// Chinese characters without kana
+    Han,
+    /// Letters of a script not listed here
+    Other,
+    /// No letters at all, e.g. digits and punctuation only
+    Unknown,
+}
+
+/// Finds the writing system most of the letters in `text` belong to
+///
+/// A simple Unicode block heuristic: every letter is assigned a script by its
+/// code point, and the most frequent script wins. Digits, punctuation and
+/// whitespace are ignored. Chinese characters count as [`Script::Japanese`]
+/// when the text also contains kana.
+///
+/// Text in an unexpected script is a sign of misrecognition, e.g. Cyrillic out
+/// of a session with a Spanish locale, and worth re-running with another
+/// locale.
+///
+/// # Examples
+///
+/// ```
+/// use swift_scribe::{detect_script, Script};
+///
+/// assert_eq!(detect_script("¿Dónde está la estación?"), Script::Latin);
+/// assert_eq!(detect_script("Где вокзал?"), Script::Cyrillic);
+/// assert_eq!(detect_script("42!"), Script::Unknown);
+/// ```
+pub fn detect_script(text: &str) -> Script {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    for script in text.chars().filter(|c| c.is_alphabetic()).map(script_of) {
+        match counts.iter_mut().find(|(seen, _)| *seen == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+
+    // Kanji in Japanese text would otherwise outvote the kana
+    if let Some(kana) = counts
+        .iter()
+        .position(|(script, _)| *script == Script::Japanese)
+    {
+        if let Some(han) = counts.iter().position(|(script, _)| *script == Script::Han) {
+            counts[kana].1 += counts[han].1;
+            counts.remove(han);
+        }
+    }
+
+    // On a tie, the script seen first wins
+    counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map_or(Script::Unknown, |(script, _)| *script)
+}
+
+/// Script of a single letter
+fn script_of(c: char) -> Script {
+    match c as u32 {
+        0x0041..=0x024F | 0x1E00..=0x1EFF => Script::Latin,
+        0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x0400..=0x052F => Script::Cyrillic,
+        0x0530..=0x058F => Script::Armenian,
+        0x10A0..=0x10FF => Script::Georgian,
+        0x0590..=0x05FF => Script::Hebrew,
+        0x0600..=0x06FF | 0x0750..=0x077F => Script::Arabic,
+        0x0900..=0x097F => Script::Devanagari,
+        0x0E00..=0x0E7F => Script::Thai,
+        0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+        0x3040..=0x30FF => Script::Japanese,
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF => Script::Han,
+        _ => Script::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_dominant_script() {
+        assert_eq!(detect_script("Hola, ¿qué tal?"), Script::Latin);
+        assert_eq!(detect_script("Привет мир"), Script::Cyrillic);
+        assert_eq!(detect_script("Καλημέρα"), Script::Greek);
+        assert_eq!(detect_script("שלום"), Script::Hebrew);
+        assert_eq!(detect_script("안녕하세요"), Script::Hangul);
+        assert_eq!(detect_script("你好世界"), Script::Han);
+        // Two kanji and two kana still read as Japanese
+        assert_eq!(detect_script("日本です"), Script::Japanese);
+        // A stray Latin word doesn't outvote the surrounding Cyrillic
+        assert_eq!(
+            detect_script("мы пошли в Starbucks вчера вечером"),
+            Script::Cyrillic
+        );
+        assert_eq!(detect_script("১২৩ বাংলা"), Script::Other);
+        assert_eq!(detect_script(" 3.14 — ok? "), Script::Latin);
+        assert_eq!(detect_script("3.14 —"), Script::Unknown);
+    }
+}
@@ -0,0 +1,220 @@
+//! Re-segmentation of streaming results on sentence boundaries
+
+use crate::{ResultKind, StreamingResult};
+
+/// Buffers final results and re-emits them as one final per sentence
+///
+/// Sentences end at `.`, `!` or `?` (optionally followed by closing quotes or
+/// brackets) when followed by whitespace or the end of the buffered text, so
+/// decimals like `3.5` don't split. Partials are prefixed with the sentence
+/// still being assembled so they always show the whole sentence in progress.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SentenceSegmenter {
+    /// Text of the unfinished sentence
+    buffer: String,
+    /// Timestamp of the first final that contributed to `buffer`
+    start: Option<f64>,
+    /// Timestamp of the most recent final
+    last: f64,
+}
+
+impl SentenceSegmenter {
+    /// Feeds one recognizer result, returning the results to emit in its place
+    pub(crate) fn push(&mut self, result: StreamingResult) -> Vec<StreamingResult> {
+        if !result.is_final {
+            if self.buffer.is_empty() || result.kind == ResultKind::EndOfSpeech {
+                return vec![result];
+            }
+            return vec![StreamingResult {
+                text: join(&self.buffer, &result.text),
+                stable_text: result
+                    .stable_text
+                    .as_deref()
+                    .map(|stable| join(&self.buffer, stable)),
+                start_timestamp: self.start,
+                words: None,
+                ..result
+            }];
+        }
+
+        let text = result.text.trim();
+        if text.is_empty() {
+            return Vec::new();
+        }
+        if self.buffer.is_empty() {
+            self.start = Some(result.timestamp);
+        }
+        self.buffer = join(&self.buffer, text);
+        self.last = result.timestamp;
+
+        let mut sentences = Vec::new();
+        while let Some(end) = sentence_end(&self.buffer) {
+            let rest = self.buffer.split_off(end);
+            let sentence = std::mem::replace(&mut self.buffer, rest.trim_start().to_string());
+            // Alternatives and word timings cover the whole final, so they
+            // only fit a sentence that is all of it
+            let whole = sentence == text;
+            let alternatives = if whole {
+                result.alternatives.clone()
+            } else {
+                Vec::new()
+            };
+            sentences.push(StreamingResult {
+                text: sentence,
+                start_timestamp: self.start,
+                alternatives,
+                words: result.words.clone().filter(|_| whole),
+                ..result.clone()
+            });
+            // Whatever follows the boundary arrived with this result
+            self.start = Some(result.timestamp);
+        }
+        if self.buffer.is_empty() {
+            self.start = None;
+        }
+        sentences
+    }
+
+    /// Emits the trailing unfinished sentence, if any
+    pub(crate) fn flush(&mut self) -> Option<StreamingResult> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        Some(StreamingResult {
+            text: std::mem::take(&mut self.buffer),
+            is_final: true,
+            // The helper has exited, so nothing can be revised any more
+            kind: ResultKind::Committed,
+            timestamp: self.last,
+            start_timestamp: self.start.take(),
+            alternatives: Vec::new(),
+            is_revision: false,
+            stable_text: None,
+            words: None,
+        })
+    }
+}
+
+fn join(head: &str, tail: &str) -> String {
+    match (head.is_empty(), tail.is_empty()) {
+        (true, _) => tail.to_string(),
+        (_, true) => head.to_string(),
+        _ => format!("{} {}", head, tail),
+    }
+}
+
+/// Whether `text` ends with a sentence terminator, ignoring closing quotes and brackets
+pub(crate) fn ends_sentence(text: &str) -> bool {
+    text.trim_end()
+        .trim_end_matches(['"', '\'', '”', '’', ')', ']'])
+        .ends_with(['.', '!', '?'])
+}
+
+/// Byte offset just past the first sentence terminator in `text`
+fn sentence_end(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, ch)) = chars.next() {
+        if !matches!(ch, '.' | '!' | '?') {
+            continue;
+        }
+        // Keep runs like "?!" and closing quotes with the sentence they end
+        while let Some(&(_, next)) = chars.peek() {
+            if matches!(next, '.' | '!' | '?' | '"' | '\'' | '”' | '’' | ')' | ']') {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        match chars.peek() {
+            None => return Some(text.len()),
+            Some(&(idx, next)) if next.is_whitespace() => return Some(idx),
+            Some(_) => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str, is_final: bool, timestamp: f64) -> StreamingResult {
+        StreamingResult {
+            text: text.to_string(),
+            is_final,
+            kind: Default::default(),
+            timestamp,
+            start_timestamp: None,
+            alternatives: Vec::new(),
+            is_revision: false,
+            stable_text: None,
+            words: None,
+        }
+    }
+
+    fn texts(results: &[StreamingResult]) -> Vec<&str> {
+        results.iter().map(|r| r.text.as_str()).collect()
+    }
+
+    #[test]
+    fn merges_and_splits_finals_on_sentence_boundaries() {
+        let mut segmenter = SentenceSegmenter::default();
+
+        assert!(segmenter.push(result("so the plan", true, 1.0)).is_empty());
+        let out = segmenter.push(result("is simple. We ship it! Then what", true, 2.0));
+        assert_eq!(texts(&out), ["so the plan is simple.", "We ship it!"]);
+        assert_eq!(out[0].start_timestamp, Some(1.0));
+        assert_eq!(out[0].timestamp, 2.0);
+        assert_eq!(out[1].start_timestamp, Some(2.0));
+
+        let out = segmenter.push(result("happens? Nothing", true, 3.0));
+        assert_eq!(texts(&out), ["Then what happens?"]);
+        assert_eq!(out[0].start_timestamp, Some(2.0));
+
+        let rest = segmenter.flush().expect("trailing sentence");
+        assert_eq!(rest.text, "Nothing");
+        assert!(rest.is_final);
+        assert_eq!((rest.start_timestamp, rest.timestamp), (Some(3.0), 3.0));
+        assert!(segmenter.flush().is_none());
+    }
+
+    #[test]
+    fn alternatives_stay_with_whole_finals_only() {
+        let mut segmenter = SentenceSegmenter::default();
+        let with_alternatives = |text| StreamingResult {
+            alternatives: vec!["alternative".to_string()],
+            ..result(text, true, 1.0)
+        };
+
+        let out = segmenter.push(with_alternatives("I scream."));
+        assert_eq!(out[0].alternatives, ["alternative"]);
+
+        let out = segmenter.push(with_alternatives("Ice cream. For"));
+        assert_eq!(texts(&out), ["Ice cream."]);
+        assert!(out[0].alternatives.is_empty());
+    }
+
+    #[test]
+    fn partials_include_the_sentence_in_progress() {
+        let mut segmenter = SentenceSegmenter::default();
+        assert_eq!(
+            texts(&segmenter.push(result("hello", false, 1.0))),
+            ["hello"]
+        );
+
+        segmenter.push(result("hello there", true, 1.5));
+        let out = segmenter.push(result("my friend", false, 2.0));
+        assert_eq!(texts(&out), ["hello there my friend"]);
+        assert!(!out[0].is_final);
+        assert_eq!(out[0].start_timestamp, Some(1.5));
+    }
+
+    #[test]
+    fn decimals_and_quotes_do_not_split_early() {
+        assert_eq!(sentence_end("it costs 3.5 dollars"), None);
+        assert_eq!(sentence_end("he said \"stop.\" Then"), Some(15));
+        assert_eq!(sentence_end("really?!"), Some(8));
+        assert!(ends_sentence("he said \"stop.\" "));
+        assert!(!ends_sentence("costs 3.5"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Forced splitting of segments that run longer than a maximum duration
+
+use crate::{TranscriptionSegment, WordTiming};
+use std::ops::Range;
+use std::time::Duration;
+
+/// Splits every segment longer than `max` at the word gaps closest to the limit
+///
+/// Words are timed by the segment's [`words`](TranscriptionSegment::words) when
+/// it has one timing per word of its text. Otherwise, as the recognizer only
+/// timed the whole segment, each word gets a share of its segment's span in
+/// proportion to its length, as in alignment. Words are then packed into pieces
+/// spanning at most `max` in order; a piece always holds at least one word, so a
+/// single word longer than `max` stays whole. Segments within the limit, or of a
+/// single word, are returned unchanged.
+pub(crate) fn split_long(
+    segments: Vec<TranscriptionSegment>,
+    max: Duration,
+) -> Vec<TranscriptionSegment> {
+    let max_secs = max.as_secs_f64();
+    let mut split = Vec::with_capacity(segments.len());
+    for segment in segments {
+        if segment.duration_secs <= max_secs || segment.text.split_whitespace().nth(1).is_none() {
+            split.push(segment);
+            continue;
+        }
+
+        let words: Vec<&str> = segment.text.split_whitespace().collect();
+        // Timings no longer match once disfluency removal or ITN rewrote the text
+        let timings = segment
+            .words
+            .as_deref()
+            .filter(|timings| timings.len() == words.len());
+        let spans = match timings {
+            Some(timings) => timings.iter().map(|word| (word.start, word.end)).collect(),
+            None => estimate_spans(&segment, &words),
+        };
+
+        let mut first = 0;
+        for last in 0..words.len() {
+            if last > first && spans[last].1 - spans[first].0 > max_secs {
+                split.push(piece_of(&segment, &words, &spans, timings, first..last));
+                first = last;
+            }
+        }
+        split.push(piece_of(
+            &segment,
+            &words,
+            &spans,
+            timings,
+            first..words.len(),
+        ));
+    }
+    split
+}
+
+/// Start and end of each word, sharing out the segment's span by word length
+fn estimate_spans(segment: &TranscriptionSegment, words: &[&str]) -> Vec<(f64, f64)> {
+    let total: usize = words.iter().map(|word| word.chars().count()).sum();
+    let mut start = segment.start_secs;
+    words
+        .iter()
+        .map(|word| {
+            let end = start + segment.duration_secs * word.chars().count() as f64 / total as f64;
+            let span = (start, end);
+            start = end;
+            span
+        })
+        .collect()
+}
+
+fn piece_of(
+    segment: &TranscriptionSegment,
+    words: &[&str],
+    spans: &[(f64, f64)],
+    timings: Option<&[WordTiming]>,
+    range: Range<usize>,
+) -> TranscriptionSegment {
+    let start_secs = spans[range.start].0;
+    TranscriptionSegment {
+        text: words[range.clone()].join(" "),
+        start_secs,
+        duration_secs: spans[range.end - 1].1 - start_secs,
+        words: timings.map(|timings| timings[range].to_vec()),
+        ..segment.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_secs: f64, duration_secs: f64) -> TranscriptionSegment {
+        TranscriptionSegment {
+            text: text.to_string(),
+            start_secs,
+            duration_secs,
+            confidence: Some(0.9),
+            script: None,
+            locale: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn splits_at_word_gaps() {
+        // Every word is four characters, so each gets a second of the span
+        let monologue = segment("abcd efgh ijkl mnop qrst", 10.0, 5.0);
+        let short = segment("done", 15.0, 1.0);
+        let pieces = split_long(vec![monologue, short.clone()], Duration::from_secs(2));
+
+        let texts: Vec<&str> = pieces.iter().map(|piece| piece.text.as_str()).collect();
+        assert_eq!(texts, ["abcd efgh", "ijkl mnop", "qrst", "done"]);
+        let starts: Vec<f64> = pieces.iter().map(|piece| piece.start_secs).collect();
+        assert_eq!(starts, [10.0, 12.0, 14.0, 15.0]);
+        assert_eq!(pieces[2].duration_secs, 1.0);
+        assert_eq!(pieces[3], short);
+        assert!(pieces.iter().all(|piece| piece.confidence == Some(0.9)));
+    }
+
+    #[test]
+    fn keeps_words_longer_than_the_limit_whole() {
+        let pieces = split_long(
+            vec![
+                segment("ab verylongword", 0.0, 7.0),
+                segment("Supercalifragilistic", 7.0, 3.0),
+            ],
+            Duration::from_secs(2),
+        );
+        let texts: Vec<&str> = pieces.iter().map(|piece| piece.text.as_str()).collect();
+        assert_eq!(texts, ["ab", "verylongword", "Supercalifragilistic"]);
+        assert_eq!(pieces[1].start_secs, 1.0);
+        assert_eq!(pieces[1].duration_secs, 6.0);
+    }
+
+    #[test]
+    fn splits_at_reported_word_timings() {
+        let timing = |text: &str, start: f64, end: f64| WordTiming {
+            text: text.to_string(),
+            start,
+            end,
+            confidence: None,
+        };
+        // A long pause after the first word, which a length-based estimate misses
+        let mut monologue = segment("so then we left", 0.0, 6.0);
+        monologue.words = Some(vec![
+            timing("so", 0.0, 0.3),
+            timing("then", 3.0, 3.4),
+            timing("we", 3.5, 3.7),
+            timing("left", 3.8, 6.0),
+        ]);
+        let pieces = split_long(vec![monologue], Duration::from_secs(3));
+
+        let texts: Vec<&str> = pieces.iter().map(|piece| piece.text.as_str()).collect();
+        assert_eq!(texts, ["so", "then we left"]);
+        assert_eq!((pieces[0].start_secs, pieces[0].duration_secs), (0.0, 0.3));
+        assert_eq!((pieces[1].start_secs, pieces[1].duration_secs), (3.0, 3.0));
+        assert_eq!(pieces[1].words.as_ref().map(Vec::len), Some(3));
+
+        // Timings that no longer match the text fall back to the estimate
+        let mut rewritten = segment("twenty five", 0.0, 6.0);
+        rewritten.words = Some(vec![timing("twenty-five", 0.0, 6.0)]);
+        let pieces = split_long(vec![rewritten], Duration::from_secs(3));
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces.iter().all(|piece| piece.words.is_none()));
+    }
+}
@@ -0,0 +1,116 @@
+//! Holding back partials until the recognizer settles on them
+
+use crate::{ResultKind, StreamingResult};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How eagerly partial results are shown
+///
+/// The recognizer revises its partials as it hears more audio. Showing every
+/// one gives the fastest feedback; waiting for a partial to stop changing gives
+/// fewer updates that are less likely to be taken back. Finals are never
+/// delayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StabilityMode {
+    /// Every partial as soon as it arrives (default)
+    #[default]
+    Responsive,
+    /// Only partials that stayed unchanged for a short confirmation window
+    Stable,
+}
+
+/// How long a partial must go unrevised before [`StabilityMode::Stable`] shows it
+const CONFIRMATION_WINDOW: Duration = Duration::from_millis(300);
+
+/// Holds partials back until they have been stable for [`CONFIRMATION_WINDOW`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StabilityGate {
+    /// Newest partial and when its text first appeared
+    candidate: Option<(StreamingResult, Instant)>,
+    /// Text of the partial last shown, so a repeat isn't shown twice
+    shown: Option<String>,
+}
+
+impl StabilityGate {
+    /// Takes a result from the helper, returning it if it can be shown right away
+    pub(crate) fn push(
+        &mut self,
+        result: StreamingResult,
+        now: Instant,
+    ) -> Option<StreamingResult> {
+        if result.is_final {
+            self.candidate = None;
+            self.shown = None;
+            return Some(result);
+        }
+        if result.kind == ResultKind::EndOfSpeech {
+            return Some(result);
+        }
+        match &mut self.candidate {
+            // The same text again keeps its clock running
+            Some((candidate, _)) if candidate.text == result.text => *candidate = result,
+            _ => self.candidate = Some((result, now)),
+        }
+        None
+    }
+
+    /// Returns the held partial once its text has survived the confirmation window
+    pub(crate) fn confirmed(&mut self, now: Instant) -> Option<StreamingResult> {
+        let (_, since) = self.candidate.as_ref()?;
+        if now.duration_since(*since) < CONFIRMATION_WINDOW {
+            return None;
+        }
+        let (result, _) = self.candidate.take()?;
+        if self.shown.as_deref() == Some(result.text.as_str()) {
+            return None;
+        }
+        self.shown = Some(result.text.clone());
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(text: &str, is_final: bool) -> StreamingResult {
+        StreamingResult {
+            text: text.to_string(),
+            is_final,
+            kind: ResultKind::default(),
+            timestamp: 0.0,
+            start_timestamp: None,
+            alternatives: Vec::new(),
+            is_revision: false,
+            stable_text: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn shows_partials_once_they_settle() {
+        let mut gate = StabilityGate::default();
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        assert!(gate.push(result("the", false), at(0)).is_none());
+        assert!(gate.push(result("the cap", false), at(100)).is_none());
+        assert!(gate.confirmed(at(350)).is_none(), "revised 250ms ago");
+        assert!(gate.push(result("the cap", false), at(380)).is_none());
+        assert_eq!(gate.confirmed(at(400)).unwrap().text, "the cap");
+        assert!(gate.confirmed(at(800)).is_none());
+
+        // Repeating a shown partial doesn't show it again
+        gate.push(result("the cap", false), at(900));
+        assert!(gate.confirmed(at(1300)).is_none());
+
+        // Finals pass straight through and drop the held partial
+        gate.push(result("the cat", false), at(1400));
+        assert_eq!(
+            gate.push(result("the cat", true), at(1410)).unwrap().text,
+            "the cat"
+        );
+        assert!(gate.confirmed(at(2000)).is_none());
+    }
+}
@@ -0,0 +1,69 @@
+//! Forwarding of a helper's stderr that remembers its last lines
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::process::ChildStderr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+/// Number of stderr lines kept for error messages
+const KEPT_LINES: usize = 20;
+
+/// Reads a helper's stderr on a background thread, keeping its last lines
+///
+/// When forwarding, the helper's messages still reach the terminal as they
+/// would with an inherited stderr. Either way the last lines stay available to
+/// explain an exit.
+pub(crate) struct StderrTail {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    forwarder: Option<JoinHandle<()>>,
+}
+
+impl StderrTail {
+    /// Starts reading `stderr`, copying it to this process's stderr if `forward` is set
+    pub(crate) fn capture(stderr: ChildStderr, forward: bool) -> Self {
+        let lines = Arc::new(Mutex::new(VecDeque::with_capacity(KEPT_LINES)));
+        let kept = Arc::clone(&lines);
+        let forwarder = std::thread::spawn(move || {
+            for line in BufReader::new(stderr).split(b'\n') {
+                let Ok(line) = line else { break };
+                if forward {
+                    let mut terminal = std::io::stderr().lock();
+                    let _ = terminal
+                        .write_all(&line)
+                        .and_then(|_| terminal.write_all(b"\n"));
+                }
+
+                let mut kept = kept.lock().unwrap_or_else(PoisonError::into_inner);
+                if kept.len() == KEPT_LINES {
+                    kept.pop_front();
+                }
+                kept.push_back(String::from_utf8_lossy(&line).into_owned());
+            }
+        });
+        Self {
+            lines,
+            forwarder: Some(forwarder),
+        }
+    }
+
+    /// Waits for the helper's stderr to close and returns the kept lines
+    ///
+    /// Only call this once the helper has exited, or it blocks until it does.
+    pub(crate) fn finish(&mut self) -> String {
+        if let Some(forwarder) = self.forwarder.take() {
+            let _ = forwarder.join();
+        }
+        self.lines()
+    }
+
+    /// Returns the lines kept so far
+    pub(crate) fn lines(&self) -> String {
+        let lines = self.lines.lock().unwrap_or_else(PoisonError::into_inner);
+        lines
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,162 @@
+//! Capture of the audio the system is playing, through ScreenCaptureKit
+
+use screencapturekit::{
+    cm_sample_buffer::CMSampleBuffer,
+    sc_content_filter::{InitParams, SCContentFilter},
+    sc_error_handler::StreamErrorHandler,
+    sc_output_handler::{SCStreamOutputType, StreamOutput},
+    sc_shareable_content::SCShareableContent,
+    sc_stream::SCStream,
+    sc_stream_configuration::SCStreamConfiguration,
+};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Sample rate the capture is configured for; it is captured in mono
+pub(crate) const CAPTURE_SAMPLE_RATE: u32 = 48000;
+
+/// A running ScreenCaptureKit stream delivering mono f32 audio
+///
+/// The stream lives on its own thread, so the transcriber holding this stays
+/// `Send`. Dropping it stops the capture.
+pub(crate) struct SystemAudioCapture {
+    samples: Receiver<Vec<f32>>,
+    /// Samples captured but not yet drained
+    queued: Arc<AtomicUsize>,
+    failed: Arc<AtomicBool>,
+    /// Dropping this tells the capture thread to stop the stream
+    _stop: Sender<()>,
+}
+
+impl SystemAudioCapture {
+    /// Starts capturing the audio of the main display
+    ///
+    /// At most `limit` of audio is kept waiting to be drained; buffers captured
+    /// beyond that are dropped.
+    pub(crate) fn start(limit: Duration) -> Result<Self, String> {
+        let failed = Arc::new(AtomicBool::new(false));
+        let queued = Arc::new(AtomicUsize::new(0));
+        let (sender, samples) = channel();
+        let (ready_sender, ready) = channel();
+        let (stop, stopped) = channel::<()>();
+
+        let output = AudioOutput {
+            sender,
+            queued: Arc::clone(&queued),
+            max_queued: (limit.as_secs_f64() * CAPTURE_SAMPLE_RATE as f64) as usize,
+        };
+        let flag = Arc::clone(&failed);
+        std::thread::spawn(move || {
+            let stream = match open_stream(output, flag) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = ready_sender.send(Err(e));
+                    return;
+                }
+            };
+            let _ = ready_sender.send(Ok(()));
+            // Returns once the capture is dropped
+            let _ = stopped.recv();
+            let _ = stream.stop_capture();
+        });
+
+        ready
+            .recv()
+            .map_err(|_| "System audio capture thread exited unexpectedly".to_string())??;
+        Ok(Self {
+            samples,
+            queued,
+            failed,
+            _stop: stop,
+        })
+    }
+
+    /// Takes the samples captured since the last call
+    pub(crate) fn drain(&self) -> Result<Vec<f32>, String> {
+        if self.failed.load(Ordering::Relaxed) {
+            return Err("System audio capture stopped with an error".to_string());
+        }
+        let samples: Vec<f32> = self.samples.try_iter().flatten().collect();
+        self.queued.fetch_sub(samples.len(), Ordering::Relaxed);
+        Ok(samples)
+    }
+}
+
+/// Sets up an audio-only stream of the main display and starts it
+fn open_stream(output: AudioOutput, failed: Arc<AtomicBool>) -> Result<SCStream, String> {
+    // Listing shareable content is what fails without Screen Recording permission
+    let content = SCShareableContent::try_current().map_err(|e| {
+        format!(
+            "Screen Recording permission is required to capture system audio; grant it in \
+             System Settings > Privacy & Security > Screen Recording ({})",
+            e
+        )
+    })?;
+    let display = content
+        .displays
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No display available to capture system audio from".to_string())?;
+
+    let config = SCStreamConfiguration {
+        // Video can't be turned off, so keep the frames as small as possible
+        width: 2,
+        height: 2,
+        captures_audio: true,
+        sample_rate: CAPTURE_SAMPLE_RATE,
+        channel_count: 1,
+        excludes_current_process_audio: true,
+        ..Default::default()
+    };
+    let filter = SCContentFilter::new(InitParams::Display(display));
+    let mut stream = SCStream::new(filter, config, ErrorFlag(failed));
+    stream.add_output(output, SCStreamOutputType::Audio);
+    stream
+        .start_capture()
+        .map_err(|e| format!("Failed to start system audio capture: {}", e))?;
+    Ok(stream)
+}
+
+/// Forwards captured audio buffers to the transcriber
+struct AudioOutput {
+    sender: Sender<Vec<f32>>,
+    /// Shared with [`SystemAudioCapture`], which subtracts what it drains
+    queued: Arc<AtomicUsize>,
+    /// Samples allowed to wait undrained before new buffers are dropped
+    max_queued: usize,
+}
+
+impl StreamOutput for AudioOutput {
+    fn did_output_sample_buffer(&self, sample: CMSampleBuffer, of_type: SCStreamOutputType) {
+        if !matches!(of_type, SCStreamOutputType::Audio) {
+            return;
+        }
+        // Mono capture arrives as a single buffer of native-endian f32 samples
+        let Some(buffer) = sample.sys_ref.get_av_audio_buffer_list().into_iter().next() else {
+            return;
+        };
+        let samples: Vec<f32> = buffer
+            .data
+            .chunks_exact(4)
+            .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        // A transcriber that isn't polled would otherwise buffer without bound
+        if self.queued.load(Ordering::Relaxed) + samples.len() > self.max_queued {
+            log::debug!("Dropping system audio the transcriber hasn't taken yet");
+            return;
+        }
+        self.queued.fetch_add(samples.len(), Ordering::Relaxed);
+        let _ = self.sender.send(samples);
+    }
+}
+
+/// Records that ScreenCaptureKit stopped the stream with an error
+struct ErrorFlag(Arc<AtomicBool>);
+
+impl StreamErrorHandler for ErrorFlag {
+    fn on_error(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
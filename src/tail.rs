@@ -0,0 +1,176 @@
+//! Streaming transcription of an audio file that is still being written
+
+use crate::{AudioFormat, AudioInputMode, ScribeError, StreamingTranscriber, FINISH_GRACE};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+/// Most bytes of the file fed per poll
+const TAIL_CHUNK: usize = 64 * 1024;
+
+/// A file being followed as it grows, after `start_tailing_file`
+pub(crate) struct FileTail {
+    path: PathBuf,
+    file: File,
+    /// Bytes of `file` fed so far
+    offset: u64,
+    /// Container of the file, once its header has been written
+    format: Option<AudioFormat>,
+    /// Whether the file was truncated or replaced; the session restarts on the
+    /// new one once the helper has finished the old
+    replaced: bool,
+}
+
+impl FileTail {
+    fn open(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            offset: 0,
+            format: None,
+            replaced: false,
+        })
+    }
+
+    /// Reads what was appended since the last call, up to [`TAIL_CHUNK`] bytes
+    ///
+    /// Nothing is read until the file holds a whole container header.
+    fn read_appended(&mut self) -> Result<Vec<u8>, String> {
+        let read_error =
+            |e: std::io::Error| format!("Failed to read {}: {}", self.path.display(), e);
+        if self.format.is_none() {
+            let mut head = [0u8; 4];
+            self.file.seek(SeekFrom::Start(0)).map_err(read_error)?;
+            if self.file.read_exact(&mut head).is_err() {
+                return Ok(Vec::new());
+            }
+            let format = AudioFormat::from_magic(&head).ok_or_else(|| {
+                format!("{} is not a CAF, AIFF or WAV stream", self.path.display())
+            })?;
+            self.format = Some(format);
+            self.file.seek(SeekFrom::Start(0)).map_err(read_error)?;
+        }
+
+        let mut data = Vec::with_capacity(TAIL_CHUNK);
+        (&mut self.file)
+            .take(TAIL_CHUNK as u64)
+            .read_to_end(&mut data)
+            .map_err(read_error)?;
+        self.offset += data.len() as u64;
+        Ok(data)
+    }
+
+    /// Whether the writer truncated the file, or put a new one at its path
+    ///
+    /// A path that is missing for now, between a rotation's rename and the
+    /// new file's creation, doesn't count.
+    fn was_replaced(&self) -> bool {
+        let Ok(open) = self.file.metadata() else {
+            return false;
+        };
+        if open.len() < self.offset {
+            return true;
+        }
+        std::fs::metadata(&self.path)
+            .is_ok_and(|current| current.dev() != open.dev() || current.ino() != open.ino())
+    }
+}
+
+impl StreamingTranscriber {
+    /// Starts a session that transcribes an audio file as it is written
+    ///
+    /// For recordings still in progress, such as one written by another app or
+    /// process: the file is read from the start, and whatever is appended
+    /// afterwards is fed to the helper, like
+    /// [`feed_encoded`](Self::feed_encoded) would, whenever results are polled,
+    /// so keep calling [`poll_result`](Self::poll_result). The container, CAF,
+    /// AIFF or WAV, is recognized from the header once the writer has put it in
+    /// the file. CAF suits recordings of unknown length best; a WAV or AIFF
+    /// writer has to leave the header's sizes open until it finishes.
+    ///
+    /// When the writer truncates the file, or rotates it by putting a new one
+    /// at `path`, the audio already read is finished first: its last results
+    /// arrive, then a new session starts on the new file. The rest of the old
+    /// file is read before switching, but audio written to it after that is
+    /// not. Truncation is noticed once the file is shorter than what was read.
+    /// [`stop`](Self::stop) or a new [`start`](Self::start) ends the tailing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transcriber doesn't use programmatic input,
+    /// `path` can't be opened, or the session can't be started, as with
+    /// [`start`](Self::start). A file that turns out not to be CAF, AIFF or WAV
+    /// makes the poll that reads its header return an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    /// use std::path::Path;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start_tailing_file(Path::new("recording.caf")).unwrap();
+    /// while let Ok(result) = transcriber.poll_result() {
+    ///     if let Some(result) = result {
+    ///         println!("{}", result.text);
+    ///     }
+    /// }
+    /// ```
+    pub fn start_tailing_file(&mut self, path: &Path) -> Result<(), ScribeError> {
+        if !matches!(self.input_mode, AudioInputMode::Programmatic) {
+            return Err(ScribeError::WrongInputMode(
+                "Tailing a file requires programmatic input mode".to_string(),
+            ));
+        }
+        let tail = FileTail::open(path)?;
+        self.start()?;
+        self.tail = Some(tail);
+        Ok(())
+    }
+
+    /// Feeds what was appended to the tailed file since the last poll
+    pub(crate) fn feed_tailed_file(&mut self) -> Result<(), ScribeError> {
+        let Some(tail) = self.tail.as_mut() else {
+            return Ok(());
+        };
+        if tail.replaced || self.writer.is_none() {
+            return Ok(());
+        }
+        let data = tail.read_appended()?;
+        if let Some(format) = tail.format.filter(|_| !data.is_empty()) {
+            return self.feed_encoded(&data, format);
+        }
+        if tail.was_replaced() {
+            log::debug!(
+                "{} was replaced; finishing its session",
+                tail.path.display()
+            );
+            tail.replaced = true;
+            self.request_finish(Some(FINISH_GRACE));
+        }
+        Ok(())
+    }
+
+    /// Whether the helper's output ended because the tailed file was replaced
+    pub(crate) fn tail_replaced(&self) -> bool {
+        self.tail.as_ref().is_some_and(|tail| tail.replaced)
+    }
+
+    /// Starts a new session on the file now at the tailed path
+    pub(crate) fn restart_tail(&mut self) -> Result<(), ScribeError> {
+        let Some(tail) = self.tail.take() else {
+            return Ok(());
+        };
+        self.stop()?;
+        let tail = FileTail::open(&tail.path)?;
+        self.start()?;
+        self.tail = Some(tail);
+        Ok(())
+    }
+}
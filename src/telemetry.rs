@@ -0,0 +1,35 @@
+//! Counters and histograms exported through the `metrics` crate
+//!
+//! Every function compiles to nothing unless the `metrics` feature is enabled,
+//! so call sites don't need their own `cfg` attributes.
+
+use std::time::Duration;
+
+/// A file transcription completed successfully after `elapsed`
+pub(crate) fn file_transcribed(elapsed: Duration) {
+    #[cfg(feature = "metrics")]
+    {
+        metrics::counter!("swift_scribe.files_transcribed").increment(1);
+        metrics::histogram!("swift_scribe.transcription_duration_seconds")
+            .record(elapsed.as_secs_f64());
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = elapsed;
+}
+
+/// A helper process could not be launched
+pub(crate) fn helper_spawn_failed() {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("swift_scribe.helper_spawn_failures").increment(1);
+}
+
+/// A streaming result was handed to the caller
+pub(crate) fn stream_result(is_final: bool) {
+    #[cfg(feature = "metrics")]
+    {
+        let kind = if is_final { "final" } else { "partial" };
+        metrics::counter!("swift_scribe.stream_results_total", "kind" => kind).increment(1);
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = is_final;
+}
@@ -0,0 +1,197 @@
+//! The consolidated transcript of a streaming session
+
+use crate::{
+    detect_script, FileTranscription, ScribeError, StreamingResult, StreamingTranscriber,
+    TranscriptionSegment, FINISH_GRACE,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The final results of a session, kept to build its transcript at the end
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SessionTranscript {
+    /// Unix time the session started, in seconds
+    started_at: f64,
+    finals: Vec<StreamingResult>,
+    /// The transcript once the session has been finished
+    finished: Option<FileTranscription>,
+}
+
+impl SessionTranscript {
+    /// Starts recording a new session
+    pub(crate) fn begin() -> Self {
+        Self {
+            started_at: unix_now(),
+            ..Self::default()
+        }
+    }
+
+    /// Records a result the caller is about to receive
+    pub(crate) fn record(&mut self, result: &StreamingResult) {
+        if result.is_final && self.finished.is_none() {
+            self.finals.push(result.clone());
+        }
+    }
+
+    /// Builds the transcript from the finals recorded so far
+    fn assemble(&self, ended_at: f64) -> FileTranscription {
+        let mut previous_end = self.started_at;
+        let segments: Vec<TranscriptionSegment> = self
+            .finals
+            .iter()
+            .filter(|result| !result.text.trim().is_empty())
+            .map(|result| {
+                // Without a merged start, a final covers the time since the one before it
+                let start = result
+                    .start_timestamp
+                    .unwrap_or(previous_end)
+                    .max(self.started_at);
+                previous_end = result.timestamp;
+                let text = result.text.trim().to_string();
+                TranscriptionSegment {
+                    script: Some(detect_script(&text)),
+                    text,
+                    start_secs: start - self.started_at,
+                    duration_secs: (result.timestamp - start).max(0.0),
+                    confidence: None,
+                    locale: None,
+                    words: result.words.clone(),
+                }
+            })
+            .collect();
+
+        FileTranscription {
+            full_text: segments
+                .iter()
+                .map(|segment| segment.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            segments,
+            confidence: None,
+            backend: None,
+            locale: None,
+            duration_secs: Some((ended_at - self.started_at).max(0.0)),
+        }
+    }
+}
+
+fn unix_now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |since| since.as_secs_f64())
+}
+
+impl StreamingTranscriber {
+    /// Ends the session and returns its whole transcript as timed segments
+    ///
+    /// Stops taking audio, lets the helper finish recognizing what it has
+    /// already received, and waits for its last results (up to ten seconds, after
+    /// which the helper is stopped). Every final result of the session goes
+    /// into the transcript, including those already returned by
+    /// [`poll_result`](Self::poll_result); partials are left out.
+    ///
+    /// Segment timings are when the results arrived, in seconds since
+    /// [`start`](Self::start), not positions in the audio. Streaming results
+    /// carry no confidence, backend or locale, so those fields are `None`;
+    /// `duration_secs` is the length of the session.
+    ///
+    /// The session is stopped afterwards. Calling this again returns the same
+    /// transcript until the next [`start`](Self::start). On a socket connection
+    /// the helper is told the audio has ended by shutting down the sending half
+    /// of the socket, and results are collected until it closes the connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transcriber was never started, or if reading the
+    /// helper's last results fails for a reason other than the helper exiting.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    /// transcriber.feed_audio_i16(&[0; 16000], 16000, 1).unwrap();
+    ///
+    /// let transcript = transcriber.finish_transcript().unwrap();
+    /// for segment in &transcript.segments {
+    ///     println!("{:>6.2}s {}", segment.start_secs, segment.text);
+    /// }
+    /// ```
+    pub fn finish_transcript(&mut self) -> Result<FileTranscription, ScribeError> {
+        let Some(session) = &self.transcript else {
+            return Err(ScribeError::NotStarted);
+        };
+        if let Some(transcript) = &session.finished {
+            return Ok(transcript.clone());
+        }
+
+        if self.reader.is_some() {
+            // A spawned helper still running after the grace period is killed,
+            // which ends its output
+            self.request_finish(Some(FINISH_GRACE));
+            loop {
+                match self.poll_result() {
+                    Ok(Some(_)) => {}
+                    Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+                    Err(_) if self.output_ended => break,
+                    Err(e) => return Err(e),
+                }
+            }
+            self.stop()?;
+        }
+
+        let session = self.transcript.as_mut().ok_or(ScribeError::NotStarted)?;
+        let transcript = session.assemble(unix_now());
+        session.finished = Some(transcript.clone());
+        Ok(transcript)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResultKind;
+
+    fn result(text: &str, is_final: bool, timestamp: f64) -> StreamingResult {
+        StreamingResult {
+            text: text.to_string(),
+            is_final,
+            kind: ResultKind::Final,
+            timestamp,
+            start_timestamp: None,
+            alternatives: Vec::new(),
+            is_revision: false,
+            stable_text: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn assembles_finals_into_timed_segments() {
+        let mut transcript = SessionTranscript {
+            started_at: 100.0,
+            ..SessionTranscript::default()
+        };
+        transcript.record(&result("hello", false, 101.0));
+        transcript.record(&result(" hello there ", true, 102.0));
+        transcript.record(&result("", true, 102.5));
+        transcript.record(&StreamingResult {
+            start_timestamp: Some(103.0),
+            ..result("general Kenobi", true, 104.5)
+        });
+
+        let file = transcript.assemble(106.0);
+        assert_eq!(file.full_text, "hello there general Kenobi");
+        let timings: Vec<(f64, f64)> = file
+            .segments
+            .iter()
+            .map(|segment| (segment.start_secs, segment.duration_secs))
+            .collect();
+        assert_eq!(timings, [(0.0, 2.0), (3.0, 1.5)]);
+        assert_eq!(file.duration_secs, Some(6.0));
+    }
+}
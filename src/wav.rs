@@ -0,0 +1,188 @@
+//! WAV decoding through the `hound` crate
+
+use crate::{BackpressureWarning, ScribeError, StreamingTranscriber, Transcriber};
+use hound::{SampleFormat, WavReader};
+use std::io::Read;
+
+/// Frames decoded and fed to the streaming helper at a time
+const CHUNK_FRAMES: usize = 4096;
+
+/// Opens a WAV stream, returning the reader and its sample rate and channel count
+fn open<R: Read>(reader: R) -> Result<(WavReader<R>, u32, u16), String> {
+    let wav = WavReader::new(reader).map_err(|e| format!("Failed to read WAV: {}", e))?;
+    let spec = wav.spec();
+    if spec.sample_rate == 0 || spec.channels == 0 {
+        return Err(format!(
+            "Invalid WAV layout: {} Hz, {} channels",
+            spec.sample_rate, spec.channels
+        ));
+    }
+    Ok((wav, spec.sample_rate, spec.channels))
+}
+
+/// Decodes every sample as 16-bit PCM, whatever the file's sample format
+fn samples<R: Read>(wav: &mut WavReader<R>) -> Box<dyn Iterator<Item = Result<i16, String>> + '_> {
+    let spec = wav.spec();
+    let read_error = |e: hound::Error| format!("Failed to read WAV: {}", e);
+    match spec.sample_format {
+        SampleFormat::Float => Box::new(wav.samples::<f32>().map(move |sample| {
+            sample
+                .map(|s| (s.clamp(-1.0, 1.0) * 32767.0) as i16)
+                .map_err(read_error)
+        })),
+        SampleFormat::Int => {
+            let bits = u32::from(spec.bits_per_sample);
+            Box::new(wav.samples::<i32>().map(move |sample| {
+                sample
+                    .map(|s| match bits {
+                        ..=16 => (s << (16 - bits)) as i16,
+                        _ => (s >> (bits - 16)) as i16,
+                    })
+                    .map_err(read_error)
+            }))
+        }
+    }
+}
+
+impl Transcriber {
+    /// Transcribes WAV audio read from `reader`
+    ///
+    /// Decodes the stream with `hound`, so any integer or float PCM WAV works,
+    /// including ones held in memory or received over the network rather than
+    /// saved to a file. The audio is converted to 16-bit PCM at its own sample
+    /// rate and channel count and staged as a temporary WAV file in
+    /// [`temp_dir`](Self::temp_dir) for the helper.
+    ///
+    /// Requires the `hound` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream isn't a WAV file `hound` can decode, or
+    /// the transcription fails as for [`transcribe_file`](Self::transcribe_file).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::Transcriber;
+    /// use std::io::Cursor;
+    ///
+    /// let transcriber = Transcriber::new().unwrap();
+    /// let upload: Vec<u8> = std::fs::read("upload.wav").unwrap();
+    /// let text = transcriber.transcribe_wav_reader(Cursor::new(upload)).unwrap();
+    /// ```
+    pub fn transcribe_wav_reader<R: Read>(&self, reader: R) -> Result<String, ScribeError> {
+        let (mut wav, sample_rate, channels) = open(reader)?;
+        let pcm = samples(&mut wav).collect::<Result<Vec<_>, _>>()?;
+        self.transcribe_samples(&pcm, sample_rate, channels)
+    }
+}
+
+impl StreamingTranscriber {
+    /// Feeds WAV audio read from `reader` to the transcriber
+    ///
+    /// Decodes the stream with `hound` and feeds it in chunks through
+    /// [`feed_audio_i16`](Self::feed_audio_i16) at the file's own sample rate
+    /// and channel count, so it is resampled and mixed down as usual. The whole
+    /// stream is fed before returning, as fast as it can be decoded.
+    ///
+    /// Requires the `hound` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream isn't a WAV file `hound` can decode, or
+    /// for the same reasons as [`feed_audio_i16`](Self::feed_audio_i16). Audio
+    /// fed before an error stays fed.
+    ///
+    /// # Returns
+    ///
+    /// The last [`BackpressureWarning`] raised while feeding, if the helper fell
+    /// behind.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    /// use std::fs::File;
+    /// use std::io::BufReader;
+    ///
+    /// let mut transcriber = StreamingTranscriber::builder()
+    ///     .with_programmatic_input()
+    ///     .build()
+    ///     .unwrap();
+    /// transcriber.start().unwrap();
+    /// let file = BufReader::new(File::open("call.wav").unwrap());
+    /// transcriber.feed_wav_reader(file).unwrap();
+    /// ```
+    pub fn feed_wav_reader<R: Read>(
+        &mut self,
+        reader: R,
+    ) -> Result<Option<BackpressureWarning>, ScribeError> {
+        let (mut wav, sample_rate, channels) = open(reader)?;
+        let chunk_len = CHUNK_FRAMES * usize::from(channels);
+        let mut decoded = samples(&mut wav);
+        let mut chunk = Vec::with_capacity(chunk_len);
+        let mut warning = None;
+        loop {
+            chunk.clear();
+            for sample in decoded.by_ref().take(chunk_len) {
+                chunk.push(sample?);
+            }
+            if chunk.is_empty() {
+                return Ok(warning);
+            }
+            if let Some(raised) = self.feed_audio_i16(&chunk, sample_rate, channels)? {
+                warning = Some(raised);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hound::{WavSpec, WavWriter};
+    use std::io::Cursor;
+
+    fn encode(spec: WavSpec, write: impl FnOnce(&mut WavWriter<&mut Cursor<Vec<u8>>>)) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        let mut writer = WavWriter::new(&mut bytes, spec).unwrap();
+        write(&mut writer);
+        writer.finalize().unwrap();
+        bytes.into_inner()
+    }
+
+    fn decode(bytes: Vec<u8>) -> Vec<i16> {
+        let (mut wav, _, _) = open(Cursor::new(bytes)).unwrap();
+        samples(&mut wav).collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn decodes_any_sample_format_to_i16() {
+        let spec = |bits_per_sample, sample_format| WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample,
+            sample_format,
+        };
+
+        let pcm8 = encode(spec(8, SampleFormat::Int), |w| {
+            w.write_sample(-128i8).unwrap();
+            w.write_sample(64i8).unwrap();
+        });
+        assert_eq!(decode(pcm8), [-32768, 16384]);
+
+        let pcm24 = encode(spec(24, SampleFormat::Int), |w| {
+            w.write_sample(-8_388_608i32).unwrap();
+            w.write_sample(256i32).unwrap();
+        });
+        assert_eq!(decode(pcm24), [-32768, 1]);
+
+        let float = encode(spec(32, SampleFormat::Float), |w| {
+            w.write_sample(0.5f32).unwrap();
+            w.write_sample(-2.0f32).unwrap();
+        });
+        assert_eq!(decode(float), [16383, -32767]);
+
+        assert!(open(Cursor::new(b"not a wav".to_vec())).is_err());
+    }
+}
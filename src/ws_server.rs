@@ -0,0 +1,317 @@
+//! WebSocket server pushing streaming results to remote clients
+
+use crate::{ScribeError, StreamingResult, StreamingTranscriber};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tungstenite::{Error, Message, WebSocket};
+
+/// How often the server threads check for new clients, results and shutdown
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Results queued for a client before it counts as fallen behind and is dropped
+const CLIENT_QUEUE: usize = 256;
+
+/// How long a client may take to complete the handshake or accept a message
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A listening WebSocket server and the clients connected to it
+///
+/// Each client is served by its own thread, fed through a bounded channel, so a
+/// slow client never holds up [`StreamingTranscriber::poll_result`]; one that
+/// falls [`CLIENT_QUEUE`] results behind is disconnected. Dropping the server
+/// stops accepting and closes every connection with a close frame.
+pub(crate) struct WsServer {
+    addr: SocketAddr,
+    clients: Arc<Mutex<Vec<SyncSender<String>>>>,
+    stopped: Arc<AtomicBool>,
+    /// Joined on drop, so the address is free again once the server is gone
+    acceptor: Option<JoinHandle<()>>,
+}
+
+impl WsServer {
+    /// Binds `addr` and starts accepting clients in the background
+    pub(crate) fn bind(addr: &str) -> Result<Self, String> {
+        let listener = TcpListener::bind(addr)
+            .map_err(|e| format!("Failed to bind WebSocket server to {}: {}", addr, e))?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to bind WebSocket server to {}: {}", addr, e))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure WebSocket server: {}", e))?;
+
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (accepted, flag) = (Arc::clone(&clients), Arc::clone(&stopped));
+        let acceptor = std::thread::spawn(move || accept_clients(listener, accepted, flag));
+
+        log::debug!("Serving streaming results on ws://{}", local_addr);
+        Ok(Self {
+            addr: local_addr,
+            clients,
+            stopped,
+            acceptor: Some(acceptor),
+        })
+    }
+
+    pub(crate) fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Sends a result to every connected client, forgetting those that left or
+    /// fell behind
+    pub(crate) fn broadcast(&self, result: &StreamingResult) {
+        let Ok(json) = serde_json::to_string(result) else {
+            return;
+        };
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        clients.retain(|client| match client.try_send(json.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                log::debug!("Dropping a WebSocket client that fell behind");
+                false
+            }
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+}
+
+impl Drop for WsServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        // Client threads close their connections once their channel disconnects
+        self.clients
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+        if let Some(acceptor) = self.acceptor.take() {
+            let _ = acceptor.join();
+        }
+    }
+}
+
+fn accept_clients(
+    listener: TcpListener,
+    clients: Arc<Mutex<Vec<SyncSender<String>>>>,
+    stopped: Arc<AtomicBool>,
+) {
+    while !stopped.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                let (sender, results) = sync_channel(CLIENT_QUEUE);
+                clients
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(sender);
+                std::thread::spawn(move || {
+                    if let Err(e) = serve_client(stream, results) {
+                        log::debug!("WebSocket client {} disconnected: {}", peer, e);
+                    }
+                });
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                log::warn!("WebSocket server stopped accepting clients: {}", e);
+                return;
+            }
+        }
+    }
+}
+
+/// Completes the handshake and forwards results until either side closes
+fn serve_client(stream: TcpStream, results: Receiver<String>) -> Result<(), String> {
+    stream.set_nonblocking(false).map_err(|e| e.to_string())?;
+    // A client that connects and never completes the handshake is given up on
+    stream
+        .set_read_timeout(Some(CLIENT_TIMEOUT))
+        .and_then(|()| stream.set_write_timeout(Some(CLIENT_TIMEOUT)))
+        .map_err(|e| e.to_string())?;
+    let mut socket = tungstenite::accept(stream).map_err(|e| e.to_string())?;
+    // Reads time out so the thread keeps forwarding results between client messages
+    socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .map_err(|e| e.to_string())?;
+
+    loop {
+        match results.recv_timeout(POLL_INTERVAL) {
+            Ok(json) => socket
+                .send(Message::text(json))
+                .map_err(|e| e.to_string())?,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return close(socket),
+        }
+        if !still_open(&mut socket)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Sends a close frame and waits briefly for the client to acknowledge it
+fn close(mut socket: WebSocket<TcpStream>) -> Result<(), String> {
+    socket.close(None).map_err(|e| e.to_string())?;
+    for _ in 0..20 {
+        if !still_open(&mut socket)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Handles whatever the client sent, returning whether the connection is still open
+///
+/// Pings are answered by the library; clients have nothing else to say.
+fn still_open(socket: &mut WebSocket<TcpStream>) -> Result<bool, String> {
+    match socket.read() {
+        Ok(_) => Ok(true),
+        Err(Error::Io(e)) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+            Ok(true)
+        }
+        Err(Error::ConnectionClosed) => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+impl StreamingTranscriber {
+    /// Pushes every streaming result to WebSocket clients connected to `addr`
+    ///
+    /// Starts a small server that accepts any number of clients. Each result
+    /// returned by [`poll_result`](Self::poll_result) (and so by
+    /// [`poll_delta`](Self::poll_delta)) is also sent to every client as a text
+    /// message holding the result's JSON, in the same format as the helper's
+    /// output. Clients that disconnect are dropped quietly, as are clients that
+    /// stop reading and fall 256 results behind; results are not buffered for
+    /// clients that connect later.
+    ///
+    /// The server keeps running across [`stop`](Self::stop) and
+    /// [`start`](Self::start), and shuts down, sending each client a close
+    /// frame, when the transcriber is dropped or `serve_ws` is called again.
+    /// Pass port 0 to pick a free port; the bound address is returned.
+    ///
+    /// Requires the `ws-server` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `addr` can't be bound.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use swift_scribe::StreamingTranscriber;
+    ///
+    /// let mut transcriber = StreamingTranscriber::new().unwrap();
+    /// let addr = transcriber.serve_ws("0.0.0.0:9001").unwrap();
+    /// println!("Results on ws://{}", addr);
+    ///
+    /// transcriber.start().unwrap();
+    /// loop {
+    ///     let _ = transcriber.poll_result().unwrap();
+    ///     std::thread::sleep(std::time::Duration::from_millis(10));
+    /// }
+    /// ```
+    pub fn serve_ws(&mut self, addr: &str) -> Result<SocketAddr, ScribeError> {
+        // Release the old address first, in case the new one is the same
+        self.ws_server = None;
+        let server = WsServer::bind(addr)?;
+        let local_addr = server.local_addr();
+        self.ws_server = Some(server);
+        Ok(local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResultKind;
+    use std::time::Instant;
+
+    fn connect(addr: SocketAddr) -> WebSocket<tungstenite::stream::MaybeTlsStream<TcpStream>> {
+        let (socket, _) = tungstenite::connect(format!("ws://{}", addr)).unwrap();
+        socket
+    }
+
+    /// Broadcasts until `expected` clients are registered, as accepting is asynchronous
+    fn wait_for_clients(server: &WsServer, expected: usize) {
+        let started = Instant::now();
+        while server.clients.lock().unwrap().len() < expected {
+            assert!(
+                started.elapsed() < Duration::from_secs(2),
+                "clients never connected"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn result(text: &str) -> StreamingResult {
+        StreamingResult {
+            text: text.to_string(),
+            is_final: true,
+            kind: ResultKind::Final,
+            timestamp: 1.5,
+            start_timestamp: None,
+            alternatives: Vec::new(),
+            is_revision: false,
+            stable_text: None,
+            words: None,
+        }
+    }
+
+    #[test]
+    fn pushes_results_to_every_client() {
+        let server = WsServer::bind("127.0.0.1:0").unwrap();
+        let mut first = connect(server.local_addr());
+        let mut second = connect(server.local_addr());
+        wait_for_clients(&server, 2);
+
+        let result = result("hello");
+        server.broadcast(&result);
+        for client in [&mut first, &mut second] {
+            let Message::Text(json) = client.read().unwrap() else {
+                panic!("expected a text message");
+            };
+            let received: StreamingResult = serde_json::from_str(&json).unwrap();
+            assert_eq!(received.text, "hello");
+            assert!(received.is_final);
+        }
+
+        // A client that leaves is forgotten at the next broadcast
+        first.close(None).unwrap();
+        while first.read().is_ok() {}
+        std::thread::sleep(Duration::from_millis(200));
+        server.broadcast(&result);
+        server.broadcast(&result);
+        assert_eq!(server.clients.lock().unwrap().len(), 1);
+
+        // Dropping the server closes the remaining connection cleanly
+        drop(server);
+        loop {
+            match second.read().unwrap() {
+                Message::Text(_) => {}
+                message => {
+                    assert!(matches!(message, Message::Close(_)), "got {:?}", message);
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn drops_clients_that_fall_behind() {
+        let server = WsServer::bind("127.0.0.1:0").unwrap();
+        // Connected but never handshaking, so nothing is taken off its queue
+        let _stalled = TcpStream::connect(server.local_addr()).unwrap();
+        wait_for_clients(&server, 1);
+
+        for _ in 0..CLIENT_QUEUE {
+            server.broadcast(&result("queued"));
+        }
+        assert_eq!(server.clients.lock().unwrap().len(), 1);
+        server.broadcast(&result("one too many"));
+        assert!(server.clients.lock().unwrap().is_empty());
+    }
+}
@@ -1,12 +1,76 @@
-/// Tests for the new programmatic audio input API
+//! Tests for the new programmatic audio input API
 
-use swift_scribe::StreamingTranscriber;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use swift_scribe::{
+    AudioFormat, AudioInputMode, Backend, BackpressurePolicy, BatchOptions, BatchOutcome,
+    BatchOutput, ChannelMix, ConfidenceAggregation, EmptyOutputPolicy, FileTranscription,
+    FormatChangePolicy, LineEnding, LocaleStatus, OutputFormat, PcmFormat, PreflightIssue,
+    Priority, ResampleQuality, RestartPolicy, ResultKind, ScribeError, Script, SkipReason,
+    StabilityMode, StderrMode, StreamingResult, StreamingTranscriber, TaskHint, TempNaming,
+    Transcriber, TranscriptionConfig, TranscriptionSegment, WordSegment, WordTiming,
+    SUPPORTED_PROTOCOL_VERSIONS,
+};
+
+/// A mock helper script, removed along with its directory when dropped
+struct MockHelper {
+    path: PathBuf,
+    _dir: tempfile::TempDir,
+}
+
+impl std::ops::Deref for MockHelper {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for MockHelper {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Writes an executable shell script that stands in for a helper binary
+fn mock_helper(name: &str, script: &str) -> MockHelper {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    MockHelper { path, _dir: dir }
+}
+
+/// Writes a mock streaming helper that announces the protocol version before running `script`
+fn mock_stream_helper(name: &str, script: &str) -> MockHelper {
+    mock_helper(name, &format!("echo '{{\"protocol\":1}}'\n{}", script))
+}
+
+/// Polls until a result arrives, the stream errors, or two seconds pass
+fn poll_until_result(
+    transcriber: &mut StreamingTranscriber,
+) -> Result<Option<StreamingResult>, String> {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while Instant::now() < deadline {
+        if let Some(result) = transcriber.poll_result()? {
+            return Ok(Some(result));
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    Ok(None)
+}
 
 #[test]
 fn test_builder_default_microphone_mode() {
     let builder = StreamingTranscriber::builder();
     let transcriber = builder.build();
-    assert!(transcriber.is_ok(), "Builder should create transcriber successfully");
+    assert!(
+        transcriber.is_ok(),
+        "Builder should create transcriber successfully"
+    );
 }
 
 #[test]
@@ -14,7 +78,10 @@ fn test_builder_programmatic_mode() {
     let transcriber = StreamingTranscriber::builder()
         .with_programmatic_input()
         .build();
-    assert!(transcriber.is_ok(), "Builder with programmatic input should succeed");
+    assert!(
+        transcriber.is_ok(),
+        "Builder with programmatic input should succeed"
+    );
 }
 
 #[test]
@@ -31,7 +98,10 @@ fn test_builder_chaining() {
 fn test_new_creates_microphone_mode() {
     let result = StreamingTranscriber::new();
     // May fail if helper not found, but shouldn't panic
-    assert!(result.is_ok() || result.is_err(), "Should handle missing helper gracefully");
+    assert!(
+        result.is_ok() || result.is_err(),
+        "Should handle missing helper gracefully"
+    );
 }
 
 #[test]
@@ -49,9 +119,7 @@ fn test_builder_multiple_input_modes() {
     let t1 = StreamingTranscriber::builder()
         .with_programmatic_input()
         .build();
-    let t2 = StreamingTranscriber::builder()
-        .with_microphone()
-        .build();
+    let t2 = StreamingTranscriber::builder().with_microphone().build();
 
     assert!(t1.is_ok());
     assert!(t2.is_ok());
@@ -72,9 +140,7 @@ fn test_builder_creates_correct_mode() {
     let prog_tx = StreamingTranscriber::builder()
         .with_programmatic_input()
         .build();
-    let mic_tx = StreamingTranscriber::builder()
-        .with_microphone()
-        .build();
+    let mic_tx = StreamingTranscriber::builder().with_microphone().build();
 
     assert!(prog_tx.is_ok());
     assert!(mic_tx.is_ok());
@@ -84,5 +150,3226 @@ fn test_builder_creates_correct_mode() {
 fn test_default_builder() {
     // Test that builder creates successfully
     let result = StreamingTranscriber::builder().build();
-    assert!(result.is_ok() || result.is_err(), "Builder should create a result");
+    assert!(
+        result.is_ok() || result.is_err(),
+        "Builder should create a result"
+    );
+}
+
+#[test]
+fn test_last_raw_line_records_helper_output() {
+    let line = r#"{"text":"hello world","isFinal":true,"timestamp":1.5}"#;
+    let helper = mock_stream_helper("raw_line_ok", &format!("echo '{}'", line));
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    assert!(transcriber.last_raw_line().is_none());
+
+    transcriber.start().unwrap();
+    let result = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("expected a result");
+    assert_eq!(result.text, "hello world");
+    assert_eq!(transcriber.last_raw_line(), Some(line));
+}
+
+#[test]
+fn test_poll_result_holds_partial_lines() {
+    // Half a line, split inside the "é", then the rest once the first polls are done
+    let helper = mock_stream_helper(
+        "partial_line",
+        r#"printf '{"text":"caf\303'; sleep 0.5; printf '\251","isFinal":true,"timestamp":0}\n'; sleep 5"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    transcriber.start().unwrap();
+
+    let until = Instant::now() + Duration::from_millis(200);
+    while Instant::now() < until {
+        let polled = Instant::now();
+        assert!(transcriber.poll_result().unwrap().is_none());
+        assert!(
+            polled.elapsed() < Duration::from_millis(50),
+            "poll_result blocked"
+        );
+        thread::sleep(Duration::from_millis(10));
+    }
+    let result = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("expected a result");
+    assert_eq!(result.text, "café");
+}
+
+#[test]
+fn test_poll_result_timeout() {
+    let silent = mock_stream_helper("poll_timeout_silent", "sleep 5");
+    let mut transcriber = StreamingTranscriber::with_helper_path(&silent).unwrap();
+    transcriber.start().unwrap();
+    let started = Instant::now();
+    assert!(transcriber
+        .poll_result_timeout(Duration::from_millis(50))
+        .unwrap()
+        .is_none());
+    assert!(
+        started.elapsed() >= Duration::from_millis(50),
+        "returned early: {:?}",
+        started.elapsed()
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(1),
+        "overslept: {:?}",
+        started.elapsed()
+    );
+
+    // A result ends the wait as soon as it is written
+    let helper = mock_stream_helper(
+        "poll_timeout_result",
+        r#"sleep 0.2; echo '{"text":"hello","isFinal":true,"timestamp":0}'; sleep 5"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    transcriber.start().unwrap();
+    let started = Instant::now();
+    let result = transcriber
+        .poll_result_timeout(Duration::from_secs(5))
+        .unwrap();
+    assert_eq!(result.expect("expected a result").text, "hello");
+    assert!(
+        started.elapsed() < Duration::from_secs(2),
+        "waited too long: {:?}",
+        started.elapsed()
+    );
+}
+
+#[test]
+fn test_results_iterator() {
+    let helper = mock_stream_helper(
+        "results_iter",
+        r#"echo '{"text":"hel","isFinal":false,"timestamp":0}'; sleep 0.1; echo '{"text":"hello","isFinal":true,"timestamp":1}'"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    transcriber.start().unwrap();
+    let texts: Vec<String> = transcriber
+        .results()
+        .map(|result| result.unwrap().text)
+        .collect();
+    assert_eq!(texts, ["hel", "hello"]);
+    // Polling after the end reports it as such
+    assert!(matches!(
+        transcriber.poll_result(),
+        Err(ScribeError::StreamEnded)
+    ));
+
+    // Errors other than the output ending are yielded once, then iteration stops
+    let garbled = mock_stream_helper("results_iter_garbled", "echo 'not json'; sleep 5");
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&garbled)
+        .with_strict_parsing(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    let mut results = transcriber.results();
+    assert!(matches!(
+        results.next(),
+        Some(Err(ScribeError::ParseResult { .. }))
+    ));
+    assert!(results.next().is_none());
+}
+
+#[test]
+fn test_result_word_timings() {
+    let line = r#"{"text":"hello world","isFinal":true,"timestamp":1.5,"words":[{"text":"hello","start":0.2,"end":0.6,"confidence":0.9},{"text":"world","start":0.7,"end":1.1}]}"#;
+    let result: StreamingResult = serde_json::from_str(line).unwrap();
+    let words = result.words.expect("expected word timings");
+    assert_eq!(
+        words,
+        [
+            WordTiming {
+                text: "hello".to_string(),
+                start: 0.2,
+                end: 0.6,
+                confidence: Some(0.9)
+            },
+            WordTiming {
+                text: "world".to_string(),
+                start: 0.7,
+                end: 1.1,
+                confidence: None
+            },
+        ]
+    );
+
+    // Output from helpers that don't report timings still parses
+    let older: StreamingResult =
+        serde_json::from_str(r#"{"text":"hi","isFinal":true,"timestamp":1}"#).unwrap();
+    assert!(older.words.is_none());
+}
+
+#[test]
+fn test_result_kinds() {
+    let helper = mock_stream_helper(
+        "result_kinds",
+        r#"echo '{"text":"one","isFinal":false,"kind":"volatile","timestamp":1}'
+echo '{"text":"one two","isFinal":true,"kind":"final","timestamp":2}'
+echo '{"text":"one two","isFinal":true,"kind":"committed","timestamp":3}'
+echo '{"text":"three","isFinal":true,"timestamp":4}'"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    transcriber.start().unwrap();
+
+    let mut kinds = Vec::new();
+    while let Some(result) = poll_until_result(&mut transcriber).unwrap_or(None) {
+        kinds.push(result.kind);
+    }
+    // The last line predates the kind field and falls back to is_final
+    assert_eq!(
+        kinds,
+        [
+            ResultKind::Volatile,
+            ResultKind::Final,
+            ResultKind::Committed,
+            ResultKind::Final
+        ]
+    );
+}
+
+#[test]
+fn test_parse_error_includes_raw_line() {
+    let helper = mock_stream_helper("raw_line_bad", "echo '{\"text\": 42}'");
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_strict_parsing(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    let err = poll_until_result(&mut transcriber).unwrap_err();
+    assert!(
+        err.contains(r#"{"text": 42}"#),
+        "error should quote the line: {}",
+        err
+    );
+    assert_eq!(transcriber.last_raw_line(), Some(r#"{"text": 42}"#));
+}
+
+/// Starts a mock helper that reports its own nice value as the result text
+fn helper_niceness(name: &str, priority: Priority) -> i32 {
+    let helper = mock_stream_helper(
+        name,
+        r#"echo "{\"text\":\"$(ps -o nice= -p $$ | tr -d ' ')\",\"isFinal\":true,\"timestamp\":0}""#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_priority(priority)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    let result = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("expected a result");
+    result.text.parse().unwrap()
+}
+
+#[test]
+fn test_low_priority_raises_helper_nice_value() {
+    let normal = helper_niceness("nice_normal", Priority::Normal);
+    let low = helper_niceness("nice_low", Priority::Low);
+    assert_eq!(low, (normal + 10).min(19));
+}
+
+#[test]
+fn test_transcriber_low_priority_raises_helper_nice_value() {
+    let helper = mock_helper("transcribe_nice", "ps -o nice= -p $$");
+    let niceness = |priority| -> i32 {
+        let transcriber = Transcriber::builder()
+            .with_helper_path(&helper)
+            .with_priority(priority)
+            .build()
+            .unwrap();
+        transcriber
+            .transcribe_file(&helper)
+            .unwrap()
+            .parse()
+            .unwrap()
+    };
+    assert_eq!(
+        niceness(Priority::Low),
+        (niceness(Priority::Normal) + 10).min(19)
+    );
+}
+
+#[test]
+fn test_sentence_segmentation_regroups_finals() {
+    let helper = mock_stream_helper(
+        "sentences",
+        r#"echo '{"text":"first part","isFinal":true,"timestamp":1}'
+echo '{"text":"of one. Second","isFinal":true,"timestamp":2}'
+echo '{"text":"still going","isFinal":true,"timestamp":3}'"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_sentence_segmentation(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    let first = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("first sentence");
+    assert_eq!(first.text, "first part of one.");
+    assert_eq!((first.start_timestamp, first.timestamp), (Some(1.0), 2.0));
+
+    // The unfinished sentence is flushed once the helper exits
+    let rest = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("trailing sentence");
+    assert_eq!(rest.text, "Second still going");
+    assert!(rest.is_final);
+    assert_eq!(rest.start_timestamp, Some(2.0));
+    assert!(poll_until_result(&mut transcriber).is_err());
+}
+
+#[test]
+fn test_poll_delta_tracks_display_text() {
+    let helper = mock_stream_helper(
+        "deltas",
+        r#"echo '{"text":"one","isFinal":false,"timestamp":1}'
+echo '{"text":"one two","isFinal":true,"timestamp":2}'
+echo '{"text":"tree","isFinal":false,"timestamp":3}'
+echo '{"text":"three","isFinal":false,"timestamp":4}'
+sleep 1"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    transcriber.start().unwrap();
+
+    let mut display = String::new();
+    let mut deltas = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while deltas.len() < 4 && Instant::now() < deadline {
+        match transcriber.poll_delta().unwrap() {
+            Some(delta) => {
+                delta.apply_to(&mut display);
+                deltas.push(delta);
+            }
+            None => thread::sleep(Duration::from_millis(10)),
+        }
+    }
+
+    assert_eq!(display, "one two three");
+    assert_eq!(deltas[1].appended, " two");
+    assert_eq!(deltas[3].replaced_from, Some("one two t".len()));
+    assert_eq!(deltas[3].appended, "hree");
+}
+
+#[test]
+fn test_results_flag_partial_revisions() {
+    let helper = mock_stream_helper(
+        "revisions",
+        r#"echo '{"text":"the cat","isFinal":false,"timestamp":1}'
+echo '{"text":"the cat sat","isFinal":false,"timestamp":2}'
+echo '{"text":"the hat","isFinal":false,"timestamp":3}'
+echo '{"text":" the hat sat","isFinal":true,"timestamp":4}'
+echo '{"text":"down","isFinal":false,"timestamp":5}'
+sleep 1"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    transcriber.start().unwrap();
+
+    let mut revisions = Vec::new();
+    while let Ok(Some(result)) = poll_until_result(&mut transcriber) {
+        revisions.push(result.is_revision);
+        if revisions.len() == 5 {
+            break;
+        }
+    }
+    assert_eq!(revisions, [false, false, true, false, false]);
+}
+
+#[test]
+fn test_final_carryover() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_carryover",
+        r#"echo '{"text":"hello there","isFinal":false,"timestamp":1}'
+echo '{"text":"Hello there.","isFinal":true,"timestamp":2}'
+echo '{"text":"","isFinal":false,"timestamp":3}'
+echo '{"text":"how","isFinal":false,"timestamp":4}'
+echo '{"text":"How are you?","isFinal":true,"timestamp":5}'
+echo '{"text":"fine","isFinal":false,"timestamp":6}'
+sleep 1"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_final_carryover(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    let mut texts = Vec::new();
+    while let Ok(Some(result)) = poll_until_result(&mut transcriber) {
+        texts.push(result.text);
+        if texts.len() == 6 {
+            break;
+        }
+    }
+    assert_eq!(
+        texts,
+        [
+            "hello there",
+            "Hello there.",
+            "Hello there.",
+            "Hello there. how",
+            "How are you?",
+            "How are you? fine"
+        ]
+    );
+}
+
+#[test]
+fn test_poll_delta_with_final_carryover() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_carryover_delta",
+        r#"echo '{"text":"Hello there.","isFinal":true,"timestamp":1}'
+echo '{"text":"how","isFinal":false,"timestamp":2}'
+sleep 1"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_final_carryover(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    let mut display = String::new();
+    let mut deltas = 0;
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while deltas < 2 && Instant::now() < deadline {
+        match transcriber.poll_delta().unwrap() {
+            Some(delta) => {
+                delta.apply_to(&mut display);
+                deltas += 1;
+            }
+            None => thread::sleep(Duration::from_millis(10)),
+        }
+    }
+
+    assert_eq!(display, "Hello there. how");
+}
+
+#[test]
+fn test_volatile_tail_can_be_left_out() {
+    // Marks the settled text only when asked to, as the helper does
+    let helper = mock_stream_helper(
+        "transcribe_stream_volatile_tail",
+        r#"case "$*" in *--stable-text*) s1=',"stableText":""'; s2=',"stableText":"the"'; s3=',"stableText":"the cat"' ;; esac
+echo "{\"text\":\"the\",\"isFinal\":false,\"timestamp\":1$s1}"
+echo "{\"text\":\"the cat\",\"isFinal\":false,\"timestamp\":2$s2}"
+echo "{\"text\":\"the cat sat\",\"isFinal\":false,\"timestamp\":3$s3}"
+echo "{\"text\":\"the cat sad\",\"isFinal\":false,\"timestamp\":4$s3}"
+echo '{"text":"The cat sat.","isFinal":true,"timestamp":5}'
+sleep 1"#,
+    );
+    let texts = |volatile_tail| {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path(&helper)
+            .with_volatile_tail(volatile_tail)
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+        let mut texts = Vec::new();
+        while let Ok(Some(result)) = poll_until_result(&mut transcriber) {
+            texts.push(result.text);
+            if texts.last().is_some_and(|text| text.ends_with('.')) {
+                break;
+            }
+        }
+        texts
+    };
+
+    assert_eq!(
+        texts(true),
+        [
+            "the",
+            "the cat",
+            "the cat sat",
+            "the cat sad",
+            "The cat sat."
+        ]
+    );
+    // Only settled text, and only when it grows
+    assert_eq!(texts(false), ["the", "the cat", "The cat sat."]);
+}
+
+#[test]
+fn test_start_with_context() {
+    // Reports the context it was started with
+    let helper = mock_stream_helper(
+        "transcribe_stream_context",
+        r#"while [ $# -gt 0 ]; do [ "$1" = --context ] && context=$2; shift; done
+echo "{\"text\":\"$context\",\"isFinal\":true,\"timestamp\":0}"
+sleep 1"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .build()
+        .unwrap();
+
+    let prior: Vec<String> = (0..150).map(|n| format!("w{}", n)).collect();
+    transcriber.start_with_context(&prior.join("\n")).unwrap();
+    let result = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert_eq!(result.text, prior[50..].join(" "));
+    transcriber.stop().unwrap();
+
+    // Only the session it was given to continues the transcript
+    transcriber.start().unwrap();
+    let result = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert_eq!(result.text, "");
+}
+
+#[test]
+fn test_start_tailing_file() {
+    // Reports each line of audio it is fed
+    let helper = mock_stream_helper(
+        "transcribe_stream_tail",
+        r#"while read -r line; do echo "{\"text\":\"$line\",\"isFinal\":true,\"timestamp\":0}"; done"#,
+    );
+    let dir = tempfile::tempdir().unwrap();
+    let recording = dir.path().join("recording.wav");
+    std::fs::write(&recording, "RIFFhello\n").unwrap();
+
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .build()
+        .unwrap();
+    transcriber.start_tailing_file(&recording).unwrap();
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "RIFFhello"
+    );
+
+    // Appended audio is picked up as it is written
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&recording)
+        .unwrap();
+    std::io::Write::write_all(&mut file, b"world\n").unwrap();
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "world"
+    );
+
+    // A rotated file starts a new session from its header
+    let rotated = dir.path().join("next.wav");
+    std::fs::write(&rotated, "RIFFagain\n").unwrap();
+    std::fs::rename(&rotated, &recording).unwrap();
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "RIFFagain"
+    );
+
+    let mut microphone = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .build()
+        .unwrap();
+    assert!(microphone.start_tailing_file(&recording).is_err());
+}
+
+#[test]
+fn test_connect_socket_feeds_and_polls() {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::os::unix::net::UnixListener;
+
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join("helper.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+
+    // Stands in for a helper agent: reports how much audio it received
+    let agent = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        writeln!(stream, r#"{{"protocol":1}}"#).unwrap();
+        let mut audio = [0u8; 320];
+        stream.read_exact(&mut audio).unwrap();
+        writeln!(
+            stream,
+            r#"{{"text":"got {} bytes","isFinal":true,"timestamp":0}}"#,
+            audio.len()
+        )
+        .unwrap();
+        // Wait for the client to hang up
+        let _ = BufReader::new(stream).read_line(&mut String::new());
+    });
+
+    let mut transcriber = StreamingTranscriber::connect_socket(&socket).unwrap();
+    assert!(transcriber.is_running());
+    assert_eq!(transcriber.helper_path(), socket);
+    assert!(
+        transcriber.poll_result().unwrap().is_none(),
+        "poll should not block"
+    );
+
+    transcriber.feed_audio_i16(&[0; 160], 16000, 1).unwrap();
+    let result = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("expected a result");
+    assert_eq!(result.text, "got 320 bytes");
+    assert!(matches!(
+        transcriber.ping(),
+        Err(ScribeError::UnsupportedOnSocket("Ping"))
+    ));
+
+    transcriber.stop().unwrap();
+    assert!(!transcriber.is_running());
+    agent.join().unwrap();
+}
+
+#[test]
+fn test_socket_connections_take_builder_options() {
+    let socket = std::path::Path::new("/nonexistent/helper.sock");
+    let Err(err) = StreamingTranscriber::builder().with_socket(socket).build() else {
+        panic!("a socket needs programmatic input");
+    };
+    assert!(
+        matches!(err, ScribeError::WrongInputMode(_)),
+        "unexpected error: {}",
+        err
+    );
+
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_programmatic_input()
+        .with_socket(socket)
+        .with_sentence_segmentation(true)
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.helper_path(), socket);
+    let config = transcriber.config();
+    assert_eq!(config.socket_path.as_deref(), Some(socket));
+    assert!(config.sentence_segmentation);
+    assert_eq!(
+        StreamingTranscriber::from_config(&config).unwrap().config(),
+        config
+    );
+
+    // Connecting waits for start, and fails when nothing is listening
+    assert!(!transcriber.is_running());
+    assert!(transcriber.start().is_err());
+}
+
+/// Starts a programmatic transcriber against a helper that discards its input
+fn sink_transcriber(policy: FormatChangePolicy) -> (StreamingTranscriber, MockHelper) {
+    let helper = mock_stream_helper("transcribe_stream_sink", "cat > /dev/null");
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_programmatic_input()
+        .with_helper_path(&helper)
+        .with_format_change_policy(policy)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    (transcriber, helper)
+}
+
+#[test]
+fn test_format_change_adapts_by_default() {
+    let (mut transcriber, _helper) = sink_transcriber(FormatChangePolicy::Adapt);
+    transcriber.feed_audio_i16(&[0; 960], 48000, 2).unwrap();
+    transcriber.feed_audio_i16(&[0; 441], 44100, 1).unwrap();
+    transcriber.feed_audio_f32(&[0.0; 441], 44100, 1).unwrap();
+}
+
+#[test]
+fn test_channel_mix() {
+    // Reports the first two samples the helper receives
+    let helper = mock_stream_helper(
+        "transcribe_stream_channel_mix",
+        r#"samples=$(head -c 4 | od -An -td2 | tr -s ' ' | sed 's/^ //;s/ $//')
+echo "{\"text\":\"$samples\",\"isFinal\":true,\"timestamp\":0}"
+cat > /dev/null"#,
+    );
+    let start = |mix| {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_programmatic_input()
+            .with_helper_path(&helper)
+            .with_channel_mix(mix)
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+        transcriber
+    };
+
+    // Left only, from interleaved stereo with a noisy right channel
+    let mut transcriber = start(ChannelMix::Left);
+    transcriber
+        .feed_audio_i16(&[100, -9000, 200, 8000], 16000, 2)
+        .unwrap();
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "100 200"
+    );
+    assert_eq!(transcriber.config().channel_mix, ChannelMix::Left);
+
+    let mut transcriber = start(ChannelMix::Weighted(vec![1.0, 0.0, 0.0]));
+    let err = transcriber
+        .feed_audio_i16(&[100, -9000, 200, 8000], 16000, 2)
+        .unwrap_err();
+    assert!(
+        matches!(err, ScribeError::InvalidChannelMix(_)),
+        "unexpected error: {}",
+        err
+    );
+    assert!(transcriber
+        .feed_audio_f32_planar(&[&[0.1], &[0.2]], 16000)
+        .is_err());
+    transcriber.feed_audio_i16(&[0; 6], 16000, 3).unwrap();
+}
+
+#[test]
+fn test_declared_input_format() {
+    let helper = mock_stream_helper("transcribe_stream_sink", "cat > /dev/null");
+    let builder = || {
+        StreamingTranscriber::builder()
+            .with_helper_path(&helper)
+            .with_format_change_policy(FormatChangePolicy::Error)
+    };
+    let mut transcriber = builder()
+        .with_programmatic_input()
+        .with_input_format(48000, 2)
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.config().input_sample_rate, Some(48000));
+    assert_eq!(transcriber.config().input_channels, Some(2));
+    transcriber.start().unwrap();
+    transcriber.feed_audio_i16_fmt(&[0; 960]).unwrap();
+    transcriber.feed_audio_f32_fmt(&[0.0; 960]).unwrap();
+    // Both fed in the declared format, which an explicit call can still contradict
+    let err = transcriber
+        .feed_audio_i16(&[0; 441], 44100, 1)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("from 48000 Hz/2 ch"),
+        "unexpected error: {}",
+        err
+    );
+
+    let (mut undeclared, _helper) = sink_transcriber(FormatChangePolicy::Adapt);
+    let err = undeclared
+        .feed_audio_i16_fmt(&[0; 160])
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("No input format declared"),
+        "unexpected error: {}",
+        err
+    );
+
+    assert!(
+        builder().with_input_format(48000, 2).build().is_err(),
+        "microphone input"
+    );
+    assert!(builder()
+        .with_programmatic_input()
+        .with_input_format(0, 1)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_capture_fed_audio() {
+    let helper = mock_stream_helper("transcribe_stream_sink", "cat > /dev/null");
+    let capture = helper.parent().unwrap().join("fed.wav");
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_capture_fed_audio(&capture)
+        .with_resample_quality(ResampleQuality::Fast)
+        .build()
+        .unwrap();
+    assert_eq!(
+        transcriber.config().capture_fed_audio.as_deref(),
+        Some(capture.as_path())
+    );
+    transcriber.start().unwrap();
+    // 0.1s of 48kHz stereo reaches the helper as 1600 16kHz mono samples, as
+    // linear interpolation holds nothing back for the next chunk
+    transcriber.feed_audio_i16(&[1000; 9600], 48000, 2).unwrap();
+    transcriber
+        .feed_audio_i16(&[-1000; 1600], 16000, 1)
+        .unwrap();
+
+    let wav = std::fs::read(&capture).unwrap();
+    assert_eq!(wav.len(), 44 + 3200 * 2);
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 36 + 6400);
+    assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16000);
+    assert_eq!(u16::from_le_bytes(wav[22..24].try_into().unwrap()), 1);
+    assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 6400);
+    assert_eq!(i16::from_le_bytes([wav[44 + 3200], wav[45 + 3200]]), -1000);
+
+    // A new session starts a new recording
+    transcriber.stop().unwrap();
+    transcriber.start().unwrap();
+    assert_eq!(std::fs::metadata(&capture).unwrap().len(), 44);
+
+    let microphone = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_capture_fed_audio(&capture)
+        .build();
+    assert!(microphone.is_err());
+}
+
+#[test]
+fn test_keep_alive_sends_silence() {
+    let helper = mock_stream_helper("transcribe_stream_sink", "cat > /dev/null");
+    let capture = helper.parent().unwrap().join("keep-alive.wav");
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_skip_silence(true)
+        .with_capture_fed_audio(&capture)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        transcriber.keep_alive(),
+        Err(ScribeError::NotStarted)
+    ));
+
+    transcriber.start().unwrap();
+    transcriber.keep_alive().unwrap();
+    transcriber.keep_alive().unwrap();
+    // Sent even though silence is skipped: 2 x 1600 silent samples
+    let wav = std::fs::read(&capture).unwrap();
+    assert_eq!(wav.len(), 44 + 6400);
+    assert!(wav[44..].iter().all(|&byte| byte == 0));
+}
+
+#[test]
+fn test_format_change_errors_when_configured() {
+    let (mut transcriber, _helper) = sink_transcriber(FormatChangePolicy::Error);
+    transcriber.feed_audio_i16(&[0; 960], 48000, 2).unwrap();
+
+    let err = transcriber
+        .feed_audio_i16(&[0; 441], 44100, 1)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Input format changed"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(err.contains("48000 Hz/2 ch") && err.contains("44100 Hz/1 ch"));
+
+    // The format is only locked for the current session
+    transcriber.stop().unwrap();
+    transcriber.start().unwrap();
+    transcriber.feed_audio_i16(&[0; 441], 44100, 1).unwrap();
+}
+
+#[test]
+fn test_transcriber_temp_dir() {
+    let helper = mock_helper("transcribe_temp_dir", "true");
+    let default = Transcriber::with_helper_path(&helper).unwrap();
+    assert_eq!(default.temp_dir(), std::env::temp_dir());
+
+    let dir = helper.parent().unwrap();
+    let custom = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_dir(dir)
+        .build()
+        .unwrap();
+    assert_eq!(custom.temp_dir(), dir);
+
+    let missing = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_dir("/nonexistent/temp/dir")
+        .build();
+    assert!(missing.is_err(), "Should error on nonexistent temp dir");
+}
+
+#[test]
+fn test_quick_benchmark_cleans_up_reference_clip() {
+    // The mock fails unless the reference clip exists when it runs, and
+    // recognizes nothing in it, as the clip holds no words
+    let helper = mock_helper("transcribe_benchmark", r#"test -s "$1""#);
+    let temp_dir = helper.parent().unwrap().join("benchmark_tmp");
+    std::fs::create_dir_all(&temp_dir).unwrap();
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_dir(&temp_dir)
+        .with_empty_output_policy(EmptyOutputPolicy::AsError)
+        .build()
+        .unwrap();
+    let rtf = transcriber.quick_benchmark().unwrap();
+
+    assert!(rtf > 0.0);
+    assert_eq!(
+        std::fs::read_dir(&temp_dir).unwrap().count(),
+        0,
+        "reference clip left behind"
+    );
+}
+
+/// Creates an empty directory of fake audio files for the batch tests
+fn batch_dir(files: &[&str]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for file in files {
+        std::fs::write(dir.path().join(file), b"").unwrap();
+    }
+    dir
+}
+
+fn batch_transcriber() -> (Transcriber, MockHelper) {
+    let helper = mock_helper("transcribe_batch", r#"echo "text of $(basename "$1")""#);
+    (Transcriber::with_helper_path(&helper).unwrap(), helper)
+}
+
+#[test]
+fn test_batch_overwrite_replaces_transcript() {
+    let batch = batch_dir(&["b.wav", "a.m4a", "notes.txt"]);
+    let dir = batch.path();
+    std::fs::write(dir.join("transcriptions.txt"), "stale\n").unwrap();
+
+    let (transcriber, _helper) = batch_transcriber();
+    let entries = transcriber
+        .transcribe_directory(dir, &BatchOptions::default())
+        .unwrap();
+
+    assert_eq!(entries.len(), 2, "non-audio files should be ignored");
+    assert!(entries[0].path.ends_with("a.m4a"));
+    let transcript = std::fs::read_to_string(dir.join("transcriptions.txt")).unwrap();
+    assert_eq!(
+        transcript,
+        "\n=== a.m4a ===\ntext of a.m4a\n\n=== b.wav ===\ntext of b.wav\n"
+    );
+
+    // A run where every file fails still replaces the old transcript
+    let failing_helper = mock_helper("transcribe_batch_fail", "exit 1");
+    let failing = Transcriber::with_helper_path(&failing_helper).unwrap();
+    let entries = failing
+        .transcribe_directory(dir, &BatchOptions::default())
+        .unwrap();
+    assert!(entries
+        .iter()
+        .all(|entry| matches!(entry.outcome, BatchOutcome::Failed(_))));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("transcriptions.txt")).unwrap(),
+        ""
+    );
+}
+
+#[test]
+fn test_batch_append_skips_existing_sections() {
+    let batch = batch_dir(&["a.m4a"]);
+    let dir = batch.path();
+    let (transcriber, _helper) = batch_transcriber();
+    let options = BatchOptions {
+        output: BatchOutput::Append,
+        ..Default::default()
+    };
+
+    transcriber.transcribe_directory(dir, &options).unwrap();
+    std::fs::write(dir.join("b.m4a"), b"").unwrap();
+    let entries = transcriber.transcribe_directory(dir, &options).unwrap();
+
+    assert!(matches!(
+        entries[0].outcome,
+        BatchOutcome::Skipped(SkipReason::AlreadyTranscribed)
+    ));
+    assert!(matches!(entries[1].outcome, BatchOutcome::Transcribed(_)));
+    let transcript = std::fs::read_to_string(dir.join("transcriptions.txt")).unwrap();
+    assert_eq!(transcript.matches("=== a.m4a ===").count(), 1);
+    assert_eq!(transcript.matches("=== b.m4a ===").count(), 1);
+}
+
+#[test]
+fn test_batch_skips_files_without_audio() {
+    let batch = batch_dir(&["a.m4a", "broken.m4a", "clip.m4a"]);
+    let dir = batch.path();
+    let helper = mock_helper(
+        "transcribe_no_audio",
+        r#"for last; do :; done
+case "$(basename "$last")" in
+  clip.m4a) echo "Error: No audio track in $last" >&2; exit 1 ;;
+  broken.m4a) echo "Error: The operation couldn't be completed" >&2; exit 1 ;;
+  *) [ "$1" = "--json" ] && echo '{"text":"text","segments":[]}' || echo text ;;
+esac"#,
+    );
+    let transcriber = std::sync::Arc::new(Transcriber::with_helper_path(&helper).unwrap());
+    let err = transcriber
+        .transcribe_file(&dir.join("clip.m4a"))
+        .unwrap_err();
+    assert!(
+        matches!(&err, ScribeError::NoAudioTrack(path) if path.ends_with("clip.m4a")),
+        "{}",
+        err
+    );
+    let err = err.to_string();
+    assert!(
+        err.starts_with("No audio track in ") && err.ends_with("clip.m4a"),
+        "{}",
+        err
+    );
+
+    let entries = transcriber
+        .transcribe_directory(dir, &BatchOptions::default())
+        .unwrap();
+    assert!(matches!(entries[0].outcome, BatchOutcome::Transcribed(_)));
+    assert!(
+        matches!(entries[1].outcome, BatchOutcome::Failed(_)),
+        "real errors still fail"
+    );
+    assert!(matches!(
+        entries[2].outcome,
+        BatchOutcome::Skipped(SkipReason::NoAudioTrack)
+    ));
+
+    // The lazy and threaded batches keep the error's kind too
+    let results: Vec<_> = transcriber
+        .transcribe_directory_iter(dir, &BatchOptions::default())
+        .unwrap()
+        .collect();
+    let options = BatchOptions {
+        ordered: true,
+        ..Default::default()
+    };
+    let channel = std::sync::Arc::clone(&transcriber)
+        .transcribe_directory_channel(dir, &options, 2)
+        .unwrap();
+    for results in [results, channel.iter().collect()] {
+        assert!(results[0].1.is_ok());
+        assert!(!matches!(results[1].1, Err(ScribeError::NoAudioTrack(_))));
+        assert!(matches!(results[2].1, Err(ScribeError::NoAudioTrack(_))));
+    }
+}
+
+#[test]
+fn test_batch_per_file_sidecar() {
+    let batch = batch_dir(&["a.m4a", "a.wav", "b.wav"]);
+    let dir = batch.path();
+    std::fs::write(dir.join("b.wav.txt"), "kept\n").unwrap();
+    let options = BatchOptions {
+        output: BatchOutput::PerFileSidecar,
+        skip_existing: true,
+        ..Default::default()
+    };
+
+    let (transcriber, _helper) = batch_transcriber();
+    let entries = transcriber.transcribe_directory(dir, &options).unwrap();
+
+    assert!(matches!(entries[0].outcome, BatchOutcome::Transcribed(_)));
+    assert!(matches!(entries[1].outcome, BatchOutcome::Transcribed(_)));
+    assert!(matches!(entries[2].outcome, BatchOutcome::Skipped(_)));
+    // Files differing only in extension don't share a sidecar
+    assert_eq!(
+        std::fs::read_to_string(dir.join("a.m4a.txt")).unwrap(),
+        "text of a.m4a\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("a.wav.txt")).unwrap(),
+        "text of a.wav\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("b.wav.txt")).unwrap(),
+        "kept\n"
+    );
+    assert!(!dir.join("transcriptions.txt").exists());
+}
+
+#[test]
+fn test_config_round_trips_through_json() {
+    let config = TranscriptionConfig {
+        priority: Priority::Low,
+        format_change: FormatChangePolicy::Error,
+        sentence_segmentation: true,
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert!(
+        json.contains(r#""priority":"low""#),
+        "unexpected json: {}",
+        json
+    );
+    assert_eq!(
+        serde_json::from_str::<TranscriptionConfig>(&json).unwrap(),
+        config
+    );
+
+    // Missing fields fall back to the builder defaults
+    let partial: TranscriptionConfig = serde_json::from_str(r#"{"priority":"low"}"#).unwrap();
+    assert_eq!(
+        partial,
+        TranscriptionConfig {
+            priority: Priority::Low,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_from_config_applies_settings() {
+    let helper = mock_stream_helper("transcribe_stream_config", "cat > /dev/null");
+    let temp_dir = helper.parent().unwrap().join("config_tmp");
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let config: TranscriptionConfig = serde_json::from_value(serde_json::json!({
+        "helper_path": helper.path,
+        "temp_dir": temp_dir,
+        "input_mode": "programmatic",
+    }))
+    .unwrap();
+
+    let transcriber = Transcriber::from_config(&config).unwrap();
+    assert_eq!(transcriber.helper_path(), helper.path);
+    assert_eq!(transcriber.temp_dir(), temp_dir);
+
+    // Feeding audio only works if the programmatic input mode was applied
+    let mut streaming = StreamingTranscriber::from_config(&config).unwrap();
+    streaming.start().unwrap();
+    streaming.feed_audio_i16(&[0; 160], 16000, 1).unwrap();
+}
+
+#[test]
+fn test_config_reports_effective_settings() {
+    let helper = mock_stream_helper("transcribe_stream_effective", "cat > /dev/null");
+    let streaming = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_microphone()
+        .with_programmatic_input()
+        .with_partial_rate(10.0)
+        .with_max_duration(Duration::from_secs(90))
+        .build()
+        .unwrap();
+    let config = streaming.config();
+    assert_eq!(config.input_mode, AudioInputMode::Programmatic);
+    assert_eq!(config.helper_path.as_deref(), Some(helper.path.as_path()));
+    assert_eq!(config.partial_rate, Some(10.0));
+    assert_eq!(config.max_duration_secs, Some(90.0));
+    assert_eq!(
+        StreamingTranscriber::from_config(&config).unwrap().config(),
+        config
+    );
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_verbatim(false)
+        .with_expected_helper_sha256("AB".repeat(32))
+        .build()
+        .unwrap();
+    let config = transcriber.config();
+    assert!(!config.verbatim);
+    assert_eq!(config.expected_helper_sha256, Some("ab".repeat(32)));
+    assert_eq!(config.input_mode, AudioInputMode::default());
+}
+
+#[test]
+fn test_trim_silence_passes_flag_and_reports_trimmed_seconds() {
+    let helper = mock_helper(
+        "transcribe_trim",
+        r#"if [ "$2" = "--json" ]; then
+  echo "{\"text\":\"$1\",\"trimmedLeading\":1.5,\"trimmedTrailing\":0.25}"
+else
+  echo "$@"
+fi"#,
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_trim_silence(true)
+        .build()
+        .unwrap();
+
+    let text = transcriber.transcribe_file(&helper).unwrap();
+    assert_eq!(text, format!("--trim-silence {}", helper.display()));
+
+    let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.text, "--trim-silence");
+    assert_eq!(result.trimmed_leading_secs, Some(1.5));
+    assert_eq!(result.trimmed_trailing_secs, Some(0.25));
+}
+
+#[test]
+fn test_batch_crlf_and_bom() {
+    let batch = batch_dir(&["a.m4a"]);
+    let dir = batch.path();
+    let options = BatchOptions {
+        output: BatchOutput::Append,
+        line_ending: LineEnding::CrLf,
+        bom: true,
+        ..Default::default()
+    };
+    let (transcriber, _helper) = batch_transcriber();
+    transcriber.transcribe_directory(dir, &options).unwrap();
+
+    // A second append run must neither duplicate the section nor repeat the BOM
+    std::fs::write(dir.join("b.m4a"), b"").unwrap();
+    let entries = transcriber.transcribe_directory(dir, &options).unwrap();
+    assert!(matches!(entries[0].outcome, BatchOutcome::Skipped(_)));
+
+    let bytes = std::fs::read(dir.join("transcriptions.txt")).unwrap();
+    assert_eq!(
+        bytes,
+        b"\xEF\xBB\xBF\r\n=== a.m4a ===\r\ntext of a.m4a\r\n\r\n=== b.m4a ===\r\ntext of b.m4a\r\n"
+    );
+}
+
+/// A file helper that reports its PID, and stays resident in serve mode
+const SERVING_HELPER: &str = r#"if [ "$1" = "--serve" ]; then
+  echo '{"ok":true,"output":"ready"}'
+  while read -r line; do echo "{\"ok\":true,\"output\":\"served $$\"}"; done
+else
+  echo "spawned $$"
+fi"#;
+
+#[test]
+fn test_warm_up_keeps_helper_resident() {
+    let helper = mock_helper("transcribe_serve", SERVING_HELPER);
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    assert!(transcriber
+        .transcribe_file(&helper)
+        .unwrap()
+        .starts_with("spawned"));
+
+    transcriber.warm_up().unwrap();
+    transcriber.warm_up().unwrap();
+    let first = transcriber.transcribe_file(&helper).unwrap();
+    let second = transcriber.transcribe_file(&helper).unwrap();
+    assert!(first.starts_with("served"), "unexpected output: {}", first);
+    assert_eq!(
+        first, second,
+        "both calls should reach the same resident helper"
+    );
+}
+
+#[test]
+fn test_helper_idle_timeout() {
+    let helper = mock_helper("transcribe_serve_idle", SERVING_HELPER);
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_helper_idle_timeout(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.config().helper_idle_timeout_secs, Some(0.2));
+    assert_eq!(transcriber.helper_idle_time(), None);
+
+    transcriber.warm_up().unwrap();
+    let first = transcriber.transcribe_file(&helper).unwrap();
+    assert!(first.starts_with("served"), "unexpected output: {}", first);
+    assert!(transcriber.helper_idle_time().unwrap() < Duration::from_millis(200));
+
+    // Stopped once idle, and loaded again by the next file
+    thread::sleep(Duration::from_millis(500));
+    assert_eq!(transcriber.helper_idle_time(), None);
+    let reloaded = transcriber.transcribe_file(&helper).unwrap();
+    assert!(
+        reloaded.starts_with("served"),
+        "unexpected output: {}",
+        reloaded
+    );
+    assert_ne!(first, reloaded);
+}
+
+#[test]
+fn test_pool_dispatches_round_robin() {
+    let helper = mock_helper("transcribe_serve_pool", SERVING_HELPER);
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    assert!(transcriber.pool(0).is_err());
+
+    let pool = transcriber.pool(2).unwrap();
+    assert_eq!(pool.size(), 2);
+    let outputs: Vec<String> = (0..4).map(|_| pool.transcribe(&helper).unwrap()).collect();
+    assert!(
+        outputs.iter().all(|output| output.starts_with("served")),
+        "{:?}",
+        outputs
+    );
+    assert_ne!(
+        outputs[0], outputs[1],
+        "consecutive files should reach different workers"
+    );
+    assert_eq!(outputs[0], outputs[2]);
+    assert_eq!(outputs[1], outputs[3]);
+
+    // Concurrent callers share the same two warm helpers
+    let concurrent: Vec<String> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..6)
+            .map(|_| scope.spawn(|| pool.transcribe(&helper).unwrap()))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    assert!(concurrent
+        .iter()
+        .all(|output| *output == outputs[0] || *output == outputs[1]));
+}
+
+#[test]
+fn test_warm_up_falls_back_when_resident_helper_dies() {
+    let helper = mock_helper(
+        "transcribe_serve_dies",
+        r#"if [ "$1" = "--serve" ]; then echo '{"ok":true,"output":"ready"}'; else echo "spawned"; fi"#,
+    );
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    transcriber.warm_up().unwrap();
+    assert_eq!(transcriber.transcribe_file(&helper).unwrap(), "spawned");
+}
+
+#[test]
+fn test_warm_up_reports_helper_failure() {
+    let helper = mock_helper(
+        "transcribe_serve_fails",
+        r#"echo '{"ok":false,"error":"model unavailable"}'"#,
+    );
+    let err = Transcriber::with_helper_path(&helper)
+        .unwrap()
+        .warm_up()
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("model unavailable"),
+        "unexpected error: {}",
+        err
+    );
+
+    let stream_helper = mock_helper(
+        "transcribe_stream_warm",
+        r#"[ "$1" = "--warm-up" ] || exit 1; echo "not installed" >&2; exit 1"#,
+    );
+    let err = StreamingTranscriber::with_helper_path(&stream_helper)
+        .unwrap()
+        .warm_up()
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not installed"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_metadata_confidence_uses_configured_aggregation() {
+    let helper = mock_helper(
+        "transcribe_segments",
+        r#"echo '{"text":"hi there","segments":[{"text":"hi","start":0,"duration":3,"confidence":0.9},{"text":"there","start":3,"duration":1,"confidence":0.5}]}'"#,
+    );
+    let confidence = |aggregation| {
+        Transcriber::builder()
+            .with_helper_path(&helper)
+            .with_confidence_aggregation(aggregation)
+            .build()
+            .unwrap()
+            .transcribe_file_with_metadata(&helper)
+            .unwrap()
+            .confidence
+            .unwrap()
+    };
+
+    let default = Transcriber::with_helper_path(&helper).unwrap();
+    let result = default.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.segments.len(), 2);
+    assert_eq!(result.segments[1].start_secs, 3.0);
+    assert!(
+        (result.confidence.unwrap() - 0.8).abs() < 1e-6,
+        "default is duration weighted"
+    );
+    assert!((confidence(ConfidenceAggregation::Mean) - 0.7).abs() < 1e-6);
+    assert!((confidence(ConfidenceAggregation::Min) - 0.5).abs() < 1e-6);
+}
+
+#[test]
+fn test_timestamp_offset_shifts_segments() {
+    let helper = mock_helper(
+        "transcribe_offset",
+        r#"echo '{"text":"hi there","segments":[{"text":"hi","start":0.1,"duration":3,"confidence":0.9},{"text":"there","start":3,"duration":1,"confidence":0.5}]}'"#,
+    );
+    let starts = |offset| {
+        let transcriber = Transcriber::builder()
+            .with_helper_path(&helper)
+            .with_timestamp_offset(offset)
+            .build()
+            .unwrap();
+        assert_eq!(transcriber.config().timestamp_offset_secs, offset);
+        let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+        assert_eq!(
+            result.segments[0].duration_secs, 3.0,
+            "durations are unchanged"
+        );
+        result
+            .segments
+            .iter()
+            .map(|s| s.start_secs)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(starts(0.5), [0.6, 3.5]);
+    // Earlier starts are clamped at the beginning of the file
+    assert_eq!(starts(-0.25), [0.0, 2.75]);
+
+    for offset in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let Err(err) = Transcriber::builder()
+            .with_helper_path(&helper)
+            .with_timestamp_offset(offset)
+            .build()
+        else {
+            panic!("offset {} was accepted", offset);
+        };
+        assert!(
+            err.to_string().contains("must be finite"),
+            "unexpected error: {}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_candidate_locales_tag_segments() {
+    // Reports the locale it was asked for, per segment only for the first one
+    let helper = mock_helper(
+        "transcribe_locales",
+        r#"case "$*" in
+*"--locales zh-CN,en-US"*) echo '{"text":"ni hao","segments":[{"text":"ni hao","start":0,"duration":1,"locale":"zh-CN"}],"locale":"zh-CN"}' ;;
+*) echo '{"text":"hello","segments":[{"text":"hello","start":0,"duration":1}],"locale":"en-US"}' ;;
+esac"#,
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_candidate_locales(["zh-CN", "en-US"])
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.config().candidate_locales, ["zh-CN", "en-US"]);
+    let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.segments[0].locale.as_deref(), Some("zh-CN"));
+
+    // A file-wide locale from an older helper is copied onto each segment
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    assert!(transcriber.config().candidate_locales.is_empty());
+    let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.segments[0].locale.as_deref(), Some("en-US"));
+
+    let invalid = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_candidate_locales(["en-US,zh-CN"])
+        .build();
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_max_segment_duration_splits_long_segments() {
+    let helper = mock_helper(
+        "transcribe_long_segment",
+        r#"case "$*" in
+*--segments*) echo '{"text":"one two three four","start":0,"duration":8}' ;;
+*) echo '{"text":"one two three four","segments":[{"text":"one two three four","start":0,"duration":8}]}' ;;
+esac"#,
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_max_segment_duration(Duration::from_secs(5))
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.config().max_segment_duration_secs, Some(5.0));
+
+    let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+    let cues: Vec<(&str, f64)> = result
+        .segments
+        .iter()
+        .map(|s| (s.text.as_str(), s.start_secs))
+        .collect();
+    // "one two" takes 6 of the 15 characters, so 3.2 of the 8 seconds
+    assert_eq!(cues, [("one two", 0.0), ("three four", 3.2)]);
+    assert_eq!(result.text, "one two three four");
+
+    let mut jsonl = Vec::new();
+    let count = transcriber
+        .transcribe_file_to_writer(&helper, &mut jsonl, OutputFormat::Jsonl)
+        .unwrap();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn test_transcribe_file_to_writer_streams_segments() {
+    let helper = mock_helper(
+        "transcribe_segment_lines",
+        r#"case "$*" in *--segments*) ;; *) exit 1 ;; esac
+echo '{"text":"Hello","start":0.5,"duration":1,"confidence":0.9}'
+echo '{"text":"um","start":1.5,"duration":0.25}'
+echo '{"text":"world","start":2,"duration":0.5}'
+echo"#,
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_verbatim(false)
+        .with_timestamp_offset(1.0)
+        .build()
+        .unwrap();
+
+    let mut srt = Vec::new();
+    let count = transcriber
+        .transcribe_file_to_writer(&helper, &mut srt, OutputFormat::Srt)
+        .unwrap();
+    // Short segments are merged into one caption-length cue
+    assert_eq!(count, 1);
+    assert_eq!(
+        String::from_utf8(srt).unwrap(),
+        "1\n00:00:01,500 --> 00:00:03,500\nHello world\n\n"
+    );
+
+    let windows = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_line_ending(LineEnding::CrLf)
+        .with_bom(true)
+        .build()
+        .unwrap();
+    assert_eq!(windows.config().line_ending, LineEnding::CrLf);
+    let mut vtt = Vec::new();
+    windows
+        .transcribe_file_to_writer(&helper, &mut vtt, OutputFormat::Vtt)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(vtt).unwrap(),
+        "\u{feff}WEBVTT\r\n\r\n00:00:00.500 --> 00:00:02.500\r\nHello um world\r\n\r\n"
+    );
+
+    let mut jsonl = Vec::new();
+    transcriber
+        .transcribe_file_to_writer(&helper, &mut jsonl, OutputFormat::Jsonl)
+        .unwrap();
+    let first: TranscriptionSegment =
+        serde_json::from_slice(jsonl.split(|&b| b == b'\n').next().unwrap()).unwrap();
+    assert_eq!(first.confidence, Some(0.9));
+
+    // A failing writer stops the transcription
+    struct Broken;
+    impl std::io::Write for Broken {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let err = transcriber
+        .transcribe_file_to_writer(&helper, Broken, OutputFormat::Vtt)
+        .unwrap_err();
+    assert!(err.contains("disk full"), "unexpected error: {}", err);
+}
+
+#[test]
+fn test_numbers_spoken_across_segments_are_normalized_together() {
+    let segments = r#"{"text":"In","start":0,"duration":0.5}
+{"text":"twenty","start":0.5,"duration":0.5}
+{"text":"twenty","start":1,"duration":0.5}
+{"text":"five","start":1.5,"duration":0.5}
+{"text":"sales","start":2,"duration":0.5}
+{"text":"rose","start":2.5,"duration":0.5}
+{"text":"ten","start":3,"duration":0.5}
+{"text":"percent.","start":3.5,"duration":0.5}"#;
+    let helper = mock_helper(
+        "transcribe_split_numbers",
+        &format!(
+            r#"case "$*" in
+*--segments*) cat <<'EOF'
+{segments}
+EOF
+;;
+*) printf '{{"text":"In twenty twenty five sales rose ten percent.","segments":[%s]}}' "$(cat <<'EOF' | paste -sd, -
+{segments}
+EOF
+)" ;;
+esac"#
+        ),
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_inverse_text_normalization(true)
+        .build()
+        .unwrap();
+
+    let expected = [
+        ("In", 0.0),
+        ("2025", 0.5),
+        ("sales", 2.0),
+        ("rose", 2.5),
+        ("10%.", 3.0),
+    ];
+    let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.text, "In 2025 sales rose 10%.");
+    let texts: Vec<(&str, f64)> = result
+        .segments
+        .iter()
+        .map(|s| (s.text.as_str(), s.start_secs))
+        .collect();
+    assert_eq!(texts, expected);
+    assert_eq!(result.segments[1].duration_secs, 1.5);
+
+    let mut jsonl = Vec::new();
+    transcriber
+        .transcribe_file_to_writer(&helper, &mut jsonl, OutputFormat::Jsonl)
+        .unwrap();
+    let written: Vec<TranscriptionSegment> = String::from_utf8(jsonl)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let texts: Vec<(&str, f64)> = written
+        .iter()
+        .map(|s| (s.text.as_str(), s.start_secs))
+        .collect();
+    assert_eq!(texts, expected);
+}
+
+#[test]
+fn test_empty_output_policy() {
+    let helper = mock_helper(
+        "transcribe_silent",
+        r#"[ "$1" = "--json" ] && echo '{"text":""}' || echo"#,
+    );
+    let tolerant = Transcriber::with_helper_path(&helper).unwrap();
+    assert_eq!(tolerant.transcribe_file(&helper).unwrap(), "");
+
+    let strict = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_empty_output_policy(EmptyOutputPolicy::AsError)
+        .build()
+        .unwrap();
+    let err = strict.transcribe_file(&helper).unwrap_err();
+    assert!(
+        matches!(&err, ScribeError::NoSpeechDetected(path) if *path == helper.path),
+        "{}",
+        err
+    );
+    let err = err.to_string();
+    assert!(
+        err.starts_with("No speech detected"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(strict.transcribe_file_with_metadata(&helper).is_err());
+}
+
+#[test]
+fn test_verbatim_toggle() {
+    let helper = mock_helper(
+        "transcribe_disfluent",
+        r#"if [ "$1" = "--json" ]; then
+  echo '{"text":"Um, I I think so","segments":[{"text":"Um,","start":0.0,"duration":0.4},{"text":"I I think so","start":0.4,"duration":1.0}]}'
+else
+  echo 'Um, I I think so'
+fi"#,
+    );
+    let verbatim = Transcriber::with_helper_path(&helper).unwrap();
+    assert_eq!(
+        verbatim.transcribe_file(&helper).unwrap(),
+        "Um, I I think so"
+    );
+
+    let cleaned = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_verbatim(false)
+        .build()
+        .unwrap();
+    assert_eq!(cleaned.transcribe_file(&helper).unwrap(), "I think so");
+    let result = cleaned.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.text, "I think so");
+    assert_eq!(result.segments.len(), 1);
+    assert_eq!(result.segments[0].start_secs, 0.4);
+}
+
+#[test]
+fn test_merge_segments_into_cues() {
+    let seg = |text: &str, start: f64, duration: f64, confidence: f32| TranscriptionSegment {
+        text: text.to_string(),
+        start_secs: start,
+        duration_secs: duration,
+        confidence: Some(confidence),
+        script: None,
+        locale: None,
+        words: None,
+    };
+    let segments = [
+        seg("so", 0.0, 0.5, 1.0),
+        seg("the plan", 0.5, 1.5, 0.5),
+        seg("is done.", 2.0, 1.0, 1.0),
+        seg("Next", 3.0, 0.5, 1.0),
+        seg("we review the open items", 3.5, 1.0, 1.0),
+        seg("later", 5.5, 2.0, 1.0),
+        seg("and then we close out every remaining item", 7.5, 1.0, 1.0),
+    ];
+
+    let cues = TranscriptionSegment::merge_to(&segments, 40, 4.0);
+    let texts: Vec<&str> = cues.iter().map(|c| c.text.as_str()).collect();
+    // Split at the sentence boundary, then the duration limit, then the character limit
+    assert_eq!(
+        texts,
+        [
+            "so the plan is done.",
+            "Next we review the open items",
+            "later",
+            "and then we close out every remaining item",
+        ]
+    );
+    assert_eq!((cues[0].start_secs, cues[0].duration_secs), (0.0, 3.0));
+    assert_eq!(cues[0].confidence, Some(0.75));
+
+    assert!(TranscriptionSegment::merge_to(&[], 40, 4.0).is_empty());
+}
+
+#[test]
+fn test_audio_passthrough() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_passthrough",
+        r#"[ "$1" = "--audio-out" ] && printf '\001\002\003\004' >&"$2"
+echo '{"text":"hello","isFinal":true,"timestamp":1.0}'
+sleep 5"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_audio_passthrough(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    let mut captured = Vec::new();
+    let mut buf = [0u8; 16];
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while captured.len() < 4 && Instant::now() < deadline {
+        let n = transcriber.read_captured_audio(&mut buf).unwrap();
+        captured.extend_from_slice(&buf[..n]);
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(captured, [1, 2, 3, 4]);
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "hello"
+    );
+    transcriber.stop().unwrap();
+
+    // Programmatic input has no captured audio to pass through
+    assert!(StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_audio_passthrough(true)
+        .build()
+        .is_err());
+    let mut plain = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    assert!(plain.read_captured_audio(&mut buf).is_err());
+}
+
+#[test]
+fn test_feed_encoded_passes_container_through() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_encoded",
+        r#"printf '{"text":"%s","isFinal":true,"timestamp":0}\n' "$(head -c 8)"
+cat > /dev/null"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_programmatic_input()
+        .with_helper_path(&helper)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    transcriber.feed_encoded(b"caff", AudioFormat::Caf).unwrap();
+    transcriber.feed_encoded(b"desc", AudioFormat::Caf).unwrap();
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "caffdesc"
+    );
+
+    let err = transcriber
+        .feed_encoded(b"RIFF", AudioFormat::Wav)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("from CAF to WAV"), "unexpected error: {}", err);
+    assert!(transcriber.feed_audio_i16(&[0; 160], 16000, 1).is_err());
+}
+
+#[test]
+fn test_feed_encoded_rejects_unusable_input() {
+    let (mut transcriber, _helper) = sink_transcriber(FormatChangePolicy::Adapt);
+    let err = transcriber
+        .feed_encoded(b"....ftypM4A ", AudioFormat::M4a)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("can't decode M4A"),
+        "unexpected error: {}",
+        err
+    );
+    let err = transcriber
+        .feed_encoded(b"not a header", AudioFormat::Aiff)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("AIFF header"), "unexpected error: {}", err);
+
+    transcriber.feed_audio_i16(&[0; 160], 16000, 1).unwrap();
+    assert!(transcriber.feed_encoded(b"caff", AudioFormat::Caf).is_err());
+}
+
+#[test]
+fn test_tracks() {
+    let helper = mock_helper(
+        "transcribe_tracks",
+        r#"case "$1" in
+  --list-tracks) echo '[{"index":0,"channels":1,"sampleRate":48000,"duration":2.5,"language":"en"},{"index":1,"channels":2,"sampleRate":44100,"duration":2.5}]' ;;
+  --track) echo "track $2" ;;
+  *) echo "default track" ;;
+esac"#,
+    );
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+
+    let tracks = transcriber.list_tracks(&helper).unwrap();
+    assert_eq!(tracks.len(), 2);
+    assert_eq!((tracks[1].index, tracks[1].channel_count), (1, 2));
+    assert_eq!(tracks[0].language.as_deref(), Some("en"));
+    assert_eq!(tracks[1].language, None);
+
+    assert_eq!(transcriber.transcribe_track(&helper, 1).unwrap(), "track 1");
+    assert_eq!(
+        transcriber.transcribe_file(&helper).unwrap(),
+        "default track"
+    );
+}
+
+#[test]
+fn test_helper_errors_include_command_line() {
+    let helper = mock_helper("transcribe_rejects", "echo 'unknown option' >&2; exit 2");
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_trim_silence(true)
+        .build()
+        .unwrap();
+    let err = transcriber
+        .transcribe_file_with_metadata(&helper)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("unknown option"), "unexpected error: {}", err);
+    let expected = format!(
+        "{} --trim-silence --json {}",
+        helper.display(),
+        helper.display()
+    );
+    assert!(
+        err.contains(&expected),
+        "error should quote the command: {}",
+        err
+    );
+}
+
+#[test]
+fn test_ensure_locale_available() {
+    let helper = mock_helper(
+        "transcribe_locales",
+        r#"[ "$1" = "--locale-status" ] || exit 1
+case "$2" in
+  xx-XX) echo '{"status":"unsupported"}' ;;
+  yy-YY) echo 'asset server unreachable' >&2; exit 1 ;;
+  *) if [ "$3" = "--download" ]; then
+       # More download logging than a pipe buffer holds
+       head -c 200000 /dev/zero | tr '\0' '.' >&2
+       echo '{"progress":0.5}'
+       echo '{"progress":1.0}'
+       echo '{"status":"installed"}'
+     else
+       echo '{"status":"not_installed"}'
+     fi ;;
+esac"#,
+    );
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+
+    assert_eq!(
+        transcriber.ensure_locale_available("de-DE", false).unwrap(),
+        LocaleStatus::NotInstalled
+    );
+    assert_eq!(
+        transcriber.ensure_locale_available("xx-XX", true).unwrap(),
+        LocaleStatus::Unsupported
+    );
+
+    let mut progress = Vec::new();
+    let status = transcriber
+        .ensure_locale_available_with_progress("de-DE", true, |fraction| progress.push(fraction))
+        .unwrap();
+    assert_eq!(status, LocaleStatus::Installed);
+    assert_eq!(progress, [0.5, 1.0]);
+
+    let err = transcriber
+        .ensure_locale_available("yy-YY", true)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("asset server unreachable"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_backpressure_policies() {
+    let helper = mock_stream_helper("transcribe_stream_backlog", "cat > /dev/null");
+    let second = vec![0i16; 16000];
+    let feed_twice = |policy| {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_programmatic_input()
+            .with_helper_path(&helper)
+            .with_backpressure(policy)
+            .with_backlog_limit(Duration::from_millis(1500))
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+        // One second of audio fed instantly is within the limit
+        assert_eq!(
+            transcriber
+                .feed_audio_i16(&second, 16000, 1)
+                .map_err(String::from),
+            Ok(None)
+        );
+        let result = transcriber.feed_audio_i16(&second, 16000, 1);
+        transcriber.stop().unwrap();
+        result
+    };
+
+    let warning = feed_twice(BackpressurePolicy::Block)
+        .unwrap()
+        .expect("warning");
+    assert!(!warning.dropped);
+    assert!(warning.backlog > Duration::from_millis(1500));
+
+    assert!(
+        feed_twice(BackpressurePolicy::Drop)
+            .unwrap()
+            .unwrap()
+            .dropped
+    );
+
+    let err = feed_twice(BackpressurePolicy::Error)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.starts_with("Helper backlog"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_transcribe_raw_file() {
+    let helper = mock_helper("transcribe_raw", r#"echo "$*""#);
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let raw = dir.path().join("capture.pcm");
+
+    // 3 frames of 24-bit stereo
+    std::fs::write(&raw, [0u8; 18]).unwrap();
+    let args = transcriber
+        .transcribe_raw_file(&raw, PcmFormat::S24Le, 48000, 2)
+        .unwrap();
+    assert_eq!(
+        args,
+        format!(
+            "--raw-format s24le --sample-rate 48000 --channels 2 {}",
+            raw.display()
+        )
+    );
+
+    let err = transcriber
+        .transcribe_raw_file(&raw, PcmFormat::S16Le, 16000, 4)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("not a multiple of the 8-byte frame"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(transcriber
+        .transcribe_raw_file(&raw, PcmFormat::S16Le, 0, 1)
+        .is_err());
+    assert!(transcriber
+        .transcribe_raw_file(&dir.path().join("missing.pcm"), PcmFormat::F32Le, 16000, 1)
+        .is_err());
+}
+
+#[test]
+fn test_transcribe_pcm_range() {
+    // Reports the size of the staged WAV: a 44-byte header and the window's frames
+    let helper = mock_helper("transcribe_pcm_range", r#"wc -c < "$1" | tr -d ' '"#);
+    let temp_dir = helper.parent().unwrap().join("pcm_range_tmp");
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_dir(&temp_dir)
+        .build()
+        .unwrap();
+    let ms = Duration::from_millis;
+
+    // One second of 16 kHz stereo; 250 ms is 4000 frames of 4 bytes
+    let pcm = vec![0i16; 16000 * 2];
+    let size = transcriber
+        .transcribe_pcm_range(&pcm, 16000, 2, ms(250), ms(500))
+        .unwrap();
+    assert_eq!(size, (44 + 4000 * 4).to_string());
+    let size = transcriber
+        .transcribe_pcm_range(&pcm, 16000, 2, ms(0), ms(1000))
+        .unwrap();
+    assert_eq!(size, (44 + 16000 * 4).to_string());
+    assert_eq!(
+        std::fs::read_dir(&temp_dir).unwrap().count(),
+        0,
+        "window left behind"
+    );
+
+    let err = transcriber
+        .transcribe_pcm_range(&pcm, 16000, 2, ms(500), ms(1500))
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Invalid PCM range"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(transcriber
+        .transcribe_pcm_range(&pcm, 16000, 2, ms(500), ms(500))
+        .is_err());
+    assert!(transcriber
+        .transcribe_pcm_range(&pcm, 16000, 3, ms(0), ms(100))
+        .is_err());
+    assert!(transcriber
+        .transcribe_pcm_range(&pcm, 0, 2, ms(0), ms(100))
+        .is_err());
+}
+
+#[test]
+fn test_transcribe_bytes() {
+    // Reports the extension and size of the staged file
+    let helper = mock_helper(
+        "transcribe_bytes",
+        r#"printf '%s %s' "${1##*.}" "$(wc -c < "$1" | tr -d ' ')""#,
+    );
+    let temp_dir = helper.parent().unwrap().join("bytes_tmp");
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_dir(&temp_dir)
+        .build()
+        .unwrap();
+
+    // 16 kHz mono WAV holding four silent samples
+    const WAV: &[u8] = b"RIFF\x2c\x00\x00\x00WAVEfmt \x10\x00\x00\x00\x01\x00\x01\x00\x80\x3e\x00\x00\x00\x7d\x00\x00\x02\x00\x10\x00data\x08\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00";
+    assert_eq!(
+        transcriber.transcribe_bytes(WAV, AudioFormat::Wav).unwrap(),
+        "wav 52"
+    );
+    assert_eq!(
+        transcriber
+            .transcribe_bytes(b"ID3", AudioFormat::Mp3)
+            .unwrap(),
+        "mp3 3"
+    );
+    assert_eq!(
+        std::fs::read_dir(&temp_dir).unwrap().count(),
+        0,
+        "staged audio left behind"
+    );
+
+    let err = transcriber
+        .transcribe_bytes(WAV, AudioFormat::Caf)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(err, "Audio data is not CAF");
+    assert!(transcriber.transcribe_bytes(&[], AudioFormat::Wav).is_err());
+
+    // The staged file is removed when the helper fails too
+    let failing = mock_helper(
+        "transcribe_bytes_failing",
+        "echo 'Error: unreadable' >&2; exit 1",
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&failing)
+        .with_temp_dir(&temp_dir)
+        .build()
+        .unwrap();
+    assert!(transcriber.transcribe_bytes(WAV, AudioFormat::Wav).is_err());
+    assert_eq!(
+        std::fs::read_dir(&temp_dir).unwrap().count(),
+        0,
+        "staged audio left behind"
+    );
+}
+
+#[test]
+fn test_transcribe_file_dual() {
+    let helper = mock_helper(
+        "transcribe_dual",
+        r#"echo "Hello, world. It's 3.5 degrees -- \"well-known\"!""#,
+    );
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    let (raw, formatted) = transcriber.transcribe_file_dual(&helper).unwrap();
+    assert_eq!(
+        formatted,
+        r#"Hello, world. It's 3.5 degrees -- "well-known"!"#
+    );
+    assert_eq!(raw, "hello world it's 3.5 degrees well-known");
+}
+
+#[test]
+fn test_sequential_temp_naming() {
+    // Reports the path of the staged WAV it was given
+    let helper = mock_helper("transcribe_temp_naming", r#"echo "$1""#);
+    let temp_dir = helper.parent().unwrap().join("temp_naming_tmp");
+    std::fs::create_dir_all(&temp_dir).unwrap();
+    let naming = TempNaming::Sequential {
+        prefix: "case".to_string(),
+    };
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_dir(&temp_dir)
+        .with_temp_naming(naming.clone())
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.config().temp_naming, naming);
+
+    // A name that is already taken is skipped rather than written through
+    let planted = helper.parent().unwrap().join("planted");
+    std::fs::write(&planted, "keep").unwrap();
+    std::os::unix::fs::symlink(&planted, temp_dir.join("case-0.wav")).unwrap();
+
+    for expected in ["case-1.wav", "case-2.wav"] {
+        let path = transcriber
+            .transcribe_pcm_range(
+                &[0; 1600],
+                16000,
+                1,
+                Duration::ZERO,
+                Duration::from_millis(100),
+            )
+            .unwrap();
+        assert_eq!(PathBuf::from(path), temp_dir.join(expected));
+    }
+    assert_eq!(
+        std::fs::read_dir(&temp_dir).unwrap().count(),
+        1,
+        "staged file left behind"
+    );
+    assert_eq!(std::fs::read_to_string(&planted).unwrap(), "keep");
+
+    let invalid = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_temp_naming(TempNaming::Sequential {
+            prefix: "a/b".to_string(),
+        })
+        .build();
+    assert!(invalid.is_err());
+}
+
+#[test]
+fn test_ping() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_ping",
+        r#"echo '{"text":"hello","isFinal":true,"timestamp":1}'
+while read cmd id; do echo "{\"pong\":$id}"; done <&4"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    assert!(transcriber.ping().is_err(), "not started yet");
+    transcriber.start().unwrap();
+
+    assert!(transcriber.ping().unwrap() < Duration::from_secs(5));
+    assert!(transcriber.ping().is_ok());
+    // The result read while waiting for the pong is not lost
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "hello"
+    );
+    transcriber.stop().unwrap();
+
+    let exited = mock_stream_helper("transcribe_stream_ping_exited", "exit 0");
+    let mut transcriber = StreamingTranscriber::with_helper_path(&exited).unwrap();
+    transcriber.start().unwrap();
+    assert!(transcriber.ping().is_err());
+}
+
+#[test]
+fn test_set_locale() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_set_locale",
+        r#"echo '{"text":"hello","isFinal":true,"timestamp":1}'
+while read cmd id; do
+  if [ "$id" = "xx-XX" ]; then
+    echo "{\"locale\":\"$id\",\"error\":\"Locale not installed\"}"
+  else
+    echo "{\"locale\":\"$id\"}"
+  fi
+done <&4"#,
+    );
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    assert!(matches!(
+        transcriber.set_locale("zh-CN"),
+        Err(ScribeError::NotStarted)
+    ));
+    transcriber.start().unwrap();
+
+    assert!(transcriber
+        .set_locale("bad locale")
+        .unwrap_err()
+        .to_string()
+        .contains("Invalid locale"));
+    transcriber.set_locale("zh-CN").unwrap();
+    let err = transcriber.set_locale("xx-XX").unwrap_err().to_string();
+    assert!(
+        err.contains("could not switch") && err.contains("not installed"),
+        "{}",
+        err
+    );
+    // The result read while waiting for the switch is not lost
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "hello"
+    );
+    transcriber.stop().unwrap();
+}
+
+#[test]
+fn test_mic_buffer_frames() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_buffer_frames",
+        r#"echo "{\"text\":\"$1 $2\",\"isFinal\":true,\"timestamp\":0}""#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_mic_buffer_frames(512)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    let result = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert_eq!(result.text, "--buffer-frames 512");
+
+    let build = |builder: swift_scribe::StreamingTranscriberBuilder| {
+        builder
+            .with_helper_path(&helper)
+            .build()
+            .err()
+            .map(String::from)
+            .unwrap_or_default()
+    };
+    assert!(build(StreamingTranscriber::builder().with_mic_buffer_frames(64)).contains("outside"));
+    assert!(build(
+        StreamingTranscriber::builder()
+            .with_programmatic_input()
+            .with_mic_buffer_frames(1024)
+    )
+    .contains("requires microphone input"));
+    let programmatic = StreamingTranscriber::builder()
+        .with_programmatic_input()
+        .with_mic_buffer_frames(1024);
+    assert!(matches!(
+        programmatic.with_helper_path(&helper).build(),
+        Err(ScribeError::WrongInputMode(_))
+    ));
+}
+
+#[test]
+fn test_speech_and_total_duration() {
+    let helper = mock_helper(
+        "transcribe_speech_duration",
+        r#"echo '{"text":"hi there","segments":[{"text":"hi","start":1,"duration":1.5},{"text":"there","start":10,"duration":0.5}],"duration":60}'"#,
+    );
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    let result = transcriber.transcribe_file_with_metadata(&helper).unwrap();
+    assert_eq!(result.speech_duration, Duration::from_secs(2));
+    assert_eq!(result.total_duration, Some(Duration::from_secs(60)));
+
+    let unreported = mock_helper(
+        "transcribe_speech_duration_unreported",
+        r#"echo '{"text":"","segments":[]}'"#,
+    );
+    let transcriber = Transcriber::with_helper_path(&unreported).unwrap();
+    let result = transcriber
+        .transcribe_file_with_metadata(&unreported)
+        .unwrap();
+    assert_eq!(result.speech_duration, Duration::ZERO);
+    assert_eq!(result.total_duration, None);
+}
+
+#[test]
+fn test_preflight() {
+    let helper = mock_helper(
+        "transcribe_preflight",
+        r#"case "$*" in
+  --authorization) echo '{"authorization":"denied"}' ;;
+  "--locale-status xx-XX") echo '{"status":"unsupported"}' ;;
+  --locale-status*) echo '{"status":"not_installed"}' ;;
+  *--list-tracks*.txt) echo '[]' ;;
+  *--list-tracks*) echo '[{"index":0,"channels":1,"sampleRate":16000,"duration":1}]' ;;
+esac"#,
+    );
+    let notes = helper.parent().unwrap().join("notes.txt");
+    std::fs::write(&notes, "not audio").unwrap();
+    let missing = PathBuf::from("/nonexistent/recording.m4a");
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_candidate_locales(["xx-XX", "de-DE"])
+        .build()
+        .unwrap();
+    let report = transcriber.preflight(&[helper.to_path_buf(), notes.clone(), missing.clone()]);
+    assert!(!report.is_ok());
+    assert_eq!(report.issues.len(), 4, "{:?}", report.issues);
+    assert!(matches!(&report.issues[0], PreflightIssue::Permission(m) if m.contains("denied")));
+    assert_eq!(
+        report.issues[1],
+        PreflightIssue::Locale {
+            locale: "de-DE".to_string(),
+            status: LocaleStatus::NotInstalled
+        }
+    );
+    assert!(
+        matches!(&report.issues[2], PreflightIssue::Input { path, reason }
+        if *path == notes && reason.contains("No audio track"))
+    );
+    assert!(
+        matches!(&report.issues[3], PreflightIssue::Input { path, reason }
+        if *path == missing && reason.contains("not found"))
+    );
+
+    // A helper that can't run is reported once; inputs still get file checks
+    let broken = mock_helper(
+        "transcribe_preflight_broken",
+        "echo 'dyld: missing library' >&2; exit 1",
+    );
+    let report = Transcriber::with_helper_path(&broken)
+        .unwrap()
+        .preflight(&[broken.to_path_buf(), missing]);
+    assert_eq!(report.issues.len(), 2, "{:?}", report.issues);
+    assert!(report.issues[0].to_string().contains("missing library"));
+    assert!(matches!(report.issues[1], PreflightIssue::Input { .. }));
+}
+
+#[test]
+fn test_backend() {
+    // Counts its runs next to itself, to show the answer is cached
+    let helper = mock_helper(
+        "transcribe_backend",
+        r#"echo run >> "$0.runs"
+[ "$1" = --backend-info ] && echo '{ "backend": "SpeechAnalyzer", "os_version": "26.0" }'"#,
+    );
+    let runs = helper.with_extension("runs");
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    assert_eq!(transcriber.backend().unwrap(), Backend::SpeechAnalyzer);
+    assert_eq!(transcriber.backend().unwrap(), Backend::SpeechAnalyzer);
+    assert_eq!(std::fs::read_to_string(&runs).unwrap().lines().count(), 1);
+
+    let legacy = mock_helper(
+        "transcribe_backend_legacy",
+        r#"echo '{"backend":"sf_speech_recognizer","os_version":"15.5"}'"#,
+    );
+    assert_eq!(
+        Transcriber::with_helper_path(&legacy)
+            .unwrap()
+            .backend()
+            .unwrap(),
+        Backend::SfSpeechRecognizer
+    );
+
+    let unknown = mock_helper(
+        "transcribe_backend_unknown",
+        r#"echo '{"backend":"whisper"}'"#,
+    );
+    let err = Transcriber::with_helper_path(&unknown)
+        .unwrap()
+        .backend()
+        .unwrap_err();
+    assert!(
+        matches!(err, ScribeError::ParseResult { .. }),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_transcribe_file_json() {
+    let helper = mock_helper(
+        "transcribe_file_json",
+        r#"echo '{"text":"hi there","segments":[{"text":"hi","start":0,"duration":1,"confidence":0.5}],"backend":"speech_analyzer","locale":"en-US","duration":2.5}'"#,
+    );
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    let transcription = transcriber.transcribe_file_json(&helper).unwrap();
+    assert_eq!(transcription.full_text, "hi there");
+    assert_eq!(transcription.segments.len(), 1);
+    assert_eq!(transcription.segments[0].script, Some(Script::Latin));
+    assert_eq!(transcription.confidence, Some(0.5));
+    assert_eq!(transcription.backend, Some(Backend::SpeechAnalyzer));
+    assert_eq!(transcription.locale.as_deref(), Some("en-US"));
+    assert_eq!(transcription.duration_secs, Some(2.5));
+
+    // Same camelCase keys as streaming results
+    let json = serde_json::to_value(&transcription).unwrap();
+    assert_eq!(json["fullText"], "hi there");
+    assert_eq!(json["duration"], 2.5);
+    assert_eq!(
+        serde_json::from_value::<FileTranscription>(json).unwrap(),
+        transcription
+    );
+}
+
+#[test]
+fn test_feed_reports_helper_exit() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_crash",
+        r#"echo "model unavailable" >&2
+exit 3"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    thread::sleep(Duration::from_millis(100));
+
+    let err = (0..50)
+        .find_map(|_| transcriber.feed_audio_i16(&[0; 16000], 16000, 1).err())
+        .expect("writes to an exited helper should fail");
+    assert!(
+        matches!(err, ScribeError::HelperExited { .. }),
+        "unexpected error: {}",
+        err
+    );
+    let err = err.to_string();
+    assert!(
+        err.starts_with("Helper exited"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(err.contains('3'), "exit status missing: {}", err);
+    assert!(err.contains("model unavailable"), "stderr missing: {}", err);
+}
+
+#[test]
+fn test_alternatives_stream_on_finals_only() {
+    // Alternatives are only sent when asked for, and dropped from partials
+    let helper = mock_stream_helper(
+        "transcribe_stream_alternatives",
+        r#"case "$*" in *--alternatives*) alts='"alternatives":["I scream"],' ;; *) alts='' ;; esac
+echo "{\"text\":\"ice\",\"isFinal\":false,$alts\"timestamp\":0}"
+echo "{\"text\":\"ice cream\",\"isFinal\":true,$alts\"timestamp\":1}"
+sleep 5"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_alternatives_stream(true)
+        .build()
+        .unwrap();
+    assert!(transcriber.config().alternatives_stream);
+    transcriber.start().unwrap();
+
+    let partial = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert!(partial.alternatives.is_empty());
+    let fin = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert!(fin.is_final);
+    assert_eq!(fin.text, "ice cream");
+    assert_eq!(fin.alternatives, ["I scream"]);
+    transcriber.stop().unwrap();
+
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    poll_until_result(&mut transcriber).unwrap().unwrap();
+    let fin = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert!(fin.alternatives.is_empty());
+    transcriber.stop().unwrap();
+}
+
+#[test]
+fn test_finish_transcript_collects_finals() {
+    // Finishes recognizing once the audio input is closed
+    let helper = mock_stream_helper(
+        "transcribe_stream_finish_transcript",
+        r#"echo '{"text":"hello there","isFinal":true,"timestamp":0}'
+cat > /dev/null
+echo '{"text":"general","isFinal":false,"timestamp":0}'
+echo '{"text":"general Kenobi","isFinal":true,"timestamp":0}'"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .build()
+        .unwrap();
+    assert!(transcriber.finish_transcript().is_err());
+    transcriber.start().unwrap();
+
+    let first = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert_eq!(first.text, "hello there");
+
+    let transcript = transcriber.finish_transcript().unwrap();
+    assert!(!transcriber.is_running());
+    assert_eq!(transcript.full_text, "hello there general Kenobi");
+    let texts: Vec<&str> = transcript
+        .segments
+        .iter()
+        .map(|s| s.text.as_str())
+        .collect();
+    assert_eq!(texts, ["hello there", "general Kenobi"]);
+    assert!(transcript.segments.iter().all(|s| s.start_secs >= 0.0));
+    assert!(transcript.duration_secs.is_some());
+
+    // Repeated calls return the same transcript
+    assert_eq!(transcriber.finish_transcript().unwrap(), transcript);
+}
+
+#[test]
+fn test_finish_transcript_over_a_socket_returns_when_the_peer_closes() {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixListener;
+
+    let dir = tempfile::tempdir().unwrap();
+    let socket = dir.path().join("helper.sock");
+    let listener = UnixListener::bind(&socket).unwrap();
+    // Answers once its input ends, then hangs up
+    let agent = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        writeln!(stream, r#"{{"protocol":1}}"#).unwrap();
+        let mut audio = Vec::new();
+        stream.read_to_end(&mut audio).unwrap();
+        writeln!(
+            stream,
+            r#"{{"text":"got {} bytes","isFinal":true,"timestamp":0}}"#,
+            audio.len()
+        )
+        .unwrap();
+    });
+
+    let mut transcriber = StreamingTranscriber::connect_socket(&socket).unwrap();
+    transcriber.feed_audio_i16(&[0; 160], 16000, 1).unwrap();
+    let started = Instant::now();
+    let transcript = transcriber.finish_transcript().unwrap();
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "waited {:?}",
+        started.elapsed()
+    );
+    assert_eq!(transcript.full_text, "got 320 bytes");
+    agent.join().unwrap();
+}
+
+#[test]
+fn test_session_lifecycle_transitions() {
+    // Records its pid before announcing, so it's on file once start() returns
+    let helper = mock_helper(
+        "transcribe_stream_lifecycle",
+        r#"echo $$ >> "$0.pids"
+echo '{"protocol":1}'
+cat > /dev/null"#,
+    );
+    let pids_file = PathBuf::from(format!("{}.pids", helper.display()));
+    let pids = || -> Vec<String> {
+        std::fs::read_to_string(&pids_file)
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+    let alive = |pid: &str| {
+        std::process::Command::new("kill")
+            .args(["-0", pid])
+            .status()
+            .unwrap()
+            .success()
+    };
+
+    // Starting twice replaces the first helper instead of leaking it
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    transcriber.start().unwrap();
+    let started = pids();
+    assert_eq!(started.len(), 2);
+    assert!(!alive(&started[0]), "first helper still running");
+    assert!(alive(&started[1]));
+
+    // Feeding after stop fails, and starting after stop works
+    transcriber.stop().unwrap();
+    assert!(!transcriber.is_running());
+    let err = transcriber.feed_audio_i16(&[0; 160], 16000, 1).unwrap_err();
+    assert!(
+        matches!(err, ScribeError::NotStarted),
+        "unexpected error: {}",
+        err
+    );
+    transcriber.start().unwrap();
+    transcriber.feed_audio_i16(&[0; 160], 16000, 1).unwrap();
+    transcriber.stop().unwrap();
+
+    // A strict transcriber refuses to start over a running session
+    let mut strict = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_restart_policy(RestartPolicy::Error)
+        .build()
+        .unwrap();
+    assert_eq!(strict.config().restart_policy, RestartPolicy::Error);
+    strict.start().unwrap();
+    let err = strict.start().unwrap_err().to_string();
+    assert!(
+        err.starts_with("Already started"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(strict.is_running());
+    strict.feed_audio_i16(&[0; 160], 16000, 1).unwrap();
+    strict.stop().unwrap();
+    strict.start().unwrap();
+}
+
+#[test]
+fn test_poll_reports_permission_revoked() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_revoked",
+        r#"echo '{"text":"hello","isFinal":false,"timestamp":0}'
+echo "Error: Microphone permission revoked" >&2
+exit 77"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_stderr(StderrMode::Capture)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "hello"
+    );
+    let err = transcriber
+        .poll_result_timeout(Duration::from_secs(2))
+        .unwrap_err();
+    assert!(
+        matches!(err, ScribeError::PermissionRevoked),
+        "unexpected error: {}",
+        err
+    );
+    let err = err.to_string();
+    assert!(
+        err.starts_with("Permission revoked"),
+        "unexpected error: {}",
+        err
+    );
+
+    // The session can be restarted once access is granted again
+    transcriber.stop().unwrap();
+    transcriber.start().unwrap();
+}
+
+#[test]
+fn test_start_checks_protocol_version() {
+    let helper = mock_stream_helper("transcribe_stream_protocol", "cat > /dev/null");
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    assert_eq!(transcriber.protocol_version(), None);
+    transcriber.start().unwrap();
+    assert_eq!(transcriber.protocol_version(), Some(1));
+    assert!(SUPPORTED_PROTOCOL_VERSIONS.contains(&1));
+    transcriber.stop().unwrap();
+
+    for (name, script) in [
+        (
+            "transcribe_stream_protocol_old",
+            r#"echo '{"protocol":0}'; sleep 5"#,
+        ),
+        (
+            "transcribe_stream_protocol_new",
+            r#"echo '{"protocol":99}'; sleep 5"#,
+        ),
+        // Helpers from before the announcement start straight with results
+        (
+            "transcribe_stream_protocol_none",
+            r#"echo '{"text":"hi","isFinal":true,"timestamp":0}'; sleep 5"#,
+        ),
+    ] {
+        let helper = mock_helper(name, script);
+        let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+        let err = transcriber.start().unwrap_err().to_string();
+        assert!(
+            err.starts_with("Incompatible helper protocol"),
+            "unexpected error: {}",
+            err
+        );
+        assert!(!transcriber.is_running());
+    }
+}
+
+#[test]
+fn test_session_deadline() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_deadline",
+        r#"while read cmd rest; do
+  if [ "$cmd" = finish ]; then
+    echo '{"text":"wrapped up","isFinal":true,"kind":"committed","timestamp":1}'
+    exit 0
+  fi
+done <&4"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_max_duration(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    assert_eq!(
+        poll_until_result(&mut transcriber).unwrap().unwrap().text,
+        "wrapped up"
+    );
+    let err = poll_until_result(&mut transcriber).unwrap_err();
+    assert_eq!(err, "Session deadline reached");
+
+    // A deadline that has already passed ends the session before any audio
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_max_duration(Duration::from_secs(3600))
+        .with_session_deadline(Instant::now())
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    let err = transcriber
+        .feed_audio_i16(&[0; 160], 16000, 1)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(err, "Session deadline reached");
+}
+
+#[test]
+fn test_finish_input() {
+    // The helper only writes its final result once its input ends
+    let helper = mock_stream_helper(
+        "transcribe_stream_finish_input",
+        r#"cat > /dev/null
+echo '{"text":"all done","isFinal":true,"timestamp":1}'"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    transcriber.feed_audio_i16(&[0; 1600], 16000, 1).unwrap();
+    transcriber.finish_input().unwrap();
+    transcriber.finish_input().unwrap();
+
+    let err = transcriber
+        .feed_audio_i16(&[0; 160], 16000, 1)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(err, "Audio input already finished");
+    assert!(transcriber.feed_audio_f32(&[0.0; 160], 16000, 1).is_err());
+
+    let texts: Vec<String> = transcriber
+        .results()
+        .map(|result| result.unwrap().text)
+        .collect();
+    assert_eq!(texts, ["all done"]);
+    transcriber.stop().unwrap();
+
+    // Microphone sessions have no input to finish
+    let mut transcriber = StreamingTranscriber::with_helper_path(&helper).unwrap();
+    assert!(matches!(
+        transcriber.finish_input(),
+        Err(ScribeError::WrongInputMode(_))
+    ));
+}
+
+#[test]
+fn test_stderr_modes() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_stderr",
+        r#"echo "loading model" >&2
+echo '{"text":"hi","isFinal":true,"timestamp":1}'
+sleep 5"#,
+    );
+    let start = |mode| {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path(&helper)
+            .with_stderr(mode)
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+        poll_until_result(&mut transcriber).unwrap().unwrap();
+        transcriber
+    };
+
+    let captured = start(StderrMode::Capture);
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while captured.helper_stderr().unwrap().is_empty() && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(captured.helper_stderr().unwrap(), "loading model");
+
+    assert_eq!(start(StderrMode::Null).helper_stderr(), None);
+}
+
+#[test]
+fn test_task_hint_is_passed_to_the_helper() {
+    let helper = mock_helper("transcribe_hint", r#"echo "$*""#);
+    let audio = helper.parent().unwrap().join("hint.wav");
+    std::fs::write(&audio, b"RIFF").unwrap();
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_task_hint(TaskHint::Confirmation)
+        .build()
+        .unwrap();
+    assert_eq!(
+        transcriber.transcribe_file(&audio).unwrap(),
+        format!("--task-hint confirmation {}", audio.display())
+    );
+
+    // The default leaves the helper's choice alone
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    assert_eq!(
+        transcriber.transcribe_file(&audio).unwrap(),
+        audio.display().to_string()
+    );
+}
+
+#[test]
+fn test_locale_is_passed_to_the_helper() {
+    let helper = mock_helper("transcribe_locale", r#"echo "$*""#);
+    let audio = helper.parent().unwrap().join("locale.wav");
+    std::fs::write(&audio, b"RIFF").unwrap();
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_locale("es-ES")
+        .build()
+        .unwrap();
+    assert_eq!(
+        transcriber.transcribe_file(&audio).unwrap(),
+        format!("--locale es-ES {}", audio.display())
+    );
+    assert_eq!(transcriber.config().locale.as_deref(), Some("es-ES"));
+
+    let err = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_locale("")
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "Locale must not be empty");
+    let combined = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_locale("es-ES")
+        .with_candidate_locales(["en-US"])
+        .build();
+    assert!(combined.is_err());
+
+    // Streaming sessions get it on the helper's command line
+    let stream_helper = mock_stream_helper(
+        "transcribe_stream_locale",
+        r#"echo "{\"text\":\"$*\",\"isFinal\":true,\"timestamp\":0}"; sleep 5"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&stream_helper)
+        .with_locale("ja-JP")
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    let args = poll_until_result(&mut transcriber).unwrap().unwrap().text;
+    assert!(
+        args.contains("--locale ja-JP"),
+        "unexpected arguments: {}",
+        args
+    );
+    assert_eq!(transcriber.config().locale.as_deref(), Some("ja-JP"));
+    assert!(StreamingTranscriber::builder()
+        .with_helper_path(&stream_helper)
+        .with_locale("")
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_warm_up_loads_the_configured_locale() {
+    // Both helpers fail their warm-up with the arguments they were given
+    let helper = mock_helper(
+        "transcribe_warm_locale",
+        r#"echo "{\"ok\":false,\"error\":\"$*\"}""#,
+    );
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_locale("es-ES")
+        .build()
+        .unwrap();
+    let err = transcriber.warm_up().unwrap_err().to_string();
+    assert!(err.contains("--locale es-ES"), "unexpected error: {}", err);
+
+    let stream_helper = mock_helper("transcribe_stream_warm_locale", r#"echo "$*" >&2; exit 1"#);
+    let transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&stream_helper)
+        .with_locale("ja-JP")
+        .build()
+        .unwrap();
+    let err = transcriber.warm_up().unwrap_err().to_string();
+    assert!(
+        err.contains("--warm-up --locale ja-JP"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_extra_args_follow_managed_flags() {
+    let helper = mock_helper("transcribe_extra_args", r#"echo "$*""#);
+    let audio = helper.parent().unwrap().join("extra.wav");
+    std::fs::write(&audio, b"RIFF").unwrap();
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_task_hint(TaskHint::Dictation)
+        .with_extra_args(["--custom", "value"])
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.config().extra_args, ["--custom", "value"]);
+    assert_eq!(
+        transcriber.transcribe_file(&audio).unwrap(),
+        format!("--task-hint dictation --custom value {}", audio.display())
+    );
+
+    // Streaming appends them after every flag of its own
+    let helper = mock_stream_helper(
+        "transcribe_stream_extra_args",
+        r#"echo "{\"text\":\"$*\",\"isFinal\":true,\"timestamp\":0}"
+sleep 1"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_extra_args(["--custom"])
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    let result = poll_until_result(&mut transcriber).unwrap().unwrap();
+    assert!(
+        result.text.ends_with("--control 4 --custom"),
+        "{}",
+        result.text
+    );
+}
+
+#[test]
+fn test_expected_helper_sha256() {
+    let helper = mock_helper("transcribe_pinned", "echo pinned");
+    let pinned = "1cac5639a822f598a6e97508b9bf85ff03ab63863afc535a6d67343a3c08c922";
+    let audio = helper.parent().unwrap().join("pinned.wav");
+    std::fs::write(&audio, b"RIFF").unwrap();
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_expected_helper_sha256(pinned.to_uppercase())
+        .build()
+        .unwrap();
+    assert_eq!(transcriber.transcribe_file(&audio).unwrap(), "pinned");
+
+    let transcriber = Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_expected_helper_sha256("0".repeat(64))
+        .build()
+        .unwrap();
+    let err = transcriber.transcribe_file(&audio).unwrap_err().to_string();
+    assert!(
+        err.starts_with("Helper checksum mismatch"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(err.contains(pinned), "unexpected error: {}", err);
+
+    let mut streaming = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_expected_helper_sha256("0".repeat(64))
+        .build()
+        .unwrap();
+    assert!(streaming
+        .start()
+        .unwrap_err()
+        .to_string()
+        .starts_with("Helper checksum mismatch"));
+    assert!(streaming
+        .warm_up()
+        .unwrap_err()
+        .to_string()
+        .starts_with("Helper checksum mismatch"));
+
+    assert!(Transcriber::builder()
+        .with_helper_path(&helper)
+        .with_expected_helper_sha256("not a digest")
+        .build()
+        .is_err());
+}
+
+fn metadata_batch_transcriber() -> (Transcriber, MockHelper) {
+    let helper = mock_helper(
+        "transcribe_batch_json",
+        r#"for last; do :; done; echo "{\"text\":\"text of $(basename "$last")\",\"segments\":[]}""#,
+    );
+    (Transcriber::with_helper_path(&helper).unwrap(), helper)
+}
+
+#[test]
+fn test_transcribe_directory_iter_is_lazy() {
+    let batch = batch_dir(&["b.wav", "a.m4a", "notes.txt"]);
+    let dir = batch.path();
+    let (transcriber, _helper) = metadata_batch_transcriber();
+
+    let mut results = transcriber
+        .transcribe_directory_iter(dir, &BatchOptions::default())
+        .unwrap();
+    let (path, result) = results.next().unwrap();
+    assert!(path.ends_with("a.m4a"));
+    assert_eq!(result.unwrap().text, "text of a.m4a");
+
+    // A file removed before its turn fails on its own without ending the batch
+    std::fs::remove_file(dir.join("b.wav")).unwrap();
+    let (path, result) = results.next().unwrap();
+    assert!(path.ends_with("b.wav"));
+    assert!(matches!(result, Err(ScribeError::AudioFileMissing(_))));
+    assert!(results.next().is_none());
+    assert!(!dir.join("transcriptions.txt").exists());
+}
+
+#[test]
+fn test_transcribe_directory_channel() {
+    let batch = batch_dir(&["a.m4a", "b.wav", "c.mp3", "notes.txt"]);
+    let dir = batch.path();
+    let (transcriber, _helper) = metadata_batch_transcriber();
+    let transcriber = std::sync::Arc::new(transcriber);
+
+    let results = transcriber
+        .transcribe_directory_channel(dir, &BatchOptions::default(), 2)
+        .unwrap();
+    let mut texts: Vec<String> = results
+        .iter()
+        .map(|(_, result)| result.unwrap().text)
+        .collect();
+    texts.sort();
+    assert_eq!(texts, ["text of a.m4a", "text of b.wav", "text of c.mp3"]);
+}
+
+#[test]
+fn test_transcribe_directory_channel_ordered() {
+    let batch = batch_dir(&["1.wav", "2.wav", "3.wav", "4.wav", "5.wav"]);
+    let dir = batch.path();
+    // Earlier clips take longer, so they finish last
+    let helper = mock_helper(
+        "transcribe_batch_slow_first",
+        r#"for last; do :; done; name=$(basename "$last" .wav)
+sleep "0.$((6 - name))"
+echo "{\"text\":\"clip $name\",\"segments\":[]}""#,
+    );
+    let transcriber = std::sync::Arc::new(Transcriber::with_helper_path(&helper).unwrap());
+    let options = BatchOptions {
+        ordered: true,
+        ..Default::default()
+    };
+
+    let results = transcriber
+        .transcribe_directory_channel(dir, &options, 3)
+        .unwrap();
+    let texts: Vec<String> = results
+        .iter()
+        .map(|(_, result)| result.unwrap().text)
+        .collect();
+    assert_eq!(texts, ["clip 1", "clip 2", "clip 3", "clip 4", "clip 5"]);
+}
+
+#[test]
+fn test_path_with_trailing_newline() {
+    let helper = mock_helper("transcribe_newline", r#"echo "[$1]""#);
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let audio = dir.path().join("pasted.wav");
+    std::fs::write(&audio, b"RIFF").unwrap();
+
+    // Copied from a terminal: the helper gets the path without the newline
+    let pasted = PathBuf::from(format!("{}\n", audio.display()));
+    assert_eq!(
+        transcriber.transcribe_file(&pasted).unwrap(),
+        format!("[{}]", audio.display())
+    );
+
+    let missing = PathBuf::from(format!("{}\n", dir.path().join("missing.wav").display()));
+    let err = transcriber.transcribe_file(&missing).unwrap_err();
+    assert!(
+        matches!(err, ScribeError::AudioFileMissing(_)),
+        "unexpected error: {}",
+        err
+    );
+    let err = err.to_string();
+    assert!(
+        err.starts_with("Audio file not found"),
+        "unexpected error: {}",
+        err
+    );
+    assert!(
+        err.ends_with(r#"missing.wav\n""#),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_stable_mode_waits_for_partials_to_settle() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_stability",
+        r#"echo '{"text":"ice","isFinal":false,"timestamp":1}'
+echo '{"text":"I scream","isFinal":false,"timestamp":2}'
+sleep 0.6
+echo '{"text":"I scream for","isFinal":true,"timestamp":3}'
+sleep 5"#,
+    );
+    let texts = |mode| {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path(&helper)
+            .with_stability(mode)
+            .build()
+            .unwrap();
+        assert_eq!(transcriber.config().stability, mode);
+        transcriber.start().unwrap();
+        let mut texts = Vec::new();
+        while let Ok(Some(result)) = poll_until_result(&mut transcriber) {
+            texts.push(result.text);
+            if result.is_final {
+                break;
+            }
+        }
+        texts
+    };
+
+    assert_eq!(
+        texts(StabilityMode::Responsive),
+        ["ice", "I scream", "I scream for"]
+    );
+    assert_eq!(texts(StabilityMode::Stable), ["I scream", "I scream for"]);
+}
+
+#[test]
+fn test_end_of_speech_events() {
+    let helper = mock_stream_helper(
+        "transcribe_stream_end_of_speech",
+        r#"echo '{"text":"lights on","isFinal":false,"kind":"volatile","timestamp":1}'
+echo '{"text":"","isFinal":false,"kind":"endofspeech","timestamp":2}'
+echo '{"text":"Lights on.","isFinal":true,"kind":"final","timestamp":3}'
+sleep 5"#,
+    );
+    let kinds = |mode| {
+        let mut transcriber = StreamingTranscriber::builder()
+            .with_helper_path(&helper)
+            .with_stability(mode)
+            .build()
+            .unwrap();
+        transcriber.start().unwrap();
+        let mut kinds = Vec::new();
+        while let Ok(Some(result)) = poll_until_result(&mut transcriber) {
+            kinds.push(result.kind);
+            if result.is_final {
+                break;
+            }
+        }
+        kinds
+    };
+
+    assert_eq!(
+        kinds(StabilityMode::Responsive),
+        [
+            ResultKind::Volatile,
+            ResultKind::EndOfSpeech,
+            ResultKind::Final
+        ]
+    );
+    // The event isn't held back with the unsettled partial
+    assert_eq!(
+        kinds(StabilityMode::Stable),
+        [ResultKind::EndOfSpeech, ResultKind::Final]
+    );
+}
+
+#[test]
+fn test_partial_rate() {
+    let helper = mock_stream_helper(
+        "partial_rate",
+        r#"for word in a ab abc abcd; do echo "{\"text\":\"$word\",\"isFinal\":false,\"timestamp\":1}"; done
+sleep 1
+echo '{"text":"abcde","isFinal":true,"timestamp":2}'"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_partial_rate(4.0)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    let mut texts = Vec::new();
+    let mut arrivals = Vec::new();
+    while let Some(result) = poll_until_result(&mut transcriber).unwrap_or(None) {
+        texts.push(result.text);
+        arrivals.push(Instant::now());
+    }
+    // The burst collapses to its first and newest partial, a quarter second apart
+    assert_eq!(texts, ["a", "abcd", "abcde"]);
+    assert!(arrivals[1] - arrivals[0] >= Duration::from_millis(250));
+
+    assert!(StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_partial_rate(0.0)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_align_to_reference() {
+    let helper = mock_helper(
+        "transcribe_align",
+        r#"[ "$2" = "--reference" ] && [ "$3" = "Hello there, world" ] || exit 1
+echo '{"text":"hello world","segments":[{"text":"hello","start":0.5,"duration":0.4},{"text":"world","start":1.2,"duration":0.5}]}'"#,
+    );
+    let audio = helper.parent().unwrap().join("align.wav");
+    std::fs::write(&audio, b"RIFF").unwrap();
+    let transcriber = Transcriber::with_helper_path(&helper).unwrap();
+
+    let words = transcriber.align(&audio, "Hello there, world").unwrap();
+    assert_eq!(
+        words[0],
+        WordSegment {
+            word: "Hello".to_string(),
+            start_secs: Some(0.5),
+            duration_secs: Some(0.4),
+            confidence: None,
+        }
+    );
+    assert!(!words[1].is_aligned(), "\"there,\" was never heard");
+    assert_eq!(words[2].start_secs, Some(1.2));
+
+    assert!(transcriber.align(&audio, " ... ").is_err());
+}
+
+#[test]
+fn test_skip_silence() {
+    let helper = mock_stream_helper(
+        "skip_silence",
+        r#"first=$(head -c 2 | od -An -td2 | tr -d ' ')
+echo "{\"text\":\"first sample $first\",\"isFinal\":true,\"timestamp\":0}"
+cat > /dev/null"#,
+    );
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_helper_path(&helper)
+        .with_programmatic_input()
+        .with_skip_silence(true)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+
+    assert!(transcriber.feed_audio_i16(&[], 16000, 1).unwrap().is_none());
+    // Half a second of near-silence never reaches the helper
+    transcriber
+        .feed_audio_i16(&[3, -3].repeat(8000), 16000, 2)
+        .unwrap();
+    transcriber.feed_audio_f32(&[0.0; 4000], 16000, 1).unwrap();
+    transcriber.feed_audio_i16(&[1000; 160], 16000, 1).unwrap();
+
+    let result = poll_until_result(&mut transcriber)
+        .unwrap()
+        .expect("expected a result");
+    assert_eq!(result.text, "first sample 1000");
+    assert_eq!(transcriber.skipped_silence(), Duration::from_millis(750));
 }
@@ -0,0 +1,80 @@
+//! Allocation counts for the programmatic feed path
+//!
+//! Lives in its own test binary because it installs a counting global allocator.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::path::PathBuf;
+use swift_scribe::StreamingTranscriber;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Counts the allocations made on this thread while running `f`
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+fn started_sink() -> (StreamingTranscriber, tempfile::TempDir) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let helper: PathBuf = dir.path().join("transcribe_stream_sink");
+    std::fs::write(
+        &helper,
+        "#!/bin/sh\necho '{\"protocol\":1}'\ncat > /dev/null\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&helper, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut transcriber = StreamingTranscriber::builder()
+        .with_programmatic_input()
+        .with_helper_path(&helper)
+        .build()
+        .unwrap();
+    transcriber.start().unwrap();
+    (transcriber, dir)
+}
+
+#[test]
+fn test_feed_allocations() {
+    let (mut transcriber, _dir) = started_sink();
+    let chunk = vec![1000i16; 3200];
+
+    // Warm up so the first write's lazy setup isn't counted
+    transcriber.feed_audio_i16(&chunk, 16000, 1).unwrap();
+
+    let fast = allocations_during(|| {
+        for _ in 0..100 {
+            transcriber.feed_audio_i16(&chunk, 16000, 1).unwrap();
+        }
+    });
+    let converted = allocations_during(|| {
+        for _ in 0..100 {
+            transcriber.feed_audio_i16(&chunk, 48000, 2).unwrap();
+        }
+    });
+
+    println!("allocations per 100 chunks: 16kHz mono = {fast}, 48kHz stereo = {converted}");
+    assert_eq!(fast, 0, "16kHz mono feed should not allocate");
+    assert!(converted > 0);
+}